@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
+use std::fmt::Display;
 
 #[error_code]
 pub enum FeeRouterError {
     #[msg("Base fees detected - only quote fees are allowed")]
-    BaseFeeDetected,
+    BaseFeeDetected, // runtime: base tokens received; see PoolFeeModeMismatch for pool config
     
     #[msg("24 hour period has not elapsed since last distribution")]
     TooSoonToDistribute,
@@ -86,4 +87,219 @@ pub enum FeeRouterError {
     
     #[msg("Meteora CPI call failed")]
     MeteoraCpiFailed,
+    
+    #[msg("Insufficient lamports to pay the position creation fee")]
+    InsufficientPositionCreationFee,
+    
+    #[msg("Distribution cannot be force completed until the timeout has elapsed")]
+    ForceCompleteNotYetAllowed,
+    
+    #[msg("Stream account is cancelled or expired")]
+    StreamExpiredOrCancelled,
+    
+    #[msg("Too many investors for a single snapshot")]
+    SnapshotCapacityExceeded,
+    
+    #[msg("Signer is not the policy authority")]
+    Unauthorized,
+    
+    #[msg("Protocol fee wallet is not set or does not match the provided account")]
+    ProtocolFeeWalletNotSet,
+    
+    #[msg("Claimer is already authorized for this treasury")]
+    ClaimerAlreadyAuthorized,
+    
+    #[msg("No free additional claimer slots")]
+    ClaimerListFull,
+    
+    #[msg("Claimer is not an additional claimer")]
+    ClaimerNotFound,
+    
+    #[msg("Too many investors in page for the compute budget")]
+    PageTooLarge,
+    
+    #[msg("Claimed amount exceeds the per-interval claim cap")]
+    ClaimExceedsRateLimit,
+    
+    #[msg("Raydium CLMM CPI call failed")]
+    RaydiumCpiFailed,
+    
+    #[msg("Not enough investors to start a distribution")]
+    InsufficientInvestors,
+    
+    #[msg("Distribution window has expired, force-complete required")]
+    DistributionWindowExpired,
+    
+    #[msg("Too many remaining accounts for a single page")]
+    TooManyAccountsInPage,
+    
+    #[msg("Policy has passed its sunset date")]
+    PolicySunsetted,
+    
+    #[msg("New sunset must be later than the current one")]
+    InvalidSunsetExtension,
+    
+    #[msg("Investor snapshot cannot be closed until a day after the distribution completed")]
+    SnapshotNotClosable,
+    
+    #[msg("Minimum payout must be non-zero and within the daily cap")]
+    InvalidMinPayout,
+    
+    #[msg("Distributions are paused")]
+    DistributionPaused,
+    
+    #[msg("Mint is not whitelisted for this treasury")]
+    MintNotAllowed,
+    
+    #[msg("Mint is already whitelisted for this treasury")]
+    MintAlreadyAllowed,
+    
+    #[msg("No free allowed mint slots")]
+    AllowedMintListFull,
+    
+    #[msg("Global state and creator ATA are required to auto-complete the distribution")]
+    AutoCompletionAccountsMissing,
+    
+    #[msg("Investor is already blacklisted")]
+    InvestorAlreadyBlacklisted,
+    
+    #[msg("Investor blacklist is full")]
+    BlacklistFull,
+    
+    #[msg("Investor is not blacklisted")]
+    InvestorNotBlacklisted,
+    
+    #[msg("Page processed in an earlier slot than the previous page")]
+    PageSlotOutOfOrder,
+    
+    #[msg("Pool fee collection mode is not quote-only")]
+    PoolFeeModeMismatch,
+    
+    #[msg("Page not paid - some investor ATAs are missing")]
+    PageTransferPartialFailure,
+
+    #[msg("Distribution already started for this day")]
+    DistributionAlreadyExistsForDay,
+
+    #[msg("Claimed amount is below the requested minimum")]
+    ClaimBelowMinimum,
+
+    #[msg("Policy does not use instant fee distribution")]
+    InstantDistributionDisabled,
+
+    #[msg("Too many investors for instant fee distribution")]
+    InstantDistributionTooManyInvestors,
+
+    #[msg("Stream allocation is already registered")]
+    StreamAlreadyRegistered,
+
+    #[msg("Allocation registry is full")]
+    AllocationRegistryFull,
+
+    #[msg("Registered allocation exceeds Y0")]
+    AllocationExceedsY0,
+
+    #[msg("Unsupported policy version upgrade")]
+    UnsupportedPolicyVersion,
+
+    #[msg("Page size must be between 1 and the maximum investors per page")]
+    InvalidPageSize,
+
+    #[msg("Token account mint does not match the expected mint")]
+    TokenAccountMintMismatch,
+
+    #[msg("Investor registry is full")]
+    InvestorRegistryFull,
+
+    #[msg("Page accounts do not match the investor registry")]
+    RegistryPageMismatch,
+
+    #[msg("Treasury has not claimed fees recently enough to distribute")]
+    TreasuryClaimStale,
+
+    #[msg("Payer does not have enough lamports to fund the account rent")]
+    InsufficientRentForAccount,
+
+    #[msg("Stream account data does not match the Streamflow layout")]
+    InvalidStreamAccountData,
+
+    #[msg("Investor fee share leaves the creator less than the minimum share")]
+    CreatorShareBelowMinimum,
+
+    #[msg("Daily cap is too large")]
+    DailyCapTooLarge,
+
+    #[msg("Minimum payout must be below the daily cap")]
+    MinPayoutExceedsDailyCap,
+
+    #[msg("Y0 total allocation is smaller than the minimum payout")]
+    Y0BelowMinPayout,
+
+    #[msg("Creator ATA is not owned by the registered creator wallet")]
+    CreatorWalletMismatch,
+
+    #[msg("Account does not match the PDA derived from its seeds")]
+    SeedConstraintViolation,
+
+    #[msg("Investor ATA is not owned by the investor")]
+    InvestorAtaOwnerMismatch,
+
+    #[msg("Token vault does not match the pool")]
+    PoolVaultMismatch,
+
+    #[msg("Ramp duration cannot be negative")]
+    InvalidRampDuration,
+
+    #[msg("Duration bonus multiplier must be at least 1x")]
+    InvalidDurationBonusMultiplier,
+
+    #[msg("Claim interval cannot be negative")]
+    InvalidClaimInterval,
+
+    #[msg("Rent receiver does not match the account's recorded payer")]
+    RentPayerMismatch,
+
+    #[msg("Distribution was started from the investor registry; process it with registry pages")]
+    RegistryPagesRequired,
+
+    #[msg("Investor blacklist must be passed once it has been initialized")]
+    BlacklistRequired,
+
+    #[msg("Distribution day must be the current day")]
+    InvalidDistributionDay,
+
+    #[msg("The day's payout summaries must be passed to audit a distribution")]
+    PayoutSummariesRequired,
+
+    #[msg("Page investors do not match the investor snapshot at the cursor")]
+    SnapshotPageMismatch,
+
+    #[msg("Policy update is not backed by a completed governance proposal")]
+    InvalidGovernanceProposal,
+}
+
+/// A `FeeRouterError` carrying the offending values for the logs
+///
+/// Converts into an Anchor error whose message is the variant's message
+/// followed by the context, e.g. "Base fees were claimed ... (amount: 42)".
+#[derive(Debug)]
+pub struct RichError(pub FeeRouterError, pub Option<String>);
+
+impl FeeRouterError {
+    /// Attach dynamic context to the error
+    pub fn with_context(self, ctx: impl Display) -> RichError {
+        RichError(self, Some(ctx.to_string()))
+    }
+}
+
+impl From<RichError> for anchor_lang::error::Error {
+    fn from(rich: RichError) -> Self {
+        let mut error: anchor_lang::error::Error = rich.0.into();
+        if let (anchor_lang::error::Error::AnchorError(anchor_error), Some(context)) =
+            (&mut error, rich.1)
+        {
+            anchor_error.error_msg = format!("{} ({})", anchor_error.error_msg, context);
+        }
+        error
+    }
 }
@@ -20,6 +20,9 @@ pub const POOL_AUTHORITY: Pubkey = Pubkey::new_from_array([
 /// Seeds for position PDA
 pub const POSITION_SEED: &[u8] = b"position";
 
+/// Size of a CP-AMM position account (discriminator included)
+pub const POSITION_ACCOUNT_SIZE: usize = 8 + 400;
+
 /// Seeds for position NFT account PDA
 pub const POSITION_NFT_ACCOUNT_SEED: &[u8] = b"position_nft_account";
 
@@ -288,6 +291,202 @@ pub fn add_liquidity<'info>(
     Ok(())
 }
 
+/// Parameters for removing liquidity from a position
+#[derive(Debug, Clone, Copy)]
+pub struct RemoveLiquidityParameters {
+    /// Delta liquidity to remove
+    pub liquidity_delta: u128,
+    /// Minimum token A amount to receive
+    pub token_a_amount_threshold: u64,
+    /// Minimum token B amount to receive
+    pub token_b_amount_threshold: u64,
+}
+
+impl RemoveLiquidityParameters {
+    /// Instruction discriminator for remove_liquidity (from IDL)
+    pub const DISCRIMINATOR: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+
+    /// Instruction data: discriminator followed by the little-endian parameters
+    pub fn instruction_data(&self) -> Vec<u8> {
+        let mut instruction_data = Vec::with_capacity(8 + 16 + 8 + 8);
+        instruction_data.extend_from_slice(&Self::DISCRIMINATOR);
+        instruction_data.extend_from_slice(&self.liquidity_delta.to_le_bytes());
+        instruction_data.extend_from_slice(&self.token_a_amount_threshold.to_le_bytes());
+        instruction_data.extend_from_slice(&self.token_b_amount_threshold.to_le_bytes());
+        instruction_data
+    }
+}
+
+/// Accounts for the Meteora `remove_liquidity` instruction
+pub struct RemoveLiquidityAccounts<'info> {
+    pub pool_authority: AccountInfo<'info>,
+    pub pool: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub token_a_account: AccountInfo<'info>,
+    pub token_b_account: AccountInfo<'info>,
+    pub token_a_vault: AccountInfo<'info>,
+    pub token_b_vault: AccountInfo<'info>,
+    pub token_a_mint: AccountInfo<'info>,
+    pub token_b_mint: AccountInfo<'info>,
+    pub position_nft_account: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub token_a_program: AccountInfo<'info>,
+    pub token_b_program: AccountInfo<'info>,
+    pub event_authority: AccountInfo<'info>,
+    pub meteora_program: AccountInfo<'info>,
+}
+
+/// Remove liquidity from a Meteora position
+/// 
+/// This withdraws liquidity from a position back to the owner's token
+/// accounts, e.g. to recover the seed liquidity before retiring a position.
+/// 
+/// # Arguments
+/// * `accounts` - The accounts for remove_liquidity
+/// * `params` - Liquidity parameters
+/// * `owner_seeds` - Optional seeds if owner is a PDA
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn remove_liquidity<'info>(
+    accounts: RemoveLiquidityAccounts<'info>,
+    params: RemoveLiquidityParameters,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    msg!("Removing liquidity from Meteora position via CPI");
+
+    // Build accounts for the instruction
+    let account_metas = vec![
+        AccountMeta::new_readonly(accounts.pool_authority.key(), false), // pool_authority
+        AccountMeta::new(accounts.pool.key(), false), // pool
+        AccountMeta::new(accounts.position.key(), false), // position
+        AccountMeta::new(accounts.token_a_account.key(), false), // token_a_account
+        AccountMeta::new(accounts.token_b_account.key(), false), // token_b_account
+        AccountMeta::new(accounts.token_a_vault.key(), false), // token_a_vault
+        AccountMeta::new(accounts.token_b_vault.key(), false), // token_b_vault
+        AccountMeta::new_readonly(accounts.token_a_mint.key(), false), // token_a_mint
+        AccountMeta::new_readonly(accounts.token_b_mint.key(), false), // token_b_mint
+        AccountMeta::new_readonly(accounts.position_nft_account.key(), false), // position_nft_account
+        AccountMeta::new_readonly(accounts.owner.key(), true), // owner (signer)
+        AccountMeta::new_readonly(accounts.token_a_program.key(), false), // token_a_program
+        AccountMeta::new_readonly(accounts.token_b_program.key(), false), // token_b_program
+        AccountMeta::new_readonly(accounts.event_authority.key(), false), // event_authority
+        AccountMeta::new_readonly(accounts.meteora_program.key(), false), // program
+    ];
+
+    let instruction = anchor_lang::solana_program::instruction::Instruction {
+        program_id: METEORA_CP_AMM_PROGRAM_ID,
+        accounts: account_metas,
+        data: params.instruction_data(),
+    };
+
+    let account_infos = [
+        accounts.pool_authority,
+        accounts.pool,
+        accounts.position,
+        accounts.token_a_account,
+        accounts.token_b_account,
+        accounts.token_a_vault,
+        accounts.token_b_vault,
+        accounts.token_a_mint,
+        accounts.token_b_mint,
+        accounts.position_nft_account,
+        accounts.owner,
+        accounts.token_a_program,
+        accounts.token_b_program,
+        accounts.event_authority,
+        accounts.meteora_program,
+    ];
+
+    // Invoke with optional PDA signing
+    if let Some(seeds) = owner_seeds {
+        invoke_signed(&instruction, &account_infos, seeds)?;
+    } else {
+        anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+    }
+
+    msg!("Liquidity removed successfully");
+    Ok(())
+}
+
+/// Accounts for the Meteora `close_position` instruction
+pub struct ClosePositionAccounts<'info> {
+    pub position_nft_mint: AccountInfo<'info>,
+    pub position_nft_account: AccountInfo<'info>,
+    pub pool: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub pool_authority: AccountInfo<'info>,
+    pub rent_receiver: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub event_authority: AccountInfo<'info>,
+    pub meteora_program: AccountInfo<'info>,
+}
+
+/// Close an empty Meteora position
+/// 
+/// This closes the position and its NFT, returning their rent to the
+/// `rent_receiver`. The position must have no liquidity left.
+/// 
+/// # Arguments
+/// * `accounts` - The accounts for close_position
+/// * `owner_seeds` - Optional seeds if owner is a PDA
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_position<'info>(
+    accounts: ClosePositionAccounts<'info>,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    msg!("Closing Meteora position via CPI");
+
+    // Instruction discriminator for close_position (from IDL)
+    let discriminator: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+
+    // Build accounts for the instruction
+    let account_metas = vec![
+        AccountMeta::new(accounts.position_nft_mint.key(), false), // position_nft_mint
+        AccountMeta::new(accounts.position_nft_account.key(), false), // position_nft_account
+        AccountMeta::new(accounts.pool.key(), false), // pool
+        AccountMeta::new(accounts.position.key(), false), // position
+        AccountMeta::new_readonly(accounts.pool_authority.key(), false), // pool_authority
+        AccountMeta::new(accounts.rent_receiver.key(), false), // rent_receiver
+        AccountMeta::new_readonly(accounts.owner.key(), true), // owner (signer)
+        AccountMeta::new_readonly(accounts.token_program.key(), false), // token_program
+        AccountMeta::new_readonly(accounts.event_authority.key(), false), // event_authority
+        AccountMeta::new_readonly(accounts.meteora_program.key(), false), // program
+    ];
+
+    let instruction = anchor_lang::solana_program::instruction::Instruction {
+        program_id: METEORA_CP_AMM_PROGRAM_ID,
+        accounts: account_metas,
+        data: discriminator.to_vec(),
+    };
+
+    let account_infos = [
+        accounts.position_nft_mint,
+        accounts.position_nft_account,
+        accounts.pool,
+        accounts.position,
+        accounts.pool_authority,
+        accounts.rent_receiver,
+        accounts.owner,
+        accounts.token_program,
+        accounts.event_authority,
+        accounts.meteora_program,
+    ];
+
+    // Invoke with optional PDA signing
+    if let Some(seeds) = owner_seeds {
+        invoke_signed(&instruction, &account_infos, seeds)?;
+    } else {
+        anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+    }
+
+    msg!("Position closed successfully");
+    Ok(())
+}
+
 /// Claim fees from a Meteora position
 /// 
 /// This claims accumulated fees from the position to the owner's token accounts.
@@ -399,3 +598,213 @@ pub fn claim_position_fee<'info>(
     msg!("Position fees claimed successfully");
     Ok(())
 }
+
+/// Byte offsets of the fee fields in a CP-AMM position account
+pub mod position_layout {
+    pub const POOL: usize = 8;
+    pub const FEE_A_PER_TOKEN_CHECKPOINT: usize = 72;
+    pub const FEE_B_PER_TOKEN_CHECKPOINT: usize = 104;
+    pub const FEE_A_PENDING: usize = 136;
+    pub const FEE_B_PENDING: usize = 144;
+    pub const UNLOCKED_LIQUIDITY: usize = 152;
+    pub const VESTED_LIQUIDITY: usize = 168;
+    pub const PERMANENT_LOCKED_LIQUIDITY: usize = 184;
+}
+
+/// Fee per liquidity values are fixed point with this many fractional bits
+pub const FEE_PER_LIQUIDITY_SCALE_BITS: u32 = 128;
+
+/// Read the CP-AMM pool state from a pool account
+/// 
+/// # Arguments
+/// * `pool` - The Meteora pool account
+/// 
+/// # Returns
+/// * `Result<Pool>` - The pool state after the account discriminator
+pub fn read_pool(pool: &AccountInfo) -> Result<crate::integrations::meteora::Pool> {
+    require!(
+        *pool.owner == METEORA_CP_AMM_PROGRAM_ID,
+        anchor_lang::error::ErrorCode::ConstraintOwner
+    );
+
+    let pool_data = pool.try_borrow_data()?;
+    let pool_end = 8 + std::mem::size_of::<crate::integrations::meteora::Pool>();
+    require!(
+        pool_data.len() >= pool_end,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+    );
+    Ok(bytemuck::pod_read_unaligned(&pool_data[8..pool_end]))
+}
+
+/// One token side of a `claim_position_fee` CPI
+#[derive(Clone)]
+pub struct ClaimTokenSide<'info> {
+    /// Owner's token account receiving this side's fees
+    pub token_account: AccountInfo<'info>,
+    /// Pool vault of this side
+    pub vault: AccountInfo<'info>,
+    /// Mint of this side
+    pub mint: AccountInfo<'info>,
+}
+
+/// Order the quote and base sides of a claim as the pool's token A and token B
+/// 
+/// The vaults and mints are checked against the pool so the CPI accounts
+/// cannot be swapped or substituted.
+/// 
+/// # Arguments
+/// * `pool` - The deserialized pool state
+/// * `quote` - Quote token account, vault and mint
+/// * `base` - Base token account, vault and mint
+/// 
+/// # Returns
+/// * `Result<(ClaimTokenSide, ClaimTokenSide)>` - `(token_a, token_b)`
+pub fn order_claim_token_sides<'info>(
+    pool: &crate::integrations::meteora::Pool,
+    quote: ClaimTokenSide<'info>,
+    base: ClaimTokenSide<'info>,
+) -> Result<(ClaimTokenSide<'info>, ClaimTokenSide<'info>)> {
+    let (token_a, token_b) = if quote.mint.key() == pool.token_a_mint {
+        (quote, base)
+    } else if quote.mint.key() == pool.token_b_mint {
+        (base, quote)
+    } else {
+        return Err(crate::errors::FeeRouterError::QuoteMintMismatch.into());
+    };
+
+    require!(
+        token_a.mint.key() == pool.token_a_mint && token_b.mint.key() == pool.token_b_mint,
+        crate::errors::FeeRouterError::InvalidTokenOrder
+    );
+    require!(
+        token_a.vault.key() == pool.token_a_vault && token_b.vault.key() == pool.token_b_vault,
+        crate::errors::FeeRouterError::PoolVaultMismatch
+    );
+
+    Ok((token_a, token_b))
+}
+
+/// Read the liquidity a `remove_liquidity` CPI can withdraw from a position
+/// 
+/// Vested and permanently locked liquidity cannot be removed.
+/// 
+/// # Arguments
+/// * `position` - The Meteora position account
+/// 
+/// # Returns
+/// * `Result<u128>` - The position's unlocked liquidity
+pub fn unlocked_liquidity(position: &AccountInfo) -> Result<u128> {
+    require!(
+        *position.owner == METEORA_CP_AMM_PROGRAM_ID,
+        anchor_lang::error::ErrorCode::ConstraintOwner
+    );
+
+    let position_data = position.try_borrow_data()?;
+    require!(
+        position_data.len() >= POSITION_ACCOUNT_SIZE,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+    );
+    let offset = position_layout::UNLOCKED_LIQUIDITY;
+    Ok(u128::from_le_bytes(position_data[offset..offset + 16].try_into().unwrap()))
+}
+
+/// Estimate the fees a `claim_position_fee` CPI would pay out, without a CPI
+/// 
+/// Mirrors CP-AMM's fee update: pending fees plus the position liquidity
+/// times the growth of the pool's fee per liquidity since the position's
+/// checkpoint.
+/// 
+/// # Arguments
+/// * `pool` - The Meteora pool account
+/// * `position` - The position account in that pool
+/// 
+/// # Returns
+/// * `Result<(u64, u64)>` - `(estimated_quote, estimated_base)`
+pub fn estimate_claimable_fees(pool: &AccountInfo, position: &AccountInfo) -> Result<(u64, u64)> {
+    require!(
+        *pool.owner == METEORA_CP_AMM_PROGRAM_ID && *position.owner == METEORA_CP_AMM_PROGRAM_ID,
+        anchor_lang::error::ErrorCode::ConstraintOwner
+    );
+
+    let pool_state = read_pool(pool)?;
+
+    let position_data = position.try_borrow_data()?;
+    require!(
+        position_data.len() >= POSITION_ACCOUNT_SIZE,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+    );
+    require!(
+        position_data[position_layout::POOL..position_layout::POOL + 32] == pool.key().to_bytes(),
+        crate::errors::FeeRouterError::InvalidPoolConfig
+    );
+
+    let read_u64 = |offset: usize| u64::from_le_bytes(position_data[offset..offset + 8].try_into().unwrap());
+    let read_u128 = |offset: usize| u128::from_le_bytes(position_data[offset..offset + 16].try_into().unwrap());
+    let read_u256 = |offset: usize| -> [u8; 32] { position_data[offset..offset + 32].try_into().unwrap() };
+
+    let liquidity = read_u128(position_layout::UNLOCKED_LIQUIDITY)
+        .saturating_add(read_u128(position_layout::VESTED_LIQUIDITY))
+        .saturating_add(read_u128(position_layout::PERMANENT_LOCKED_LIQUIDITY));
+
+    let fee_a = read_u64(position_layout::FEE_A_PENDING).saturating_add(accrued_fee(
+        liquidity,
+        &pool_state.fee_a_per_liquidity,
+        &read_u256(position_layout::FEE_A_PER_TOKEN_CHECKPOINT),
+    ));
+    let fee_b = read_u64(position_layout::FEE_B_PENDING).saturating_add(accrued_fee(
+        liquidity,
+        &pool_state.fee_b_per_liquidity,
+        &read_u256(position_layout::FEE_B_PER_TOKEN_CHECKPOINT),
+    ));
+
+    // Quote-only pools collect their fees in the quote token
+    if pool_state.is_token_a_only() {
+        Ok((fee_a, fee_b))
+    } else {
+        Ok((fee_b, fee_a))
+    }
+}
+
+/// `liquidity * (fee_per_liquidity - checkpoint) >> 128` on little-endian U256 values
+/// 
+/// Saturates at `u64::MAX`; a checkpoint ahead of the pool accrues nothing.
+fn accrued_fee(liquidity: u128, fee_per_liquidity: &[u8; 32], checkpoint: &[u8; 32]) -> u64 {
+    let limbs = |bytes: &[u8; 32]| -> [u64; 4] {
+        std::array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap()))
+    };
+    let current = limbs(fee_per_liquidity);
+    let previous = limbs(checkpoint);
+
+    // growth = current - previous
+    let mut growth = [0u64; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (difference, underflow_a) = current[i].overflowing_sub(previous[i]);
+        let (difference, underflow_b) = difference.overflowing_sub(borrow as u64);
+        growth[i] = difference;
+        borrow = underflow_a || underflow_b;
+    }
+    if borrow {
+        return 0;
+    }
+
+    // product = growth * liquidity (up to 384 bits)
+    let liquidity_limbs = [liquidity as u64, (liquidity >> 64) as u64];
+    let mut product = [0u64; 6];
+    for (i, growth_limb) in growth.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, liquidity_limb) in liquidity_limbs.iter().enumerate() {
+            let value = (*growth_limb as u128) * (*liquidity_limb as u128) + product[i + j] as u128 + carry;
+            product[i + j] = value as u64;
+            carry = value >> 64;
+        }
+        product[i + 2] = carry as u64;
+    }
+
+    // Drop the 128 fractional bits
+    let shift_limbs = (FEE_PER_LIQUIDITY_SCALE_BITS / 64) as usize;
+    if product[shift_limbs + 1..].iter().any(|limb| *limb != 0) {
+        return u64::MAX;
+    }
+    product[shift_limbs]
+}
@@ -30,7 +30,7 @@ pub fn validate_quote_only_pool(pool: &Pool, quote_mint: &Pubkey) -> Result<()>
     // Pool must NOT collect fees in both tokens
     require!(
         fee_mode != CollectFeeMode::Both,
-        FeeRouterError::BaseFeeDetected
+        FeeRouterError::PoolFeeModeMismatch
     );
 
     // Determine which token is A and which is B
@@ -48,14 +48,14 @@ pub fn validate_quote_only_pool(pool: &Pool, quote_mint: &Pubkey) -> Result<()>
         // Quote is token A, so pool must collect fees only in token A
         require!(
             fee_mode == CollectFeeMode::OnlyTokenA,
-            FeeRouterError::BaseFeeDetected
+            FeeRouterError::PoolFeeModeMismatch
         );
         msg!("✅ Pool collects fees only in token A (quote token)");
     } else {
         // Quote is token B, so pool must collect fees only in token B
         require!(
             fee_mode == CollectFeeMode::OnlyTokenB,
-            FeeRouterError::BaseFeeDetected
+            FeeRouterError::PoolFeeModeMismatch
         );
         msg!("✅ Pool collects fees only in token B (quote token)");
     }
@@ -1,4 +1,5 @@
 // Integration modules for external protocols
 
 pub mod meteora;
+pub mod raydium_clmm;
 pub mod streamflow;
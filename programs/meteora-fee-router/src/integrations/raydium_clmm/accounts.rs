@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+
+/// Number of ticks stored in one Raydium tick array account
+pub const TICK_ARRAY_SIZE: i32 = 60;
+
+/// Raydium CLMM PoolState account structure (leading fields only)
+/// Zero-copy and packed on-chain, so it can be read straight from account data
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PoolState {
+    pub bump: [u8; 1],
+    pub amm_config: Pubkey,
+    pub owner: Pubkey,
+    /// Token mint 0 (token_mint_0 < token_mint_1 by key order)
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    pub observation_key: Pubkey,
+    pub mint_decimals_0: u8,
+    pub mint_decimals_1: u8,
+    pub tick_spacing: u16,
+    pub liquidity: u128,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub padding3: u16,
+    pub padding4: u16,
+    pub fee_growth_global_0_x64: u128,
+    pub fee_growth_global_1_x64: u128,
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    pub swap_in_amount_token_0: u128,
+    pub swap_out_amount_token_1: u128,
+    pub swap_in_amount_token_1: u128,
+    pub swap_out_amount_token_0: u128,
+    /// Bitfield of disabled operations (bit 0 = open position / increase liquidity)
+    pub status: u8,
+    pub padding: [u8; 7],
+}
+
+impl PoolState {
+    /// Read the pool state from raw account data (skips the 8-byte discriminator)
+    pub fn from_account_data(data: &[u8]) -> Option<&Self> {
+        let end = 8 + std::mem::size_of::<PoolState>();
+        if data.len() < end {
+            return None;
+        }
+        bytemuck::try_from_bytes(&data[8..end]).ok()
+    }
+
+    /// Check if new positions can be opened in this pool
+    pub fn is_open_position_enabled(&self) -> bool {
+        self.status & 1 == 0
+    }
+
+    /// Check if the quote mint is token 0 of the pool
+    pub fn is_quote_token_0(&self, quote_mint: &Pubkey) -> bool {
+        self.token_mint_0 == *quote_mint
+    }
+}
+
+/// Start index of the tick array containing `tick`
+pub fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
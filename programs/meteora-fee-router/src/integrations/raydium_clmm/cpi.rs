@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Raydium CLMM Program ID: CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0xa5, 0xd5, 0xca, 0x9e, 0x04, 0xcf, 0x5d, 0xb5,
+    0x90, 0xb7, 0x14, 0xba, 0x2f, 0xe3, 0x2c, 0xb1,
+    0x59, 0x13, 0x3f, 0xc1, 0xc1, 0x92, 0xb7, 0x22,
+    0x57, 0xfd, 0x07, 0xd3, 0x9c, 0xb0, 0x40, 0x1e,
+]);
+
+/// Accounts for the Raydium `open_position_v2` instruction
+pub struct RaydiumOpenPositionAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub position_nft_owner: AccountInfo<'info>,
+    pub position_nft_mint: AccountInfo<'info>,
+    pub position_nft_account: AccountInfo<'info>,
+    pub metadata_account: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub protocol_position: AccountInfo<'info>,
+    pub tick_array_lower: AccountInfo<'info>,
+    pub tick_array_upper: AccountInfo<'info>,
+    pub personal_position: AccountInfo<'info>,
+    pub token_account_0: AccountInfo<'info>,
+    pub token_account_1: AccountInfo<'info>,
+    pub token_vault_0: AccountInfo<'info>,
+    pub token_vault_1: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub metadata_program: AccountInfo<'info>,
+    pub token_program_2022: AccountInfo<'info>,
+    pub vault_0_mint: AccountInfo<'info>,
+    pub vault_1_mint: AccountInfo<'info>,
+    pub raydium_program: AccountInfo<'info>,
+}
+
+/// Parameters for opening a Raydium position
+#[derive(Debug, Clone, Copy)]
+pub struct RaydiumOpenPositionParameters {
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub tick_array_lower_start_index: i32,
+    pub tick_array_upper_start_index: i32,
+    /// Liquidity to add (0 = derive from the amount of the base_flag token)
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    /// Some(true) = liquidity derived from token 0, Some(false) = from token 1
+    pub base_flag: Option<bool>,
+}
+
+impl RaydiumOpenPositionParameters {
+    /// Create parameters for a minimal single-sided quote position
+    pub fn minimal_quote_only(
+        tick_range: (i32, i32),
+        tick_array_start_indexes: (i32, i32),
+        quote_is_token_0: bool,
+        quote_amount: u64,
+    ) -> Self {
+        Self {
+            tick_lower_index: tick_range.0,
+            tick_upper_index: tick_range.1,
+            tick_array_lower_start_index: tick_array_start_indexes.0,
+            tick_array_upper_start_index: tick_array_start_indexes.1,
+            liquidity: 0,
+            amount_0_max: if quote_is_token_0 { quote_amount } else { 0 },
+            amount_1_max: if quote_is_token_0 { 0 } else { quote_amount },
+            base_flag: Some(quote_is_token_0),
+        }
+    }
+}
+
+/// Accounts for the Raydium `decrease_liquidity_v2` instruction
+pub struct RaydiumClaimFeesAccounts<'info> {
+    pub nft_owner: AccountInfo<'info>,
+    pub nft_account: AccountInfo<'info>,
+    pub personal_position: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub protocol_position: AccountInfo<'info>,
+    pub token_vault_0: AccountInfo<'info>,
+    pub token_vault_1: AccountInfo<'info>,
+    pub tick_array_lower: AccountInfo<'info>,
+    pub tick_array_upper: AccountInfo<'info>,
+    pub recipient_token_account_0: AccountInfo<'info>,
+    pub recipient_token_account_1: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_program_2022: AccountInfo<'info>,
+    pub memo_program: AccountInfo<'info>,
+    pub vault_0_mint: AccountInfo<'info>,
+    pub vault_1_mint: AccountInfo<'info>,
+    pub raydium_program: AccountInfo<'info>,
+}
+
+/// Create a new position in a Raydium CLMM pool
+///
+/// Opens a position NFT owned by `position_nft_owner` (can be a PDA) and
+/// deposits the initial liquidity in the same instruction.
+///
+/// # Arguments
+/// * `accounts` - The accounts for open_position_v2
+/// * `params` - Tick range and liquidity parameters
+/// * `owner_seeds` - Optional seeds if owner is a PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn raydium_create_position<'info>(
+    accounts: RaydiumOpenPositionAccounts<'info>,
+    params: RaydiumOpenPositionParameters,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    msg!("Creating Raydium CLMM position via CPI");
+
+    // Instruction discriminator for open_position_v2 (from IDL)
+    let discriminator: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+
+    // Build instruction data (discriminator + params)
+    let mut instruction_data = Vec::with_capacity(8 + 51);
+    instruction_data.extend_from_slice(&discriminator);
+    instruction_data.extend_from_slice(&params.tick_lower_index.to_le_bytes());
+    instruction_data.extend_from_slice(&params.tick_upper_index.to_le_bytes());
+    instruction_data.extend_from_slice(&params.tick_array_lower_start_index.to_le_bytes());
+    instruction_data.extend_from_slice(&params.tick_array_upper_start_index.to_le_bytes());
+    instruction_data.extend_from_slice(&params.liquidity.to_le_bytes());
+    instruction_data.extend_from_slice(&params.amount_0_max.to_le_bytes());
+    instruction_data.extend_from_slice(&params.amount_1_max.to_le_bytes());
+    instruction_data.push(0); // with_metadata = false
+    match params.base_flag {
+        Some(flag) => instruction_data.extend_from_slice(&[1, flag as u8]),
+        None => instruction_data.push(0),
+    }
+
+    // Build accounts for the instruction
+    let account_metas = vec![
+        AccountMeta::new(accounts.payer.key(), true), // payer (signer)
+        AccountMeta::new_readonly(accounts.position_nft_owner.key(), false), // position_nft_owner
+        AccountMeta::new(accounts.position_nft_mint.key(), true), // position_nft_mint (signer)
+        AccountMeta::new(accounts.position_nft_account.key(), false), // position_nft_account
+        AccountMeta::new(accounts.metadata_account.key(), false), // metadata_account
+        AccountMeta::new(accounts.pool_state.key(), false), // pool_state
+        AccountMeta::new(accounts.protocol_position.key(), false), // protocol_position
+        AccountMeta::new(accounts.tick_array_lower.key(), false), // tick_array_lower
+        AccountMeta::new(accounts.tick_array_upper.key(), false), // tick_array_upper
+        AccountMeta::new(accounts.personal_position.key(), false), // personal_position
+        AccountMeta::new(accounts.token_account_0.key(), false), // token_account_0
+        AccountMeta::new(accounts.token_account_1.key(), false), // token_account_1
+        AccountMeta::new(accounts.token_vault_0.key(), false), // token_vault_0
+        AccountMeta::new(accounts.token_vault_1.key(), false), // token_vault_1
+        AccountMeta::new_readonly(accounts.rent.key(), false), // rent
+        AccountMeta::new_readonly(accounts.system_program.key(), false), // system_program
+        AccountMeta::new_readonly(accounts.token_program.key(), false), // token_program
+        AccountMeta::new_readonly(accounts.associated_token_program.key(), false), // associated_token_program
+        AccountMeta::new_readonly(accounts.metadata_program.key(), false), // metadata_program
+        AccountMeta::new_readonly(accounts.token_program_2022.key(), false), // token_program_2022
+        AccountMeta::new_readonly(accounts.vault_0_mint.key(), false), // vault_0_mint
+        AccountMeta::new_readonly(accounts.vault_1_mint.key(), false), // vault_1_mint
+    ];
+
+    let instruction = anchor_lang::solana_program::instruction::Instruction {
+        program_id: RAYDIUM_CLMM_PROGRAM_ID,
+        accounts: account_metas,
+        data: instruction_data,
+    };
+
+    let account_infos = [
+        accounts.payer,
+        accounts.position_nft_owner,
+        accounts.position_nft_mint,
+        accounts.position_nft_account,
+        accounts.metadata_account,
+        accounts.pool_state,
+        accounts.protocol_position,
+        accounts.tick_array_lower,
+        accounts.tick_array_upper,
+        accounts.personal_position,
+        accounts.token_account_0,
+        accounts.token_account_1,
+        accounts.token_vault_0,
+        accounts.token_vault_1,
+        accounts.rent,
+        accounts.system_program,
+        accounts.token_program,
+        accounts.associated_token_program,
+        accounts.metadata_program,
+        accounts.token_program_2022,
+        accounts.vault_0_mint,
+        accounts.vault_1_mint,
+        accounts.raydium_program,
+    ];
+
+    // Invoke with optional PDA signing
+    if let Some(seeds) = owner_seeds {
+        invoke_signed(&instruction, &account_infos, seeds)?;
+    } else {
+        anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+    }
+
+    msg!("Raydium position created successfully");
+    Ok(())
+}
+
+/// Claim fees from a Raydium CLMM position
+///
+/// Raydium has no dedicated fee collection instruction; decreasing
+/// liquidity by zero transfers the accrued fees to the recipient accounts.
+///
+/// # Arguments
+/// * `accounts` - The accounts for decrease_liquidity_v2
+/// * `owner_seeds` - Optional seeds if owner is a PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn raydium_claim_fees<'info>(
+    accounts: RaydiumClaimFeesAccounts<'info>,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    msg!("Claiming Raydium position fees via CPI");
+
+    // Instruction discriminator for decrease_liquidity_v2 (from IDL)
+    let discriminator: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+
+    // Build instruction data (discriminator + zero liquidity and minimums)
+    let mut instruction_data = Vec::with_capacity(8 + 32);
+    instruction_data.extend_from_slice(&discriminator);
+    instruction_data.extend_from_slice(&0u128.to_le_bytes()); // liquidity
+    instruction_data.extend_from_slice(&0u64.to_le_bytes()); // amount_0_min
+    instruction_data.extend_from_slice(&0u64.to_le_bytes()); // amount_1_min
+
+    // Build accounts for the instruction
+    let account_metas = vec![
+        AccountMeta::new_readonly(accounts.nft_owner.key(), true), // nft_owner (signer)
+        AccountMeta::new_readonly(accounts.nft_account.key(), false), // nft_account
+        AccountMeta::new(accounts.personal_position.key(), false), // personal_position
+        AccountMeta::new(accounts.pool_state.key(), false), // pool_state
+        AccountMeta::new(accounts.protocol_position.key(), false), // protocol_position
+        AccountMeta::new(accounts.token_vault_0.key(), false), // token_vault_0
+        AccountMeta::new(accounts.token_vault_1.key(), false), // token_vault_1
+        AccountMeta::new(accounts.tick_array_lower.key(), false), // tick_array_lower
+        AccountMeta::new(accounts.tick_array_upper.key(), false), // tick_array_upper
+        AccountMeta::new(accounts.recipient_token_account_0.key(), false), // recipient_token_account_0
+        AccountMeta::new(accounts.recipient_token_account_1.key(), false), // recipient_token_account_1
+        AccountMeta::new_readonly(accounts.token_program.key(), false), // token_program
+        AccountMeta::new_readonly(accounts.token_program_2022.key(), false), // token_program_2022
+        AccountMeta::new_readonly(accounts.memo_program.key(), false), // memo_program
+        AccountMeta::new_readonly(accounts.vault_0_mint.key(), false), // vault_0_mint
+        AccountMeta::new_readonly(accounts.vault_1_mint.key(), false), // vault_1_mint
+    ];
+
+    let instruction = anchor_lang::solana_program::instruction::Instruction {
+        program_id: RAYDIUM_CLMM_PROGRAM_ID,
+        accounts: account_metas,
+        data: instruction_data,
+    };
+
+    let account_infos = [
+        accounts.nft_owner,
+        accounts.nft_account,
+        accounts.personal_position,
+        accounts.pool_state,
+        accounts.protocol_position,
+        accounts.token_vault_0,
+        accounts.token_vault_1,
+        accounts.tick_array_lower,
+        accounts.tick_array_upper,
+        accounts.recipient_token_account_0,
+        accounts.recipient_token_account_1,
+        accounts.token_program,
+        accounts.token_program_2022,
+        accounts.memo_program,
+        accounts.vault_0_mint,
+        accounts.vault_1_mint,
+        accounts.raydium_program,
+    ];
+
+    // Invoke with optional PDA signing
+    if let Some(seeds) = owner_seeds {
+        invoke_signed(&instruction, &account_infos, seeds)?;
+    } else {
+        anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+    }
+
+    msg!("Raydium position fees claimed successfully");
+    Ok(())
+}
@@ -0,0 +1,10 @@
+// Raydium Concentrated Liquidity (CLMM) integration
+
+pub mod cpi;
+pub mod accounts;
+pub mod validation;
+
+// Re-export commonly used items
+pub use cpi::*;
+pub use accounts::*;
+pub use validation::*;
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::integrations::raydium_clmm::accounts::PoolState;
+use crate::errors::FeeRouterError;
+
+/// Tick range for a single-sided quote position next to the current price
+/// 
+/// A CLMM position only holds (and only earns fees while price is inside
+/// its range in) both tokens. Placing the range fully on the quote side of
+/// the current tick keeps the position quote-only at creation; any base
+/// fees accrued later are still rejected by `claim_fees`.
+/// 
+/// # Arguments
+/// * `pool` - The Raydium pool state
+/// * `quote_mint` - The quote token mint
+/// 
+/// # Returns
+/// * `Result<(i32, i32)>` - The (tick_lower, tick_upper) range
+pub fn quote_only_tick_range(pool: &PoolState, quote_mint: &Pubkey) -> Result<(i32, i32)> {
+    let tick_spacing = pool.tick_spacing as i32;
+    require!(tick_spacing > 0, FeeRouterError::InvalidPoolConfig);
+
+    let aligned_tick = pool.tick_current.div_euclid(tick_spacing) * tick_spacing;
+    if pool.is_quote_token_0(quote_mint) {
+        // Range above the current price holds only token 0
+        Ok((aligned_tick + tick_spacing, aligned_tick + 2 * tick_spacing))
+    } else {
+        // Range at or below the current price holds only token 1
+        Ok((aligned_tick - tick_spacing, aligned_tick))
+    }
+}
+
+/// Check the token vaults passed to a fee claim against the pool
+/// 
+/// # Arguments
+/// * `pool` - The Raydium pool state
+/// * `token_vault_0` - The vault passed for token 0
+/// * `token_vault_1` - The vault passed for token 1
+/// 
+/// # Returns
+/// * `Result<()>` - Success if both vaults belong to the pool
+pub fn validate_claim_vaults(
+    pool: &PoolState,
+    token_vault_0: &Pubkey,
+    token_vault_1: &Pubkey,
+) -> Result<()> {
+    require!(
+        pool.token_vault_0 == *token_vault_0 && pool.token_vault_1 == *token_vault_1,
+        FeeRouterError::PoolVaultMismatch
+    );
+    Ok(())
+}
+
+/// Preflight validation before Raydium position creation
+/// 
+/// # Arguments
+/// * `pool` - The Raydium pool state
+/// * `base_mint` - The base mint
+/// * `quote_mint` - The quote mint
+/// 
+/// # Returns
+/// * `Result<()>` - Success if all validations pass
+pub fn preflight_validation(
+    pool: &PoolState,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Result<()> {
+    msg!("Running Raydium CLMM preflight validation");
+
+    require!(
+        pool.is_open_position_enabled(),
+        FeeRouterError::InvalidPoolConfig
+    );
+
+    let has_quote = pool.token_mint_0 == *quote_mint || pool.token_mint_1 == *quote_mint;
+    let has_base = pool.token_mint_0 == *base_mint || pool.token_mint_1 == *base_mint;
+    require!(has_quote, FeeRouterError::QuoteMintMismatch);
+    require!(has_base, FeeRouterError::InvalidTokenOrder);
+    require!(base_mint != quote_mint, FeeRouterError::InvalidTokenOrder);
+
+    msg!("✅ Raydium CLMM preflight validation passed");
+    Ok(())
+}
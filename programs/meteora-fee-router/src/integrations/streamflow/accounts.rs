@@ -1,16 +1,56 @@
 use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+use crate::shared::constants::BPS_DENOMINATOR;
+use crate::shared::math::checked_mul_div;
+
+/// Size of a Streamflow v1 stream (`Contract`) account
+pub const STREAMFLOW_STREAM_SIZE: usize = 1104;
+
+/// `magic` header of a Streamflow v1 stream account
+/// 
+/// Streamflow writes zero here; Anchor accounts or other programs' data
+/// start with a non-zero discriminator and are rejected.
+pub const STREAMFLOW_STREAM_MAGIC: u64 = 0;
+
+/// Byte offsets of the fields we read from a Streamflow v1 stream account
+/// 
+/// Streamflow accounts are borsh-packed without an Anchor discriminator.
+/// Offsets follow the `Contract` layout of the Streamflow protocol (mirrored
+/// by `streamLayout` in their JS SDK).
+pub mod stream_layout {
+    pub const MAGIC: usize = 0;
+    pub const VERSION: usize = 8;
+    pub const CREATED_AT: usize = 9;
+    pub const WITHDRAWN_AMOUNT: usize = 17;
+    pub const CANCELED_AT: usize = 25;
+    pub const END_TIME: usize = 33;
+    pub const SENDER: usize = 49;
+    pub const RECIPIENT: usize = 113;
+    pub const MINT: usize = 177;
+    pub const ESCROW_TOKENS: usize = 209;
+    pub const START_TIME: usize = 409;
+    pub const NET_AMOUNT_DEPOSITED: usize = 417;
+    pub const CLIFF: usize = 441;
+    pub const CLIFF_AMOUNT: usize = 449;
+    pub const CANCELABLE_BY_SENDER: usize = 457;
+    pub const CANCELABLE_BY_RECIPIENT: usize = 458;
+    pub const TRANSFERABLE_BY_SENDER: usize = 460;
+    pub const TRANSFERABLE_BY_RECIPIENT: usize = 461;
+    pub const STREAM_NAME: usize = 463;
+    pub const CLOSED: usize = 671;
+}
 
 /// Streamflow Stream Account Structure
 /// 
-/// This represents the on-chain data structure for a Streamflow stream.
-/// Based on the Streamflow protocol specification.
-#[account]
+/// The fields of a Streamflow stream this program relies on, parsed from
+/// the raw account data with `try_from_account_data`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StreamflowStream {
     /// Magic number to identify stream accounts
     pub magic: u64,
     
     /// Version of the stream account
-    pub version: u64,
+    pub version: u8,
     
     /// Timestamp when the stream was created
     pub created_at: u64,
@@ -21,6 +61,12 @@ pub struct StreamflowStream {
     /// Timestamp when the stream ends
     pub end_time: u64,
     
+    /// Timestamp when linear vesting begins (0 = no cliff)
+    pub cliff_time: u64,
+    
+    /// Amount released at the start of the stream, before linear vesting
+    pub cliff_amount: u64,
+    
     /// Total amount deposited in the stream
     pub deposited_amount: u64,
     
@@ -42,43 +88,120 @@ pub struct StreamflowStream {
     /// Stream name/identifier
     pub name: [u8; 64],
     
-    /// Whether the stream can be cancelled
+    /// Whether the sender or recipient can cancel the stream
     pub can_cancel: bool,
     
-    /// Whether the stream can be transferred
+    /// Whether the sender or recipient can transfer the stream
     pub can_transfer: bool,
     
-    /// Whether the stream has been cancelled
+    /// Whether the stream has been cancelled or closed
     pub cancelled: bool,
-    
-    /// Additional metadata
-    pub metadata: [u8; 128],
 }
 
 impl StreamflowStream {
+    /// Parse a stream from raw Streamflow account data
+    /// 
+    /// Fails with `InvalidStreamAccountData` if the account is too small or
+    /// its `magic` header does not match a Streamflow stream.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= STREAMFLOW_STREAM_SIZE,
+            FeeRouterError::InvalidStreamAccountData
+        );
+
+        let read_u64 = |offset: usize| -> Result<u64> {
+            let bytes: [u8; 8] = data[offset..offset + 8]
+                .try_into()
+                .map_err(|_| FeeRouterError::InvalidStreamAccountData)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let read_pubkey = |offset: usize| -> Result<Pubkey> {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| FeeRouterError::InvalidStreamAccountData.into())
+        };
+        let read_bool = |offset: usize| data[offset] != 0;
+
+        let magic = read_u64(stream_layout::MAGIC)?;
+        require!(
+            magic == STREAMFLOW_STREAM_MAGIC,
+            FeeRouterError::InvalidStreamAccountData
+        );
+
+        let mut name = [0u8; 64];
+        name.copy_from_slice(&data[stream_layout::STREAM_NAME..stream_layout::STREAM_NAME + 64]);
+
+        Ok(Self {
+            magic,
+            version: data[stream_layout::VERSION],
+            created_at: read_u64(stream_layout::CREATED_AT)?,
+            start_time: read_u64(stream_layout::START_TIME)?,
+            end_time: read_u64(stream_layout::END_TIME)?,
+            cliff_time: read_u64(stream_layout::CLIFF)?,
+            cliff_amount: read_u64(stream_layout::CLIFF_AMOUNT)?,
+            deposited_amount: read_u64(stream_layout::NET_AMOUNT_DEPOSITED)?,
+            withdrawn_amount: read_u64(stream_layout::WITHDRAWN_AMOUNT)?,
+            recipient: read_pubkey(stream_layout::RECIPIENT)?,
+            sender: read_pubkey(stream_layout::SENDER)?,
+            mint: read_pubkey(stream_layout::MINT)?,
+            escrow_tokens: read_pubkey(stream_layout::ESCROW_TOKENS)?,
+            name,
+            can_cancel: read_bool(stream_layout::CANCELABLE_BY_SENDER)
+                || read_bool(stream_layout::CANCELABLE_BY_RECIPIENT),
+            can_transfer: read_bool(stream_layout::TRANSFERABLE_BY_SENDER)
+                || read_bool(stream_layout::TRANSFERABLE_BY_RECIPIENT),
+            cancelled: read_u64(stream_layout::CANCELED_AT)? != 0 || read_bool(stream_layout::CLOSED),
+        })
+    }
+
+
+    /// Timestamp at which `cliff_amount` is released and linear vesting begins
+    /// 
+    /// A missing cliff (0) or a cliff outside the stream is clamped to
+    /// `[start_time, end_time]`.
+    pub fn cliff_release_time(&self) -> u64 {
+        self.cliff_time.clamp(self.start_time, self.end_time)
+    }
+    
     /// Calculate the amount that should be unlocked at a given timestamp
+    /// 
+    /// Nothing is unlocked before the cliff. `cliff_amount` unlocks at
+    /// `cliff_time` and the remainder vests linearly from `cliff_time` to
+    /// `end_time`. Streams without a cliff vest linearly from `start_time`.
     pub fn unlocked_amount(&self, current_timestamp: u64) -> u64 {
-        if current_timestamp < self.start_time {
-            // Stream hasn't started yet
-            return 0;
-        }
-        
         if current_timestamp >= self.end_time {
             // Stream has fully vested
             return self.deposited_amount;
         }
         
-        // Linear vesting calculation
-        let elapsed_time = current_timestamp - self.start_time;
-        let total_duration = self.end_time - self.start_time;
+        let cliff_amount = self.cliff_amount.min(self.deposited_amount);
+        let cliff_time = self.cliff_release_time();
+        
+        if current_timestamp < cliff_time {
+            // Nothing is released before the cliff (or the start)
+            return 0;
+        }
+        
+        // Linear vesting calculation for the post-cliff remainder
+        let elapsed_time = current_timestamp - cliff_time;
+        let total_duration = self.end_time - cliff_time;
         
         if total_duration == 0 {
             return self.deposited_amount;
         }
         
         // Calculate proportional unlock
-        let unlocked = (self.deposited_amount as u128 * elapsed_time as u128) / total_duration as u128;
-        unlocked as u64
+        let linear_amount = self.deposited_amount - cliff_amount;
+        let unlocked = (linear_amount as u128 * elapsed_time as u128) / total_duration as u128;
+        cliff_amount + unlocked as u64
+    }
+    
+    /// Cliff amount that is still locked at a given timestamp (released at the cliff)
+    pub fn locked_cliff_amount(&self, current_timestamp: u64) -> u64 {
+        if current_timestamp < self.cliff_release_time() {
+            self.cliff_amount.min(self.deposited_amount)
+        } else {
+            0
+        }
     }
     
     /// Calculate the amount still locked at a given timestamp
@@ -87,6 +210,14 @@ impl StreamflowStream {
         self.deposited_amount.saturating_sub(unlocked)
     }
     
+    /// Percentage (0-100) of the deposit still locked at a given timestamp
+    pub fn locked_fraction_pct(&self, current_timestamp: u64) -> u8 {
+        if self.deposited_amount == 0 {
+            return 0;
+        }
+        (self.locked_amount(current_timestamp) as u128 * 100 / self.deposited_amount as u128) as u8
+    }
+    
     /// Calculate the amount available for withdrawal (unlocked - withdrawn)
     pub fn withdrawable_amount(&self, current_timestamp: u64) -> u64 {
         let unlocked = self.unlocked_amount(current_timestamp);
@@ -104,6 +235,16 @@ impl StreamflowStream {
     pub fn is_fully_vested(&self, current_timestamp: u64) -> bool {
         current_timestamp >= self.end_time
     }
+    
+    /// Seconds left until the stream ends (0 once ended)
+    pub fn remaining_lock_seconds(&self, current_timestamp: u64) -> u64 {
+        self.end_time.saturating_sub(current_timestamp)
+    }
+    
+    /// Check if the stream has been cancelled or has already ended
+    pub fn is_cancelled_or_expired(&self, current_timestamp: u64) -> bool {
+        self.cancelled || self.end_time <= current_timestamp
+    }
 }
 
 /// Helper struct for investor stream data
@@ -123,27 +264,53 @@ pub struct InvestorStreamData {
     
     /// The investor's ATA for receiving payouts
     pub investor_ata: Pubkey,
+    
+    /// Timestamp when the stream becomes active
+    pub start_time: u64,
+    
+    /// Timestamp when the stream ends
+    pub end_time: u64,
+    
+    /// Portion of `locked_amount` that is an unreleased cliff amount
+    pub cliff_locked: u64,
+    
+    /// Remaining lock time in seconds (`end_time - current_timestamp`)
+    pub lock_duration_seconds: u64,
 }
 
 impl InvestorStreamData {
+    /// Locked amount that counts towards weight, optionally excluding the cliff
+    pub fn weight_locked_amount(&self, include_cliff_in_weight: bool) -> u64 {
+        if include_cliff_in_weight {
+            self.locked_amount
+        } else {
+            self.locked_amount.saturating_sub(self.cliff_locked)
+        }
+    }
+    
     /// Calculate the investor's weight in the distribution
-    pub fn calculate_weight(&self, total_locked: u64) -> u64 {
+    pub fn calculate_weight(&self, total_locked: u64, include_cliff_in_weight: bool) -> Result<u64> {
         if total_locked == 0 {
-            return 0;
+            return Ok(0);
         }
         
         // Weight as basis points (out of 10000)
         // weight = (locked_amount / total_locked) * 10000
-        ((self.locked_amount as u128 * 10000u128) / total_locked as u128) as u64
+        checked_mul_div(self.weight_locked_amount(include_cliff_in_weight), BPS_DENOMINATOR, total_locked)
     }
     
     /// Calculate payout amount based on weight and total investor fees
-    pub fn calculate_payout(&self, total_locked: u64, investor_fee_quote: u64) -> u64 {
+    pub fn calculate_payout(&self, total_locked: u64, investor_fee_quote: u64) -> Result<u64> {
         if total_locked == 0 || investor_fee_quote == 0 {
-            return 0;
+            return Ok(0);
         }
         
         // payout = floor(investor_fee_quote * locked_amount / total_locked)
-        ((investor_fee_quote as u128 * self.locked_amount as u128) / total_locked as u128) as u64
+        checked_mul_div(investor_fee_quote, self.locked_amount, total_locked)
+    }
+    
+    /// Total lock duration of the stream in seconds
+    pub fn lock_duration(&self) -> u64 {
+        self.end_time.saturating_sub(self.start_time)
     }
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::integrations::streamflow::accounts::InvestorStreamData;
+use crate::integrations::streamflow::cpi::calculate_locked_fraction;
+use crate::modules::distribution::state::CapApplicationMode;
+use crate::errors::FeeRouterError;
+use crate::shared::math::{checked_add, checked_mul_div, checked_sub};
+use crate::shared::constants::{BPS_DENOMINATOR, DURATION_BONUS_MAX_LOCK_SECONDS, DURATION_BONUS_MIN_LOCK_SECONDS, MAX_LOCK_DURATION_SECONDS};
 
 /// Distribution calculation results
 #[derive(Debug, Clone)]
@@ -18,6 +23,9 @@ pub struct DistributionCalculation {
     
     /// Amount going to creator (remainder)
     pub creator_remainder: u64,
+    
+    /// Investors whose payout fell below the minimum after the daily cap
+    pub post_cap_minimum_failures: u32,
 }
 
 /// Individual investor payout information
@@ -39,6 +47,61 @@ pub struct InvestorPayout {
     pub meets_minimum: bool,
 }
 
+/// How investors' locked amounts are weighted before the pro-rata split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightingOptions {
+    /// Max lock duration multiplier in basis points (10000 = no bonus)
+    pub duration_bonus_max_multiplier_bps: u64,
+    
+    /// Scale weights by remaining lock duration
+    pub use_duration_weighting: bool,
+}
+
+impl Default for WeightingOptions {
+    /// Plain locked-amount weighting: no duration bonus, no duration scaling
+    fn default() -> Self {
+        Self {
+            duration_bonus_max_multiplier_bps: BPS_DENOMINATOR,
+            use_duration_weighting: false,
+        }
+    }
+}
+
+/// Calculate the duration bonus multiplier for a stream's lock duration
+/// 
+/// Locks of `DURATION_BONUS_MIN_LOCK_SECONDS` or less get no bonus (1x). The
+/// multiplier then grows linearly up to `max_multiplier_bps` at
+/// `DURATION_BONUS_MAX_LOCK_SECONDS`:
+/// multiplier = min(max, base + (duration - min_lock) / (max_lock - min_lock) * (max - base))
+/// 
+/// # Arguments
+/// * `lock_duration` - Total lock duration of the stream in seconds
+/// * `max_multiplier_bps` - Maximum multiplier in basis points (10000 = 1x, no bonus)
+/// 
+/// # Returns
+/// * `u64` - The multiplier in basis points
+pub fn calculate_duration_multiplier_bps(lock_duration: u64, max_multiplier_bps: u64) -> u64 {
+    if max_multiplier_bps <= BPS_DENOMINATOR || lock_duration <= DURATION_BONUS_MIN_LOCK_SECONDS {
+        return BPS_DENOMINATOR;
+    }
+    
+    let bonus_range = DURATION_BONUS_MAX_LOCK_SECONDS - DURATION_BONUS_MIN_LOCK_SECONDS;
+    let elapsed = std::cmp::min(lock_duration - DURATION_BONUS_MIN_LOCK_SECONDS, bonus_range);
+    let bonus = ((max_multiplier_bps - BPS_DENOMINATOR) as u128 * elapsed as u128) / bonus_range as u128;
+    
+    std::cmp::min(max_multiplier_bps, BPS_DENOMINATOR + bonus as u64)
+}
+
+/// Scale a locked amount by the remaining lock duration
+/// 
+/// adjusted_weight = locked_amount * min(lock_duration_seconds, MAX) / MAX
+/// where MAX is `MAX_LOCK_DURATION_SECONDS`, so only locks with at least
+/// that much time left keep their full weight.
+pub fn calculate_duration_weighted_amount(locked_amount: u64, lock_duration_seconds: u64) -> u64 {
+    let duration = std::cmp::min(lock_duration_seconds, MAX_LOCK_DURATION_SECONDS);
+    ((locked_amount as u128 * duration as u128) / MAX_LOCK_DURATION_SECONDS as u128) as u64
+}
+
 /// Calculate complete distribution for a page of investors
 /// 
 /// This is the main calculation function that implements the formulas
@@ -51,6 +114,7 @@ pub struct InvestorPayout {
 /// * `initial_total_deposit` - Y0 - initial total deposit amount
 /// * `investor_fee_share_bps` - Maximum investor fee share in basis points
 /// * `min_payout_lamports` - Minimum payout threshold
+/// * `weighting` - Duration bonus and duration weighting applied to locked amounts
 /// 
 /// # Returns
 /// * `Result<DistributionCalculation>` - Complete distribution calculation
@@ -61,16 +125,13 @@ pub fn calculate_distribution(
     initial_total_deposit: u64,
     investor_fee_share_bps: u64,
     min_payout_lamports: u64,
+    weighting: WeightingOptions,
 ) -> Result<DistributionCalculation> {
     msg!("Calculating distribution for {} investors", investor_data.len());
     
-    // Step 1: Calculate locked fraction
+    // Step 1: Calculate locked fraction (clamped to 10000 if Y0 undercounts)
     // f_locked(t) = locked_total(t) / Y0
-    let locked_fraction_bps = if initial_total_deposit == 0 {
-        0
-    } else {
-        ((total_locked as u128 * 10000u128) / initial_total_deposit as u128) as u64
-    };
+    let locked_fraction_bps = calculate_locked_fraction(total_locked, initial_total_deposit);
     
     msg!("Locked fraction: {} bps", locked_fraction_bps);
     
@@ -82,35 +143,60 @@ pub fn calculate_distribution(
     
     // Step 3: Calculate total investor fee amount
     // investor_fee_quote = floor(claimed_quote * eligible_investor_share_bps / 10000)
-    let investor_fee_quote = ((claimed_quote as u128 * eligible_investor_share_bps as u128) / 10000u128) as u64;
+    let investor_fee_quote = checked_mul_div(claimed_quote, eligible_investor_share_bps, BPS_DENOMINATOR)?;
     
     msg!("Total investor fee amount: {} tokens", investor_fee_quote);
     
+    // Step 4: Apply duration bonus (and optional duration weighting) to each
+    // investor's locked amount
+    // weighted_i = locked_i(t) * multiplier_i / 10000
+    let weighted_locked: Vec<u64> = investor_data
+        .iter()
+        .map(|investor| {
+            let multiplier_bps = calculate_duration_multiplier_bps(
+                investor.lock_duration(),
+                weighting.duration_bonus_max_multiplier_bps,
+            );
+            let weighted = checked_mul_div(investor.locked_amount, multiplier_bps, BPS_DENOMINATOR)?;
+            if weighting.use_duration_weighting {
+                Ok(calculate_duration_weighted_amount(weighted, investor.lock_duration_seconds))
+            } else {
+                Ok(weighted)
+            }
+        })
+        .collect::<Result<_>>()?;
+    
+    // The page's weighted amounts replace its raw locked amounts in the total
+    let page_locked = investor_data
+        .iter()
+        .try_fold(0u64, |acc, investor| checked_add(acc, investor.locked_amount))?;
+    let page_weighted = weighted_locked
+        .iter()
+        .try_fold(0u64, |acc, weighted| checked_add(acc, *weighted))?;
+    let total_weighted = checked_add(checked_sub(total_locked, page_locked)?, page_weighted)?;
+    
     // Handle edge case: all unlocked = 100% to creator
-    if total_locked == 0 || investor_fee_quote == 0 {
+    if total_locked == 0 || total_weighted == 0 || investor_fee_quote == 0 {
         return Ok(DistributionCalculation {
             investor_fee_quote: 0,
             investor_payouts: vec![],
             total_distributed: 0,
             dust_amount: 0,
             creator_remainder: claimed_quote,
+            post_cap_minimum_failures: 0,
         });
     }
     
-    // Step 4: Calculate individual payouts
+    // Step 5: Calculate individual payouts
     let mut investor_payouts = Vec::new();
     let mut total_distributed = 0u64;
     
-    for investor in investor_data {
-        // Calculate weight: weight_i(t) = locked_i(t) / locked_total(t)
-        let weight_bps = if total_locked == 0 {
-            0
-        } else {
-            ((investor.locked_amount as u128 * 10000u128) / total_locked as u128) as u64
-        };
+    for (investor, weighted) in investor_data.iter().zip(weighted_locked.iter()) {
+        // Calculate weight: weight_i(t) = weighted_i(t) / weighted_total(t)
+        let weight_bps = checked_mul_div(*weighted, BPS_DENOMINATOR, total_weighted)?;
         
         // Calculate payout: payout_i = floor(investor_fee_quote * weight_i(t))
-        let payout_amount = ((investor_fee_quote as u128 * investor.locked_amount as u128) / total_locked as u128) as u64;
+        let payout_amount = checked_mul_div(investor_fee_quote, *weighted, total_weighted)?;
         
         // Check if payout meets minimum threshold
         let meets_minimum = payout_amount >= min_payout_lamports;
@@ -126,12 +212,12 @@ pub fn calculate_distribution(
             meets_minimum,
         });
         
-        total_distributed = total_distributed.saturating_add(final_payout);
+        total_distributed = checked_add(total_distributed, final_payout)?;
     }
     
-    // Step 5: Calculate dust and creator remainder
-    let dust_amount = investor_fee_quote.saturating_sub(total_distributed);
-    let creator_remainder = claimed_quote.saturating_sub(investor_fee_quote);
+    // Step 6: Calculate dust and creator remainder
+    let dust_amount = checked_sub(investor_fee_quote, total_distributed)?;
+    let creator_remainder = checked_sub(claimed_quote, investor_fee_quote)?;
     
     msg!("Distribution complete: {} distributed, {} dust, {} to creator", 
          total_distributed, dust_amount, creator_remainder);
@@ -142,51 +228,132 @@ pub fn calculate_distribution(
         total_distributed,
         dust_amount,
         creator_remainder,
+        post_cap_minimum_failures: 0,
     })
 }
 
 /// Apply daily cap to distribution amounts
 /// 
+/// Payouts are reduced to fit the cap according to `mode`. Reducing can push
+/// payouts that met the minimum below it, so a second pass zeroes those out
+/// and redistributes their amounts pro-rata to the investors still eligible.
+/// 
 /// # Arguments
 /// * `calculation` - The distribution calculation
 /// * `daily_cap_remaining` - Remaining daily cap
+/// * `min_payout_threshold` - Minimum payout an investor must still receive after capping
+/// * `mode` - How payouts are reduced to fit the cap
 /// 
 /// # Returns
 /// * `DistributionCalculation` - Capped distribution calculation
 pub fn apply_daily_cap(
     mut calculation: DistributionCalculation,
     daily_cap_remaining: u64,
+    min_payout_threshold: u64,
+    mode: CapApplicationMode,
 ) -> DistributionCalculation {
     if calculation.total_distributed <= daily_cap_remaining {
         // No capping needed
         return calculation;
     }
     
-    msg!("Applying daily cap: {} remaining", daily_cap_remaining);
+    msg!("Applying daily cap ({:?}): {} remaining", mode, daily_cap_remaining);
+    
+    let mut new_total_distributed = match mode {
+        CapApplicationMode::ProportionalScale => scale_payouts_to_cap(&mut calculation, daily_cap_remaining),
+        CapApplicationMode::TrimFromBottom => trim_payouts_to_cap(&mut calculation, daily_cap_remaining),
+    };
+    
+    // Second pass: drop payouts the cap pushed below the minimum
+    let mut freed_amount = 0u64;
+    let mut post_cap_minimum_failures = 0u32;
+    
+    for payout in &mut calculation.investor_payouts {
+        if payout.meets_minimum && payout.payout_amount < min_payout_threshold {
+            freed_amount = freed_amount.saturating_add(payout.payout_amount);
+            post_cap_minimum_failures = post_cap_minimum_failures.saturating_add(1);
+            payout.payout_amount = 0;
+            payout.meets_minimum = false;
+        }
+    }
+    
+    // Redistribute the freed amount pro-rata to the remaining eligible investors
+    let eligible_total = new_total_distributed.saturating_sub(freed_amount);
+    if freed_amount > 0 && eligible_total > 0 {
+        let mut redistributed = 0u64;
+        
+        for payout in &mut calculation.investor_payouts {
+            if payout.meets_minimum && payout.payout_amount > 0 {
+                let share = ((freed_amount as u128 * payout.payout_amount as u128) / eligible_total as u128) as u64;
+                payout.payout_amount = payout.payout_amount.saturating_add(share);
+                redistributed = redistributed.saturating_add(share);
+            }
+        }
+        
+        new_total_distributed = eligible_total.saturating_add(redistributed);
+    } else {
+        new_total_distributed = eligible_total;
+    }
+    
+    if post_cap_minimum_failures > 0 {
+        msg!("{} investors fell below minimum after capping, {} redistributed",
+             post_cap_minimum_failures, freed_amount);
+    }
+    
+    calculation.total_distributed = new_total_distributed;
+    calculation.dust_amount = daily_cap_remaining.saturating_sub(new_total_distributed);
+    calculation.post_cap_minimum_failures = post_cap_minimum_failures;
+    
+    msg!("After capping: {} distributed", new_total_distributed);
     
-    // Scale down all payouts proportionally
+    calculation
+}
+
+/// Scale down all payouts by the same factor, returning the new total
+fn scale_payouts_to_cap(calculation: &mut DistributionCalculation, daily_cap_remaining: u64) -> u64 {
     let scale_factor = if calculation.total_distributed == 0 {
         0
     } else {
-        ((daily_cap_remaining as u128 * 10000u128) / calculation.total_distributed as u128) as u64
+        ((daily_cap_remaining as u128 * BPS_DENOMINATOR as u128) / calculation.total_distributed as u128) as u64
     };
     
     let mut new_total_distributed = 0u64;
     
     for payout in &mut calculation.investor_payouts {
         if payout.payout_amount > 0 {
-            let scaled_amount = ((payout.payout_amount as u128 * scale_factor as u128) / 10000u128) as u64;
+            let scaled_amount = ((payout.payout_amount as u128 * scale_factor as u128) / BPS_DENOMINATOR as u128) as u64;
             payout.payout_amount = scaled_amount;
             new_total_distributed = new_total_distributed.saturating_add(scaled_amount);
         }
     }
     
-    calculation.total_distributed = new_total_distributed;
-    calculation.dust_amount = daily_cap_remaining.saturating_sub(new_total_distributed);
+    new_total_distributed
+}
+
+/// Reduce the smallest payouts first until the total fits the cap, returning the new total
+/// 
+/// Larger payouts keep their full amount, so their relative weights are
+/// unaffected by floor division.
+fn trim_payouts_to_cap(calculation: &mut DistributionCalculation, daily_cap_remaining: u64) -> u64 {
+    let mut excess = calculation.total_distributed.saturating_sub(daily_cap_remaining);
     
-    msg!("After capping: {} distributed", new_total_distributed);
+    // Stable sort keeps page order between equal payouts
+    let mut order: Vec<usize> = (0..calculation.investor_payouts.len()).collect();
+    order.sort_by_key(|&index| calculation.investor_payouts[index].payout_amount);
     
-    calculation
+    for index in order {
+        if excess == 0 {
+            break;
+        }
+        let payout = &mut calculation.investor_payouts[index];
+        let reduction = std::cmp::min(payout.payout_amount, excess);
+        payout.payout_amount -= reduction;
+        excess -= reduction;
+    }
+    
+    calculation.investor_payouts
+        .iter()
+        .fold(0u64, |acc, payout| acc.saturating_add(payout.payout_amount))
 }
 
 /// Validate distribution calculation
@@ -214,10 +381,106 @@ pub fn validate_distribution(
     // Validate individual payouts are non-negative
     for payout in &calculation.investor_payouts {
         require!(
-            payout.weight_bps <= 10000,
+            payout.weight_bps <= BPS_DENOMINATOR,
             anchor_lang::error::ErrorCode::ConstraintRaw
         );
     }
     
     Ok(())
 }
+
+/// Check that every payout of a page can be made before any transfer runs
+/// 
+/// A page is paid in full or not at all: any missing investor ATA, or a
+/// treasury too small for the page total, rejects the whole page.
+pub fn validate_page_payable(
+    missing_atas: &[Pubkey],
+    payout_count: usize,
+    payout_total: u64,
+    treasury_balance: u64,
+) -> Result<()> {
+    if let Some(first_missing) = missing_atas.first() {
+        return Err(FeeRouterError::PageTransferPartialFailure
+            .with_context(format!(
+                "{} of {} investor ATAs missing, first: {}",
+                missing_atas.len(), payout_count, first_missing
+            ))
+            .into());
+    }
+    
+    require!(
+        payout_total <= treasury_balance,
+        FeeRouterError::TreasuryBalanceMismatch
+    );
+    
+    Ok(())
+}
+
+/// Per-investor payout statistics for a distribution day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PayoutStatistics {
+    /// Number of payouts summarized
+    pub investor_count: u32,
+    
+    /// Sum of all payouts
+    pub total_distributed: u64,
+    
+    /// Median payout (floor of the mean of the middle two for an even count)
+    pub median_payout: u64,
+    
+    /// Largest payout
+    pub max_payout: u64,
+    
+    /// Smallest payout
+    pub min_payout: u64,
+    
+    /// Gini coefficient in basis points (0 = equal, approaching 10000 = concentrated)
+    pub gini_coefficient_bps: u64,
+}
+
+/// Summarize the payouts made to each investor over a day
+/// 
+/// The Gini coefficient uses the sorted-rank form:
+/// G = 2 * sum(i * x_i) / (n * sum(x)) - (n + 1) / n, for ascending x and i = 1..n
+/// 
+/// # Arguments
+/// * `payouts` - Amount paid to each investor (sorted in place)
+/// 
+/// # Returns
+/// * `PayoutStatistics` - All zero when there are no payouts
+pub fn calculate_payout_statistics(payouts: &mut [u64]) -> PayoutStatistics {
+    if payouts.is_empty() {
+        return PayoutStatistics::default();
+    }
+    payouts.sort_unstable();
+    
+    let n = payouts.len();
+    let total: u128 = payouts.iter().map(|payout| *payout as u128).sum();
+    let median_payout = if n % 2 == 1 {
+        payouts[n / 2]
+    } else {
+        ((payouts[n / 2 - 1] as u128 + payouts[n / 2] as u128) / 2) as u64
+    };
+    
+    let gini_coefficient_bps = if total == 0 {
+        0
+    } else {
+        let rank_weighted: u128 = payouts
+            .iter()
+            .enumerate()
+            .map(|(index, payout)| (index as u128 + 1) * *payout as u128)
+            .sum();
+        let n = n as u128;
+        let bps = BPS_DENOMINATOR as u128;
+        (2 * rank_weighted * bps / (n * total)).saturating_sub((n + 1) * bps / n) as u64
+    };
+    
+    PayoutStatistics {
+        investor_count: n as u32,
+        total_distributed: total.min(u64::MAX as u128) as u64,
+        median_payout,
+        max_payout: payouts[n - 1],
+        min_payout: payouts[0],
+        gini_coefficient_bps,
+    }
+}
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
 use crate::integrations::streamflow::accounts::{StreamflowStream, InvestorStreamData};
+use crate::errors::FeeRouterError;
+use crate::shared::constants::BPS_DENOMINATOR;
+use crate::modules::distribution::events::ShortStreamSkipped;
 
 /// Data for a single investor
 #[derive(Debug, Clone)]
@@ -19,33 +23,29 @@ pub struct StreamError {
 }
 
 /// Types of stream processing errors
-#[derive(Debug, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StreamErrorType {
     InvalidStreamData,
     MissingInvestorAta,
     StreamExpired,
     InsufficientLocked,
     AccountDeserializationFailed,
+    MintMismatch,
 }
 
 /// Read stream data from a Streamflow stream account
 /// 
-/// This function deserializes the stream account data to get
-/// information about locked amounts and vesting schedules.
+/// Streamflow accounts are not Anchor accounts, so the fields are read at
+/// their offsets in the Streamflow v1 layout after checking the account size
+/// and `magic` header.
 /// 
 /// # Arguments
 /// * `stream_account_info` - The AccountInfo for the stream account
 /// 
 /// # Returns
-/// * `Result<StreamData>` - The deserialized stream data
-/// * `Result<StreamflowStream>` - The deserialized stream data
+/// * `Result<StreamflowStream>` - The parsed stream data
 pub fn read_stream_data(stream_account_info: &AccountInfo) -> Result<StreamflowStream> {
-    // Deserialize the account data
-    let stream_data = StreamflowStream::try_deserialize(&mut stream_account_info.data.borrow().as_ref())?;
-    
-    // This would need to be updated based on actual Streamflow implementation
-    
-    Ok(stream_data)
+    StreamflowStream::try_from_account_data(&stream_account_info.data.borrow())
 }
 
 /// Calculate locked amounts for multiple investors with error handling
@@ -58,6 +58,9 @@ pub fn read_stream_data(stream_account_info: &AccountInfo) -> Result<StreamflowS
 /// * `stream_accounts` - Array of stream account infos
 /// * `current_timestamp` - Current Unix timestamp
 /// * `quote_mint` - The quote mint being distributed
+/// * `min_remaining_lock_seconds` - Streams ending sooner than this are skipped
+/// * `supplied_atas` - Keys of the investor ATAs passed in the remaining accounts, or `None`
+///   when payouts are not made from these streams (e.g. snapshots)
 /// 
 /// # Returns
 /// * `Result<(Vec<InvestorStreamData>, u64, Vec<StreamError>)>` - investor data, total locked, and errors
@@ -65,20 +68,22 @@ pub fn calculate_locked_amounts_with_errors(
     stream_accounts: &[AccountInfo],
     current_timestamp: u64,
     quote_mint: &Pubkey,
+    min_remaining_lock_seconds: u64,
+    supplied_atas: Option<&[Pubkey]>,
 ) -> Result<(Vec<InvestorStreamData>, u64, Vec<StreamError>)> {
     let mut investor_data = Vec::new();
     let mut total_locked = 0u64;
     let mut errors = Vec::new();
 
     for stream_account in stream_accounts {
-        match process_single_stream(stream_account, current_timestamp, quote_mint) {
+        match process_single_stream(stream_account, current_timestamp, quote_mint, min_remaining_lock_seconds, supplied_atas) {
             Ok(Some(data)) => {
                 total_locked = total_locked.saturating_add(data.locked_amount);
                 investor_data.push(data);
             }
             Ok(None) => {
-                // Stream has no locked amount - not an error
-                msg!("Stream {} has no locked amount", stream_account.key());
+                // Stream has no locked amount or is too short-term - not an error
+                msg!("Stream {} skipped (no locked amount or below minimum lock)", stream_account.key());
             }
             Err(error) => {
                 msg!("Error processing stream {}: {}", stream_account.key(), error.error_message);
@@ -93,15 +98,37 @@ pub fn calculate_locked_amounts_with_errors(
     Ok((investor_data, total_locked, errors))
 }
 
+/// Derive the associated token account receiving payouts for a stream recipient
+/// 
+/// # Arguments
+/// * `recipient` - The stream recipient (investor wallet)
+/// * `quote_mint` - The quote mint being distributed
+/// 
+/// # Returns
+/// * `Pubkey` - The recipient's associated token account for the quote mint
+pub fn derive_investor_ata(recipient: &Pubkey, quote_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(recipient, quote_mint)
+}
+
 /// Process a single stream account with error handling
 fn process_single_stream(
     stream_account: &AccountInfo,
     current_timestamp: u64,
     quote_mint: &Pubkey,
+    min_remaining_lock_seconds: u64,
+    supplied_atas: Option<&[Pubkey]>,
 ) -> std::result::Result<Option<InvestorStreamData>, StreamError> {
     // Try to read stream data
     let stream = match read_stream_data(stream_account) {
         Ok(stream) => stream,
+        Err(error) if error == FeeRouterError::InvalidStreamAccountData.into() => {
+            return Err(StreamError {
+                stream_account: stream_account.key(),
+                investor: None,
+                error_type: StreamErrorType::InvalidStreamData,
+                error_message: "Stream account size or magic does not match a Streamflow stream".to_string(),
+            });
+        }
         Err(_) => {
             return Err(StreamError {
                 stream_account: stream_account.key(),
@@ -112,6 +139,16 @@ fn process_single_stream(
         }
     };
 
+    // Streams of another mint must not count towards this distribution
+    if stream.mint != *quote_mint {
+        return Err(StreamError {
+            stream_account: stream_account.key(),
+            investor: Some(stream.recipient),
+            error_type: StreamErrorType::MintMismatch,
+            error_message: "Stream mint does not match the quote mint".to_string(),
+        });
+    }
+
     // Validate stream is not expired
     if stream.end_time < current_timestamp {
         return Err(StreamError {
@@ -122,6 +159,17 @@ fn process_single_stream(
         });
     }
 
+    // Skip short-term lockers (stream ends too soon to count)
+    let remaining_seconds = stream.remaining_lock_seconds(current_timestamp);
+    if remaining_seconds < min_remaining_lock_seconds {
+        emit!(ShortStreamSkipped {
+            stream_account: stream_account.key(),
+            remaining_seconds,
+            timestamp: current_timestamp as i64,
+        });
+        return Ok(None);
+    }
+
     // Calculate locked amount using the existing method
     let locked_amount = stream.locked_amount(current_timestamp);
     
@@ -129,15 +177,27 @@ fn process_single_stream(
         return Ok(None); // No locked amount, but not an error
     }
 
-    // TODO: Validate investor ATA exists
-    let investor_ata = stream.recipient; // Placeholder - would need proper ATA derivation
-    
+    // Payouts go to the recipient's quote ATA, which must be supplied with the page
+    let investor_ata = derive_investor_ata(&stream.recipient, quote_mint);
+    if supplied_atas.is_some_and(|atas| !atas.contains(&investor_ata)) {
+        return Err(StreamError {
+            stream_account: stream_account.key(),
+            investor: Some(stream.recipient),
+            error_type: StreamErrorType::MissingInvestorAta,
+            error_message: "Investor ATA not supplied in remaining accounts".to_string(),
+        });
+    }
+
     Ok(Some(InvestorStreamData {
         investor: stream.recipient,
         stream_account: stream_account.key(),
         locked_amount,
         total_deposited: stream.deposited_amount,
         investor_ata,
+        start_time: stream.start_time,
+        end_time: stream.end_time,
+        cliff_locked: stream.locked_cliff_amount(current_timestamp),
+        lock_duration_seconds: remaining_seconds,
     }))
 }
 
@@ -150,6 +210,7 @@ fn process_single_stream(
 /// * `stream_accounts` - Array of stream account infos
 /// * `current_timestamp` - Current Unix timestamp
 /// * `quote_mint` - The quote mint being distributed
+/// * `min_remaining_lock_seconds` - Streams ending sooner than this are skipped
 /// 
 /// # Returns
 /// * `Result<(Vec<InvestorStreamData>, u64)>` - Investor data and total locked
@@ -157,11 +218,14 @@ pub fn calculate_locked_amounts(
     stream_accounts: &[AccountInfo],
     current_timestamp: u64,
     quote_mint: &Pubkey,
+    min_remaining_lock_seconds: u64,
 ) -> Result<(Vec<InvestorStreamData>, u64)> {
     let (investor_data, total_locked, errors) = calculate_locked_amounts_with_errors(
         stream_accounts,
         current_timestamp,
         quote_mint,
+        min_remaining_lock_seconds,
+        None,
     )?;
 
     // Log errors but don't fail the entire operation
@@ -172,10 +236,35 @@ pub fn calculate_locked_amounts(
     Ok((investor_data, total_locked))
 }
 
+/// Count streams that end sooner than the minimum remaining lock
+/// 
+/// Unreadable accounts are ignored here; they are reported by
+/// `calculate_locked_amounts_with_errors`.
+/// 
+/// # Arguments
+/// * `stream_accounts` - Array of stream account infos
+/// * `current_timestamp` - Current Unix timestamp
+/// * `min_remaining_lock_seconds` - Minimum remaining lock required
+/// 
+/// # Returns
+/// * `u32` - Number of streams below the minimum
+pub fn count_streams_below_min_duration(
+    stream_accounts: &[AccountInfo],
+    current_timestamp: u64,
+    min_remaining_lock_seconds: u64,
+) -> u32 {
+    stream_accounts
+        .iter()
+        .filter_map(|stream_account| read_stream_data(stream_account).ok())
+        .filter(|stream| stream.remaining_lock_seconds(current_timestamp) < min_remaining_lock_seconds)
+        .count() as u32
+}
+
 /// Calculate the locked fraction for fee distribution
 /// 
 /// This implements the formula: f_locked(t) = locked_total(t) / Y0
-/// where Y0 is the initial total deposit amount.
+/// where Y0 is the initial total deposit amount. If Y0 undercounts the
+/// deposits (locked_total > Y0), the fraction is clamped to 100%.
 /// 
 /// # Arguments
 /// * `locked_total` - Total amount currently locked across all streams
@@ -184,13 +273,27 @@ pub fn calculate_locked_amounts(
 /// # Returns
 /// * `u64` - The locked fraction as basis points (out of 10000)
 pub fn calculate_locked_fraction(locked_total: u64, initial_total_deposit: u64) -> u64 {
+    std::cmp::min(calculate_uncapped_locked_fraction(locked_total, initial_total_deposit), BPS_DENOMINATOR)
+}
+
+/// Calculate the raw locked fraction without clamping to 100%
+/// 
+/// Values above 10000 mean Y0 is lower than the amount currently locked.
+/// 
+/// # Arguments
+/// * `locked_total` - Total amount currently locked across all streams
+/// * `initial_total_deposit` - Y0 - the initial total deposit amount
+/// 
+/// # Returns
+/// * `u64` - The uncapped locked fraction as basis points
+pub fn calculate_uncapped_locked_fraction(locked_total: u64, initial_total_deposit: u64) -> u64 {
     if initial_total_deposit == 0 {
         return 0;
     }
     
     // f_locked(t) = locked_total(t) / Y0
     // Return as basis points (multiply by 10000)
-    ((locked_total as u128 * 10000u128) / initial_total_deposit as u128) as u64
+    ((locked_total as u128 * BPS_DENOMINATOR as u128) / initial_total_deposit as u128) as u64
 }
 
 /// Calculate eligible investor share based on locked fraction
@@ -198,7 +301,8 @@ pub fn calculate_locked_fraction(locked_total: u64, initial_total_deposit: u64)
 /// This implements: eligible_investor_share_bps = min(investor_fee_share_bps, floor(f_locked(t) * 10000))
 /// 
 /// # Arguments
-/// * `investor_fee_share_bps` - Maximum investor fee share in basis points
+/// * `investor_fee_share_bps` - Maximum investor fee share in basis points (already ramped,
+///   see `PolicyState::fee_share_ramp`)
 /// * `locked_fraction_bps` - Current locked fraction in basis points
 /// 
 /// # Returns
@@ -224,27 +328,47 @@ pub fn calculate_investor_fee_amount(
     claimed_quote: u64,
     eligible_investor_share_bps: u64,
 ) -> u64 {
-    ((claimed_quote as u128 * eligible_investor_share_bps as u128) / 10000u128) as u64
+    ((claimed_quote as u128 * eligible_investor_share_bps as u128) / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Validate that a stream streams the expected mint
+/// 
+/// # Arguments
+/// * `stream_account_info` - The stream account to validate
+/// * `expected_mint` - The quote mint the stream must stream
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn validate_stream_mint(stream_account_info: &AccountInfo, expected_mint: &Pubkey) -> Result<()> {
+    let stream = read_stream_data(stream_account_info)?;
+    require!(
+        stream.mint == *expected_mint,
+        FeeRouterError::QuoteMintMismatch
+    );
+    Ok(())
 }
 
-/// Validate stream account ownership and program
+/// Validate stream account ownership, program and liveness
 /// 
 /// # Arguments
 /// * `stream_account_info` - The stream account to validate
+/// * `current_timestamp` - Current Unix timestamp
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn validate_stream_account(stream_account_info: &AccountInfo) -> Result<()> {
+pub fn validate_stream_account(stream_account_info: &AccountInfo, current_timestamp: u64) -> Result<()> {
     // Validate that the account is owned by the Streamflow program
     require!(
         stream_account_info.owner == &crate::integrations::streamflow::STREAMFLOW_PROGRAM_ID,
         anchor_lang::error::ErrorCode::ConstraintOwner
     );
     
-    // Additional validations could go here
-    // - Check account discriminator
-    // - Validate account size
-    // - Check magic numbers
+    // Validate the stream is still live (not cancelled, not ended)
+    let stream = read_stream_data(stream_account_info)?;
+    require!(
+        !stream.is_cancelled_or_expired(current_timestamp),
+        FeeRouterError::StreamExpiredOrCancelled
+    );
     
     Ok(())
 }
@@ -9,14 +9,15 @@ pub mod shared;
 pub mod errors;
 
 // Import what we need
-use modules::position::contexts::InitializePosition;
-use modules::position::contexts::__client_accounts_initialize_position;
+use modules::position::contexts::{InitializePosition, UpdatePositionValue, ClosePosition};
+use modules::position::contexts::{__client_accounts_initialize_position, __client_accounts_update_position_value, __client_accounts_close_position};
 use modules::position::instructions;
-use modules::claiming::contexts::{InitializeTreasury, ClaimFees};
-use modules::claiming::contexts::{__client_accounts_initialize_treasury, __client_accounts_claim_fees};
+use modules::position::state::PoolType;
+use modules::claiming::contexts::{InitializeTreasury, ClaimFees, UpdateReserveRatio, ManageClaimers, ManageAllowedMints};
+use modules::claiming::contexts::{__client_accounts_initialize_treasury, __client_accounts_claim_fees, __client_accounts_update_reserve_ratio, __client_accounts_manage_claimers, __client_accounts_manage_allowed_mints};
 use modules::claiming::instructions as claiming_instructions;
-use modules::distribution::contexts::{InitializePolicy, InitializeGlobalDistribution, StartDailyDistribution, ProcessInvestorPage, CompleteDailyDistribution};
-use modules::distribution::contexts::{__client_accounts_initialize_policy, __client_accounts_initialize_global_distribution, __client_accounts_start_daily_distribution, __client_accounts_process_investor_page, __client_accounts_complete_daily_distribution};
+use modules::distribution::contexts::{InitializeProgramConfig, ManageProgramPause, InitializePolicy, InitializeGlobalDistribution, UpdateCreatorWallet, LinkPositionToDistribution, ExtendSunset, UpgradePolicyFormat, UpdateMinPayout, UpdatePolicySettings, InitializeBlacklist, ManageBlacklist, InitializeAllocationRegistry, RegisterStreamAllocations, InitializeInvestorRegistry, AddInvestor, TakeInvestorSnapshot, ValidateSnapshotStreams, CloseInvestorSnapshot, CloseInvestorPayoutSummary, CloseInvestorCache, CloseStreamErrorLog, StartDailyDistribution, InitializeAtomicDistribution, ClaimAndDistributeInstant, ProcessInvestorPage, ProcessInvestorPageFromSnapshot, ProcessInvestorPageFromRegistry, AdjustEffectivePageSize, CompleteDailyDistribution, ForceCompleteDailyDistribution, VerifyDistributionIntegrity, AuditCompletedDistribution, GenerateDistributionReport, GetStreamLockedAmount, GetStreamErrors};
+use modules::distribution::contexts::{__client_accounts_initialize_program_config, __client_accounts_manage_program_pause, __client_accounts_initialize_policy, __client_accounts_initialize_global_distribution, __client_accounts_update_creator_wallet, __client_accounts_link_position_to_distribution, __client_accounts_extend_sunset, __client_accounts_upgrade_policy_format, __client_accounts_update_min_payout, __client_accounts_update_policy_settings, __client_accounts_initialize_blacklist, __client_accounts_manage_blacklist, __client_accounts_initialize_allocation_registry, __client_accounts_register_stream_allocations, __client_accounts_initialize_investor_registry, __client_accounts_add_investor, __client_accounts_take_investor_snapshot, __client_accounts_validate_snapshot_streams, __client_accounts_close_investor_snapshot, __client_accounts_close_investor_payout_summary, __client_accounts_close_investor_cache, __client_accounts_close_stream_error_log, __client_accounts_start_daily_distribution, __client_accounts_initialize_atomic_distribution, __client_accounts_claim_and_distribute_instant, __client_accounts_process_investor_page, __client_accounts_process_investor_page_from_snapshot, __client_accounts_process_investor_page_from_registry, __client_accounts_adjust_effective_page_size, __client_accounts_complete_daily_distribution, __client_accounts_force_complete_daily_distribution, __client_accounts_verify_distribution_integrity, __client_accounts_audit_completed_distribution, __client_accounts_generate_distribution_report, __client_accounts_get_stream_locked_amount, __client_accounts_get_stream_errors};
 use modules::distribution::instructions as distribution_instructions;
 
 #[program]
@@ -24,49 +25,257 @@ pub mod meteora_fee_router {
     use super::*;
 
     /// Initialize the honorary fee position for quote-only fee accrual
-    pub fn initialize_position(ctx: Context<InitializePosition>) -> Result<()> {
-        instructions::initialize_position(ctx)
+    pub fn initialize_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializePosition<'info>>,
+        pool_type: PoolType,
+    ) -> Result<()> {
+        instructions::initialize_position(ctx, pool_type)
+    }
+
+    /// Set the position value used for the fee APR
+    pub fn update_position_value(ctx: Context<UpdatePositionValue>, position_value_quote: u64) -> Result<()> {
+        instructions::update_position_value(ctx, position_value_quote)
+    }
+
+    /// Retire the honorary position and close its metadata
+    pub fn close_position(ctx: Context<ClosePosition>, claim_final_fees: bool) -> Result<()> {
+        instructions::close_position(ctx, claim_final_fees)
     }
 
     /// Initialize the treasury for fee claiming
-    pub fn initialize_treasury(ctx: Context<InitializeTreasury>, quote_mint: Pubkey) -> Result<()> {
-        claiming_instructions::initialize_treasury(ctx, quote_mint)
+    pub fn initialize_treasury(
+        ctx: Context<InitializeTreasury>,
+        quote_mint: Pubkey,
+        reserve_ratio_bps: u64,
+        max_claim_per_interval_bps: u64,
+        min_claim_interval_seconds: i64,
+    ) -> Result<()> {
+        claiming_instructions::initialize_treasury(ctx, quote_mint, reserve_ratio_bps, max_claim_per_interval_bps, min_claim_interval_seconds)
+    }
+
+    /// Update the portion of the treasury held back from distribution
+    pub fn update_reserve_ratio(ctx: Context<UpdateReserveRatio>, reserve_ratio_bps: u64) -> Result<()> {
+        claiming_instructions::update_reserve_ratio(ctx, reserve_ratio_bps)
+    }
+
+    /// Authorize an additional position owner PDA to claim into the treasury
+    pub fn add_claimer(ctx: Context<ManageClaimers>, claimer: Pubkey) -> Result<()> {
+        claiming_instructions::add_claimer(ctx, claimer)
+    }
+
+    /// Revoke an additional treasury claimer
+    pub fn remove_claimer(ctx: Context<ManageClaimers>, claimer: Pubkey) -> Result<()> {
+        claiming_instructions::remove_claimer(ctx, claimer)
+    }
+
+    /// Whitelist an additional mint the treasury may receive
+    pub fn add_allowed_mint(ctx: Context<ManageAllowedMints>, mint: Pubkey) -> Result<()> {
+        claiming_instructions::add_allowed_mint(ctx, mint)
+    }
+
+    /// Remove a mint from the treasury whitelist
+    pub fn remove_allowed_mint(ctx: Context<ManageAllowedMints>, mint: Pubkey) -> Result<()> {
+        claiming_instructions::remove_allowed_mint(ctx, mint)
     }
     /// Claim fees from the honorary position
-    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+    pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>) -> Result<()> {
         claiming_instructions::claim_fees(ctx)
     }
 
+    /// Claim fees from the honorary position, failing below `min_claim_amount`
+    pub fn claim_fees_with_minimum<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>,
+        min_claim_amount: u64,
+    ) -> Result<()> {
+        claiming_instructions::claim_fees_with_minimum(ctx, min_claim_amount)
+    }
+
     /// Initialize global distribution state
-    pub fn initialize_global_distribution(ctx: Context<InitializeGlobalDistribution>, quote_mint: Pubkey) -> Result<()> {
-        distribution_instructions::initialize_global_distribution(ctx, quote_mint)
+    pub fn initialize_global_distribution(
+        ctx: Context<InitializeGlobalDistribution>,
+        quote_mint: Pubkey,
+        creator_wallet: Pubkey,
+        protocol_fee_wallet: Option<Pubkey>,
+    ) -> Result<()> {
+        distribution_instructions::initialize_global_distribution(ctx, quote_mint, creator_wallet, protocol_fee_wallet)
+    }
+
+    /// Update the wallet that receives the creator share
+    pub fn update_creator_wallet(ctx: Context<UpdateCreatorWallet>, new_creator_wallet: Pubkey) -> Result<()> {
+        distribution_instructions::update_creator_wallet(ctx, new_creator_wallet)
+    }
+
+    /// Record the honorary position's creation time on the global distribution state
+    pub fn link_position_to_distribution(ctx: Context<LinkPositionToDistribution>) -> Result<()> {
+        distribution_instructions::link_position_to_distribution(ctx)
+    }
+
+    /// Push the policy sunset date later (0 removes it)
+    pub fn extend_sunset(ctx: Context<ExtendSunset>, new_sunset_timestamp: i64) -> Result<()> {
+        distribution_instructions::extend_sunset(ctx, new_sunset_timestamp)
+    }
+
+    /// Migrate the policy account to the next layout version (policy authority only)
+    pub fn upgrade_policy_format(ctx: Context<UpgradePolicyFormat>, target_version: u8) -> Result<()> {
+        distribution_instructions::upgrade_policy_format(ctx, target_version)
+    }
+
+    /// Update the policy's minimum payout threshold
+    pub fn update_min_payout(ctx: Context<UpdateMinPayout>, new_min_payout: u64) -> Result<()> {
+        distribution_instructions::update_min_payout(ctx, new_min_payout)
+    }
+
+    /// Update the policy's optional settings (policy authority only, backed by a completed proposal in governance mode)
+    pub fn update_policy_settings(
+        ctx: Context<UpdatePolicySettings>,
+        params: distribution_instructions::UpdatePolicySettingsParams,
+    ) -> Result<()> {
+        distribution_instructions::update_policy_settings(ctx, params)
+    }
+
+    /// Create the investor blacklist for a quote mint
+    pub fn initialize_blacklist(ctx: Context<InitializeBlacklist>) -> Result<()> {
+        distribution_instructions::initialize_blacklist(ctx)
+    }
+
+    /// Blacklist an investor so they receive no payouts
+    pub fn add_to_blacklist(ctx: Context<ManageBlacklist>, investor: Pubkey) -> Result<()> {
+        distribution_instructions::add_to_blacklist(ctx, investor)
+    }
+
+    /// Remove an investor from the blacklist
+    pub fn remove_from_blacklist(ctx: Context<ManageBlacklist>, investor: Pubkey) -> Result<()> {
+        distribution_instructions::remove_from_blacklist(ctx, investor)
+    }
+
+    /// Create the allocation registry for a quote mint (policy authority only)
+    pub fn initialize_allocation_registry(ctx: Context<InitializeAllocationRegistry>) -> Result<()> {
+        distribution_instructions::initialize_allocation_registry(ctx)
+    }
+
+    /// Add investor stream deposits to the allocation registry (policy authority only)
+    pub fn register_stream_allocations(ctx: Context<RegisterStreamAllocations>) -> Result<()> {
+        distribution_instructions::register_stream_allocations(ctx)
+    }
+
+    /// Create the paginated investor registry for a quote mint
+    pub fn initialize_investor_registry(ctx: Context<InitializeInvestorRegistry>, investor_capacity: u32) -> Result<()> {
+        distribution_instructions::initialize_investor_registry(ctx, investor_capacity)
+    }
+
+    /// Append an investor to the investor registry
+    pub fn add_investor(ctx: Context<AddInvestor>, investor: Pubkey, stream_account: Pubkey) -> Result<()> {
+        distribution_instructions::add_investor(ctx, investor, stream_account)
+    }
+
+    /// Initialize the program config with its pause authority (upgrade authority only)
+    pub fn initialize_program_config(ctx: Context<InitializeProgramConfig>, program_pause_authority: Pubkey) -> Result<()> {
+        distribution_instructions::initialize_program_config(ctx, program_pause_authority)
+    }
+
+    /// Pause new distribution work (pause authority only)
+    pub fn pause_distribution(ctx: Context<ManageProgramPause>) -> Result<()> {
+        distribution_instructions::pause_distribution(ctx)
+    }
+
+    /// Resume paused distribution work (pause authority only)
+    pub fn unpause_distribution(ctx: Context<ManageProgramPause>) -> Result<()> {
+        distribution_instructions::unpause_distribution(ctx)
+    }
+
+    /// Hand the pause role to a new authority (pause authority only)
+    pub fn set_program_pause_authority(ctx: Context<ManageProgramPause>, new_authority: Pubkey) -> Result<()> {
+        distribution_instructions::set_program_pause_authority(ctx, new_authority)
     }
 
     /// Initialize policy parameters
     pub fn initialize_policy(
         ctx: Context<InitializePolicy>,
-        investor_fee_share_bps: u64,
-        daily_cap_lamports: u64,
-        min_payout_lamports: u64,
-        y0_total_allocation: u64,
+        params: distribution_instructions::InitializePolicyParams,
     ) -> Result<()> {
-        distribution_instructions::initialize_policy(
-            ctx,
-            investor_fee_share_bps,
-            daily_cap_lamports,
-            min_payout_lamports,
-            y0_total_allocation,
-        )
+        distribution_instructions::initialize_policy(ctx, params)
+    }
+
+    /// Take the investor snapshot for a distribution day
+    pub fn take_investor_snapshot(ctx: Context<TakeInvestorSnapshot>, distribution_day: i64) -> Result<()> {
+        distribution_instructions::take_investor_snapshot(ctx, distribution_day)
+    }
+
+    /// Check that a snapshot's streams still stream the quote mint
+    pub fn validate_snapshot_streams(ctx: Context<ValidateSnapshotStreams>, start_index: u32) -> Result<()> {
+        distribution_instructions::validate_snapshot_streams(ctx, start_index)
+    }
+
+    /// Close a completed day's investor snapshot and recover its rent
+    pub fn close_investor_snapshot(ctx: Context<CloseInvestorSnapshot>) -> Result<()> {
+        distribution_instructions::close_investor_snapshot(ctx)
+    }
+
+    /// Close a completed day's investor payout summary and return its rent to the cranker
+    pub fn close_investor_payout_summary(ctx: Context<CloseInvestorPayoutSummary>) -> Result<()> {
+        distribution_instructions::close_investor_payout_summary(ctx)
+    }
+
+    /// Close a completed day's investor aggregate cache and return its rent to the cranker
+    pub fn close_investor_cache(ctx: Context<CloseInvestorCache>) -> Result<()> {
+        distribution_instructions::close_investor_cache(ctx)
+    }
+
+    /// Close a completed day's stream error log and reclaim its rent
+    pub fn close_stream_error_log(ctx: Context<CloseStreamErrorLog>) -> Result<()> {
+        distribution_instructions::close_stream_error_log(ctx)
     }
 
     /// Start a new daily distribution (24-hour crank)
-    pub fn start_daily_distribution(ctx: Context<StartDailyDistribution>, distribution_day: i64) -> Result<()> {
+    pub fn start_daily_distribution<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, StartDailyDistribution<'info>>,
+        distribution_day: i64,
+    ) -> Result<()> {
         distribution_instructions::start_daily_distribution(ctx, distribution_day)
     }
 
+    /// Claim fees and start a daily distribution in one instruction
+    pub fn initialize_atomic_distribution<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeAtomicDistribution<'info>>,
+        distribution_day: i64,
+    ) -> Result<()> {
+        distribution_instructions::initialize_atomic_distribution(ctx, distribution_day)
+    }
+
     /// Process a page of investors in the current distribution
-    pub fn process_investor_page(ctx: Context<ProcessInvestorPage>) -> Result<()> {
-        distribution_instructions::process_investor_page(ctx)
+    pub fn process_investor_page<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProcessInvestorPage<'info>>,
+        expected_page_index: u32,
+    ) -> Result<()> {
+        distribution_instructions::process_investor_page(ctx, expected_page_index)
+    }
+
+    /// Process a page of investors from the day's investor snapshot
+    pub fn process_investor_page_from_snapshot<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProcessInvestorPageFromSnapshot<'info>>,
+        page_index: u32,
+    ) -> Result<()> {
+        distribution_instructions::process_investor_page_from_snapshot(ctx, page_index)
+    }
+
+    /// Process the next page of investors from the investor registry
+    pub fn process_investor_page_from_registry<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProcessInvestorPageFromRegistry<'info>>,
+    ) -> Result<()> {
+        distribution_instructions::process_investor_page_from_registry(ctx)
+    }
+
+    /// Change the page size for the remaining pages of a running distribution
+    pub fn adjust_effective_page_size(ctx: Context<AdjustEffectivePageSize>, new_size: u32) -> Result<()> {
+        distribution_instructions::adjust_effective_page_size(ctx, new_size)
+    }
+
+    /// Claim fees and pay them to the snapshot investors immediately (instant mode)
+    pub fn claim_and_distribute_instant<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAndDistributeInstant<'info>>,
+        distribution_day: i64,
+    ) -> Result<()> {
+        distribution_instructions::claim_and_distribute_instant(ctx, distribution_day)
     }
 
     /// Complete the daily distribution
@@ -74,6 +283,36 @@ pub mod meteora_fee_router {
         distribution_instructions::complete_daily_distribution(ctx)
     }
 
+    /// Force completion of a distribution stuck for more than 48 hours
+    pub fn force_complete_distribution(ctx: Context<ForceCompleteDailyDistribution>) -> Result<()> {
+        distribution_instructions::force_complete_distribution(ctx)
+    }
+
+    /// Audit the accounting of a completed distribution
+    pub fn verify_distribution_integrity(ctx: Context<VerifyDistributionIntegrity>) -> Result<()> {
+        distribution_instructions::verify_distribution_integrity(ctx)
+    }
+
+    /// Audit a completed distribution against its policy snapshot and payout summaries
+    pub fn audit_completed_distribution(ctx: Context<AuditCompletedDistribution>, distribution_day: i64) -> Result<()> {
+        distribution_instructions::audit_completed_distribution(ctx, distribution_day)
+    }
+
+    /// Emit per-investor payout statistics for a completed distribution
+    pub fn generate_distribution_report(ctx: Context<GenerateDistributionReport>, distribution_day: i64) -> Result<()> {
+        distribution_instructions::generate_distribution_report(ctx, distribution_day)
+    }
+
+    /// Emit a Streamflow stream's locked amount for frontends (read-only)
+    pub fn get_stream_locked_amount(ctx: Context<GetStreamLockedAmount>) -> Result<()> {
+        distribution_instructions::get_stream_locked_amount(ctx)
+    }
+
+    /// Emit the stream errors recorded during a completed day
+    pub fn get_stream_errors(ctx: Context<GetStreamErrors>) -> Result<()> {
+        distribution_instructions::get_stream_errors(ctx)
+    }
+
     // TODO: Add other instructions as modules are built
     // pub fn initialize_policy(ctx: Context<policy::InitializePolicy>, ...) -> Result<()>
 }
@@ -3,8 +3,10 @@ use anchor_spl::token::{Mint, TokenAccount, Token};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::modules::claiming::state::TreasuryState;
 use crate::modules::position::state::PositionMetadata;
+use crate::modules::distribution::state::PolicyState;
 use crate::integrations::meteora::POOL_AUTHORITY;
-use crate::shared::constants::*;
+use crate::integrations::raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID;
+use crate::errors::FeeRouterError;
 
 /// Accounts required to initialize the treasury for fee claiming
 #[derive(Accounts)]
@@ -68,6 +70,7 @@ pub struct InitializeTreasury<'info> {
 pub struct ClaimFees<'info> {
     /// The position metadata account
     #[account(
+        mut,
         seeds = [b"position_metadata", position_nft_mint.key().as_ref()],
         bump,
     )]
@@ -91,11 +94,7 @@ pub struct ClaimFees<'info> {
     pub position_nft_account: UncheckedAccount<'info>,
 
     /// Position owner PDA (authority to claim fees)
-    #[account(
-        seeds = [VAULT_SEED, vault.key().as_ref(), POSITION_OWNER_SEED],
-        bump,
-    )]
-    /// CHECK: PDA owner of the position
+    /// CHECK: Verified against its seeds in the handler
     pub position_owner_pda: UncheckedAccount<'info>,
 
     /// The vault account (used for PDA derivation)
@@ -106,12 +105,20 @@ pub struct ClaimFees<'info> {
     #[account(
         mut,
         seeds = [b"treasury_state", quote_mint.key().as_ref()],
-        bump,
+        bump = treasury_state.bump,
         constraint = treasury_state.quote_mint == quote_mint.key(),
-        constraint = treasury_state.claim_authority == position_owner_pda.key(),
+        constraint = treasury_state.is_authorized_claimer(&position_owner_pda.key()) @ FeeRouterError::PositionOwnerMismatch,
     )]
     pub treasury_state: Account<'info, TreasuryState>,
 
+    /// Policy state (controls the base fee grace period)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
     /// Quote mint
     pub quote_mint: Account<'info, Mint>,
 
@@ -122,14 +129,14 @@ pub struct ClaimFees<'info> {
     #[account(
         mut,
         constraint = treasury_ata.key() == treasury_state.treasury_ata,
-        constraint = treasury_ata.mint == quote_mint.key(),
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
     )]
     pub treasury_ata: Account<'info, TokenAccount>,
 
     /// Position owner's quote token account (temporary holder)
     #[account(
         mut,
-        constraint = position_owner_quote_ata.mint == quote_mint.key(),
+        constraint = position_owner_quote_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
         constraint = position_owner_quote_ata.owner == position_owner_pda.key(),
     )]
     pub position_owner_quote_ata: Account<'info, TokenAccount>,
@@ -137,11 +144,21 @@ pub struct ClaimFees<'info> {
     /// Position owner's base token account (should remain empty)
     #[account(
         mut,
-        constraint = position_owner_base_ata.mint == base_mint.key(),
+        constraint = position_owner_base_ata.mint == base_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
         constraint = position_owner_base_ata.owner == position_owner_pda.key(),
     )]
     pub position_owner_base_ata: Account<'info, TokenAccount>,
 
+    /// Pool vault holding the quote token
+    /// CHECK: Validated against the Meteora pool in the handler (unused for Raydium positions)
+    #[account(mut)]
+    pub quote_vault: UncheckedAccount<'info>,
+
+    /// Pool vault holding the base token
+    /// CHECK: Validated against the Meteora pool in the handler (unused for Raydium positions)
+    #[account(mut)]
+    pub base_vault: UncheckedAccount<'info>,
+
     /// Meteora pool authority
     /// CHECK: Verified by address constraint
     #[account(address = POOL_AUTHORITY)]
@@ -152,9 +169,98 @@ pub struct ClaimFees<'info> {
     pub event_authority: UncheckedAccount<'info>,
 
     /// Meteora CP-AMM program
-    /// CHECK: Meteora program ID
+    /// CHECK: Meteora program ID (unused for Raydium positions)
     pub meteora_program: UncheckedAccount<'info>,
 
+    /// Raydium CLMM program (required for Raydium positions only)
+    /// CHECK: Verified by address constraint
+    #[account(address = RAYDIUM_CLMM_PROGRAM_ID)]
+    pub raydium_program: Option<UncheckedAccount<'info>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
+
+/// Accounts required to update the treasury reserve ratio
+#[derive(Accounts)]
+pub struct UpdateReserveRatio<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// The quote mint of the treasury
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to update the reserve)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state to update
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+}
+
+/// Accounts required to add or remove an additional treasury claimer
+///
+/// The primary claim authority is a PDA and cannot sign directly, so the
+/// claimer list is managed by the policy authority.
+#[derive(Accounts)]
+pub struct ManageClaimers<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// The quote mint of the treasury
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage claimers)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state to update
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+}
+
+/// Accounts required to add or remove a whitelisted treasury mint
+#[derive(Accounts)]
+pub struct ManageAllowedMints<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// The quote mint of the treasury
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the whitelist)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state to update
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+}
@@ -26,6 +26,12 @@ pub struct FeesClaimedFromPosition {
     
     /// Total fees claimed to date
     pub total_fees_claimed: u64,
+    
+    /// Total fees claimed from this position to date
+    pub position_total_fees_claimed: u64,
+    
+    /// Annualised position fee yield in basis points of position value (0 = value unknown)
+    pub position_fee_apr: u64,
 }
 
 /// Event emitted when treasury state is initialized
@@ -43,3 +49,64 @@ pub struct TreasuryInitialized {
     /// Timestamp of initialization
     pub timestamp: i64,
 }
+
+/// Event emitted when dust-level base fees are accepted under the grace period
+#[event]
+pub struct BaseFeesAcceptedUnderGrace {
+    /// Amount of base tokens accepted (left in the position owner's base ATA)
+    pub amount: u64,
+    
+    /// The position the base fees were claimed from
+    pub position: Pubkey,
+    
+    /// Timestamp of the claim
+    pub timestamp: i64,
+}
+
+/// Event emitted when the treasury reserve ratio is updated
+#[event]
+pub struct ReserveRatioUpdated {
+    /// The quote mint of the treasury
+    pub quote_mint: Pubkey,
+    
+    /// Previous reserve ratio in basis points
+    pub old_reserve_ratio_bps: u64,
+    
+    /// New reserve ratio in basis points
+    pub new_reserve_ratio_bps: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when an additional treasury claimer is added or removed
+#[event]
+pub struct TreasuryClaimerUpdated {
+    /// The quote mint of the treasury
+    pub quote_mint: Pubkey,
+    
+    /// The claimer (position owner PDA)
+    pub claimer: Pubkey,
+    
+    /// True if added, false if removed
+    pub added: bool,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when a mint is added to or removed from the treasury whitelist
+#[event]
+pub struct MintWhitelistUpdated {
+    /// The quote mint of the treasury
+    pub quote_mint: Pubkey,
+    
+    /// The mint that was whitelisted, if any
+    pub added: Option<Pubkey>,
+    
+    /// The mint that was removed, if any
+    pub removed: Option<Pubkey>,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
@@ -3,8 +3,11 @@ use anchor_spl::token;
 use crate::modules::claiming::contexts::*;
 use crate::modules::claiming::events::*;
 use crate::modules::claiming::state::TreasuryState;
+use crate::modules::position::state::PoolType;
 use crate::integrations::meteora;
+use crate::integrations::raydium_clmm;
 use crate::shared::constants::*;
+use crate::shared::pda::verify_pda;
 use crate::errors::FeeRouterError;
 
 /// Initialize the treasury for fee claiming
@@ -14,10 +17,19 @@ use crate::errors::FeeRouterError;
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
 /// * `quote_mint` - The quote mint for this treasury
+/// * `reserve_ratio_bps` - Portion of the treasury kept back from distribution
+/// * `max_claim_per_interval_bps` - Cap on a single claim relative to the treasury balance (0 = disabled)
+/// * `min_claim_interval_seconds` - Minimum time between fee claims (0 = one hour)
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn initialize_treasury(ctx: Context<InitializeTreasury>, quote_mint: Pubkey) -> Result<()> {
+pub fn initialize_treasury(
+    ctx: Context<InitializeTreasury>,
+    quote_mint: Pubkey,
+    reserve_ratio_bps: u64,
+    max_claim_per_interval_bps: u64,
+    min_claim_interval_seconds: i64,
+) -> Result<()> {
     msg!("Initializing treasury for quote mint: {}", quote_mint);
 
     // Validate quote mint matches the account
@@ -25,9 +37,21 @@ pub fn initialize_treasury(ctx: Context<InitializeTreasury>, quote_mint: Pubkey)
         quote_mint == ctx.accounts.quote_mint_account.key(),
         anchor_lang::error::ErrorCode::ConstraintRaw
     );
+    require!(
+        reserve_ratio_bps <= BPS_DENOMINATOR,
+        FeeRouterError::InvalidFeeShareBps
+    );
+    require!(
+        max_claim_per_interval_bps <= BPS_DENOMINATOR,
+        FeeRouterError::InvalidFeeShareBps
+    );
+    // Zero is stored as is and resolved to the default when claiming
+    TreasuryState::resolve_min_claim_interval(min_claim_interval_seconds)?;
 
-    // Initialize treasury state
+    // Initialize treasury state, whitelisting the quote mint
     let clock = Clock::get()?;
+    let mut allowed_mints = [Pubkey::default(); 4];
+    allowed_mints[0] = quote_mint;
     ctx.accounts.treasury_state.set_inner(TreasuryState {
         quote_mint,
         treasury_ata: ctx.accounts.treasury_ata.key(),
@@ -35,7 +59,15 @@ pub fn initialize_treasury(ctx: Context<InitializeTreasury>, quote_mint: Pubkey)
         last_claim_timestamp: 0,
         claim_count: 0,
         claim_authority: ctx.accounts.position_owner_pda.key(),
-        reserved: [0; 64],
+        base_fees_accepted_under_grace: 0,
+        reserve_ratio_bps,
+        additional_claimers: [Pubkey::default(); 3],
+        max_claim_per_interval_bps,
+        allowed_mints,
+        bump: ctx.bumps.treasury_state,
+        accumulated_dust_lifetime: 0,
+        min_claim_interval_seconds,
+        reserved: [0; 47],
     });
 
     // Emit event
@@ -50,17 +82,172 @@ pub fn initialize_treasury(ctx: Context<InitializeTreasury>, quote_mint: Pubkey)
     Ok(())
 }
 
+/// Update the treasury reserve ratio
+/// 
+/// Only the policy authority can change how much of the treasury balance
+/// is held back from daily distributions.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `reserve_ratio_bps` - New reserve ratio (0-10000)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn update_reserve_ratio(ctx: Context<UpdateReserveRatio>, reserve_ratio_bps: u64) -> Result<()> {
+    require!(
+        reserve_ratio_bps <= BPS_DENOMINATOR,
+        FeeRouterError::InvalidFeeShareBps
+    );
+
+    let clock = Clock::get()?;
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    let old_reserve_ratio_bps = treasury_state.reserve_ratio_bps;
+    treasury_state.reserve_ratio_bps = reserve_ratio_bps;
+
+    emit!(ReserveRatioUpdated {
+        quote_mint: treasury_state.quote_mint,
+        old_reserve_ratio_bps,
+        new_reserve_ratio_bps: reserve_ratio_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Reserve ratio updated: {} -> {} bps", old_reserve_ratio_bps, reserve_ratio_bps);
+    Ok(())
+}
+
+/// Authorize an additional position owner PDA to claim into the treasury
+/// 
+/// Lets several honorary positions share one treasury (up to 3 in addition
+/// to the primary claim authority).
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `claimer` - The position owner PDA to authorize
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn add_claimer(ctx: Context<ManageClaimers>, claimer: Pubkey) -> Result<()> {
+    ctx.accounts.treasury_state.add_claimer(claimer)?;
+
+    let clock = Clock::get()?;
+    emit!(TreasuryClaimerUpdated {
+        quote_mint: ctx.accounts.treasury_state.quote_mint,
+        claimer,
+        added: true,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Added treasury claimer: {}", claimer);
+    Ok(())
+}
+
+/// Revoke an additional claimer from the treasury
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `claimer` - The position owner PDA to revoke
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn remove_claimer(ctx: Context<ManageClaimers>, claimer: Pubkey) -> Result<()> {
+    ctx.accounts.treasury_state.remove_claimer(claimer)?;
+
+    let clock = Clock::get()?;
+    emit!(TreasuryClaimerUpdated {
+        quote_mint: ctx.accounts.treasury_state.quote_mint,
+        claimer,
+        added: false,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Removed treasury claimer: {}", claimer);
+    Ok(())
+}
+
+/// Whitelist a mint the treasury may receive
+/// 
+/// Lets one treasury accept several tokens (up to 4 including the quote mint).
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `mint` - The mint to whitelist
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn add_allowed_mint(ctx: Context<ManageAllowedMints>, mint: Pubkey) -> Result<()> {
+    ctx.accounts.treasury_state.add_allowed_mint(mint)?;
+
+    let clock = Clock::get()?;
+    emit!(MintWhitelistUpdated {
+        quote_mint: ctx.accounts.treasury_state.quote_mint,
+        added: Some(mint),
+        removed: None,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Whitelisted treasury mint: {}", mint);
+    Ok(())
+}
+
+/// Remove a mint from the treasury whitelist
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `mint` - The mint to remove
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn remove_allowed_mint(ctx: Context<ManageAllowedMints>, mint: Pubkey) -> Result<()> {
+    ctx.accounts.treasury_state.remove_allowed_mint(mint)?;
+
+    let clock = Clock::get()?;
+    emit!(MintWhitelistUpdated {
+        quote_mint: ctx.accounts.treasury_state.quote_mint,
+        added: None,
+        removed: Some(mint),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Removed treasury mint: {}", mint);
+    Ok(())
+}
+
 /// Claim fees from the honorary position
 /// 
-/// This claims accumulated fees from the Meteora position and transfers
-/// them to the treasury. Validates that only quote tokens are claimed.
+/// This claims accumulated fees from the Meteora (or Raydium CLMM) position and
+/// transfers them to the treasury. Validates that only quote tokens are claimed.
+/// Every mint received from the position, including base dust accepted under
+/// the grace period, must be whitelisted in the treasury.
+/// 
+/// For Raydium CLMM positions `raydium_program` must be passed and the
+/// remaining accounts are `[protocol_position, token_vault_0,
+/// token_vault_1, tick_array_lower, tick_array_upper, token_program_2022, memo_program]`.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>) -> Result<()> {
+    claim_fees_with_minimum(ctx, 0)
+}
+
+/// Claim fees from the honorary position, failing below a minimum amount
+/// 
+/// Same as `claim_fees`, but rejects claims of less than `min_claim_amount`
+/// quote tokens so crankers do not pay for uneconomical claims. The amount is
+/// only known after the position CPI, which is rolled back with the failure.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `min_claim_amount` - Smallest quote amount worth claiming
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn claim_fees_with_minimum<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>,
+    min_claim_amount: u64,
+) -> Result<()> {
     msg!("Claiming fees from honorary position");
 
     // Validate position metadata matches accounts
@@ -77,9 +264,21 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
         FeeRouterError::PositionMetadataMismatch
     );
 
+    // Verify the position owner PDA against its seeds and the stored canonical bump
+    let bump = verify_pda(
+        &ctx.accounts.position_owner_pda.key(),
+        &[VAULT_SEED, ctx.accounts.vault.key().as_ref(), POSITION_OWNER_SEED],
+        ctx.program_id,
+    )?;
+    ctx.accounts.position_metadata.verify_position_owner(
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.position_owner_pda.key(),
+        ctx.program_id,
+    )?;
+
     // Check if enough time has passed since last claim (optional cooldown)
     let clock = Clock::get()?;
-    let min_claim_interval = 3600; // 1 hour minimum between claims
+    let min_claim_interval = ctx.accounts.treasury_state.effective_min_claim_interval()?;
     require!(
         ctx.accounts.treasury_state.can_claim(clock.unix_timestamp, min_claim_interval),
         FeeRouterError::ClaimIntervalNotElapsed
@@ -91,7 +290,6 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
 
     // Step 1 - Claim fees from Meteora position via CPI with error handling
     let vault_key = ctx.accounts.vault.key();
-    let bump = ctx.bumps.position_owner_pda;
     let owner_seeds = &[
         VAULT_SEED,
         vault_key.as_ref(),
@@ -100,26 +298,65 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
     ];
     let signer_seeds = &[&owner_seeds[..]];
 
-    // Attempt the Meteora CPI call with error wrapping
-    meteora::cpi::claim_position_fee(
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.pool.to_account_info(),
-        ctx.accounts.position.to_account_info(),
-        ctx.accounts.position_owner_quote_ata.to_account_info(), // token_a_account
-        ctx.accounts.position_owner_base_ata.to_account_info(),  // token_b_account
-        // Note: We need to determine which vault is A and which is B based on mint order
-        ctx.accounts.pool.to_account_info(), // token_a_vault (placeholder)
-        ctx.accounts.pool.to_account_info(), // token_b_vault (placeholder)
-        ctx.accounts.quote_mint.to_account_info(), // token_a_mint (placeholder)
-        ctx.accounts.base_mint.to_account_info(),  // token_b_mint (placeholder)
-        ctx.accounts.position_nft_account.to_account_info(),
-        ctx.accounts.position_owner_pda.to_account_info(),
-        ctx.accounts.token_program.to_account_info(), // token_a_program
-        ctx.accounts.token_program.to_account_info(), // token_b_program
-        ctx.accounts.event_authority.to_account_info(),
-        ctx.accounts.meteora_program.to_account_info(),
-        Some(signer_seeds),
-    ).map_err(|_| FeeRouterError::MeteoraCpiFailed)?;
+    // Dispatch the fee claim CPI on the position's AMM backend
+    match ctx.accounts.position_metadata.pool_type {
+        PoolType::MeteoraDAMM => {
+            // Skip the CPI when the accrued fees cannot meet the minimum
+            // (positions that cannot be read are left to the CPI). A zero
+            // estimate alone does not skip it: the estimate rounds down, so
+            // whether any fees were claimed is decided on the measured amounts.
+            if let Ok((estimated_quote, estimated_base)) = meteora::cpi::estimate_claimable_fees(
+                &ctx.accounts.pool.to_account_info(),
+                &ctx.accounts.position.to_account_info(),
+            ) {
+                msg!("Estimated claimable fees - quote: {}, base: {}", estimated_quote, estimated_base);
+                if estimated_quote < min_claim_amount {
+                    return Err(FeeRouterError::ClaimBelowMinimum
+                        .with_context(format!("estimated: {}, minimum: {}", estimated_quote, min_claim_amount))
+                        .into());
+                }
+            }
+
+            // CP-AMM orders the token accounts by the pool's token A and token B
+            let pool_state = meteora::cpi::read_pool(&ctx.accounts.pool.to_account_info())?;
+            let (token_a, token_b) = meteora::cpi::order_claim_token_sides(
+                &pool_state,
+                meteora::cpi::ClaimTokenSide {
+                    token_account: ctx.accounts.position_owner_quote_ata.to_account_info(),
+                    vault: ctx.accounts.quote_vault.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                },
+                meteora::cpi::ClaimTokenSide {
+                    token_account: ctx.accounts.position_owner_base_ata.to_account_info(),
+                    vault: ctx.accounts.base_vault.to_account_info(),
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                },
+            )?;
+
+            // Attempt the Meteora CPI call with error wrapping
+            meteora::cpi::claim_position_fee(
+                ctx.accounts.pool_authority.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.position.to_account_info(),
+                token_a.token_account,
+                token_b.token_account,
+                token_a.vault,
+                token_b.vault,
+                token_a.mint,
+                token_b.mint,
+                ctx.accounts.position_nft_account.to_account_info(),
+                ctx.accounts.position_owner_pda.to_account_info(),
+                ctx.accounts.token_program.to_account_info(), // token_a_program
+                ctx.accounts.token_program.to_account_info(), // token_b_program
+                ctx.accounts.event_authority.to_account_info(),
+                ctx.accounts.meteora_program.to_account_info(),
+                Some(signer_seeds),
+            ).map_err(|_| FeeRouterError::MeteoraCpiFailed)?;
+        }
+        PoolType::RaydiumCLMM => {
+            claim_raydium_position_fees(&ctx, signer_seeds)?;
+        }
+    }
 
     // Refresh account data to get updated balances
     ctx.accounts.position_owner_quote_ata.reload()?;
@@ -134,10 +371,30 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
     msg!("Quote claimed: {}, Base claimed: {}", quote_amount_claimed, base_amount_claimed);
 
     // Step 2 - Verify only quote tokens were claimed (base should be 0)
-    require!(
-        base_amount_claimed == 0,
-        FeeRouterError::BaseFeesClaimedError
-    );
+    // Dust-level base fees may be accepted under the policy grace period; they stay
+    // in the position owner's base ATA to be swapped to quote later.
+    if base_amount_claimed > 0 {
+        if !(ctx.accounts.policy_state.allow_base_fee_grace_period
+            && base_amount_claimed < GRACE_AMOUNT_THRESHOLD)
+        {
+            return Err(FeeRouterError::BaseFeesClaimedError
+                .with_context(format!("amount: {}", base_amount_claimed))
+                .into());
+        }
+
+        // Accepted base dust is held by the program too, so its mint must be whitelisted
+        ctx.accounts.treasury_state.require_allowed_mint(&ctx.accounts.base_mint.key())?;
+
+        ctx.accounts.treasury_state.record_base_fees_under_grace(base_amount_claimed);
+
+        emit!(BaseFeesAcceptedUnderGrace {
+            amount: base_amount_claimed,
+            position: ctx.accounts.position.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Accepted {} base tokens under grace period", base_amount_claimed);
+    }
 
     // Check if any fees were actually claimed
     require!(
@@ -145,8 +402,19 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
         FeeRouterError::NoFeesToClaim
     );
 
+    // Only whitelisted mints may be claimed into the treasury
+    ctx.accounts.treasury_state.require_allowed_mint(&ctx.accounts.quote_mint.key())?;
+    if quote_amount_claimed < min_claim_amount {
+        return Err(FeeRouterError::ClaimBelowMinimum
+            .with_context(format!("claimed: {}, minimum: {}", quote_amount_claimed, min_claim_amount))
+            .into());
+    }
+
     // Step 3 - Transfer claimed quote tokens to treasury with error handling
     let treasury_balance_before = ctx.accounts.treasury_ata.amount;
+
+    // Reject unexpectedly large claims relative to the current treasury balance
+    ctx.accounts.treasury_state.check_claim_rate_limit(quote_amount_claimed, treasury_balance_before)?;
     
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -169,8 +437,10 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
         FeeRouterError::TreasuryBalanceMismatch
     );
 
-    // Step 4 - Update treasury state with overflow protection
+    // Step 4 - Update treasury and position claim history with overflow protection
     ctx.accounts.treasury_state.record_claim(quote_amount_claimed, clock.unix_timestamp);
+    ctx.accounts.position_metadata.record_claim(quote_amount_claimed, clock.unix_timestamp);
+    let position_fee_apr = ctx.accounts.position_metadata.calculate_fee_apr_bps(clock.unix_timestamp);
 
     // Step 5 - Emit event
     emit!(FeesClaimedFromPosition {
@@ -182,8 +452,73 @@ pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
         quote_mint: ctx.accounts.quote_mint.key(),
         timestamp: clock.unix_timestamp,
         total_fees_claimed: ctx.accounts.treasury_state.total_fees_claimed,
+        position_total_fees_claimed: ctx.accounts.position_metadata.total_fees_claimed,
+        position_fee_apr,
     });
 
     msg!("✅ Fees claimed successfully: {} quote tokens", quote_amount_claimed);
     Ok(())
 }
+
+/// Number of extra accounts required to claim from a Raydium CLMM position
+const RAYDIUM_CLAIM_REMAINING_ACCOUNTS: usize = 7;
+
+/// Claim fees from a Raydium CLMM honorary position into the position owner ATAs
+fn claim_raydium_position_fees<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ClaimFees<'info>>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let raydium_program = ctx.accounts.raydium_program.as_ref()
+        .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() >= RAYDIUM_CLAIM_REMAINING_ACCOUNTS,
+        anchor_lang::error::ErrorCode::AccountNotEnoughKeys
+    );
+
+    // The vaults are paid out of, so they must be the pool's own
+    require!(
+        *ctx.accounts.pool.owner == raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
+        FeeRouterError::InvalidPoolConfig
+    );
+    {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = raydium_clmm::PoolState::from_account_data(&pool_data)
+            .ok_or(FeeRouterError::InvalidPoolConfig)?;
+        raydium_clmm::validate_claim_vaults(pool, remaining_accounts[1].key, remaining_accounts[2].key)?;
+    }
+
+    // Raydium orders pool tokens by mint key
+    let quote_mint = ctx.accounts.quote_mint.to_account_info();
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let quote_ata = ctx.accounts.position_owner_quote_ata.to_account_info();
+    let base_ata = ctx.accounts.position_owner_base_ata.to_account_info();
+    let ((recipient_0, mint_0), (recipient_1, mint_1)) = if quote_mint.key() < base_mint.key() {
+        ((quote_ata, quote_mint), (base_ata, base_mint))
+    } else {
+        ((base_ata, base_mint), (quote_ata, quote_mint))
+    };
+
+    raydium_clmm::cpi::raydium_claim_fees(
+        raydium_clmm::RaydiumClaimFeesAccounts {
+            nft_owner: ctx.accounts.position_owner_pda.to_account_info(),
+            nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            personal_position: ctx.accounts.position.to_account_info(),
+            pool_state: ctx.accounts.pool.to_account_info(),
+            protocol_position: remaining_accounts[0].clone(),
+            token_vault_0: remaining_accounts[1].clone(),
+            token_vault_1: remaining_accounts[2].clone(),
+            tick_array_lower: remaining_accounts[3].clone(),
+            tick_array_upper: remaining_accounts[4].clone(),
+            recipient_token_account_0: recipient_0,
+            recipient_token_account_1: recipient_1,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_program_2022: remaining_accounts[5].clone(),
+            memo_program: remaining_accounts[6].clone(),
+            vault_0_mint: mint_0,
+            vault_1_mint: mint_1,
+            raydium_program: raydium_program.to_account_info(),
+        },
+        Some(signer_seeds),
+    ).map_err(|_| FeeRouterError::RaydiumCpiFailed.into())
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+use crate::shared::constants::{BPS_DENOMINATOR, DEFAULT_MIN_CLAIM_INTERVAL_SECONDS};
 
 /// Treasury state account to track fee claiming
 /// 
@@ -24,8 +26,32 @@ pub struct TreasuryState {
     /// Authority that can claim fees (should be position owner PDA)
     pub claim_authority: Pubkey,
     
+    /// Total base fees accepted under the grace period (left in the base ATA)
+    pub base_fees_accepted_under_grace: u64,
+    
+    /// Portion of the treasury balance held back from distribution in basis points
+    pub reserve_ratio_bps: u64,
+    
+    /// Additional position owner PDAs allowed to claim into this treasury (default = empty slot)
+    pub additional_claimers: [Pubkey; 3],
+    
+    /// Maximum single claim as basis points of the treasury balance (0 = no cap)
+    pub max_claim_per_interval_bps: u64,
+    
+    /// Mints the treasury may receive (default = empty slot)
+    pub allowed_mints: [Pubkey; 4],
+    
+    /// Cached PDA bump so constraints skip `find_program_address`
+    pub bump: u8,
+    
+    /// Rounding dust resolved by completed distributions since inception
+    pub accumulated_dust_lifetime: u64,
+    
+    /// Minimum time between fee claims (0 = `DEFAULT_MIN_CLAIM_INTERVAL_SECONDS`)
+    pub min_claim_interval_seconds: i64,
+    
     /// Reserved for future use
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 47],
 }
 
 impl TreasuryState {
@@ -35,7 +61,15 @@ impl TreasuryState {
                                    8 +  // last_claim_timestamp
                                    8 +  // claim_count
                                    32 + // claim_authority
-                                   64;  // reserved
+                                   8 +  // base_fees_accepted_under_grace
+                                   8 +  // reserve_ratio_bps
+                                   96 + // additional_claimers
+                                   8 +  // max_claim_per_interval_bps
+                                   128 + // allowed_mints
+                                   1 +  // bump
+                                   8 +  // accumulated_dust_lifetime
+                                   8 +  // min_claim_interval_seconds
+                                   47;  // reserved
 
     /// Derive the PDA for treasury state
     pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
@@ -52,6 +86,131 @@ impl TreasuryState {
         self.claim_count = self.claim_count.saturating_add(1);
     }
 
+    /// Record base fees that were accepted under the grace period
+    pub fn record_base_fees_under_grace(&mut self, base_amount: u64) {
+        self.base_fees_accepted_under_grace = self.base_fees_accepted_under_grace.saturating_add(base_amount);
+    }
+
+    /// Add a completed distribution's dust to the lifetime total and return the new total
+    pub fn record_lifetime_dust(&mut self, dust_amount: u64) -> u64 {
+        self.accumulated_dust_lifetime = self.accumulated_dust_lifetime.saturating_add(dust_amount);
+        self.accumulated_dust_lifetime
+    }
+
+    /// Calculate the amount available for distribution after the reserve
+    /// distributable_amount = floor(treasury_balance * (10000 - reserve_ratio_bps) / 10000)
+    pub fn calculate_distributable_amount(&self, treasury_balance: u64) -> u64 {
+        let distributable_bps = BPS_DENOMINATOR.saturating_sub(self.reserve_ratio_bps);
+        ((treasury_balance as u128 * distributable_bps as u128) / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Check that a claim stays within the per-interval cap
+    /// max_allowed = floor(treasury_balance * max_claim_per_interval_bps / 10000)
+    pub fn check_claim_rate_limit(&self, amount_claimed: u64, treasury_balance: u64) -> Result<()> {
+        if self.max_claim_per_interval_bps == 0 {
+            return Ok(());
+        }
+        let max_allowed = ((treasury_balance as u128 * self.max_claim_per_interval_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+        require!(
+            amount_claimed <= max_allowed,
+            FeeRouterError::ClaimExceedsRateLimit
+        );
+        Ok(())
+    }
+
+    /// Check if a position owner PDA may claim into this treasury
+    pub fn is_authorized_claimer(&self, claimer: &Pubkey) -> bool {
+        *claimer == self.claim_authority
+            || (*claimer != Pubkey::default() && self.additional_claimers.contains(claimer))
+    }
+
+    /// Add an additional claimer to the first empty slot
+    pub fn add_claimer(&mut self, claimer: Pubkey) -> Result<()> {
+        require!(
+            claimer != Pubkey::default() && !self.is_authorized_claimer(&claimer),
+            FeeRouterError::ClaimerAlreadyAuthorized
+        );
+        let slot = self
+            .additional_claimers
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(FeeRouterError::ClaimerListFull)?;
+        *slot = claimer;
+        Ok(())
+    }
+
+    /// Remove an additional claimer, freeing its slot
+    pub fn remove_claimer(&mut self, claimer: Pubkey) -> Result<()> {
+        let slot = self
+            .additional_claimers
+            .iter_mut()
+            .find(|slot| **slot == claimer && claimer != Pubkey::default())
+            .ok_or(FeeRouterError::ClaimerNotFound)?;
+        *slot = Pubkey::default();
+        Ok(())
+    }
+
+    /// Check if a mint is whitelisted for this treasury
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        *mint != Pubkey::default() && self.allowed_mints.contains(mint)
+    }
+
+    /// Reject transfers of a mint that is not whitelisted
+    pub fn require_allowed_mint(&self, mint: &Pubkey) -> Result<()> {
+        require!(
+            self.is_mint_allowed(mint),
+            FeeRouterError::MintNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Whitelist a mint in the first empty slot
+    pub fn add_allowed_mint(&mut self, mint: Pubkey) -> Result<()> {
+        require!(
+            mint != Pubkey::default() && !self.is_mint_allowed(&mint),
+            FeeRouterError::MintAlreadyAllowed
+        );
+        let slot = self
+            .allowed_mints
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(FeeRouterError::AllowedMintListFull)?;
+        *slot = mint;
+        Ok(())
+    }
+
+    /// Remove a mint from the whitelist, freeing its slot
+    pub fn remove_allowed_mint(&mut self, mint: Pubkey) -> Result<()> {
+        let slot = self
+            .allowed_mints
+            .iter_mut()
+            .find(|slot| **slot == mint && mint != Pubkey::default())
+            .ok_or(FeeRouterError::MintNotAllowed)?;
+        *slot = Pubkey::default();
+        Ok(())
+    }
+
+    /// Claim interval in effect for a configured interval (0 = `DEFAULT_MIN_CLAIM_INTERVAL_SECONDS`)
+    pub fn resolve_min_claim_interval(min_claim_interval_seconds: i64) -> Result<i64> {
+        require!(
+            min_claim_interval_seconds >= 0,
+            FeeRouterError::InvalidClaimInterval
+        );
+        if min_claim_interval_seconds == 0 {
+            Ok(DEFAULT_MIN_CLAIM_INTERVAL_SECONDS)
+        } else {
+            Ok(min_claim_interval_seconds)
+        }
+    }
+
+    /// Claim interval in effect for this treasury
+    /// 
+    /// Resolved on every read so treasuries whose interval was never set
+    /// (stored as 0) keep the default.
+    pub fn effective_min_claim_interval(&self) -> Result<i64> {
+        Self::resolve_min_claim_interval(self.min_claim_interval_seconds)
+    }
+
     /// Check if enough time has passed since last claim
     pub fn can_claim(&self, current_timestamp: i64, min_interval_seconds: i64) -> bool {
         current_timestamp >= self.last_claim_timestamp + min_interval_seconds
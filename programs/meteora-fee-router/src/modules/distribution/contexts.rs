@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use crate::modules::distribution::state::{DailyDistributionState, GlobalDistributionState, PolicyState};
+use crate::modules::distribution::state::{AllocationRegistry, DailyDistributionState, GlobalDistributionState, InvestorAggregateCache, InvestorBlacklist, InvestorPayoutSummary, InvestorRegistryPage, InvestorRegistryState, InvestorSnapshot, PolicySnapshot, PolicyState, ProgramConfig, StreamErrorLog};
+use crate::modules::claiming::contexts::{ClaimFees, ClaimFeesBumps, __client_accounts_claim_fees, __cpi_client_accounts_claim_fees};
 use crate::modules::claiming::state::TreasuryState;
+use crate::modules::position::state::PositionMetadata;
+use crate::errors::FeeRouterError;
 
 /// Accounts required to initialize policy state
 #[derive(Accounts)]
@@ -58,75 +62,1265 @@ pub struct InitializeGlobalDistribution<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Accounts required to initialize the program config
+/// 
+/// Only the program's upgrade authority can initialize it.
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    /// The program upgrade authority (pays for creation)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program config PDA to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"program_config"],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// This program
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::MeteoraFeeRouter>,
+
+    /// Program data account holding the upgrade authority
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ FeeRouterError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required for actions of the program pause authority
+#[derive(Accounts)]
+pub struct ManageProgramPause<'info> {
+    /// The program pause authority
+    pub authority: Signer<'info>,
+
+    /// Program config holding the pause state
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump,
+        constraint = program_config.program_pause_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Accounts required to update the creator wallet
+#[derive(Accounts)]
+pub struct UpdateCreatorWallet<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to update the creator)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Global distribution state holding the creator wallet
+    #[account(
+        mut,
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+}
+
+/// Accounts required to link the honorary position to the global distribution state
+#[derive(Accounts)]
+pub struct LinkPositionToDistribution<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to link the position)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Global distribution state to record the position creation time on
+    #[account(
+        mut,
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+
+    /// Position NFT mint
+    pub position_nft_mint: Account<'info, Mint>,
+
+    /// The honorary position's metadata
+    #[account(
+        seeds = [b"position_metadata", position_nft_mint.key().as_ref()],
+        bump,
+        constraint = position_metadata.quote_mint == quote_mint.key() @ FeeRouterError::PositionMetadataMismatch,
+    )]
+    pub position_metadata: Account<'info, PositionMetadata>,
+}
+
+/// Accounts required to extend the policy sunset date
+#[derive(Accounts)]
+pub struct ExtendSunset<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state to update
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+}
+
+/// Accounts required to migrate the policy account layout
+#[derive(Accounts)]
+pub struct UpgradePolicyFormat<'info> {
+    /// The policy authority (pays rent for the larger layout)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state to migrate
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: Older layouts cannot be loaded as PolicyState; decoded and checked in the handler
+    pub policy_state: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required to update the policy's minimum payout
+#[derive(Accounts)]
+pub struct UpdateMinPayout<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state to update
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+}
+
+/// Accounts required to update the policy's optional settings
+#[derive(Accounts)]
+pub struct UpdatePolicySettings<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state to update
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Completed SPL governance proposal (required when the policy has a governance authority)
+    /// CHECK: Verified by `ValidateGovernanceProposal` in the handler
+    pub governance_proposal: Option<UncheckedAccount<'info>>,
+
+    /// Proposal transaction carrying this update (required with `governance_proposal`)
+    /// CHECK: Verified by `ValidateGovernanceProposal` in the handler
+    pub governance_proposal_transaction: Option<UncheckedAccount<'info>>,
+}
+
+/// Accounts required to create the investor blacklist for a quote mint
+#[derive(Accounts)]
+pub struct InitializeBlacklist<'info> {
+    /// The policy authority (pays for creation)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the blacklist)
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Investor blacklist account
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + InvestorBlacklist::INIT_SPACE,
+        seeds = [b"blacklist", quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub investor_blacklist: Box<Account<'info, InvestorBlacklist>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required to add or remove a blacklisted investor
+#[derive(Accounts)]
+pub struct ManageBlacklist<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the blacklist)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Investor blacklist to update
+    #[account(
+        mut,
+        seeds = [b"blacklist", quote_mint.key().as_ref()],
+        bump = investor_blacklist.bump,
+        constraint = investor_blacklist.quote_mint == quote_mint.key(),
+    )]
+    pub investor_blacklist: Box<Account<'info, InvestorBlacklist>>,
+}
+
+/// Accounts required to initialize the allocation registry
+#[derive(Accounts)]
+pub struct InitializeAllocationRegistry<'info> {
+    /// The policy authority (pays for creation)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the registry)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Allocation registry account
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AllocationRegistry::INIT_SPACE,
+        seeds = [b"allocation_registry", quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub allocation_registry: Box<Account<'info, AllocationRegistry>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required to register stream allocations
+#[derive(Accounts)]
+pub struct RegisterStreamAllocations<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the registry)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Allocation registry to update
+    #[account(
+        mut,
+        seeds = [b"allocation_registry", quote_mint.key().as_ref()],
+        bump = allocation_registry.bump,
+        constraint = allocation_registry.quote_mint == quote_mint.key(),
+    )]
+    pub allocation_registry: Box<Account<'info, AllocationRegistry>>,
+
+    // Note: Streamflow stream accounts are passed as remaining_accounts
+}
+
+/// Accounts required to initialize the investor registry
+#[derive(Accounts)]
+pub struct InitializeInvestorRegistry<'info> {
+    /// The policy authority (pays for creation)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the registry)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Investor registry account
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + InvestorRegistryState::INIT_SPACE,
+        seeds = [b"investor_registry", quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub investor_registry: Box<Account<'info, InvestorRegistryState>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required to add an investor to the investor registry
+#[derive(Accounts)]
+pub struct AddInvestor<'info> {
+    /// The policy authority (pays for new registry pages)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to manage the registry)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Investor registry to update
+    #[account(
+        mut,
+        seeds = [b"investor_registry", quote_mint.key().as_ref()],
+        bump = investor_registry.bump,
+        constraint = investor_registry.quote_mint == quote_mint.key(),
+    )]
+    pub investor_registry: Box<Account<'info, InvestorRegistryState>>,
+
+    /// Current last registry page
+    /// CHECK: Created by the handler when the registry has no pages yet
+    #[account(
+        mut,
+        seeds = [
+            b"registry_page",
+            investor_registry.key().as_ref(),
+            &investor_registry.last_page_index.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub last_page: UncheckedAccount<'info>,
+
+    /// Page following the last one
+    /// CHECK: Created by the handler when the last page is full
+    #[account(
+        mut,
+        seeds = [
+            b"registry_page",
+            investor_registry.key().as_ref(),
+            &investor_registry.last_page_index.saturating_add(1).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub next_page: UncheckedAccount<'info>,
+
+    /// System program (for creating registry pages)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required to take the investor snapshot for a distribution day
+#[derive(Accounts)]
+#[instruction(distribution_day: i64)]
+pub struct TakeInvestorSnapshot<'info> {
+    /// The policy authority taking the snapshot
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Policy state (provides the minimum remaining lock filter and the authority)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Investor snapshot account (created for this day)
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + InvestorSnapshot::INIT_SPACE,
+        seeds = [
+            b"snapshot",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    // Note: Streamflow stream accounts are passed as remaining_accounts
+}
+
+/// Accounts required to check the streams of an investor snapshot
+#[derive(Accounts)]
+pub struct ValidateSnapshotStreams<'info> {
+    /// Quote mint the snapshot's streams must stream
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Investor snapshot to check
+    #[account(
+        seeds = [
+            b"snapshot",
+            investor_snapshot.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = investor_snapshot.quote_mint == quote_mint.key(),
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+
+    // Note: Streamflow stream accounts are passed as remaining_accounts
+}
+
+/// Accounts required to close an investor snapshot after its distribution
+#[derive(Accounts)]
+pub struct CloseInvestorSnapshot<'info> {
+    /// The authority closing the snapshot (can be anyone)
+    pub authority: Signer<'info>,
+
+    /// Authority that took the snapshot (receives the rent back)
+    /// CHECK: Must match the payer recorded on the snapshot
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Daily distribution state the snapshot was taken for
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            investor_snapshot.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.can_close_snapshot(Clock::get()?.unix_timestamp) @ FeeRouterError::SnapshotNotClosable,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Investor snapshot to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ FeeRouterError::RentPayerMismatch,
+        seeds = [
+            b"snapshot",
+            investor_snapshot.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = investor_snapshot.quote_mint == quote_mint.key(),
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+}
+
+/// Accounts required to close an investor payout summary after its distribution
+#[derive(Accounts)]
+pub struct CloseInvestorPayoutSummary<'info> {
+    /// The authority closing the summary (can be anyone)
+    pub authority: Signer<'info>,
+
+    /// Cranker that paid the summary's rent (receives it back)
+    /// CHECK: Must match the payer recorded on the summary
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Daily distribution state the summary was written for
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            investor_payout_summary.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.can_close_snapshot(Clock::get()?.unix_timestamp) @ FeeRouterError::SnapshotNotClosable,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Payout summary to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ FeeRouterError::RentPayerMismatch,
+        seeds = [
+            b"payout_summary",
+            investor_payout_summary.distribution_day.to_string().as_bytes(),
+            investor_payout_summary.investor.as_ref(),
+        ],
+        bump,
+    )]
+    pub investor_payout_summary: Box<Account<'info, InvestorPayoutSummary>>,
+}
+
+/// Accounts required to close an investor aggregate cache after its distribution
+#[derive(Accounts)]
+pub struct CloseInvestorCache<'info> {
+    /// The authority closing the cache (can be anyone)
+    pub authority: Signer<'info>,
+
+    /// Cranker that paid the cache's rent (receives it back)
+    /// CHECK: Must match the payer recorded on the cache
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Quote mint that was distributed
+    #[account(constraint = quote_mint.key() == investor_cache.quote_mint)]
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Daily distribution state the cache was written for
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            investor_cache.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.can_close_snapshot(Clock::get()?.unix_timestamp) @ FeeRouterError::SnapshotNotClosable,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Investor cache to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ FeeRouterError::RentPayerMismatch,
+        seeds = [
+            b"investor_cache",
+            investor_cache.distribution_day.to_string().as_bytes(),
+            investor_cache.quote_mint.as_ref(),
+            investor_cache.investor.as_ref(),
+        ],
+        bump,
+    )]
+    pub investor_cache: Box<Account<'info, InvestorAggregateCache>>,
+}
+
+/// Accounts required to close a day's stream error log after its distribution
+#[derive(Accounts)]
+pub struct CloseStreamErrorLog<'info> {
+    /// The authority closing the log (can be anyone)
+    pub authority: Signer<'info>,
+
+    /// Cranker that paid the log's rent (receives it back)
+    /// CHECK: Must match the payer recorded on the log
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Daily distribution state the log was kept for
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            stream_error_log.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.is_complete @ FeeRouterError::DistributionInProgress,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Stream error log to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ FeeRouterError::RentPayerMismatch,
+        seeds = [
+            b"stream_errors",
+            stream_error_log.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump = stream_error_log.bump,
+        constraint = stream_error_log.quote_mint == quote_mint.key(),
+    )]
+    pub stream_error_log: Box<Account<'info, StreamErrorLog>>,
+}
+
 /// Accounts required to start a new daily distribution
 #[derive(Accounts)]
-#[instruction(distribution_day: i64)]
-pub struct StartDailyDistribution<'info> {
-    /// The authority starting the distribution (can be anyone - permissionless)
-    #[account(mut)]
+#[instruction(distribution_day: i64)]
+pub struct StartDailyDistribution<'info> {
+    /// The authority starting the distribution (can be anyone - permissionless)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Program config (distributions must not be paused)
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        constraint = !program_config.is_paused @ FeeRouterError::DistributionPaused,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Global distribution state
+    #[account(
+        mut,
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+
+    /// Daily distribution state account (created for this day)
+    /// CHECK: Created by the handler so a repeated start reports `DistributionAlreadyExistsForDay`
+    #[account(
+        mut,
+        seeds = [
+            b"daily_distribution",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub daily_distribution_state: UncheckedAccount<'info>,
+
+    /// Investor snapshot for this day (must be taken before starting)
+    #[account(
+        seeds = [
+            b"snapshot",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = investor_snapshot.quote_mint == quote_mint.key(),
+        constraint = investor_snapshot.distribution_day == distribution_day,
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+
+    /// Policy parameters recorded for this day (for auditing)
+    /// CHECK: Created by the handler together with `daily_distribution_state`
+    #[account(
+        mut,
+        seeds = [
+            b"policy_snapshot",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub policy_snapshot: UncheckedAccount<'info>,
+
+    /// Streams that fail while processing this day's pages
+    /// CHECK: Created by the handler together with `daily_distribution_state`
+    #[account(
+        mut,
+        seeds = [
+            b"stream_errors",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stream_error_log: UncheckedAccount<'info>,
+
+    /// Policy state (provides the investor fee share for the day)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state to get available balance
+    #[account(
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// Treasury ATA to distribute from
+    #[account(
+        constraint = treasury_ata.key() == treasury_state.treasury_ata,
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    /// Investor registry (when passed, the day covers every registered investor)
+    #[account(
+        seeds = [b"investor_registry", quote_mint.key().as_ref()],
+        bump = investor_registry.bump,
+        constraint = investor_registry.quote_mint == quote_mint.key(),
+    )]
+    pub investor_registry: Option<Box<Account<'info, InvestorRegistryState>>>,
+
+    /// Allocation registry (the registered allocation is checked against Y0)
+    #[account(
+        seeds = [b"allocation_registry", quote_mint.key().as_ref()],
+        bump = allocation_registry.bump,
+        constraint = allocation_registry.quote_mint == quote_mint.key(),
+    )]
+    pub allocation_registry: Box<Account<'info, AllocationRegistry>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+
+    // Note: When the policy auto-claims, the ClaimFees accounts are passed as remaining_accounts
+}
+
+/// Accounts required to claim fees and start a daily distribution atomically
+#[derive(Accounts)]
+pub struct InitializeAtomicDistribution<'info> {
+    /// Accounts for claiming position fees into the treasury
+    pub claim: ClaimFees<'info>,
+
+    /// Accounts for starting the day's distribution
+    pub start: StartDailyDistribution<'info>,
+}
+
+/// Accounts required to claim fees and pay them out immediately
+#[derive(Accounts)]
+#[instruction(distribution_day: i64)]
+pub struct ClaimAndDistributeInstant<'info> {
+    /// Accounts for claiming position fees into the treasury
+    pub claim: ClaimFees<'info>,
+
+    /// The authority cranking the claim (pays for missing investor ATAs and the day's state)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program config (distributions must not be paused)
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        constraint = !program_config.is_paused @ FeeRouterError::DistributionPaused,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Investor snapshot providing the investors and their locked amounts
+    #[account(
+        seeds = [
+            b"snapshot",
+            distribution_day.to_string().as_bytes(),
+            claim.quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = investor_snapshot.quote_mint == claim.quote_mint.key(),
+        constraint = investor_snapshot.distribution_day == distribution_day,
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+
+    /// Daily distribution state for the day (created by the day's first instant distribution)
+    #[account(
+        mut,
+        seeds = [
+            b"daily_distribution",
+            distribution_day.to_string().as_bytes(),
+            claim.quote_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: Created and deserialized in the handler
+    pub daily_distribution_state: UncheckedAccount<'info>,
+
+    /// Global distribution state (provides the creator wallet, records the distribution)
+    #[account(
+        mut,
+        seeds = [b"global_distribution", claim.quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == claim.quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+
+    /// Treasury authority PDA (owns the treasury ATA)
+    #[account(
+        seeds = [b"treasury_authority", claim.quote_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA authority for treasury ATA
+    pub treasury_authority: UncheckedAccount<'info>,
+
+    /// Creator's ATA for receiving the creator share
+    #[account(
+        mut,
+        constraint = creator_ata.mint == claim.quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = creator_ata.owner == global_distribution_state.creator_wallet @ FeeRouterError::CreatorWalletMismatch,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    /// Associated token program (creates missing investor ATAs)
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Investor blacklist (blacklisted investors are skipped; required once initialized)
+    #[account(
+        seeds = [b"blacklist", claim.quote_mint.key().as_ref()],
+        bump = investor_blacklist.bump,
+        constraint = investor_blacklist.quote_mint == claim.quote_mint.key(),
+    )]
+    pub investor_blacklist: Option<Box<Account<'info, InvestorBlacklist>>>,
+
+    // Note: Investor accounts are passed as remaining_accounts
+}
+
+/// Accounts required to process a page of investors
+#[derive(Accounts)]
+pub struct ProcessInvestorPage<'info> {
+    /// The authority processing this page (can be anyone - permissionless)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint being distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Program config (distributions must not be paused)
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        constraint = !program_config.is_paused @ FeeRouterError::DistributionPaused,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Daily distribution state for the current day
+    #[account(
+        mut,
+        seeds = [
+            b"daily_distribution",
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = !daily_distribution_state.is_complete,
+        constraint = daily_distribution_state.is_open(Clock::get()?.unix_timestamp) @ FeeRouterError::DistributionWindowExpired,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Policy state (provides the duration bonus and minimum lock configuration, auto-extends the sunset)
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury ATA to distribute from
+    #[account(
+        mut,
+        constraint = treasury_ata.key() == daily_distribution_state.treasury_ata,
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    /// Treasury authority PDA (owns the treasury ATA)
+    /// CHECK: Verified against its seeds in the handler
+    pub treasury_authority: UncheckedAccount<'info>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// System program (for creating missing investor ATAs)
+    pub system_program: Program<'info, System>,
+
+    /// Global distribution state (required when this page triggers auto-completion)
+    #[account(
+        mut,
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Option<Box<Account<'info, GlobalDistributionState>>>,
+
+    /// Treasury state (required when this page triggers auto-completion, records lifetime dust)
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Option<Box<Account<'info, TreasuryState>>>,
+
+    /// Creator's ATA (required when this page triggers auto-completion)
+    #[account(
+        mut,
+        constraint = creator_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = Some(creator_ata.owner) == global_distribution_state.as_ref().map(|state| state.creator_wallet) @ FeeRouterError::CreatorWalletMismatch,
+    )]
+    pub creator_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protocol fee ATA (required on auto-completion when dust goes to the protocol)
+    #[account(
+        mut,
+        constraint = protocol_fee_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = Some(protocol_fee_ata.owner) == global_distribution_state.as_ref().and_then(|state| state.protocol_fee_wallet) @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_fee_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protocol share ATA (required on auto-completion when the policy takes a protocol cut)
+    #[account(
+        mut,
+        constraint = protocol_share_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = protocol_share_ata.owner == policy_state.protocol_fee_wallet @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_share_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Investor blacklist (blacklisted investors are skipped; required once initialized)
+    #[account(
+        seeds = [b"blacklist", quote_mint.key().as_ref()],
+        bump = investor_blacklist.bump,
+        constraint = investor_blacklist.quote_mint == quote_mint.key(),
+    )]
+    pub investor_blacklist: Option<Box<Account<'info, InvestorBlacklist>>>,
+
+    /// Stream error log for the day (failed streams are recorded)
+    #[account(
+        mut,
+        seeds = [
+            b"stream_errors",
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump = stream_error_log.bump,
+    )]
+    pub stream_error_log: Box<Account<'info, StreamErrorLog>>,
+
+    /// Investor snapshot the day was started from (fixes the page investors and locked amounts)
+    #[account(
+        seeds = [
+            b"snapshot",
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = investor_snapshot.quote_mint == quote_mint.key(),
+        constraint = investor_snapshot.taken_at == daily_distribution_state.lock_snapshot_timestamp,
+    )]
+    pub investor_snapshot: Box<Account<'info, InvestorSnapshot>>,
+
+    // Note: Investor accounts are passed as remaining_accounts, 6 per investor:
+    // [stream, investor_wallet, investor_ata, associated_token_program, investor_cache, payout_summary]
+}
+
+/// Accounts required to process a page of investors from the day's snapshot
+#[derive(Accounts)]
+pub struct ProcessInvestorPageFromSnapshot<'info> {
+    /// Accounts of the regular page instruction (including the day's investor snapshot)
+    pub page: ProcessInvestorPage<'info>,
+
+    /// Associated token program (for creating missing investor ATAs)
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // Note: Investor accounts are passed as remaining_accounts, 3 per snapshot entry
+    // in page order: [investor_wallet, investor_ata, payout_summary]
+}
+
+/// Accounts required to process a page of investors from the investor registry
+#[derive(Accounts)]
+pub struct ProcessInvestorPageFromRegistry<'info> {
+    /// Accounts of the regular page instruction
+    pub page: ProcessInvestorPage<'info>,
+
+    /// Investor registry the day was started with
+    #[account(
+        seeds = [b"investor_registry", page.quote_mint.key().as_ref()],
+        bump = investor_registry.bump,
+        constraint = investor_registry.quote_mint == page.quote_mint.key(),
+    )]
+    pub investor_registry: Box<Account<'info, InvestorRegistryState>>,
+
+    /// Registry page holding the investors at the day's cursor
+    #[account(
+        seeds = [
+            b"registry_page",
+            investor_registry.key().as_ref(),
+            &registry_page.page_index.to_le_bytes(),
+        ],
+        bump = registry_page.bump,
+        constraint = registry_page.registry == investor_registry.key(),
+    )]
+    pub registry_page: Box<Account<'info, InvestorRegistryPage>>,
+
+    // Note: Investor accounts are passed as remaining_accounts like `process_investor_page`,
+    // in registry order starting at the day's cursor
+}
+
+/// Accounts required to change the page size of a running distribution
+#[derive(Accounts)]
+pub struct AdjustEffectivePageSize<'info> {
+    /// The policy authority
     pub authority: Signer<'info>,
 
     /// Quote mint being distributed
     pub quote_mint: Account<'info, Mint>,
 
-    /// Global distribution state
+    /// Policy state (identifies the authority)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Daily distribution state still accepting pages
     #[account(
         mut,
-        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        seeds = [
+            b"daily_distribution",
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
         bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.is_open(Clock::get()?.unix_timestamp) @ FeeRouterError::DistributionWindowExpired,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+}
+
+/// Accounts required to complete a daily distribution
+#[derive(Accounts)]
+pub struct CompleteDailyDistribution<'info> {
+    /// The authority completing the distribution (can be anyone - permissionless)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Global distribution state to update
+    #[account(
+        mut,
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
         constraint = global_distribution_state.quote_mint == quote_mint.key(),
     )]
     pub global_distribution_state: Account<'info, GlobalDistributionState>,
 
-    /// Daily distribution state account (created for this day)
+    /// Daily distribution state to mark as complete
     #[account(
-        init,
-        payer = authority,
-        space = 8 + DailyDistributionState::INIT_SPACE,
+        mut,
         seeds = [
             b"daily_distribution",
-            distribution_day.to_string().as_bytes(),
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
             quote_mint.key().as_ref(),
         ],
         bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = !daily_distribution_state.has_more_investors(),
+        constraint = !daily_distribution_state.is_complete,
     )]
     pub daily_distribution_state: Account<'info, DailyDistributionState>,
 
-    /// Treasury state to get available balance
+    /// Policy state (provides the explicit creator share, auto-extends the sunset)
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state (provides lifetime claimed fees for the balance check, records lifetime dust)
     #[account(
+        mut,
         seeds = [b"treasury_state", quote_mint.key().as_ref()],
-        bump,
+        bump = treasury_state.bump,
         constraint = treasury_state.quote_mint == quote_mint.key(),
     )]
     pub treasury_state: Account<'info, TreasuryState>,
 
-    /// Treasury ATA to distribute from
+    /// Treasury ATA to transfer creator share from
     #[account(
-        constraint = treasury_ata.key() == treasury_state.treasury_ata,
-        constraint = treasury_ata.mint == quote_mint.key(),
+        mut,
+        constraint = treasury_ata.key() == daily_distribution_state.treasury_ata,
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
     )]
     pub treasury_ata: Account<'info, TokenAccount>,
 
-    /// System program
-    pub system_program: Program<'info, System>,
+    /// Treasury authority PDA (owns the treasury ATA)
+    #[account(
+        seeds = [b"treasury_authority", quote_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA authority for treasury ATA
+    pub treasury_authority: UncheckedAccount<'info>,
 
-    /// Rent sysvar
-    pub rent: Sysvar<'info, Rent>,
+    /// Creator's ATA for receiving the creator share
+    #[account(
+        mut,
+        constraint = creator_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = creator_ata.owner == global_distribution_state.creator_wallet @ FeeRouterError::CreatorWalletMismatch,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    /// Protocol fee ATA (required when the policy routes dust to the protocol)
+    #[account(
+        mut,
+        constraint = protocol_fee_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = Some(protocol_fee_ata.owner) == global_distribution_state.protocol_fee_wallet @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_fee_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Protocol share ATA (required when the policy takes a protocol cut)
+    #[account(
+        mut,
+        constraint = protocol_share_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = protocol_share_ata.owner == policy_state.protocol_fee_wallet @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_share_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
 }
 
-/// Accounts required to process a page of investors
+/// Accounts required to report on a completed daily distribution
 #[derive(Accounts)]
-pub struct ProcessInvestorPage<'info> {
-    /// The authority processing this page (can be anyone - permissionless)
-    #[account(mut)]
+#[instruction(distribution_day: i64)]
+pub struct GenerateDistributionReport<'info> {
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Completed daily distribution state to report on
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.is_complete @ FeeRouterError::DistributionInProgress,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    // Note: The day's InvestorPayoutSummary accounts are passed as remaining_accounts
+}
+
+/// Accounts required to query a stream's locked amount
+#[derive(Accounts)]
+pub struct GetStreamLockedAmount<'info> {
+    /// The caller (can be anyone - permissionless, read-only)
     pub authority: Signer<'info>,
 
-    /// Quote mint being distributed
+    // Note: The Streamflow stream account is passed as remaining_accounts[0]
+}
+
+/// Accounts required to read a completed day's stream errors
+#[derive(Accounts)]
+pub struct GetStreamErrors<'info> {
+    /// Quote mint that was distributed
     pub quote_mint: Account<'info, Mint>,
 
-    /// Daily distribution state for the current day
+    /// Completed daily distribution state the errors were recorded for
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            stream_error_log.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = daily_distribution_state.is_complete @ FeeRouterError::DistributionInProgress,
+    )]
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
+
+    /// Stream error log of the day
+    #[account(
+        seeds = [
+            b"stream_errors",
+            stream_error_log.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump = stream_error_log.bump,
+        constraint = stream_error_log.quote_mint == quote_mint.key(),
+    )]
+    pub stream_error_log: Box<Account<'info, StreamErrorLog>>,
+}
+
+/// Accounts required to audit a completed daily distribution
+#[derive(Accounts)]
+pub struct VerifyDistributionIntegrity<'info> {
+    /// The auditor (can be anyone - permissionless, read-only)
+    pub authority: Signer<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Global distribution state the day was recorded in
+    #[account(
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+
+    /// Daily distribution state to audit
     #[account(
-        mut,
         seeds = [
             b"daily_distribution",
             daily_distribution_state.distribution_day.to_string().as_bytes(),
@@ -134,37 +1328,75 @@ pub struct ProcessInvestorPage<'info> {
         ],
         bump,
         constraint = daily_distribution_state.quote_mint == quote_mint.key(),
-        constraint = !daily_distribution_state.is_complete,
     )]
     pub daily_distribution_state: Account<'info, DailyDistributionState>,
 
-    /// Treasury ATA to distribute from
+    /// Policy parameters recorded when the day started
     #[account(
-        mut,
-        constraint = treasury_ata.key() == daily_distribution_state.treasury_ata,
-        constraint = treasury_ata.mint == quote_mint.key(),
+        seeds = [
+            b"policy_snapshot",
+            daily_distribution_state.distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = policy_snapshot.quote_mint == quote_mint.key(),
     )]
-    pub treasury_ata: Account<'info, TokenAccount>,
+    pub policy_snapshot: Account<'info, PolicySnapshot>,
+}
 
-    /// Treasury authority PDA (owns the treasury ATA)
+/// Accounts required to audit a completed daily distribution from its payout summaries
+#[derive(Accounts)]
+#[instruction(distribution_day: i64)]
+pub struct AuditCompletedDistribution<'info> {
+    /// The auditor (can be anyone - permissionless, read-only)
+    pub authority: Signer<'info>,
+
+    /// Quote mint that was distributed
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Global distribution state the day was recorded in
     #[account(
-        seeds = [b"treasury_authority", quote_mint.key().as_ref()],
+        seeds = [b"global_distribution", quote_mint.key().as_ref()],
+        bump = global_distribution_state.bump,
+        constraint = global_distribution_state.quote_mint == quote_mint.key(),
+    )]
+    pub global_distribution_state: Account<'info, GlobalDistributionState>,
+
+    /// Completed daily distribution state to audit
+    #[account(
+        seeds = [
+            b"daily_distribution",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
         bump,
+        constraint = daily_distribution_state.quote_mint == quote_mint.key(),
+        constraint = daily_distribution_state.is_complete @ FeeRouterError::DistributionInProgress,
     )]
-    /// CHECK: PDA authority for treasury ATA
-    pub treasury_authority: UncheckedAccount<'info>,
+    pub daily_distribution_state: Account<'info, DailyDistributionState>,
 
-    /// Token program
-    pub token_program: Program<'info, Token>,
+    /// Policy parameters recorded when the day started
+    #[account(
+        seeds = [
+            b"policy_snapshot",
+            distribution_day.to_string().as_bytes(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = policy_snapshot.quote_mint == quote_mint.key(),
+    )]
+    pub policy_snapshot: Account<'info, PolicySnapshot>,
 
-    // Note: Investor accounts will be passed as remaining_accounts
-    // Each investor needs their ATA for receiving tokens
+    // Note: The day's InvestorPayoutSummary accounts are passed as remaining_accounts
 }
 
-/// Accounts required to complete a daily distribution
+/// Accounts required to force-complete a stuck daily distribution
+///
+/// Same as `CompleteDailyDistribution` but without the requirement that all
+/// investors have been processed (the timeout is checked in the instruction).
 #[derive(Accounts)]
-pub struct CompleteDailyDistribution<'info> {
-    /// The authority completing the distribution (can be anyone - permissionless)
+pub struct ForceCompleteDailyDistribution<'info> {
+    /// The authority force-completing the distribution (can be anyone - permissionless)
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -175,7 +1407,7 @@ pub struct CompleteDailyDistribution<'info> {
     #[account(
         mut,
         seeds = [b"global_distribution", quote_mint.key().as_ref()],
-        bump,
+        bump = global_distribution_state.bump,
         constraint = global_distribution_state.quote_mint == quote_mint.key(),
     )]
     pub global_distribution_state: Account<'info, GlobalDistributionState>,
@@ -190,16 +1422,33 @@ pub struct CompleteDailyDistribution<'info> {
         ],
         bump,
         constraint = daily_distribution_state.quote_mint == quote_mint.key(),
-        constraint = !daily_distribution_state.has_more_investors(),
         constraint = !daily_distribution_state.is_complete,
     )]
     pub daily_distribution_state: Account<'info, DailyDistributionState>,
 
-    /// Treasury ATA to transfer creator remainder from
+    /// Policy state (provides the explicit creator share, auto-extends the sunset)
+    #[account(
+        mut,
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Treasury state (provides lifetime claimed fees for the balance check, records lifetime dust)
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// Treasury ATA to transfer creator share from
     #[account(
         mut,
         constraint = treasury_ata.key() == daily_distribution_state.treasury_ata,
-        constraint = treasury_ata.mint == quote_mint.key(),
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
     )]
     pub treasury_ata: Account<'info, TokenAccount>,
 
@@ -211,13 +1460,30 @@ pub struct CompleteDailyDistribution<'info> {
     /// CHECK: PDA authority for treasury ATA
     pub treasury_authority: UncheckedAccount<'info>,
 
-    /// Creator's ATA for receiving remainder
+    /// Creator's ATA for receiving the creator share
     #[account(
         mut,
-        constraint = creator_ata.mint == quote_mint.key(),
+        constraint = creator_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = creator_ata.owner == global_distribution_state.creator_wallet @ FeeRouterError::CreatorWalletMismatch,
     )]
     pub creator_ata: Account<'info, TokenAccount>,
 
+    /// Protocol fee ATA (required when the policy routes dust to the protocol)
+    #[account(
+        mut,
+        constraint = protocol_fee_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = Some(protocol_fee_ata.owner) == global_distribution_state.protocol_fee_wallet @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_fee_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Protocol share ATA (required when the policy takes a protocol cut)
+    #[account(
+        mut,
+        constraint = protocol_share_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = protocol_share_ata.owner == policy_state.protocol_fee_wallet @ FeeRouterError::ProtocolFeeWalletNotSet,
+    )]
+    pub protocol_share_ata: Option<Account<'info, TokenAccount>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::integrations::streamflow::cpi::StreamErrorType;
+use crate::shared::crypto::HashAlgorithm;
 
 /// Event emitted when a new daily distribution is started
 #[event]
@@ -19,6 +21,22 @@ pub struct DailyDistributionStarted {
     pub timestamp: i64,
 }
 
+/// Event emitted alongside `DailyDistributionStarted` with the planned number of pages
+#[event]
+pub struct ExpectedPageCount {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Pages needed to process every investor at `page_size`
+    pub expected_pages: u32,
+    
+    /// Total number of investors to process
+    pub total_investors: u32,
+    
+    /// Maximum investors per page used for the estimate
+    pub page_size: u32,
+}
+
 /// Event emitted when a page of investors is processed
 #[event]
 pub struct InvestorsProcessed {
@@ -46,6 +64,59 @@ pub struct InvestorsProcessed {
     /// Whether this was the final page
     pub is_final_page: bool,
     
+    /// Seconds from the distribution start until this page completed
+    pub page_processing_latency_seconds: i64,
+    
+    /// Slot the page was processed in
+    pub slot: u64,
+    
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Progress summary of a daily distribution, emitted after every page and on completion
+#[event]
+pub struct DistributionSummaryView {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Investors processed so far
+    pub investors_processed: u32,
+    
+    /// Total investors for the day
+    pub total_investors: u32,
+    
+    /// Amount distributed to investors so far
+    pub amount_distributed: u64,
+    
+    /// Pages processed so far
+    pub pages_processed: u32,
+    
+    /// Whether the distribution is complete
+    pub is_complete: bool,
+    
+    /// Share of investors processed (0-100)
+    pub progress_pct: u8,
+    
+    /// Projected completion timestamp (0 before the first page)
+    pub estimated_completion_time: i64,
+    
+    /// Today's investor payouts annualized against Y0, in basis points
+    pub effective_apr_bps: u64,
+}
+
+/// Warning emitted when the auto-claim before a distribution start fails
+#[event]
+pub struct AutoClaimFailed {
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Why the claim failed
+    pub reason: String,
+    
     /// Timestamp
     pub timestamp: i64,
 }
@@ -65,6 +136,15 @@ pub struct DailyDistributionCompleted {
     /// Total investors processed
     pub total_investors_processed: u32,
     
+    /// Largest page (in investors) processed during the day
+    pub max_page_size_used: u32,
+    
+    /// Smallest page (in investors) processed during the day
+    pub min_page_size_used: u32,
+    
+    /// Number of pages processed during the day
+    pub actual_page_count: u32,
+    
     /// Timestamp when completed
     pub timestamp: i64,
 }
@@ -84,6 +164,15 @@ pub struct GlobalDistributionUpdated {
     /// Total amount distributed across all time
     pub total_amount_distributed: u64,
     
+    /// Total paid to investors across all time
+    pub total_investor_payouts: u64,
+    
+    /// Total paid to the creator across all time
+    pub total_creator_payouts: u64,
+    
+    /// Age of the linked honorary position in days (0 if not linked)
+    pub position_age_days: u64,
+    
     /// Timestamp
     pub timestamp: i64,
 }
@@ -159,7 +248,7 @@ pub struct CreatorPayoutCompleted {
     /// Creator who received the payout
     pub creator: Pubkey,
     
-    /// Amount paid to creator (remainder after investor payouts)
+    /// Amount paid to creator (explicit creator share)
     pub creator_remainder: u64,
     
     /// Total amount that was available for distribution
@@ -174,3 +263,657 @@ pub struct CreatorPayoutCompleted {
     /// Timestamp when payout completed
     pub timestamp: i64,
 }
+
+/// Event emitted when a stuck distribution is force-completed
+#[event]
+pub struct DistributionForceCompleted {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Investors processed before the forced completion
+    pub investors_processed: u32,
+    
+    /// Amount distributed to investors before the forced completion
+    pub amount_distributed: u64,
+    
+    /// Reason for forcing completion
+    pub reason: String,
+    
+    /// Timestamp when force completed
+    pub timestamp: i64,
+}
+
+/// Event emitted when a distribution is completed inline after too many pages
+#[event]
+pub struct AutoCompletionTriggered {
+    /// Pages processed when completion was triggered
+    pub pages_processed: u32,
+    
+    /// Investors not yet processed at completion
+    pub investors_remaining: u32,
+    
+    /// Timestamp of the completion
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor snapshot is taken for a distribution day
+#[event]
+pub struct InvestorSnapshotTaken {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Number of investors captured
+    pub investor_count: u32,
+    
+    /// Total locked amount captured
+    pub total_locked: u64,
+    
+    /// Timestamp when taken
+    pub timestamp: i64,
+}
+
+/// Event emitted after checking snapshot streams against the quote mint
+#[event]
+pub struct SnapshotIntegrityReport {
+    /// The distribution day of the snapshot
+    pub distribution_day: i64,
+    
+    /// Quote mint the streams were checked against
+    pub quote_mint: Pubkey,
+    
+    /// Index of the first snapshot entry checked
+    pub start_index: u32,
+    
+    /// Number of streams checked
+    pub streams_checked: u32,
+    
+    /// Streams that are unreadable or stream another mint
+    pub violations: Vec<Pubkey>,
+    
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a stream is skipped for ending before the policy's minimum lock
+#[event]
+pub struct ShortStreamSkipped {
+    /// The skipped stream account
+    pub stream_account: Pubkey,
+    
+    /// Seconds left until the stream ends
+    pub remaining_seconds: u64,
+    
+    /// Timestamp when skipped
+    pub timestamp: i64,
+}
+
+/// Event emitted when part of the treasury balance is held back as reserve
+#[event]
+pub struct TreasuryReserveRetained {
+    /// Amount kept in the treasury ATA
+    pub reserve_amount: u64,
+    
+    /// Amount available for this day's distribution
+    pub distributable_amount: u64,
+    
+    /// Timestamp when retained
+    pub timestamp: i64,
+}
+
+/// Event emitted with the treasury balance a daily distribution starts from
+#[event]
+pub struct TreasuryBalanceAtDistributionStart {
+    /// Quote mint of the distribution
+    pub quote_mint: Pubkey,
+    
+    /// The treasury ATA
+    pub treasury_ata: Pubkey,
+    
+    /// Treasury ATA balance, after any auto-claim
+    pub balance: u64,
+    
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Timestamp when started
+    pub timestamp: i64,
+}
+
+/// Event emitted with the treasury balance left after a daily distribution completes
+#[event]
+pub struct TreasuryBalancePostDistribution {
+    /// Treasury ATA balance after all payouts
+    pub final_balance: u64,
+    
+    /// Total paid to investors during the day
+    pub distributed_to_investors: u64,
+    
+    /// Amount paid to the creator
+    pub distributed_to_creator: u64,
+    
+    /// Rounding dust left in the treasury
+    pub dust_remaining: u64,
+    
+    /// Timestamp when completed
+    pub timestamp: i64,
+}
+
+/// Event emitted when the rolling average distribution rate is updated
+#[event]
+pub struct DistributionRateUpdated {
+    /// New rolling average of paid out / treasury balance in basis points
+    pub new_average_bps: u64,
+    
+    /// The distribution day that was folded in
+    pub distribution_day: i64,
+}
+
+/// Event emitted when the creator wallet is changed
+#[event]
+pub struct CreatorWalletUpdated {
+    /// Previous creator wallet
+    pub old_wallet: Pubkey,
+    
+    /// New creator wallet
+    pub new_wallet: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Warning event emitted when the locked total exceeds Y0 and the locked fraction is clamped
+#[event]
+pub struct LockedFractionExceededY0 {
+    /// Total locked across the page
+    pub locked_total: u64,
+    
+    /// Y0 used for the locked fraction
+    pub y0_total_allocation: u64,
+    
+    /// Locked fraction before clamping in basis points
+    pub uncapped_bps: u64,
+}
+
+/// Event emitted when the policy sunset date is extended
+#[event]
+pub struct PolicySunsetExtended {
+    /// Quote mint of the policy
+    pub quote_mint: Pubkey,
+    
+    /// Previous sunset timestamp
+    pub old_sunset_timestamp: i64,
+    
+    /// New sunset timestamp (0 = no sunset)
+    pub new_sunset_timestamp: i64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when a completed distribution auto-extends the policy sunset
+#[event]
+pub struct SunsetAutoExtended {
+    /// Sunset timestamp after the extension
+    pub new_sunset_timestamp: i64,
+    
+    /// Auto-extensions so far, including this one
+    pub extensions_count: u32,
+    
+    /// Timestamp of the extension
+    pub timestamp: i64,
+}
+
+/// Event emitted when a policy account is migrated to a new layout version
+#[event]
+pub struct PolicyFormatUpgraded {
+    /// Quote mint of the policy
+    pub quote_mint: Pubkey,
+    
+    /// Layout version before the upgrade
+    pub from_version: u8,
+    
+    /// Layout version after the upgrade
+    pub to_version: u8,
+    
+    /// Timestamp of the upgrade
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor payout summary is closed and its rent returned
+#[event]
+pub struct InvestorPayoutSummaryClosed {
+    /// The distribution day of the summary
+    pub distribution_day: i64,
+    
+    /// The investor the summary belongs to
+    pub investor: Pubkey,
+    
+    /// Cranker the rent was returned to
+    pub payer: Pubkey,
+    
+    /// Lamports returned to the payer
+    pub rent_recovered: u64,
+}
+
+/// Event emitted when an investor aggregate cache is closed and its rent returned
+#[event]
+pub struct InvestorCacheClosed {
+    /// The distribution day of the cache
+    pub distribution_day: i64,
+    
+    /// Quote mint of the distribution
+    pub quote_mint: Pubkey,
+    
+    /// The investor the cache belongs to
+    pub investor: Pubkey,
+    
+    /// Cranker the rent was returned to
+    pub payer: Pubkey,
+    
+    /// Lamports returned to the payer
+    pub rent_recovered: u64,
+}
+
+/// Event emitted when an investor snapshot is closed and its rent recovered
+#[event]
+pub struct InvestorSnapshotClosed {
+    /// The distribution day of the snapshot
+    pub distribution_day: i64,
+    
+    /// Quote mint that was distributed
+    pub quote_mint: Pubkey,
+    
+    /// Authority the rent was returned to
+    pub payer: Pubkey,
+    
+    /// Lamports returned to the payer
+    pub rent_recovered: u64,
+}
+
+/// Event emitted when the protocol cut of a distribution is paid
+#[event]
+pub struct ProtocolFeeCollected {
+    /// Protocol cut transferred
+    pub amount: u64,
+    
+    /// Wallet that received the cut
+    pub wallet: Pubkey,
+    
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Timestamp of the transfer
+    pub timestamp: i64,
+}
+
+/// Event emitted when the policy's minimum payout is updated
+#[event]
+pub struct MinPayoutUpdated {
+    /// Previous minimum payout in lamports
+    pub old_value: u64,
+    
+    /// New minimum payout in lamports
+    pub new_value: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when the policy's optional settings are updated
+#[event]
+pub struct PolicySettingsUpdated {
+    /// Quote mint of the policy
+    pub quote_mint: Pubkey,
+    
+    /// Whether completed distributions auto-extend the sunset
+    pub auto_extend_sunset: bool,
+    
+    /// Hash algorithm for page hashes and completion proofs
+    pub page_hash_algorithm: HashAlgorithm,
+    
+    /// SPL governance backing policy updates (default = direct authority mode)
+    pub governance_authority: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted by the post-distribution integrity audit
+#[event]
+pub struct DistributionIntegrityReport {
+    /// Whether every check passed
+    pub is_valid: bool,
+    
+    /// Largest amount paid out beyond an expected limit
+    pub discrepancy_amount: u64,
+    
+    /// Results of the individual checks (see `DailyDistributionState::integrity_checks`)
+    pub check_results: [bool; 5],
+    
+    /// Timestamp of the audit
+    pub timestamp: i64,
+}
+
+/// Event emitted by the completed distribution audit
+#[event]
+pub struct AuditResult {
+    /// Whether no invariant was violated
+    pub passed: bool,
+    
+    /// Description of each violated invariant
+    pub violations: Vec<String>,
+    
+    /// Timestamp of the audit
+    pub timestamp: i64,
+}
+
+/// Event emitted with per-investor payout statistics for a completed day
+#[event]
+pub struct DistributionReport {
+    /// The distribution day reported on
+    pub distribution_day: i64,
+    
+    /// Number of investors paid
+    pub investor_count: u32,
+    
+    /// Total quote paid to the reported investors
+    pub total_distributed: u64,
+    
+    /// Median payout per investor
+    pub median_payout: u64,
+    
+    /// Largest payout to a single investor
+    pub max_payout: u64,
+    
+    /// Smallest payout to a single investor
+    pub min_payout: u64,
+    
+    /// Gini coefficient of the payouts (0 = equal, 10000 = one investor got everything)
+    pub gini_coefficient_bps: u64,
+    
+    /// Timestamp of the report
+    pub timestamp: i64,
+}
+
+/// Event emitted when distributions are paused or resumed
+#[event]
+pub struct DistributionPauseUpdated {
+    /// Whether distributions are now paused
+    pub is_paused: bool,
+    
+    /// The pause authority that made the change
+    pub authority: Pubkey,
+    
+    /// Number of recorded distribution pauses
+    pub pause_count: u32,
+    
+    /// Number of recorded distribution resumes
+    pub resume_count: u32,
+    
+    /// Total seconds spent paused
+    pub total_paused_seconds: i64,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when the program pause authority changes
+#[event]
+pub struct ProgramPauseAuthorityUpdated {
+    /// Previous pause authority
+    pub old_authority: Pubkey,
+    
+    /// New pause authority
+    pub new_authority: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor is added to the blacklist
+#[event]
+pub struct InvestorBlacklisted {
+    /// The blacklisted investor wallet
+    pub investor: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor is removed from the blacklist
+#[event]
+pub struct InvestorUnblacklisted {
+    /// The investor wallet removed from the blacklist
+    pub investor: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when the honorary position is linked to the global distribution state
+#[event]
+pub struct PositionLinkedToDistribution {
+    /// Quote mint of the distribution
+    pub quote_mint: Pubkey,
+    
+    /// The linked position
+    pub position: Pubkey,
+    
+    /// Creation timestamp of the position
+    pub position_initialized_at: i64,
+    
+    /// Age of the position in days
+    pub position_age_days: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when claimed fees are paid out in the claim instruction
+#[event]
+pub struct InstantDistributionCompleted {
+    /// The distribution day of the investor snapshot used
+    pub distribution_day: i64,
+    
+    /// Quote mint that was distributed
+    pub quote_mint: Pubkey,
+    
+    /// Quote tokens claimed into the treasury by this instruction
+    pub claimed_amount: u64,
+    
+    /// Number of investors paid
+    pub investors_paid: u32,
+    
+    /// Total amount paid to investors
+    pub total_investor_payouts: u64,
+    
+    /// Amount paid to the creator
+    pub creator_amount: u64,
+    
+    /// Timestamp when distributed
+    pub timestamp: i64,
+}
+
+/// Event emitted when stream deposits are added to the allocation registry
+#[event]
+pub struct StreamAllocationsRegistered {
+    /// Quote mint of the registry
+    pub quote_mint: Pubkey,
+    
+    /// Number of streams registered by this update
+    pub streams_registered: u32,
+    
+    /// Deposits added by this update
+    pub allocation_added: u64,
+    
+    /// Registered allocation after the update
+    pub total_registered_allocation: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted with a stream's locked amount for frontends
+#[event]
+pub struct StreamLockedAmountQueried {
+    /// The Streamflow stream account
+    pub stream_account: Pubkey,
+    
+    /// The investor receiving the stream
+    pub investor: Pubkey,
+    
+    /// Amount still locked
+    pub locked_amount: u64,
+    
+    /// Total amount deposited into the stream
+    pub total_deposited: u64,
+    
+    /// Percentage (0-100) of the deposit still locked
+    pub lock_fraction_pct: u8,
+    
+    /// Whether the stream is currently vesting
+    pub is_active: bool,
+    
+    /// Timestamp when the stream is fully unlocked
+    pub estimated_unlock_date: i64,
+    
+    /// Timestamp of the query
+    pub timestamp: i64,
+}
+
+/// Event emitted when a completed distribution updates the health score
+#[event]
+pub struct HealthScoreUpdated {
+    /// Score before this distribution
+    pub old_score: u8,
+    
+    /// Score of this distribution (0-100)
+    pub new_score: u8,
+    
+    /// `HEALTH_FLAG_*` reasons the score was lowered
+    pub reason_flags: u8,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted for each stream error recorded during a day's distribution
+#[event]
+pub struct StreamErrorReported {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Position of the error in the day's log
+    pub index: u32,
+    
+    /// The Streamflow stream account that failed
+    pub stream_account: Pubkey,
+    
+    /// Why the stream failed
+    pub error_type: StreamErrorType,
+}
+
+/// Event emitted when a day's stream error log is closed
+#[event]
+pub struct StreamErrorLogClosed {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Quote mint that was distributed
+    pub quote_mint: Pubkey,
+    
+    /// Cranker the rent was returned to
+    pub payer: Pubkey,
+    
+    /// Rent lamports returned to the payer
+    pub rent_recovered: u64,
+}
+
+/// Event emitted after each investor page so auditors can verify it off-chain
+#[event]
+pub struct PageCompletionProof {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Zero-based index of the page within the day
+    pub page_index: u32,
+    
+    /// Hash of the page's investor wallets, in page order
+    pub investor_keys_hash: [u8; 32],
+    
+    /// Hash of the paid `(investor, amount)` pairs (see `calculate_payouts_hash`)
+    pub payouts_hash: [u8; 32],
+    
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the page size of a running distribution is changed
+#[event]
+pub struct EffectivePageSizeAdjusted {
+    /// The distribution day
+    pub distribution_day: i64,
+    
+    /// Page size before the change
+    pub old_size: u32,
+    
+    /// Page size for the remaining pages
+    pub new_size: u32,
+    
+    /// Pages now expected for the whole day
+    pub expected_page_count: u32,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor is added to the investor registry
+#[event]
+pub struct InvestorRegistered {
+    /// Quote mint of the registry
+    pub quote_mint: Pubkey,
+    
+    /// The registered investor
+    pub investor: Pubkey,
+    
+    /// The investor's Streamflow stream account
+    pub stream_account: Pubkey,
+    
+    /// Registry page the investor was added to
+    pub page_index: u32,
+    
+    /// Investors registered after this one
+    pub investor_count: u32,
+    
+    /// Timestamp of the registration
+    pub timestamp: i64,
+}
+
+/// Event emitted when a completed distribution adds to the treasury's lifetime dust
+#[event]
+pub struct LifetimeDustUpdated {
+    /// Lifetime dust total after this distribution
+    pub new_total: u64,
+    
+    /// Day of the distribution that added the dust
+    pub distribution_day: i64,
+}
+
+/// Event emitted when an investor is skipped because their ATA is frozen
+#[event]
+pub struct InvestorAtaFrozen {
+    /// The skipped investor
+    pub investor: Pubkey,
+    
+    /// The investor's frozen ATA
+    pub ata: Pubkey,
+    
+    /// Day of the distribution
+    pub distribution_day: i64,
+}
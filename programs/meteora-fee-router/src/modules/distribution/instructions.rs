@@ -1,11 +1,100 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
+use anchor_spl::associated_token::{self, get_associated_token_address};
 use crate::modules::distribution::contexts::*;
 use crate::modules::distribution::events::*;
-use crate::modules::distribution::state::{DailyDistributionState, GlobalDistributionState, PolicyState};
+use crate::modules::distribution::state::{migrate_v1_to_v2, AllocationRegistry, CapApplicationMode, DailyDistributionState, DustDestination, FeeDistributionMode, GlobalDistributionState, InvestorAggregateCache, InvestorBlacklist, InvestorEntry, InvestorPayoutSummary, InvestorRegistryPage, InvestorRegistryState, InvestorSnapshot, PolicySnapshot, PolicyState, PolicyStateV1, ProgramConfig, SnapshotEntry, StreamErrorLog};
+use crate::integrations::meteora;
 use crate::integrations::streamflow;
+use crate::integrations::streamflow::accounts::InvestorStreamData;
 use crate::shared::constants::*;
+use crate::shared::crypto::HashAlgorithm;
+use crate::shared::pda::verify_pda;
+use crate::shared::rent;
+use crate::shared::compute_budget::{validate_remaining_accounts_length, MAX_SAFE_INVESTORS_PER_PAGE};
+use crate::shared::governance::ValidateGovernanceProposal;
+use crate::modules::claiming::contexts::{ClaimFees, ClaimFeesBumps};
+use crate::modules::claiming::instructions as claiming_instructions;
+use crate::modules::claiming::state::TreasuryState;
+use crate::modules::position::state::PoolType;
 use crate::errors::FeeRouterError;
+use std::collections::BTreeSet;
+
+/// Parameters for initializing a policy
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct InitializePolicyParams {
+    /// Maximum investor share (0-10000)
+    pub investor_fee_share_bps: u64,
+    /// Explicit creator share (investor + creator <= 10000)
+    pub creator_fee_share_bps: u64,
+    /// Daily distribution cap (0 = no cap)
+    pub daily_cap_lamports: u64,
+    /// Minimum payout threshold
+    pub min_payout_lamports: u64,
+    /// Total investor allocation at TGE
+    pub y0_total_allocation: u64,
+    /// Accept dust-level base fees on claim
+    pub allow_base_fee_grace_period: bool,
+    /// One-time lamport fee paid to the policy authority per honorary position (0 = free)
+    pub position_creation_fee_lamports: u64,
+    /// Investor share at the start of the ramp
+    pub ramp_start_bps: u64,
+    /// Investor share at the end of the ramp
+    pub ramp_end_bps: u64,
+    /// Ramp duration in seconds (0 = ramp disabled)
+    pub ramp_duration_seconds: i64,
+    /// Timestamp the ramp starts from
+    pub ramp_start_timestamp: i64,
+    /// Give long-term lockers extra weight
+    pub duration_bonus_enabled: bool,
+    /// Maximum lock duration multiplier (e.g. 20000 = 2x)
+    pub duration_bonus_max_multiplier_bps: u64,
+    /// Minimum remaining lock for a stream to count (0 = no filter)
+    pub min_remaining_lock_seconds: u64,
+    /// EMA smoothing factor for the rolling distribution rate (0-10000)
+    pub avg_alpha_bps: u64,
+    /// Where rounding dust goes on completion
+    pub dust_destination: DustDestination,
+    /// Minimum snapshot investors required to start a distribution (0 = `DEFAULT_MIN_INVESTORS_TO_DISTRIBUTE`)
+    pub min_investors_to_distribute: u32,
+    /// Count unreleased cliff amounts towards investor weight
+    pub include_cliff_in_weight: bool,
+    /// No new distributions can start at or after this timestamp (0 = no sunset)
+    pub sunset_timestamp: i64,
+    /// Protocol cut of each distribution (0 = no cut)
+    pub protocol_fee_bps: u64,
+    /// Wallet receiving the protocol cut
+    pub protocol_fee_wallet: Pubkey,
+    /// Complete the day inline after this many pages (0 = disabled)
+    pub max_pages_before_auto_complete: u32,
+    /// How payouts are reduced to fit the daily cap
+    pub cap_application_mode: CapApplicationMode,
+    /// Claim position fees into the treasury when a distribution starts
+    pub auto_claim_before_distribution: bool,
+    /// Scale investor weights by remaining lock duration
+    pub use_duration_weighting: bool,
+    /// Distribute fees as they are claimed or in the daily crank
+    pub fee_distribution_mode: FeeDistributionMode,
+    /// Hash algorithm for page hashes and completion proofs
+    pub page_hash_algorithm: HashAlgorithm,
+    /// Investors per page for new distributions (0 = maximum)
+    pub target_page_size: u32,
+    /// Longest time since the last fee claim a distribution may start after (0 = disabled)
+    pub max_claim_interval_seconds: i64,
+    /// Push the sunset back by one distribution interval on each completed distribution
+    pub auto_extend_sunset: bool,
+}
+
+/// Optional policy settings the authority can change after initialization
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Default)]
+pub struct UpdatePolicySettingsParams {
+    /// Push the sunset back by one distribution interval on each completed distribution
+    pub auto_extend_sunset: Option<bool>,
+    /// Hash algorithm for page hashes and completion proofs
+    pub page_hash_algorithm: Option<HashAlgorithm>,
+    /// SPL governance whose proposals must back later updates (default = direct authority mode)
+    pub governance_authority: Option<Pubkey>,
+}
 
 /// Initialize the policy state
 /// 
@@ -14,31 +103,55 @@ use crate::errors::FeeRouterError;
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
-/// * `investor_fee_share_bps` - Maximum investor share (0-10000)
-/// * `daily_cap_lamports` - Daily distribution cap (0 = no cap)
-/// * `min_payout_lamports` - Minimum payout threshold
-/// * `y0_total_allocation` - Total investor allocation at TGE
+/// * `params` - The policy parameters
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
 pub fn initialize_policy(
     ctx: Context<InitializePolicy>,
-    investor_fee_share_bps: u64,
-    daily_cap_lamports: u64,
-    min_payout_lamports: u64,
-    y0_total_allocation: u64,
+    params: InitializePolicyParams,
 ) -> Result<()> {
     msg!("Initializing policy for quote mint: {}", ctx.accounts.quote_mint.key());
 
     // Initialize policy state
     ctx.accounts.policy_state.set_inner(PolicyState {
         quote_mint: ctx.accounts.quote_mint.key(),
-        investor_fee_share_bps,
-        daily_cap_lamports,
-        min_payout_lamports,
-        y0_total_allocation,
+        investor_fee_share_bps: params.investor_fee_share_bps,
+        creator_fee_share_bps: params.creator_fee_share_bps,
+        daily_cap_lamports: params.daily_cap_lamports,
+        min_payout_lamports: params.min_payout_lamports,
+        y0_total_allocation: params.y0_total_allocation,
         policy_authority: ctx.accounts.authority.key(),
-        reserved: [0; 64],
+        allow_base_fee_grace_period: params.allow_base_fee_grace_period,
+        position_creation_fee_lamports: params.position_creation_fee_lamports,
+        ramp_start_bps: params.ramp_start_bps,
+        ramp_end_bps: params.ramp_end_bps,
+        ramp_duration_seconds: params.ramp_duration_seconds,
+        ramp_start_timestamp: params.ramp_start_timestamp,
+        duration_bonus_enabled: params.duration_bonus_enabled,
+        duration_bonus_max_multiplier_bps: params.duration_bonus_max_multiplier_bps,
+        min_remaining_lock_seconds: params.min_remaining_lock_seconds,
+        avg_alpha_bps: params.avg_alpha_bps,
+        dust_destination: params.dust_destination,
+        min_investors_to_distribute: params.min_investors_to_distribute,
+        include_cliff_in_weight: params.include_cliff_in_weight,
+        sunset_timestamp: params.sunset_timestamp,
+        protocol_fee_bps: params.protocol_fee_bps,
+        protocol_fee_wallet: params.protocol_fee_wallet,
+        max_pages_before_auto_complete: params.max_pages_before_auto_complete,
+        cap_application_mode: params.cap_application_mode,
+        auto_claim_before_distribution: params.auto_claim_before_distribution,
+        use_duration_weighting: params.use_duration_weighting,
+        fee_distribution_mode: params.fee_distribution_mode,
+        bump: ctx.bumps.policy_state,
+        version: CURRENT_POLICY_VERSION,
+        page_hash_algorithm: params.page_hash_algorithm,
+        target_page_size: params.target_page_size,
+        max_claim_interval_seconds: params.max_claim_interval_seconds,
+        auto_extend_sunset: params.auto_extend_sunset,
+        blacklist_enabled: false,
+        governance_authority: Pubkey::default(),
+        reserved: [0; 15],
     });
 
     // Validate policy parameters
@@ -56,12 +169,16 @@ pub fn initialize_policy(
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
 /// * `quote_mint` - The quote mint for this distribution system
+/// * `creator_wallet` - Wallet that receives the creator share
+/// * `protocol_fee_wallet` - Optional wallet receiving dust routed to the protocol
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
 pub fn initialize_global_distribution(
     ctx: Context<InitializeGlobalDistribution>, 
-    quote_mint: Pubkey
+    quote_mint: Pubkey,
+    creator_wallet: Pubkey,
+    protocol_fee_wallet: Option<Pubkey>,
 ) -> Result<()> {
     msg!("Initializing global distribution state for quote mint: {}", quote_mint);
 
@@ -78,322 +195,3285 @@ pub fn initialize_global_distribution(
         last_distribution_timestamp: 0, // No distributions yet
         total_distributions: 0,
         total_amount_distributed: 0,
-        reserved: [0; 64],
+        rolling_avg_distribution_bps_of_treasury: 0,
+        creator_wallet,
+        protocol_fee_wallet,
+        total_investor_payouts: 0,
+        total_creator_payouts: 0,
+        avg_seconds_per_page: 0,
+        bump: ctx.bumps.global_distribution_state,
+        position_initialized_at: 0,
+        health_score: 100,
+        auto_extension_count: 0,
+        dust_rollover: 0,
+        reserved: [0; 2],
     });
 
     msg!("✅ Global distribution state initialized successfully");
     Ok(())
 }
 
-/// Start a new daily distribution
+/// Initialize the program config
 /// 
-/// This creates the daily distribution state and validates that 24 hours
-/// have passed since the last distribution. Can be called by anyone.
+/// Creates the program-wide config with its pause authority. Must be called
+/// by the upgrade authority, once, at deployment.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
-/// * `distribution_day` - The day timestamp (start of day)
+/// * `program_pause_authority` - The authority allowed to pause distributions
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn start_daily_distribution(
-    ctx: Context<StartDailyDistribution>, 
-    distribution_day: i64
+pub fn initialize_program_config(
+    ctx: Context<InitializeProgramConfig>,
+    program_pause_authority: Pubkey,
 ) -> Result<()> {
-    msg!("Starting daily distribution for day: {}", distribution_day);
+    ctx.accounts.program_config.set_inner(ProgramConfig {
+        program_pause_authority,
+        is_paused: false,
+        pause_count: 0,
+        resume_count: 0,
+        last_paused_at: 0,
+        total_paused_seconds: 0,
+        reserved: [0; 8],
+    });
 
-    let clock = Clock::get()?;
-    let current_day = DailyDistributionState::get_day_start(clock.unix_timestamp);
-    
-    // Validate the distribution day is correct (today)
-    require!(
-        distribution_day == current_day,
-        FeeRouterError::InvalidPageIndex // TODO: Add better error
-    );
+    msg!("✅ Program config initialized with pause authority {}", program_pause_authority);
+    Ok(())
+}
 
-    // Check if 24 hours have passed since last distribution
-    require!(
-        DailyDistributionState::can_start_new_distribution(
-            ctx.accounts.global_distribution_state.last_distribution_timestamp,
-            clock.unix_timestamp
-        ),
-        FeeRouterError::TooSoonToDistribute
-    );
+/// Pause new distribution work
+/// 
+/// While paused, distributions cannot start and investor pages are rejected.
+/// Completing an already processed day is still allowed. The pause is
+/// counted in the program config's metrics. Only the program pause
+/// authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn pause_distribution(ctx: Context<ManageProgramPause>) -> Result<()> {
+    set_distribution_paused(ctx, true)
+}
 
-    // Get available treasury balance
-    let treasury_balance = ctx.accounts.treasury_ata.amount;
-    require!(
-        treasury_balance > 0,
-        FeeRouterError::NoFeesToClaim // TODO: Add better error for no funds to distribute
-    );
+/// Resume paused distribution work
+/// 
+/// The resume and the time spent paused are counted in the program config's
+/// metrics. Only the program pause authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn unpause_distribution(ctx: Context<ManageProgramPause>) -> Result<()> {
+    set_distribution_paused(ctx, false)
+}
 
-    // TODO: Get total number of investors from Streamflow or other source
-    // For now, we'll use a placeholder
-    let total_investors = 100u32; // This should come from investor registry
+fn set_distribution_paused(ctx: Context<ManageProgramPause>, is_paused: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let was_paused = ctx.accounts.program_config.is_paused;
+    ctx.accounts.program_config.is_paused = is_paused;
 
-    // Initialize daily distribution state
-    ctx.accounts.daily_distribution_state.set_inner(DailyDistributionState {
-        distribution_day,
-        quote_mint: ctx.accounts.quote_mint.key(),
-        treasury_ata: ctx.accounts.treasury_ata.key(),
-        total_amount_to_distribute: treasury_balance,
-        amount_distributed: 0,
-        current_cursor: 0,
-        total_investors,
-        investors_processed: 0,
-        is_complete: false,
-        started_at: clock.unix_timestamp,
-        completed_at: 0,
-        dust_carried_over: 0, // TODO: Carry over from previous day
-        daily_cap_total: DEFAULT_DAILY_CAP_LAMPORTS,
-        daily_cap_remaining: DEFAULT_DAILY_CAP_LAMPORTS,
-        min_payout_threshold: DEFAULT_MIN_PAYOUT_LAMPORTS,
-        initial_total_deposit: 1_000_000_000, // TODO: Get from config/state
-        investor_fee_share_bps: DEFAULT_INVESTOR_FEE_SHARE_BPS,
-        last_page_hash: [0; 32], // No pages processed yet
-        pages_processed: 0,
-        failed_payouts_count: 0,
-        reserved: [0; 20],
-    });
+    // Only actual state changes count towards the pause metrics
+    let program_config = &mut ctx.accounts.program_config;
+    if is_paused && !was_paused {
+        program_config.record_pause(clock.unix_timestamp);
+    } else if !is_paused && was_paused {
+        program_config.record_resume(clock.unix_timestamp);
+    }
 
-    // Emit event
-    emit!(DailyDistributionStarted {
-        distribution_day,
-        quote_mint: ctx.accounts.quote_mint.key(),
-        total_amount_to_distribute: treasury_balance,
-        total_investors,
+    emit!(DistributionPauseUpdated {
+        is_paused,
+        authority: ctx.accounts.authority.key(),
+        pause_count: program_config.pause_count,
+        resume_count: program_config.resume_count,
+        total_paused_seconds: program_config.total_paused_seconds,
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("✅ Daily distribution started with {} tokens for {} investors", 
-         treasury_balance, total_investors);
+    msg!("✅ Distributions {}", if is_paused { "paused" } else { "resumed" });
     Ok(())
 }
 
-/// Process a page of investors
+/// Hand the pause role to a new authority
 /// 
-/// This processes a batch of investors (up to MAX_INVESTORS_PER_PAGE)
-/// and distributes their share of fees based on locked token amounts.
-/// Implements the complete Section 4 distribution logic.
+/// Only the current program pause authority can call this.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
+/// * `new_authority` - The new program pause authority
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn process_investor_page(ctx: Context<ProcessInvestorPage>) -> Result<()> {
-    msg!("Processing investor page starting from cursor: {}", 
-         ctx.accounts.daily_distribution_state.current_cursor);
-
+pub fn set_program_pause_authority(ctx: Context<ManageProgramPause>, new_authority: Pubkey) -> Result<()> {
     let clock = Clock::get()?;
-    
-    // Check if there are more investors to process
-    require!(
-        ctx.accounts.daily_distribution_state.has_more_investors(),
-        FeeRouterError::DistributionNotStarted
-    );
+    let program_config = &mut ctx.accounts.program_config;
+    let old_authority = program_config.program_pause_authority;
+    program_config.program_pause_authority = new_authority;
 
-    // Get remaining accounts (these should be Streamflow stream accounts)
-    let remaining_accounts = &ctx.remaining_accounts;
-    require!(
-        !remaining_accounts.is_empty(),
-        FeeRouterError::NoInvestors
-    );
+    emit!(ProgramPauseAuthorityUpdated {
+        old_authority,
+        new_authority,
+        timestamp: clock.unix_timestamp,
+    });
 
-    // Step 1: Idempotency check - validate this page hasn't been processed
-    let investor_keys: Vec<Pubkey> = remaining_accounts.iter().map(|acc| acc.key()).collect();
-    ctx.accounts.daily_distribution_state.validate_page_for_retry(&investor_keys)?;
+    msg!("✅ Program pause authority updated: {} -> {}", old_authority, new_authority);
+    Ok(())
+}
 
-    // Step 2: Read Streamflow stream data for this page of investors
-    let (investor_data, total_locked) = streamflow::cpi::calculate_locked_amounts(
-        remaining_accounts,
-        clock.unix_timestamp as u64,
-        &ctx.accounts.quote_mint.key(),
-    )?;
+/// Update the creator wallet
+/// 
+/// Changes the wallet whose ATA receives the creator share. Only the
+/// policy authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `new_creator_wallet` - The new creator wallet
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn update_creator_wallet(
+    ctx: Context<UpdateCreatorWallet>,
+    new_creator_wallet: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let global_state = &mut ctx.accounts.global_distribution_state;
+    let old_wallet = global_state.creator_wallet;
+    global_state.creator_wallet = new_creator_wallet;
 
-    msg!("Found {} investors with {} total locked tokens", 
-         investor_data.len(), total_locked);
+    emit!(CreatorWalletUpdated {
+        old_wallet,
+        new_wallet: new_creator_wallet,
+        timestamp: clock.unix_timestamp,
+    });
 
-    // Step 3: Calculate distribution using Section 4 formulas
-    let effective_distribution_amount = ctx.accounts.daily_distribution_state.get_effective_distribution_amount();
-    
-    let distribution_calc = streamflow::calculations::calculate_distribution(
-        effective_distribution_amount,
-        &investor_data,
-        total_locked,
-        ctx.accounts.daily_distribution_state.initial_total_deposit,
-        ctx.accounts.daily_distribution_state.investor_fee_share_bps,
-        ctx.accounts.daily_distribution_state.min_payout_threshold,
-    )?;
+    msg!("✅ Creator wallet updated: {} -> {}", old_wallet, new_creator_wallet);
+    Ok(())
+}
 
-    // Step 4: Apply daily cap
-    let final_calc = streamflow::calculations::apply_daily_cap(
-        distribution_calc,
-        ctx.accounts.daily_distribution_state.daily_cap_remaining,
-    );
+/// Link the honorary position to the global distribution state
+/// 
+/// Copies the position's creation timestamp so the position age can be read
+/// from the global state alone. Only the policy authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn link_position_to_distribution(ctx: Context<LinkPositionToDistribution>) -> Result<()> {
+    let clock = Clock::get()?;
+    let global_state = &mut ctx.accounts.global_distribution_state;
+    global_state.position_initialized_at = ctx.accounts.position_metadata.created_at;
+    let position_age_days = global_state.position_age_days(clock.unix_timestamp);
 
-    // Step 5: Validate calculation
-    streamflow::calculations::validate_distribution(&final_calc, effective_distribution_amount)?;
+    emit!(PositionLinkedToDistribution {
+        quote_mint: global_state.quote_mint,
+        position: ctx.accounts.position_metadata.position,
+        position_initialized_at: global_state.position_initialized_at,
+        position_age_days,
+        timestamp: clock.unix_timestamp,
+    });
 
-    // Step 6: Execute transfers to investors
-    let treasury_authority_bump = ctx.bumps.treasury_authority;
-    let quote_mint_key = ctx.accounts.quote_mint.key();
-    let treasury_seeds = &[
-        b"treasury_authority",
-        quote_mint_key.as_ref(),
-        &[treasury_authority_bump],
-    ];
-    let _signer_seeds = &[&treasury_seeds[..]];
+    msg!("✅ Linked position {} ({} days old)", ctx.accounts.position_metadata.position, position_age_days);
+    Ok(())
+}
+
+/// Extend the policy sunset date
+/// 
+/// Pushes the sunset later (or removes it with 0). Only the policy
+/// authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `new_sunset_timestamp` - The new sunset timestamp (0 = no sunset)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn extend_sunset(ctx: Context<ExtendSunset>, new_sunset_timestamp: i64) -> Result<()> {
+    let policy_state = &mut ctx.accounts.policy_state;
+    require!(
+        policy_state.is_valid_sunset_extension(new_sunset_timestamp),
+        FeeRouterError::InvalidSunsetExtension
+    );
 
-    let mut actual_distributed = 0u64;
-    let mut investors_processed = 0u32;
+    let clock = Clock::get()?;
+    let old_sunset_timestamp = policy_state.sunset_timestamp;
+    policy_state.sunset_timestamp = new_sunset_timestamp;
 
-    for payout in &final_calc.investor_payouts {
-        if payout.payout_amount > 0 && payout.meets_minimum {
-            // TODO: Transfer tokens to investor
-            // This requires the investor ATAs to be passed in remaining_accounts
-            // For now, we'll simulate the transfer
-            
-            actual_distributed = actual_distributed.saturating_add(payout.payout_amount);
-            investors_processed += 1;
+    emit!(PolicySunsetExtended {
+        quote_mint: policy_state.quote_mint,
+        old_sunset_timestamp,
+        new_sunset_timestamp,
+        timestamp: clock.unix_timestamp,
+    });
 
-            msg!("Would pay {} tokens to investor {}", payout.payout_amount, payout.investor);
-        }
-    }
+    msg!("✅ Policy sunset extended: {} -> {}", old_sunset_timestamp, new_sunset_timestamp);
+    Ok(())
+}
 
-    // Step 7: Update state with idempotency tracking
-    let page_hash = DailyDistributionState::calculate_page_hash(&investor_keys);
-    ctx.accounts.daily_distribution_state.update_page_state(
-        page_hash,
-        investors_processed,
-        actual_distributed
+/// Migrate the policy account to the next layout version
+/// 
+/// The stored data is decoded in its old layout, the account is grown to
+/// the new layout (the authority tops up rent) and every field is mapped
+/// across; new fields get defaults that keep the old behavior. Versions must
+/// be upgraded one at a time. Only the policy authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `target_version` - The version to migrate to (current version + 1)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn upgrade_policy_format(ctx: Context<UpgradePolicyFormat>, target_version: u8) -> Result<()> {
+    let policy_info = ctx.accounts.policy_state.to_account_info();
+    require_keys_eq!(*policy_info.owner, *ctx.program_id, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    let from_version = PolicyState::stored_format_version(&policy_info.try_borrow_data()?)?;
+    require!(
+        Some(target_version) == from_version.checked_add(1),
+        FeeRouterError::UnsupportedPolicyVersion
     );
 
-    // Update daily cap
-    ctx.accounts.daily_distribution_state.update_daily_cap(actual_distributed);
-
-    // Add dust to carry over
-    ctx.accounts.daily_distribution_state.add_dust(final_calc.dust_amount);
+    let policy_state = match target_version {
+        2 => {
+            let policy = PolicyStateV1::try_from_account_data(&policy_info.try_borrow_data()?)?;
+            require_keys_eq!(policy.policy_authority, ctx.accounts.authority.key(), FeeRouterError::Unauthorized);
+            migrate_v1_to_v2(&policy, ctx.bumps.policy_state)
+        }
+        _ => return err!(FeeRouterError::UnsupportedPolicyVersion),
+    };
 
-    let is_final_page = !ctx.accounts.daily_distribution_state.has_more_investors();
+    // Grow the account to the new layout, keeping it rent exempt
+    let rent_shortfall = rent::required_rent(PolicyState::INIT_SPACE)?.saturating_sub(policy_info.lamports());
+    if rent_shortfall > 0 {
+        rent::require_rent_lamports(&ctx.accounts.authority.to_account_info(), rent_shortfall)?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: policy_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    policy_info.realloc(PolicyState::INIT_SPACE, true)?;
+    policy_state.try_serialize(&mut &mut policy_info.try_borrow_mut_data()?[..])?;
 
-    // Step 8: Emit event
-    emit!(InvestorsProcessed {
-        distribution_day: ctx.accounts.daily_distribution_state.distribution_day,
-        quote_mint: ctx.accounts.quote_mint.key(),
-        investors_in_page: investors_processed,
-        amount_distributed_in_page: actual_distributed,
-        new_cursor: ctx.accounts.daily_distribution_state.current_cursor,
-        total_investors_processed: ctx.accounts.daily_distribution_state.investors_processed,
-        total_amount_distributed: ctx.accounts.daily_distribution_state.amount_distributed,
-        is_final_page,
+    let clock = Clock::get()?;
+    emit!(PolicyFormatUpgraded {
+        quote_mint: policy_state.quote_mint,
+        from_version,
+        to_version: target_version,
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("✅ Processed {} investors, distributed {} tokens, {} dust", 
-         investors_processed, actual_distributed, final_calc.dust_amount);
-    
-    if is_final_page {
-        msg!("🎉 All investors processed for this day!");
-    }
-
+    msg!("✅ Policy format upgraded: v{} -> v{}", from_version, target_version);
     Ok(())
 }
 
-/// Complete the daily distribution
+/// Update the policy's minimum payout threshold
 /// 
-/// This marks the daily distribution as complete, pays the creator remainder,
-/// and updates the global state. Can only be called after all investors 
-/// have been processed.
+/// Lets the policy authority retune what counts as dust without
+/// re-initializing the policy. Must be non-zero and within the daily cap.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
+/// * `new_min_payout` - The new minimum payout in lamports
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn complete_daily_distribution(ctx: Context<CompleteDailyDistribution>) -> Result<()> {
-    msg!("Completing daily distribution for day: {}", 
-         ctx.accounts.daily_distribution_state.distribution_day);
+pub fn update_min_payout(ctx: Context<UpdateMinPayout>, new_min_payout: u64) -> Result<()> {
+    let policy_state = &mut ctx.accounts.policy_state;
+    require!(
+        policy_state.is_valid_min_payout(new_min_payout),
+        FeeRouterError::InvalidMinPayout
+    );
 
     let clock = Clock::get()?;
+    let old_value = policy_state.min_payout_lamports;
+    policy_state.min_payout_lamports = new_min_payout;
 
-    // Step 1: Calculate creator remainder
-    // creator_remainder = total_amount_to_distribute - amount_distributed + dust_carried_over
-    let total_available = ctx.accounts.daily_distribution_state.get_effective_distribution_amount();
-    let total_investor_payouts = ctx.accounts.daily_distribution_state.amount_distributed;
-    let dust_amount = ctx.accounts.daily_distribution_state.dust_carried_over;
-    
-    let creator_remainder = total_available.saturating_sub(total_investor_payouts);
-    
-    msg!("Creator remainder calculation: {} total - {} to investors = {} remainder", 
-         total_available, total_investor_payouts, creator_remainder);
-
-    // Step 2: Transfer remainder to creator
-    if creator_remainder > 0 {
-        let treasury_authority_bump = ctx.bumps.treasury_authority;
-        let quote_mint_key = ctx.accounts.quote_mint.key();
-        let treasury_seeds = &[
-            b"treasury_authority",
-            quote_mint_key.as_ref(),
-            &[treasury_authority_bump],
-        ];
-        let signer_seeds = &[&treasury_seeds[..]];
+    emit!(MinPayoutUpdated {
+        old_value,
+        new_value: new_min_payout,
+        timestamp: clock.unix_timestamp,
+    });
 
-        let transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.treasury_ata.to_account_info(),
-                to: ctx.accounts.creator_ata.to_account_info(),
-                authority: ctx.accounts.treasury_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
+    msg!("✅ Minimum payout updated: {} -> {}", old_value, new_min_payout);
+    Ok(())
+}
 
-        token::transfer(transfer_ctx, creator_remainder)?;
-        
-        msg!("✅ Transferred {} tokens to creator", creator_remainder);
+/// Update the policy's optional settings
+/// 
+/// Lets the policy authority toggle behaviour fixed at initialization.
+/// Settings left as `None` keep their current value.
+/// 
+/// When the policy has a governance authority the update must also be carried
+/// by a completed proposal of that governance: `governance_proposal` and
+/// `governance_proposal_transaction` must be passed, and the transaction must
+/// hold this exact instruction.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `params` - The settings to change
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn update_policy_settings(ctx: Context<UpdatePolicySettings>, params: UpdatePolicySettingsParams) -> Result<()> {
+    let instruction_data = anchor_lang::InstructionData::data(&crate::instruction::UpdatePolicySettings {
+        params: params.clone(),
+    });
+    ValidateGovernanceProposal::validate(
+        &ctx.accounts.policy_state.governance_authority,
+        ctx.accounts.governance_proposal.as_ref().map(|account| account.as_ref()),
+        ctx.accounts.governance_proposal_transaction.as_ref().map(|account| account.as_ref()),
+        ctx.program_id,
+        &instruction_data,
+    )?;
 
-        // Emit creator payout event
-        emit!(CreatorPayoutCompleted {
-            distribution_day: ctx.accounts.daily_distribution_state.distribution_day,
-            quote_mint: ctx.accounts.quote_mint.key(),
-            creator: ctx.accounts.creator_ata.owner,
-            creator_remainder,
-            total_distributed_amount: total_available,
-            total_investor_payouts,
-            dust_amount,
-            timestamp: clock.unix_timestamp,
-        });
-    } else {
-        msg!("No creator remainder to distribute");
+    let policy_state = &mut ctx.accounts.policy_state;
+    if let Some(auto_extend_sunset) = params.auto_extend_sunset {
+        policy_state.auto_extend_sunset = auto_extend_sunset;
+    }
+    if let Some(page_hash_algorithm) = params.page_hash_algorithm {
+        policy_state.page_hash_algorithm = page_hash_algorithm;
+    }
+    if let Some(governance_authority) = params.governance_authority {
+        policy_state.governance_authority = governance_authority;
     }
 
-    // Step 3: Mark daily distribution as complete
-    ctx.accounts.daily_distribution_state.mark_complete(clock.unix_timestamp);
-
-    // Step 4: Update global distribution state
-    ctx.accounts.global_distribution_state.update_after_distribution(
-        clock.unix_timestamp, // Use current timestamp instead of day
-        total_available // Include full amount (investors + creator)
-    );
-
-    // Step 5: Emit completion events
-    emit!(DailyDistributionCompleted {
-        distribution_day: ctx.accounts.daily_distribution_state.distribution_day,
-        quote_mint: ctx.accounts.quote_mint.key(),
-        total_amount_distributed: total_available,
-        total_investors_processed: ctx.accounts.daily_distribution_state.investors_processed,
+    let clock = Clock::get()?;
+    emit!(PolicySettingsUpdated {
+        quote_mint: policy_state.quote_mint,
+        auto_extend_sunset: policy_state.auto_extend_sunset,
+        page_hash_algorithm: policy_state.page_hash_algorithm,
+        governance_authority: policy_state.governance_authority,
         timestamp: clock.unix_timestamp,
     });
 
-    emit!(GlobalDistributionUpdated {
-        quote_mint: ctx.accounts.quote_mint.key(),
-        last_distribution_day: ctx.accounts.global_distribution_state.last_distribution_timestamp,
-        total_distributions: ctx.accounts.global_distribution_state.total_distributions,
-        total_amount_distributed: ctx.accounts.global_distribution_state.total_amount_distributed,
-        timestamp: clock.unix_timestamp,
+    msg!("✅ Policy settings updated: auto_extend_sunset={}, page_hash_algorithm={:?}, governance_authority={}",
+         policy_state.auto_extend_sunset, policy_state.page_hash_algorithm, policy_state.governance_authority);
+    Ok(())
+}
+
+/// Create the investor blacklist for a quote mint
+/// 
+/// The blacklist starts empty. Only the policy authority can create it.
+/// From then on every investor page must pass it.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn initialize_blacklist(ctx: Context<InitializeBlacklist>) -> Result<()> {
+    ctx.accounts.investor_blacklist.set_inner(InvestorBlacklist {
+        quote_mint: ctx.accounts.quote_mint.key(),
+        blacklisted: Vec::new(),
+        bump: ctx.bumps.investor_blacklist,
+    });
+    ctx.accounts.policy_state.blacklist_enabled = true;
+
+    msg!("✅ Investor blacklist initialized for quote mint {}", ctx.accounts.quote_mint.key());
+    Ok(())
+}
+
+/// Blacklist an investor so they receive no payouts
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `investor` - The investor wallet to blacklist
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn add_to_blacklist(ctx: Context<ManageBlacklist>, investor: Pubkey) -> Result<()> {
+    ctx.accounts.investor_blacklist.add(investor)?;
+
+    let clock = Clock::get()?;
+    emit!(InvestorBlacklisted {
+        investor,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Blacklisted investor: {}", investor);
+    Ok(())
+}
+
+/// Remove an investor from the blacklist
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `investor` - The investor wallet to remove
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn remove_from_blacklist(ctx: Context<ManageBlacklist>, investor: Pubkey) -> Result<()> {
+    ctx.accounts.investor_blacklist.remove(investor)?;
+
+    let clock = Clock::get()?;
+    emit!(InvestorUnblacklisted {
+        investor,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Removed investor from blacklist: {}", investor);
+    Ok(())
+}
+
+/// Create the allocation registry for a quote mint
+/// 
+/// The registry starts empty. Only the policy authority can create it.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn initialize_allocation_registry(ctx: Context<InitializeAllocationRegistry>) -> Result<()> {
+    ctx.accounts.allocation_registry.set_inner(AllocationRegistry {
+        quote_mint: ctx.accounts.quote_mint.key(),
+        total_registered_allocation: 0,
+        registered_streams: Vec::new(),
+        bump: ctx.bumps.allocation_registry,
+    });
+
+    msg!("✅ Allocation registry initialized for quote mint {}", ctx.accounts.quote_mint.key());
+    Ok(())
+}
+
+/// Add investor streams' deposited amounts to the allocation registry
+/// 
+/// Streamflow stream accounts are passed as remaining accounts. Each stream
+/// can only be registered once, so the total tracks the investors' original
+/// allocation that Y0 should match.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn register_stream_allocations(ctx: Context<RegisterStreamAllocations>) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        FeeRouterError::NoInvestors
+    );
+
+    let quote_mint = ctx.accounts.quote_mint.key();
+    let registry = &mut ctx.accounts.allocation_registry;
+    let mut allocation_added = 0u64;
+    for stream_account in ctx.remaining_accounts.iter() {
+        require!(
+            stream_account.owner == &streamflow::STREAMFLOW_PROGRAM_ID,
+            anchor_lang::error::ErrorCode::ConstraintOwner
+        );
+        let stream = streamflow::cpi::read_stream_data(stream_account)?;
+        require!(
+            stream.mint == quote_mint,
+            FeeRouterError::QuoteMintMismatch
+        );
+
+        registry.register(stream_account.key(), stream.deposited_amount)?;
+        allocation_added = allocation_added.saturating_add(stream.deposited_amount);
+    }
+
+    let clock = Clock::get()?;
+    emit!(StreamAllocationsRegistered {
+        quote_mint,
+        streams_registered: ctx.remaining_accounts.len() as u32,
+        allocation_added,
+        total_registered_allocation: registry.total_registered_allocation,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Registered {} streams: {} added, {} total allocation",
+         ctx.remaining_accounts.len(), allocation_added, registry.total_registered_allocation);
+    Ok(())
+}
+
+/// Create the investor registry for a quote mint
+/// 
+/// The registry starts without pages; they are created as investors are
+/// added. Only the policy authority can create it.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `investor_capacity` - Maximum number of investors (up to `MAX_REGISTRY_INVESTORS`)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn initialize_investor_registry(ctx: Context<InitializeInvestorRegistry>, investor_capacity: u32) -> Result<()> {
+    require!(
+        investor_capacity > 0 && investor_capacity <= MAX_REGISTRY_INVESTORS,
+        FeeRouterError::InvestorRegistryFull
+    );
+
+    ctx.accounts.investor_registry.set_inner(InvestorRegistryState {
+        quote_mint: ctx.accounts.quote_mint.key(),
+        investor_capacity,
+        investor_count: 0,
+        page_count: 0,
+        last_page_index: 0,
+        bump: ctx.bumps.investor_registry,
+    });
+
+    msg!("✅ Investor registry initialized for quote mint {} (capacity {})",
+         ctx.accounts.quote_mint.key(), investor_capacity);
+    Ok(())
+}
+
+/// Append an investor to the investor registry
+/// 
+/// The investor goes to the last registry page; a new page is created and
+/// linked once the last one holds `INVESTOR_REGISTRY_PAGE_SIZE` investors.
+/// Only the policy authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `investor` - The investor's wallet
+/// * `stream_account` - The investor's Streamflow stream account
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn add_investor(ctx: Context<AddInvestor>, investor: Pubkey, stream_account: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.investor_registry.has_capacity(),
+        FeeRouterError::InvestorRegistryFull
+    );
+
+    let registry_key = ctx.accounts.investor_registry.key();
+    let entry = InvestorEntry { investor, stream_account };
+    let last_page_account = ctx.accounts.last_page.to_account_info();
+    let mut last_page = if ctx.accounts.investor_registry.page_count == 0 {
+        None
+    } else {
+        require_keys_eq!(*last_page_account.owner, *ctx.program_id, FeeRouterError::RegistryPageMismatch);
+        Some(InvestorRegistryPage::try_deserialize(&mut last_page_account.data.borrow().as_ref())?)
+    };
+
+    let last_page_len = last_page.as_ref().map_or(0, |page| page.entries.len());
+    let page_index = ctx.accounts.investor_registry.next_entry_page_index(last_page_len);
+    let appends_to_last_page = last_page.as_ref().is_some_and(|page| page.page_index == page_index);
+    if appends_to_last_page {
+        // Room left on the last page
+        if let Some(page) = last_page.as_mut() {
+            page.push(entry)?;
+            let mut data = last_page_account.try_borrow_mut_data()?;
+            page.try_serialize(&mut &mut data[..])?;
+        }
+    } else {
+        // First page, or the last page is full
+        let (page_account, bump) = match last_page {
+            None => (&ctx.accounts.last_page, ctx.bumps.last_page),
+            Some(_) => (&ctx.accounts.next_page, ctx.bumps.next_page),
+        };
+        create_pda_account(
+            page_account,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            &[b"registry_page", registry_key.as_ref(), &page_index.to_le_bytes(), &[bump]],
+            8 + InvestorRegistryPage::INIT_SPACE,
+            &InvestorRegistryPage {
+                registry: registry_key,
+                page_index,
+                next_page: Pubkey::default(),
+                entries: vec![entry],
+                bump,
+            },
+        )?;
+
+        // Link the previous page to the new one
+        if let Some(page) = last_page.as_mut() {
+            page.next_page = ctx.accounts.next_page.key();
+            let mut data = last_page_account.try_borrow_mut_data()?;
+            page.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    let investor_registry = &mut ctx.accounts.investor_registry;
+    investor_registry.record_added(page_index);
+
+    let clock = Clock::get()?;
+    emit!(InvestorRegistered {
+        quote_mint: investor_registry.quote_mint,
+        investor,
+        stream_account,
+        page_index,
+        investor_count: investor_registry.investor_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Investor {} registered on page {} ({} investors)",
+         investor, page_index, investor_registry.investor_count);
+    Ok(())
+}
+
+/// Take the investor snapshot for a distribution day
+/// 
+/// Reads the Streamflow streams passed as remaining accounts and records each
+/// investor's locked amount. `start_daily_distribution` requires this snapshot,
+/// so every cranker works from the same investor set. Only the policy
+/// authority can take it, so nobody can claim the day with a partial set.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `distribution_day` - The day timestamp (start of day)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn take_investor_snapshot(
+    ctx: Context<TakeInvestorSnapshot>,
+    distribution_day: i64
+) -> Result<()> {
+    msg!("Taking investor snapshot for day: {}", distribution_day);
+
+    let clock = Clock::get()?;
+    let current_day = DailyDistributionState::get_day_start(clock.unix_timestamp);
+
+    // Snapshots can only be taken for the current day
+    require!(
+        distribution_day == current_day,
+        FeeRouterError::InvalidDistributionDay
+    );
+
+    let remaining_accounts = &ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        FeeRouterError::NoInvestors
+    );
+    require!(
+        remaining_accounts.len() <= MAX_SNAPSHOT_INVESTORS,
+        FeeRouterError::SnapshotCapacityExceeded
+    );
+
+    // Reject cross-mint streams before they enter the snapshot
+    for stream_account in remaining_accounts.iter() {
+        streamflow::cpi::validate_stream_mint(stream_account, &ctx.accounts.quote_mint.key())?;
+    }
+
+    let (investor_data, total_locked) = streamflow::cpi::calculate_locked_amounts(
+        remaining_accounts,
+        clock.unix_timestamp as u64,
+        &ctx.accounts.quote_mint.key(),
+        ctx.accounts.policy_state.min_remaining_lock_seconds,
+    )?;
+
+    let entries: Vec<SnapshotEntry> = investor_data
+        .iter()
+        .map(|data| SnapshotEntry {
+            investor: data.investor,
+            stream_account: data.stream_account,
+            locked_amount: data.locked_amount,
+            cliff_locked: data.cliff_locked,
+            lock_duration_seconds: data.lock_duration_seconds,
+        })
+        .collect();
+    let investor_count = entries.len() as u32;
+
+    let snapshot = &mut ctx.accounts.investor_snapshot;
+    snapshot.distribution_day = distribution_day;
+    snapshot.quote_mint = ctx.accounts.quote_mint.key();
+    snapshot.investor_count = investor_count;
+    snapshot.total_locked = total_locked;
+    snapshot.taken_at = clock.unix_timestamp;
+    snapshot.payer = ctx.accounts.authority.key();
+    snapshot.entries = entries;
+
+    emit!(InvestorSnapshotTaken {
+        distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        investor_count,
+        total_locked,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Investor snapshot taken: {} investors, {} total locked", investor_count, total_locked);
+    Ok(())
+}
+
+/// Check that the snapshot's streams still stream the quote mint
+/// 
+/// Stream accounts are passed as remaining accounts in snapshot entry order,
+/// starting at `start_index`, so large snapshots can be checked over several
+/// transactions. Violations are reported in a `SnapshotIntegrityReport`
+/// event rather than failing. Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `start_index` - Index of the first snapshot entry being checked
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn validate_snapshot_streams(ctx: Context<ValidateSnapshotStreams>, start_index: u32) -> Result<()> {
+    let snapshot = &ctx.accounts.investor_snapshot;
+    let quote_mint = ctx.accounts.quote_mint.key();
+    let start = start_index as usize;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    require!(
+        !remaining_accounts.is_empty() && start + remaining_accounts.len() <= snapshot.entries.len(),
+        FeeRouterError::InvalidPageIndex
+    );
+
+    let mut violations: Vec<Pubkey> = Vec::new();
+    for (entry, stream_account) in snapshot.entries[start..].iter().zip(remaining_accounts.iter()) {
+        require!(
+            stream_account.key() == entry.stream_account,
+            FeeRouterError::PaginationError
+        );
+        if streamflow::cpi::validate_stream_mint(stream_account, &quote_mint).is_err() {
+            violations.push(entry.stream_account);
+        }
+    }
+
+    let clock = Clock::get()?;
+    emit!(SnapshotIntegrityReport {
+        distribution_day: snapshot.distribution_day,
+        quote_mint,
+        start_index,
+        streams_checked: remaining_accounts.len() as u32,
+        violations: violations.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    if violations.is_empty() {
+        msg!("✅ {} snapshot streams match quote mint {}", remaining_accounts.len(), quote_mint);
+    } else {
+        msg!("⚠️ {} of {} snapshot streams do not stream quote mint {}",
+             violations.len(), remaining_accounts.len(), quote_mint);
+    }
+    Ok(())
+}
+
+/// Close the investor snapshot of a completed distribution day
+/// 
+/// Returns the snapshot's rent to the authority that took it. Only allowed
+/// once the day's distribution completed more than `SNAPSHOT_RETENTION_SECONDS`
+/// (one day) ago, so the snapshot can still be audited. Can be called by
+/// anyone as part of the post-distribution cleanup.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_investor_snapshot(ctx: Context<CloseInvestorSnapshot>) -> Result<()> {
+    let distribution_day = ctx.accounts.investor_snapshot.distribution_day;
+    let payer = ctx.accounts.investor_snapshot.payer;
+    msg!("Closing investor snapshot for day: {}", distribution_day);
+
+    // Lamports are moved to the payer by the `close` constraint on exit
+    let rent_recovered = ctx.accounts.investor_snapshot.to_account_info().lamports();
+
+    emit!(InvestorSnapshotClosed {
+        distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        payer,
+        rent_recovered,
+    });
+
+    msg!("✅ Investor snapshot closed, {} lamports returned to {}", rent_recovered, payer);
+    Ok(())
+}
+
+/// Close the stream error log of a completed distribution day
+/// 
+/// Returns the log's rent to the cranker that started the day. Can be
+/// called by anyone once the day's distribution is complete.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_stream_error_log(ctx: Context<CloseStreamErrorLog>) -> Result<()> {
+    let distribution_day = ctx.accounts.stream_error_log.distribution_day;
+    let payer = ctx.accounts.stream_error_log.payer;
+    msg!("Closing stream error log for day: {}", distribution_day);
+
+    // Lamports are moved to the payer by the `close` constraint on exit
+    let rent_recovered = ctx.accounts.stream_error_log.to_account_info().lamports();
+
+    emit!(StreamErrorLogClosed {
+        distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        payer,
+        rent_recovered,
+    });
+
+    msg!("✅ Stream error log closed, {} lamports returned to {}", rent_recovered, payer);
+    Ok(())
+}
+
+/// Close an investor payout summary of a completed distribution day
+/// 
+/// Returns the summary's rent to the cranker that paid for it. Kept for
+/// `SNAPSHOT_RETENTION_SECONDS` after completion, like the day's snapshot,
+/// so it can still be audited. Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_investor_payout_summary(ctx: Context<CloseInvestorPayoutSummary>) -> Result<()> {
+    let summary = &ctx.accounts.investor_payout_summary;
+    msg!("Closing payout summary of {} for day: {}", summary.investor, summary.distribution_day);
+
+    // Lamports are moved to the payer by the `close` constraint on exit
+    let rent_recovered = summary.to_account_info().lamports();
+
+    emit!(InvestorPayoutSummaryClosed {
+        distribution_day: summary.distribution_day,
+        investor: summary.investor,
+        payer: summary.payer,
+        rent_recovered,
+    });
+
+    msg!("✅ Payout summary closed, {} lamports returned to {}", rent_recovered, summary.payer);
+    Ok(())
+}
+
+/// Close an investor aggregate cache of a completed distribution day
+/// 
+/// Returns the cache's rent to the cranker that paid for it once the day's
+/// snapshot may be closed. Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_investor_cache(ctx: Context<CloseInvestorCache>) -> Result<()> {
+    let cache = &ctx.accounts.investor_cache;
+    msg!("Closing investor cache of {} for day: {}", cache.investor, cache.distribution_day);
+
+    // Lamports are moved to the payer by the `close` constraint on exit
+    let rent_recovered = cache.to_account_info().lamports();
+
+    emit!(InvestorCacheClosed {
+        distribution_day: cache.distribution_day,
+        quote_mint: cache.quote_mint,
+        investor: cache.investor,
+        payer: cache.payer,
+        rent_recovered,
+    });
+
+    msg!("✅ Investor cache closed, {} lamports returned to {}", rent_recovered, cache.payer);
+    Ok(())
+}
+
+/// Start a new daily distribution
+/// 
+/// This creates the daily distribution state and validates that 24 hours
+/// have passed since the last distribution. Requires the day's investor
+/// snapshot. Can be called by anyone.
+/// 
+/// When the policy enables `auto_claim_before_distribution`, the `ClaimFees`
+/// accounts are passed as remaining accounts and fees are claimed into the
+/// treasury first. A claim skipped because the claim interval has not elapsed
+/// or no fees have accrued only emits `AutoClaimFailed`; any other claim
+/// failure aborts the start.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `distribution_day` - The day timestamp (start of day)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn start_daily_distribution<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, StartDailyDistribution<'info>>, 
+    distribution_day: i64
+) -> Result<()> {
+    let auto_claim = ctx.accounts.policy_state.auto_claim_before_distribution;
+    begin_daily_distribution(ctx, distribution_day, auto_claim)
+}
+
+/// Claim fees and start the day's distribution in one instruction
+/// 
+/// Unlike `auto_claim_before_distribution`, a failed claim aborts the whole
+/// instruction, so a distribution never starts from a stale balance. The
+/// treasury balance after the claim becomes the day's distributable amount.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing the `ClaimFees` and `StartDailyDistribution` accounts
+/// * `distribution_day` - The day timestamp (start of day)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn initialize_atomic_distribution<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeAtomicDistribution<'info>>,
+    distribution_day: i64
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let accounts = ctx.accounts;
+    let bumps = ctx.bumps;
+
+    require_keys_eq!(
+        accounts.claim.quote_mint.key(),
+        accounts.start.quote_mint.key(),
+        FeeRouterError::QuoteMintMismatch
+    );
+
+    msg!("Claiming fees before starting distribution for day: {}", distribution_day);
+    claiming_instructions::claim_fees(Context::new(
+        program_id,
+        &mut accounts.claim,
+        &[],
+        bumps.claim,
+    ))?;
+
+    // Write the claim back so the start accounts see the new balance
+    accounts.claim.exit(program_id)?;
+    accounts.start.treasury_state.reload()?;
+    accounts.start.treasury_ata.reload()?;
+
+    begin_daily_distribution(
+        Context::new(program_id, &mut accounts.start, &[], bumps.start),
+        distribution_day,
+        false,
+    )
+}
+
+/// Validate and create the day's distribution state, optionally claiming first
+fn begin_daily_distribution<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, StartDailyDistribution<'info>>,
+    distribution_day: i64,
+    auto_claim: bool,
+) -> Result<()> {
+    msg!("Starting daily distribution for day: {}", distribution_day);
+
+    // Report a repeated start clearly instead of as an account collision
+    let daily_distribution_pda = ctx.accounts.daily_distribution_state.key();
+    if !ctx.accounts.daily_distribution_state.data_is_empty() {
+        msg!("Distribution already started for day {}: {}", distribution_day, daily_distribution_pda);
+        return err!(FeeRouterError::DistributionAlreadyExistsForDay);
+    }
+
+    let clock = Clock::get()?;
+    let current_day = DailyDistributionState::get_day_start(clock.unix_timestamp);
+    
+    // Validate the distribution day is correct (today)
+    require!(
+        distribution_day == current_day,
+        FeeRouterError::InvalidDistributionDay
+    );
+
+    // Distributions that already started can still complete after the sunset
+    if ctx.accounts.policy_state.is_sunsetted(clock.unix_timestamp) {
+        return Err(FeeRouterError::PolicySunsetted
+            .with_context(format!("sunset: {}", ctx.accounts.policy_state.sunset_timestamp))
+            .into());
+    }
+
+    // Check if 24 hours have passed since last distribution
+    require!(
+        DailyDistributionState::can_start_new_distribution(
+            ctx.accounts.global_distribution_state.last_distribution_timestamp,
+            clock.unix_timestamp
+        ),
+        FeeRouterError::TooSoonToDistribute
+    );
+
+    // Investors must not have been allocated more than Y0 (plus the rounding buffer)
+    let registry = &ctx.accounts.allocation_registry;
+    let y0_total_allocation = ctx.accounts.policy_state.y0_total_allocation;
+    if registry.exceeds_y0(y0_total_allocation) {
+        return Err(FeeRouterError::AllocationExceedsY0
+            .with_context(format!(
+                "registered: {}, y0: {}, overage_bps: {}",
+                registry.total_registered_allocation,
+                y0_total_allocation,
+                registry.overage_bps(y0_total_allocation)
+            ))
+            .into());
+    }
+
+    // Claim pending fees first so the day does not start from a stale balance
+    if auto_claim {
+        match auto_claim_fees(ctx.program_id, &ctx.accounts.quote_mint.key(), ctx.remaining_accounts, clock.unix_timestamp)? {
+            None => {
+                ctx.accounts.treasury_state.reload()?;
+                ctx.accounts.treasury_ata.reload()?;
+            }
+            Some(reason) => {
+                msg!("⚠️ Auto-claim skipped, starting with the current treasury balance: {}", reason);
+                emit!(AutoClaimFailed {
+                    quote_mint: ctx.accounts.quote_mint.key(),
+                    reason: reason.to_string(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+    }
+
+    // The treasury balance must reflect a recent claim
+    let last_claim_timestamp = ctx.accounts.treasury_state.last_claim_timestamp;
+    if ctx.accounts.policy_state.is_claim_stale(last_claim_timestamp, clock.unix_timestamp) {
+        return Err(FeeRouterError::TreasuryClaimStale
+            .with_context(format!(
+                "last claim: {}, max interval: {}s",
+                last_claim_timestamp, ctx.accounts.policy_state.max_claim_interval_seconds
+            ))
+            .into());
+    }
+
+    // Get available treasury balance
+    let treasury_balance = ctx.accounts.treasury_ata.amount;
+    emit!(TreasuryBalanceAtDistributionStart {
+        quote_mint: ctx.accounts.quote_mint.key(),
+        treasury_ata: ctx.accounts.treasury_ata.key(),
+        balance: treasury_balance,
+        distribution_day,
+        timestamp: clock.unix_timestamp,
+    });
+    require!(
+        treasury_balance > 0,
+        FeeRouterError::NoFeesToClaim // TODO: Add better error for no funds to distribute
+    );
+
+    // Keep the reserve portion in the treasury ATA
+    let distributable_amount = ctx.accounts.treasury_state.calculate_distributable_amount(treasury_balance);
+    let reserve_amount = treasury_balance.saturating_sub(distributable_amount);
+
+    emit!(TreasuryReserveRetained {
+        reserve_amount,
+        distributable_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Record the policy parameters in effect for auditing
+    let day_string = distribution_day.to_string();
+    let quote_mint_key = ctx.accounts.quote_mint.key();
+    create_pda_account(
+        &ctx.accounts.policy_snapshot,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        &[b"policy_snapshot", day_string.as_bytes(), quote_mint_key.as_ref(), &[ctx.bumps.policy_snapshot]],
+        8 + PolicySnapshot::INIT_SPACE,
+        &PolicySnapshot::from_policy(&ctx.accounts.policy_state, distribution_day, clock.unix_timestamp),
+    )?;
+
+    // Investor set is fixed by the snapshot taken for this day, or by the registry when passed
+    let total_investors = match ctx.accounts.investor_registry.as_ref() {
+        Some(investor_registry) => investor_registry.investor_count,
+        None => ctx.accounts.investor_snapshot.investor_count,
+    };
+    require!(
+        ctx.accounts.policy_state.has_enough_investors(total_investors),
+        FeeRouterError::InsufficientInvestors
+    );
+
+    let effective_page_size = ctx.accounts.policy_state.initial_page_size();
+    let expected_page_count = DailyDistributionState::calculate_expected_page_count(total_investors, effective_page_size);
+
+    // Initialize daily distribution state from the policy, seeded with earlier days' dust.
+    // That dust never left the treasury, so it is already part of the distributable
+    // balance and is moved out of the fresh amount rather than added on top of it.
+    let daily_cap = ctx.accounts.policy_state.daily_cap_for_distribution();
+    let dust_rolled_over = std::cmp::min(
+        ctx.accounts.global_distribution_state.take_dust_rollover(),
+        distributable_amount,
+    );
+    if dust_rolled_over > 0 {
+        msg!("Carrying over {} dust from previous distributions", dust_rolled_over);
+    }
+    let daily_distribution_state = DailyDistributionState {
+        distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        treasury_ata: ctx.accounts.treasury_ata.key(),
+        total_amount_to_distribute: distributable_amount.saturating_sub(dust_rolled_over),
+        amount_distributed: 0,
+        current_cursor: 0,
+        total_investors,
+        investors_processed: 0,
+        is_complete: false,
+        started_at: clock.unix_timestamp,
+        completed_at: 0,
+        dust_carried_over: dust_rolled_over,
+        daily_cap_total: daily_cap,
+        daily_cap_remaining: daily_cap,
+        min_payout_threshold: ctx.accounts.policy_state.min_payout_lamports,
+        initial_total_deposit: ctx.accounts.policy_state.y0_total_allocation,
+        investor_fee_share_bps: ctx.accounts.policy_state.fee_share_ramp(clock.unix_timestamp),
+        last_page_hash: [0; 32], // No pages processed yet
+        pages_processed: 0,
+        streams_below_min_duration: 0,
+        ata_creation_fees_lamports: 0,
+        processed_bitmap: vec![0; DailyDistributionState::bitmap_len(total_investors)],
+        treasury_balance_at_start: treasury_balance,
+        lock_snapshot_timestamp: ctx.accounts.investor_snapshot.taken_at,
+        last_page_completed_at: 0,
+        total_fees_claimed_at_distribution_start: ctx.accounts.treasury_state.total_fees_claimed,
+        max_page_size_used: 0,
+        min_page_size_used: 0,
+        skipped_blacklisted: 0,
+        execution_slot: clock.slot,
+        current_page_slot: 0,
+        expected_page_count,
+        page_cursor_history: [0; 8],
+        page_hash_history: [[0; 32]; 4],
+        effective_page_size,
+        failed_payouts_count: 0,
+        dust_rolled_over,
+        registry_driven: ctx.accounts.investor_registry.is_some(),
+        creator_remainder: 0,
+    };
+    create_pda_account(
+        &ctx.accounts.daily_distribution_state,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        &[b"daily_distribution", day_string.as_bytes(), quote_mint_key.as_ref(), &[ctx.bumps.daily_distribution_state]],
+        8 + DailyDistributionState::space_for(total_investors),
+        &daily_distribution_state,
+    )?;
+
+    // Failed streams are recorded here while the day's pages are processed
+    create_pda_account(
+        &ctx.accounts.stream_error_log,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        &[b"stream_errors", day_string.as_bytes(), quote_mint_key.as_ref(), &[ctx.bumps.stream_error_log]],
+        8 + StreamErrorLog::INIT_SPACE,
+        &StreamErrorLog {
+            distribution_day,
+            quote_mint: quote_mint_key,
+            entries: Vec::new(),
+            total_errors: 0,
+            payer: ctx.accounts.authority.key(),
+            bump: ctx.bumps.stream_error_log,
+        },
+    )?;
+
+    // Emit event
+    emit!(DailyDistributionStarted {
+        distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        total_amount_to_distribute: distributable_amount,
+        total_investors,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(ExpectedPageCount {
+        distribution_day,
+        expected_pages: expected_page_count,
+        total_investors,
+        page_size: effective_page_size,
+    });
+
+    msg!("✅ Daily distribution started with {} tokens for {} investors ({} kept in reserve)", 
+         distributable_amount, total_investors, reserve_amount);
+    Ok(())
+}
+
+/// Check that an account is the investor's payout summary PDA for the day
+fn require_payout_summary_pda(
+    summary_account: &AccountInfo,
+    distribution_day: i64,
+    investor: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let (expected_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, investor, program_id);
+    require!(
+        summary_account.key() == expected_summary,
+        FeeRouterError::PaginationError
+    );
+    Ok(())
+}
+
+/// Read the day's payouts from `InvestorPayoutSummary` accounts
+/// 
+/// Each account must be the investor's summary PDA for the day, and each
+/// investor may appear only once.
+fn read_payout_summaries(
+    summary_accounts: &[AccountInfo],
+    distribution_day: i64,
+    program_id: &Pubkey,
+) -> Result<Vec<u64>> {
+    let mut reported_investors = BTreeSet::new();
+    let mut payouts: Vec<u64> = Vec::with_capacity(summary_accounts.len());
+    for summary_account in summary_accounts.iter() {
+        require_keys_eq!(*summary_account.owner, *program_id, FeeRouterError::PaginationError);
+        let summary = InvestorPayoutSummary::try_deserialize(&mut summary_account.data.borrow().as_ref())?;
+        require_payout_summary_pda(summary_account, distribution_day, &summary.investor, program_id)?;
+        require!(
+            reported_investors.insert(summary.investor),
+            FeeRouterError::PaginationError
+        );
+        payouts.push(summary.amount_paid);
+    }
+    Ok(payouts)
+}
+
+/// Check that an existing investor ATA holds the quote mint and belongs to the investor
+/// 
+/// Missing ATAs are created on payout and pass.
+fn require_investor_ata(investor_ata: &AccountInfo, quote_mint: &Pubkey, investor: &Pubkey) -> Result<()> {
+    if investor_ata.data_is_empty() {
+        return Ok(());
+    }
+    let token_account = token::TokenAccount::try_deserialize(&mut investor_ata.data.borrow().as_ref())?;
+    require_keys_eq!(token_account.mint, *quote_mint, FeeRouterError::TokenAccountMintMismatch);
+    require_keys_eq!(token_account.owner, *investor, FeeRouterError::InvestorAtaOwnerMismatch);
+    Ok(())
+}
+
+/// Check whether an existing token account is frozen (missing accounts are not)
+fn is_frozen_token_account(account: &AccountInfo) -> Result<bool> {
+    if account.data_is_empty() {
+        return Ok(false);
+    }
+    let token_account = token::TokenAccount::try_deserialize(&mut account.data.borrow().as_ref())?;
+    Ok(token_account.is_frozen())
+}
+
+/// Add a payout to the investor's payout summary, creating the PDA on first payout
+/// 
+/// `summary` is used when the investor has not been paid yet this day.
+fn record_investor_payout<'info>(
+    summary_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    summary: InvestorPayoutSummary,
+    payout_amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if summary_account.data_is_empty() {
+        let mut summary = summary;
+        summary.record_payout(payout_amount, timestamp);
+        let day_string = summary.distribution_day.to_string();
+        let (_, bump) = InvestorPayoutSummary::derive_pda(summary.distribution_day, &summary.investor, program_id);
+        return create_pda_account(
+            summary_account,
+            payer,
+            system_program,
+            program_id,
+            &[b"payout_summary", day_string.as_bytes(), summary.investor.as_ref(), &[bump]],
+            8 + InvestorPayoutSummary::INIT_SPACE,
+            &summary,
+        );
+    }
+
+    require_keys_eq!(*summary_account.owner, *program_id, FeeRouterError::PaginationError);
+    let mut summary = InvestorPayoutSummary::try_deserialize(&mut summary_account.data.borrow().as_ref())?;
+    summary.record_payout(payout_amount, timestamp);
+    let mut data = summary_account.try_borrow_mut_data()?;
+    summary.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Create a program-owned PDA and write its initial state
+/// 
+/// Used instead of `init` where an existing account must be reported with
+/// a specific error rather than a system program collision.
+fn create_pda_account<'info, T: AccountSerialize>(
+    pda_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    pda_seeds: &[&[u8]],
+    space: usize,
+    state: &T,
+) -> Result<()> {
+    let rent_lamports = rent::required_rent(space)?;
+
+    let current_lamports = pda_account.lamports();
+    rent::require_rent_lamports(payer, rent_lamports.saturating_sub(current_lamports))?;
+    if current_lamports == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: pda_account.clone(),
+                },
+                &[pda_seeds],
+            ),
+            rent_lamports,
+            space as u64,
+            program_id,
+        )?;
+    } else {
+        // Lamports sent to the PDA in advance must not block its creation
+        let top_up = rent_lamports.saturating_sub(current_lamports);
+        if top_up > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: pda_account.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Allocate {
+                    account_to_allocate: pda_account.clone(),
+                },
+                &[pda_seeds],
+            ),
+            space as u64,
+        )?;
+        anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Assign {
+                    account_to_assign: pda_account.clone(),
+                },
+                &[pda_seeds],
+            ),
+            program_id,
+        )?;
+    }
+
+    let mut data = pda_account.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Run `claim_fees` in-process on the `ClaimFees` accounts in `accounts`
+/// 
+/// Claims that cannot happen yet are detected before the position CPI and
+/// skipped: the claim interval has not elapsed, or the position has no
+/// accrued quote fees. Returns the reason in that case. Every other failure,
+/// including those raised after the CPI, is returned as an error.
+fn auto_claim_fees<'info>(
+    program_id: &Pubkey,
+    quote_mint: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    timestamp: i64,
+) -> Result<Option<FeeRouterError>> {
+    let mut remaining_accounts = accounts;
+    let mut bumps = ClaimFeesBumps::default();
+    let mut reallocs = BTreeSet::new();
+    let mut claim_accounts = ClaimFees::try_accounts(program_id, &mut remaining_accounts, &[], &mut bumps, &mut reallocs)?;
+    require_keys_eq!(claim_accounts.quote_mint.key(), *quote_mint, FeeRouterError::QuoteMintMismatch);
+
+    let treasury_state = &claim_accounts.treasury_state;
+    if !treasury_state.can_claim(timestamp, treasury_state.effective_min_claim_interval()?) {
+        return Ok(Some(FeeRouterError::ClaimIntervalNotElapsed));
+    }
+    if claim_accounts.position_metadata.pool_type == PoolType::MeteoraDAMM {
+        if let Ok((estimated_quote, _)) = meteora::cpi::estimate_claimable_fees(
+            &claim_accounts.pool.to_account_info(),
+            &claim_accounts.position.to_account_info(),
+        ) {
+            if estimated_quote == 0 {
+                return Ok(Some(FeeRouterError::NoFeesToClaim));
+            }
+        }
+    }
+
+    claiming_instructions::claim_fees(Context::new(program_id, &mut claim_accounts, remaining_accounts, bumps))?;
+    claim_accounts.exit(program_id)?;
+    Ok(None)
+}
+
+/// Process a page of investors
+/// 
+/// This processes a batch of investors (up to MAX_SAFE_INVESTORS_PER_PAGE)
+/// and distributes their share of fees based on locked token amounts.
+/// Implements the complete Section 4 distribution logic.
+/// 
+/// Remaining accounts are passed per investor as
+/// `[stream, investor_wallet, investor_ata, associated_token_program, investor_cache, payout_summary]`.
+/// Missing investor ATAs are created (idempotently) before the transfer,
+/// paid for by the cranker, as are the investors' `InvestorPayoutSummary` PDAs.
+/// 
+/// Locked amounts come from the day's `InvestorSnapshot` entries at the cursor.
+/// The streams are still read to check them and to add them to the investor's
+/// aggregate cache; a cache from an earlier page is never used in place of
+/// them, since it covers other streams and would pay them a second time.
+/// 
+/// Once `max_pages_before_auto_complete` pages have been processed the day is
+/// completed inline, as if `complete_daily_distribution` had been called; the
+/// global state, treasury state and creator ATA must then be passed.
+/// 
+/// Once the investor blacklist has been initialized it must be passed, and
+/// blacklisted investors are skipped.
+/// Investors whose ATA is frozen are skipped too and counted in
+/// `failed_payouts_count`; their payout stays in the treasury.
+/// 
+/// Pages must be submitted in order: `expected_page_index` has to equal the
+/// number of pages already processed for the day. The investors must be the
+/// day's snapshot entries from the cursor on, in snapshot order, and are paid
+/// on the snapshot's locked amounts, so every cranker produces the same
+/// distribution. Days started from the investor registry only accept
+/// `process_investor_page_from_registry`.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `expected_page_index` - Index of the page being submitted
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn process_investor_page<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessInvestorPage<'info>>,
+    expected_page_index: u32,
+) -> Result<()> {
+    // Registry days only take the registry's investors, in registry order
+    require!(
+        !ctx.accounts.daily_distribution_state.registry_driven,
+        FeeRouterError::RegistryPagesRequired
+    );
+
+    // The page must be the snapshot entries at the cursor, stream and wallet alike
+    let page_investors = validate_remaining_accounts_length(ctx.remaining_accounts.len())? as usize;
+    let cursor = ctx.accounts.daily_distribution_state.current_cursor as usize;
+    let entries: Vec<SnapshotEntry> = ctx.accounts.investor_snapshot.entries
+        .get(cursor..cursor.saturating_add(page_investors))
+        .ok_or(FeeRouterError::SnapshotPageMismatch)?
+        .to_vec();
+    for (entry, investor_accounts) in entries.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_INVESTOR)) {
+        require!(
+            investor_accounts[0].key() == entry.stream_account && investor_accounts[1].key() == entry.investor,
+            FeeRouterError::SnapshotPageMismatch
+        );
+    }
+
+    pay_investor_page(ctx, expected_page_index, Some(&entries))
+}
+
+/// Pay one page of investors passed as remaining accounts
+/// 
+/// Shared by `process_investor_page` and `process_investor_page_from_registry`,
+/// which check beforehand that the page belongs to the day's investor set.
+/// When `snapshot_entries` is passed the payouts use its locked amounts
+/// rather than the amounts read from the streams.
+fn pay_investor_page<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessInvestorPage<'info>>,
+    expected_page_index: u32,
+    snapshot_entries: Option<&[SnapshotEntry]>,
+) -> Result<()> {
+    msg!("Processing investor page starting from cursor: {}", 
+         ctx.accounts.daily_distribution_state.current_cursor);
+    require_blacklist_if_enabled(&ctx.accounts.policy_state, ctx.accounts.investor_blacklist.is_some())?;
+
+    // Reject obviously invalid pages before any other work
+    // Remaining accounts are grouped per investor, stream account first
+    let remaining_accounts = ctx.remaining_accounts;
+    let page_investors = validate_remaining_accounts_length(remaining_accounts.len())?;
+    let effective_page_size = ctx.accounts.daily_distribution_state.effective_page_size;
+    if page_investors > effective_page_size {
+        return Err(FeeRouterError::TooManyAccountsInPage
+            .with_context(format!("investors: {}, effective page size: {}", page_investors, effective_page_size))
+            .into());
+    }
+
+    let clock = Clock::get()?;
+    
+    // Check if there are more investors to process
+    require!(
+        ctx.accounts.daily_distribution_state.has_more_investors(),
+        FeeRouterError::DistributionNotStarted
+    );
+
+    let distribution_day = ctx.accounts.daily_distribution_state.distribution_day;
+    let lock_snapshot_timestamp = ctx.accounts.daily_distribution_state.lock_snapshot_timestamp;
+
+    // Every investor's cache must be the PDA for this day and quote mint
+    let quote_mint_key = ctx.accounts.quote_mint.key();
+    let mut stream_accounts: Vec<AccountInfo<'info>> = Vec::new();
+    for investor_accounts in remaining_accounts.chunks(ACCOUNTS_PER_INVESTOR) {
+        let investor = investor_accounts[1].key();
+        let (expected_cache, _) = InvestorAggregateCache::derive_pda(distribution_day, &quote_mint_key, &investor, ctx.program_id);
+        require!(
+            investor_accounts[4].key() == expected_cache,
+            FeeRouterError::PaginationError
+        );
+        stream_accounts.push(investor_accounts[0].clone());
+    }
+
+    // Every stream passed in must be live - fail loudly instead of skipping
+    for stream_account in stream_accounts.iter() {
+        streamflow::cpi::validate_stream_account(stream_account, clock.unix_timestamp as u64)?;
+    }
+
+    // Step 1: Ordering and idempotency checks - this must be the next page and not a replay
+    ctx.accounts.daily_distribution_state.validate_page_order(expected_page_index, page_investors)?;
+    let investor_keys: Vec<Pubkey> = remaining_accounts
+        .chunks(ACCOUNTS_PER_INVESTOR)
+        .map(|investor_accounts| investor_accounts[0].key())
+        .collect();
+    ctx.accounts.daily_distribution_state.validate_page_for_retry(&investor_keys, ctx.accounts.policy_state.page_hash_algorithm)?;
+
+    // Step 2: Read Streamflow stream data for this page of investors
+    let min_remaining_lock_seconds = ctx.accounts.policy_state.min_remaining_lock_seconds;
+    let supplied_atas: Vec<Pubkey> = remaining_accounts
+        .chunks(ACCOUNTS_PER_INVESTOR)
+        .map(|investor_accounts| investor_accounts[2].key())
+        .collect();
+    let (stream_data, stream_total_locked, stream_errors) = streamflow::cpi::calculate_locked_amounts_with_errors(
+        &stream_accounts,
+        clock.unix_timestamp as u64,
+        &ctx.accounts.quote_mint.key(),
+        min_remaining_lock_seconds,
+        Some(&supplied_atas),
+    )?;
+
+    // Keep failed streams for review once the day is complete
+    if !stream_errors.is_empty() {
+        msg!("Encountered {} stream processing errors (continuing with valid streams)", stream_errors.len());
+        for error in stream_errors.iter() {
+            ctx.accounts.stream_error_log.record(error.stream_account, error.error_type);
+        }
+    }
+
+    // Add this page's streams to each investor's aggregate for the day. Streams
+    // of one investor on earlier pages were already paid there, so the cache
+    // never stands in for the streams passed in.
+    let mut written_caches: Vec<Pubkey> = Vec::new();
+    for investor_accounts in remaining_accounts.chunks(ACCOUNTS_PER_INVESTOR) {
+        let investor = investor_accounts[1].key();
+        let investor_streams: Vec<&InvestorStreamData> = stream_data
+            .iter()
+            .filter(|data| data.investor == investor)
+            .collect();
+        if investor_streams.is_empty() || written_caches.contains(&investor) {
+            continue;
+        }
+        written_caches.push(investor);
+        let mut cache = load_investor_cache(&investor_accounts[4], ctx.program_id)
+            .filter(|cache| cache.is_valid_for(distribution_day, lock_snapshot_timestamp))
+            .unwrap_or_else(|| InvestorAggregateCache::new(distribution_day, quote_mint_key, investor, ctx.accounts.authority.key()));
+        cache.add_streams(&investor_streams, clock.unix_timestamp);
+        write_investor_cache(
+            &investor_accounts[4],
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+            cache,
+        )?;
+    }
+
+    // The snapshot fixes the locked amounts for pages checked against it
+    let (mut investor_data, total_locked) = match snapshot_entries {
+        Some(entries) => {
+            let investor_data = snapshot_investor_data(entries, &quote_mint_key);
+            let total_locked = investor_data
+                .iter()
+                .fold(0u64, |acc, data| acc.saturating_add(data.locked_amount));
+            (investor_data, total_locked)
+        }
+        None => (stream_data, stream_total_locked),
+    };
+    let total_locked = apply_cliff_weighting(&mut investor_data, total_locked, ctx.accounts.policy_state.include_cliff_in_weight);
+
+    msg!("Found {} investors with {} total locked tokens", 
+         investor_data.len(), total_locked);
+
+    if min_remaining_lock_seconds > 0 {
+        let short_streams = streamflow::cpi::count_streams_below_min_duration(
+            &stream_accounts,
+            clock.unix_timestamp as u64,
+            min_remaining_lock_seconds,
+        );
+        let daily_state = &mut ctx.accounts.daily_distribution_state;
+        daily_state.streams_below_min_duration = daily_state.streams_below_min_duration.saturating_add(short_streams);
+    }
+
+    // Warn if Y0 undercounts the locked total (fraction is clamped to 100%)
+    let initial_total_deposit = ctx.accounts.daily_distribution_state.initial_total_deposit;
+    let uncapped_bps = streamflow::cpi::calculate_uncapped_locked_fraction(total_locked, initial_total_deposit);
+    if uncapped_bps > BPS_DENOMINATOR {
+        msg!("⚠️ Locked total {} exceeds Y0 {} - clamping locked fraction", total_locked, initial_total_deposit);
+        emit!(LockedFractionExceededY0 {
+            locked_total: total_locked,
+            y0_total_allocation: initial_total_deposit,
+            uncapped_bps,
+        });
+    }
+
+    // Skip blacklisted investors; their locked amount still counts towards the
+    // total so their share stays in the treasury instead of going to others
+    skip_blacklisted_investors(
+        &mut investor_data,
+        ctx.accounts.investor_blacklist.as_deref().map(|blacklist| &**blacklist),
+        &mut ctx.accounts.daily_distribution_state,
+    );
+
+    // Step 3: Calculate distribution using Section 4 formulas
+    let effective_distribution_amount = ctx.accounts.daily_distribution_state.get_effective_distribution_amount();
+    
+    let distribution_calc = streamflow::calculations::calculate_distribution(
+        effective_distribution_amount,
+        &investor_data,
+        total_locked,
+        ctx.accounts.daily_distribution_state.initial_total_deposit,
+        ctx.accounts.daily_distribution_state.investor_fee_share_bps,
+        ctx.accounts.daily_distribution_state.min_payout_threshold,
+        ctx.accounts.policy_state.weighting_options(),
+    )?;
+
+    // Step 4: Apply daily cap
+    let final_calc = streamflow::calculations::apply_daily_cap(
+        distribution_calc,
+        ctx.accounts.daily_distribution_state.daily_cap_remaining,
+        ctx.accounts.daily_distribution_state.min_payout_threshold,
+        ctx.accounts.policy_state.cap_application_mode,
+    );
+
+    // Step 5: Validate calculation
+    streamflow::calculations::validate_distribution(&final_calc, effective_distribution_amount)?;
+
+    // Step 6: Validate every payout before moving any tokens
+    let mut planned_payouts: Vec<PlannedPayout<'_, 'info>> = Vec::new();
+    let mut missing_atas: Vec<Pubkey> = Vec::new();
+    for payout in final_calc.investor_payouts.iter().filter(|payout| payout.payout_amount > 0 && payout.meets_minimum) {
+        let expected_ata = get_associated_token_address(&payout.investor, &quote_mint_key);
+        let investor_accounts = remaining_accounts
+            .chunks(ACCOUNTS_PER_INVESTOR)
+            .find(|investor_accounts| investor_accounts[1].key() == payout.investor)
+            .filter(|investor_accounts| investor_accounts[2].key() == expected_ata);
+        match investor_accounts {
+            Some(investor_accounts) => planned_payouts.push(PlannedPayout {
+                investor: payout.investor,
+                amount: payout.payout_amount,
+                wallet: &investor_accounts[1],
+                ata: &investor_accounts[2],
+                associated_token_program: &investor_accounts[3],
+                payout_summary: &investor_accounts[5],
+            }),
+            None => missing_atas.push(expected_ata),
+        }
+    }
+
+    // Step 7: Execute transfers to investors
+    let treasury_authority_bump = verify_pda(
+        &ctx.accounts.treasury_authority.key(),
+        &[b"treasury_authority", quote_mint_key.as_ref()],
+        ctx.program_id,
+    )?;
+    let treasury_seeds = &[
+        b"treasury_authority",
+        quote_mint_key.as_ref(),
+        &[treasury_authority_bump],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let PagePayoutTotals { amount_distributed: actual_distributed, investors_paid: investors_processed } = pay_planned_investors(
+        ctx.accounts,
+        &planned_payouts,
+        &missing_atas,
+        signer_seeds,
+        ctx.program_id,
+        clock.unix_timestamp,
+    )?;
+
+    // Step 8: Update state with idempotency tracking - the cursor advances past
+    // every investor of the page, including skipped ones, so it stays aligned
+    // with the investor set
+    let page_index = ctx.accounts.daily_distribution_state.pages_processed;
+    ctx.accounts.daily_distribution_state.record_page_stats(page_investors);
+    ctx.accounts.daily_distribution_state.record_page_slot(clock.slot)?;
+    let page_hash_algorithm = ctx.accounts.policy_state.page_hash_algorithm;
+    let page_hash = DailyDistributionState::calculate_page_hash(&investor_keys, page_hash_algorithm);
+    ctx.accounts.daily_distribution_state.update_page_state(
+        page_hash,
+        page_investors,
+        actual_distributed
+    );
+
+    // Update daily cap
+    ctx.accounts.daily_distribution_state.update_daily_cap(actual_distributed);
+
+    // Add dust to carry over
+    ctx.accounts.daily_distribution_state.add_dust(final_calc.dust_amount);
+    ctx.accounts.daily_distribution_state.last_page_completed_at = clock.unix_timestamp;
+
+    let is_final_page = !ctx.accounts.daily_distribution_state.has_more_investors();
+
+    // Step 9: Emit event
+    emit!(InvestorsProcessed {
+        distribution_day: ctx.accounts.daily_distribution_state.distribution_day,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        investors_in_page: investors_processed,
+        amount_distributed_in_page: actual_distributed,
+        new_cursor: ctx.accounts.daily_distribution_state.current_cursor,
+        total_investors_processed: ctx.accounts.daily_distribution_state.investors_processed,
+        total_amount_distributed: ctx.accounts.daily_distribution_state.amount_distributed,
+        is_final_page,
+        page_processing_latency_seconds: ctx.accounts.daily_distribution_state.page_processing_latency_seconds(),
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(ctx.accounts.daily_distribution_state.to_summary());
+
+    // Lets auditors check the page against an off-chain investor list
+    let investor_wallets: Vec<Pubkey> = remaining_accounts
+        .chunks(ACCOUNTS_PER_INVESTOR)
+        .map(|investor_accounts| investor_accounts[1].key())
+        .collect();
+    emit!(PageCompletionProof {
+        distribution_day,
+        page_index,
+        investor_keys_hash: DailyDistributionState::calculate_page_hash(&investor_wallets, page_hash_algorithm),
+        payouts_hash: DailyDistributionState::calculate_payouts_hash(&final_calc.investor_payouts, page_hash_algorithm),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Processed {} investors, distributed {} tokens, {} dust", 
+         investors_processed, actual_distributed, final_calc.dust_amount);
+    
+    if is_final_page {
+        msg!("🎉 All investors processed for this day!");
+    }
+
+    // Step 10: Complete inline once the page backstop is reached
+    let pages_processed = ctx.accounts.daily_distribution_state.pages_processed;
+    if ctx.accounts.policy_state.should_auto_complete(pages_processed) {
+        auto_complete_daily_distribution(ctx.accounts, signer_seeds, clock.unix_timestamp)?;
+    }
+
+    Ok(())
+}
+
+/// An investor payout matched to the investor's accounts
+struct PlannedPayout<'a, 'info> {
+    /// Investor wallet being paid
+    investor: Pubkey,
+    /// Payout amount
+    amount: u64,
+    /// Investor wallet account (authority of a created ATA)
+    wallet: &'a AccountInfo<'info>,
+    /// Investor quote ATA, created if missing
+    ata: &'a AccountInfo<'info>,
+    /// Associated token program used to create the ATA
+    associated_token_program: &'a AccountInfo<'info>,
+    /// Investor payout summary PDA for the day
+    payout_summary: &'a AccountInfo<'info>,
+}
+
+/// What a page actually paid out
+struct PagePayoutTotals {
+    /// Total transferred to investors
+    amount_distributed: u64,
+    /// Number of investors paid
+    investors_paid: u32,
+}
+
+/// Pay a page's planned investor payouts from the treasury
+/// 
+/// Shared by `pay_investor_page` and `process_investor_page_from_snapshot`.
+/// Every payout's accounts are validated and the page checked payable before
+/// any tokens move. Investors with a frozen ATA are skipped and counted in
+/// `failed_payouts_count`; everyone else gets their ATA created if needed,
+/// the transfer and their payout summary updated. ATA rent is recorded on
+/// the daily state.
+fn pay_planned_investors<'info>(
+    page: &mut ProcessInvestorPage<'info>,
+    planned_payouts: &[PlannedPayout<'_, 'info>],
+    missing_atas: &[Pubkey],
+    signer_seeds: &[&[&[u8]]],
+    program_id: &Pubkey,
+    timestamp: i64,
+) -> Result<PagePayoutTotals> {
+    let distribution_day = page.daily_distribution_state.distribution_day;
+    let quote_mint_key = page.quote_mint.key();
+    for payout in planned_payouts {
+        require!(
+            payout.associated_token_program.key() == associated_token::ID,
+            anchor_lang::error::ErrorCode::InvalidProgramId
+        );
+        require_payout_summary_pda(payout.payout_summary, distribution_day, &payout.investor, program_id)?;
+        require_investor_ata(payout.ata, &quote_mint_key, &payout.investor)?;
+    }
+    let page_payout_total = planned_payouts
+        .iter()
+        .fold(0u64, |acc, payout| acc.saturating_add(payout.amount));
+    streamflow::calculations::validate_page_payable(
+        missing_atas,
+        planned_payouts.len() + missing_atas.len(),
+        page_payout_total,
+        page.treasury_ata.amount,
+    )?;
+
+    let rent = Rent::get()?;
+    let mut amount_distributed = 0u64;
+    let mut investors_paid = 0u32;
+    let mut ata_creation_fees = 0u64;
+    let mut failed_payouts = 0u32;
+
+    for payout in planned_payouts {
+        let investor = payout.investor;
+
+        // A transfer to a frozen ATA would abort the whole page - skip the investor instead
+        if is_frozen_token_account(payout.ata)? {
+            failed_payouts += 1;
+            msg!("⚠️ Skipping investor {} - ATA {} is frozen", investor, payout.ata.key());
+            emit!(InvestorAtaFrozen {
+                investor,
+                ata: payout.ata.key(),
+                distribution_day,
+            });
+            continue;
+        }
+
+        // Create the investor ATA if it doesn't exist yet
+        if payout.ata.data_is_empty() {
+            ata_creation_fees = ata_creation_fees.saturating_add(rent.minimum_balance(token::TokenAccount::LEN));
+            msg!("Creating ATA for investor {}", investor);
+        }
+        associated_token::create_idempotent(CpiContext::new(
+            payout.associated_token_program.clone(),
+            associated_token::Create {
+                payer: page.authority.to_account_info(),
+                associated_token: payout.ata.clone(),
+                authority: payout.wallet.clone(),
+                mint: page.quote_mint.to_account_info(),
+                system_program: page.system_program.to_account_info(),
+                token_program: page.token_program.to_account_info(),
+            },
+        ))?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                page.token_program.to_account_info(),
+                token::Transfer {
+                    from: page.treasury_ata.to_account_info(),
+                    to: payout.ata.clone(),
+                    authority: page.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payout.amount,
+        )?;
+
+        amount_distributed = amount_distributed.saturating_add(payout.amount);
+        investors_paid += 1;
+        record_investor_payout(
+            payout.payout_summary,
+            &page.authority.to_account_info(),
+            &page.system_program.to_account_info(),
+            program_id,
+            InvestorPayoutSummary::new(distribution_day, investor, page.authority.key()),
+            payout.amount,
+            timestamp,
+        )?;
+
+        msg!("Paid {} tokens to investor {}", payout.amount, investor);
+    }
+
+    let daily_state = &mut page.daily_distribution_state;
+    daily_state.ata_creation_fees_lamports = daily_state.ata_creation_fees_lamports.saturating_add(ata_creation_fees);
+    daily_state.add_failed_payouts(failed_payouts);
+
+    Ok(PagePayoutTotals {
+        amount_distributed,
+        investors_paid,
+    })
+}
+
+/// Settle the day from within `process_investor_page` once the page backstop is hit
+fn auto_complete_daily_distribution<'info>(
+    accounts: &mut ProcessInvestorPage<'info>,
+    signer_seeds: &[&[&[u8]]],
+    timestamp: i64,
+) -> Result<()> {
+    let pages_processed = accounts.daily_distribution_state.pages_processed;
+    let investors_remaining = accounts.daily_distribution_state.total_investors
+        .saturating_sub(accounts.daily_distribution_state.investors_processed);
+    msg!("Auto-completing distribution after {} pages, {} investors remaining",
+         pages_processed, investors_remaining);
+
+    let creator_ata = accounts.creator_ata.as_ref().ok_or(FeeRouterError::AutoCompletionAccountsMissing)?;
+    let creator = creator_ata.owner;
+    let treasury_transfer = |to: AccountInfo<'info>| {
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: accounts.treasury_ata.to_account_info(),
+                to,
+                authority: accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+    };
+    let transfers = SettlementTransfers {
+        creator: treasury_transfer(creator_ata.to_account_info()),
+        protocol_dust: accounts.protocol_fee_ata.as_ref().map(|ata| treasury_transfer(ata.to_account_info())),
+        protocol_share: accounts.protocol_share_ata.as_ref().map(|ata| treasury_transfer(ata.to_account_info())),
+    };
+
+    let global_state = accounts.global_distribution_state
+        .as_mut()
+        .ok_or(FeeRouterError::AutoCompletionAccountsMissing)?;
+    let treasury_state = accounts.treasury_state
+        .as_mut()
+        .ok_or(FeeRouterError::AutoCompletionAccountsMissing)?;
+    settle_daily_distribution(
+        &mut accounts.daily_distribution_state,
+        global_state,
+        treasury_state,
+        &mut accounts.policy_state,
+        transfers,
+        creator,
+        timestamp,
+    )?;
+
+    emit!(AutoCompletionTriggered {
+        pages_processed,
+        investors_remaining,
+        timestamp,
+    });
+
+    msg!("✅ Daily distribution auto-completed");
+    Ok(())
+}
+
+/// Process a page of investors from the day's investor snapshot
+/// 
+/// Alternative to `process_investor_page` that reads the locked amounts
+/// recorded by `take_investor_snapshot` instead of re-reading stream
+/// accounts. Pages are fixed slices of `MAX_SAFE_INVESTORS_PER_PAGE` snapshot
+/// entries and must be processed in order.
+/// 
+/// Remaining accounts are passed per snapshot entry of the page as
+/// `[investor_wallet, investor_ata, payout_summary]`. Snapshot entries carry no lock
+/// schedule, so no duration bonus applies on this path. Not available for days
+/// started from the investor registry. Cliff weighting, frozen ATAs, payout
+/// summaries and auto-completion work as in `process_investor_page`.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `page_index` - Index of the snapshot page to process
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn process_investor_page_from_snapshot<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessInvestorPageFromSnapshot<'info>>,
+    page_index: u32,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let accounts = ctx.accounts;
+    let remaining_accounts = ctx.remaining_accounts;
+    msg!("Processing snapshot page {} starting from cursor: {}",
+         page_index, accounts.page.daily_distribution_state.current_cursor);
+
+    let clock = Clock::get()?;
+
+    // Registry days only take the registry's investors, in registry order
+    require!(
+        !accounts.page.daily_distribution_state.registry_driven,
+        FeeRouterError::RegistryPagesRequired
+    );
+
+    require_blacklist_if_enabled(&accounts.page.policy_state, accounts.page.investor_blacklist.is_some())?;
+
+    // Check if there are more investors to process
+    require!(
+        accounts.page.daily_distribution_state.has_more_investors(),
+        FeeRouterError::DistributionNotStarted
+    );
+
+    // Step 1: Select the page entries - pages must follow the cursor
+    require!(
+        InvestorSnapshot::page_start(page_index, MAX_SAFE_INVESTORS_PER_PAGE)
+            == accounts.page.daily_distribution_state.current_cursor,
+        FeeRouterError::InvalidPageIndex
+    );
+    let page_entries: Vec<SnapshotEntry> = accounts.page.investor_snapshot
+        .page_entries(page_index, MAX_SAFE_INVESTORS_PER_PAGE)
+        .ok_or(FeeRouterError::InvalidPageIndex)?
+        .to_vec();
+
+    require!(
+        remaining_accounts.len() == page_entries.len() * SNAPSHOT_ACCOUNTS_PER_INVESTOR,
+        FeeRouterError::PaginationError
+    );
+
+    // Step 2: Idempotency check - validate this page hasn't been processed
+    let investor_keys: Vec<Pubkey> = page_entries
+        .iter()
+        .map(|entry| entry.stream_account)
+        .collect();
+    accounts.page.daily_distribution_state.validate_page_for_retry(&investor_keys, accounts.page.policy_state.page_hash_algorithm)?;
+
+    // Step 3: Build investor data from the snapshot entries
+    let quote_mint_key = accounts.page.quote_mint.key();
+    let mut investor_data = snapshot_investor_data(&page_entries, &quote_mint_key);
+    let total_locked = investor_data
+        .iter()
+        .fold(0u64, |acc, data| acc.saturating_add(data.locked_amount));
+    let total_locked = apply_cliff_weighting(&mut investor_data, total_locked, accounts.page.policy_state.include_cliff_in_weight);
+
+    msg!("Found {} snapshot entries with {} total locked tokens",
+         investor_data.len(), total_locked);
+
+    // Blacklisted investors keep their weight so their share stays in the treasury
+    skip_blacklisted_investors(
+        &mut investor_data,
+        accounts.page.investor_blacklist.as_deref().map(|blacklist| &**blacklist),
+        &mut accounts.page.daily_distribution_state,
+    );
+
+    // Step 4: Calculate distribution using Section 4 formulas
+    let effective_distribution_amount = accounts.page.daily_distribution_state.get_effective_distribution_amount();
+
+    let distribution_calc = streamflow::calculations::calculate_distribution(
+        effective_distribution_amount,
+        &investor_data,
+        total_locked,
+        accounts.page.daily_distribution_state.initial_total_deposit,
+        accounts.page.daily_distribution_state.investor_fee_share_bps,
+        accounts.page.daily_distribution_state.min_payout_threshold,
+        accounts.page.policy_state.weighting_options(),
+    )?;
+
+    let final_calc = streamflow::calculations::apply_daily_cap(
+        distribution_calc,
+        accounts.page.daily_distribution_state.daily_cap_remaining,
+        accounts.page.daily_distribution_state.min_payout_threshold,
+        accounts.page.policy_state.cap_application_mode,
+    );
+    streamflow::calculations::validate_distribution(&final_calc, effective_distribution_amount)?;
+
+    // Step 5: Validate every payout before moving any tokens
+    let associated_token_program = accounts.associated_token_program.to_account_info();
+    let mut planned_payouts: Vec<PlannedPayout<'_, 'info>> = Vec::new();
+    let mut missing_atas: Vec<Pubkey> = Vec::new();
+    for payout in final_calc.investor_payouts.iter().filter(|payout| payout.payout_amount > 0 && payout.meets_minimum) {
+        let investor_accounts = remaining_accounts
+            .chunks(SNAPSHOT_ACCOUNTS_PER_INVESTOR)
+            .find(|investor_accounts| investor_accounts[0].key() == payout.investor)
+            .filter(|investor_accounts| investor_accounts[1].key() == payout.investor_ata);
+        match investor_accounts {
+            Some(investor_accounts) => planned_payouts.push(PlannedPayout {
+                investor: payout.investor,
+                amount: payout.payout_amount,
+                wallet: &investor_accounts[0],
+                ata: &investor_accounts[1],
+                associated_token_program: &associated_token_program,
+                payout_summary: &investor_accounts[2],
+            }),
+            None => missing_atas.push(payout.investor_ata),
+        }
+    }
+
+    // Step 6: Execute transfers to investors
+    let treasury_authority_bump = verify_pda(
+        &accounts.page.treasury_authority.key(),
+        &[b"treasury_authority", quote_mint_key.as_ref()],
+        program_id,
+    )?;
+    let treasury_seeds = &[
+        b"treasury_authority",
+        quote_mint_key.as_ref(),
+        &[treasury_authority_bump],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let PagePayoutTotals { amount_distributed: actual_distributed, investors_paid: investors_processed } = pay_planned_investors(
+        &mut accounts.page,
+        &planned_payouts,
+        &missing_atas,
+        signer_seeds,
+        program_id,
+        clock.unix_timestamp,
+    )?;
+
+    // Step 7: Update state - the cursor advances past every entry of the page
+    let page_investors = page_entries.len() as u32;
+    let page_hash_algorithm = accounts.page.policy_state.page_hash_algorithm;
+    let daily_state = &mut accounts.page.daily_distribution_state;
+    daily_state.record_page_stats(page_investors);
+    daily_state.record_page_slot(clock.slot)?;
+    daily_state.update_page_state(
+        DailyDistributionState::calculate_page_hash(&investor_keys, page_hash_algorithm),
+        page_investors,
+        actual_distributed
+    );
+    daily_state.update_daily_cap(actual_distributed);
+    daily_state.add_dust(final_calc.dust_amount);
+    daily_state.last_page_completed_at = clock.unix_timestamp;
+
+    let is_final_page = !daily_state.has_more_investors();
+
+    // Step 8: Emit event
+    emit!(InvestorsProcessed {
+        distribution_day: daily_state.distribution_day,
+        quote_mint: quote_mint_key,
+        investors_in_page: investors_processed,
+        amount_distributed_in_page: actual_distributed,
+        new_cursor: daily_state.current_cursor,
+        total_investors_processed: daily_state.investors_processed,
+        total_amount_distributed: daily_state.amount_distributed,
+        is_final_page,
+        page_processing_latency_seconds: daily_state.page_processing_latency_seconds(),
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(daily_state.to_summary());
+
+    msg!("✅ Processed snapshot page {}: paid {} investors, distributed {} tokens, {} dust",
+         page_index, investors_processed, actual_distributed, final_calc.dust_amount);
+
+    if is_final_page {
+        msg!("🎉 All investors processed for this day!");
+    }
+
+    // Step 9: Complete inline once the page backstop is reached
+    let pages_processed = accounts.page.daily_distribution_state.pages_processed;
+    if accounts.page.policy_state.should_auto_complete(pages_processed) {
+        auto_complete_daily_distribution(&mut accounts.page, signer_seeds, clock.unix_timestamp)?;
+    }
+
+    Ok(())
+}
+
+/// Require the investor blacklist on pages once the policy has one
+fn require_blacklist_if_enabled(policy_state: &PolicyState, blacklist_passed: bool) -> Result<()> {
+    require!(
+        !policy_state.blacklist_enabled || blacklist_passed,
+        FeeRouterError::BlacklistRequired
+    );
+    Ok(())
+}
+
+/// Drop blacklisted investors from a page, counting each skipped investor once
+fn skip_blacklisted_investors(
+    investor_data: &mut Vec<InvestorStreamData>,
+    blacklist: Option<&InvestorBlacklist>,
+    daily_state: &mut DailyDistributionState,
+) {
+    let Some(blacklist) = blacklist else {
+        return;
+    };
+    let mut skipped: Vec<Pubkey> = Vec::new();
+    investor_data.retain(|data| {
+        if !blacklist.is_blacklisted(&data.investor) {
+            return true;
+        }
+        if !skipped.contains(&data.investor) {
+            skipped.push(data.investor);
+        }
+        false
+    });
+    if !skipped.is_empty() {
+        msg!("Skipping {} blacklisted investors", skipped.len());
+        daily_state.skipped_blacklisted = daily_state.skipped_blacklisted.saturating_add(skipped.len() as u32);
+    }
+}
+
+/// Exclude unreleased cliff amounts from weight unless the policy counts them
+/// 
+/// Returns the locked total left after the adjustment.
+fn apply_cliff_weighting(investor_data: &mut [InvestorStreamData], total_locked: u64, include_cliff_in_weight: bool) -> u64 {
+    if include_cliff_in_weight {
+        return total_locked;
+    }
+    let mut total_locked = total_locked;
+    for data in investor_data.iter_mut() {
+        total_locked = total_locked.saturating_sub(data.cliff_locked);
+        data.locked_amount = data.weight_locked_amount(false);
+        data.cliff_locked = 0;
+    }
+    total_locked
+}
+
+/// Build investor stream data from snapshot entries
+/// 
+/// Snapshot entries carry no lock schedule, so the locked amount doubles as
+/// the deposited amount.
+fn snapshot_investor_data(entries: &[SnapshotEntry], quote_mint: &Pubkey) -> Vec<InvestorStreamData> {
+    entries
+        .iter()
+        .map(|entry| InvestorStreamData {
+            investor: entry.investor,
+            stream_account: entry.stream_account,
+            locked_amount: entry.locked_amount,
+            total_deposited: entry.locked_amount,
+            investor_ata: get_associated_token_address(&entry.investor, quote_mint),
+            start_time: 0,
+            end_time: 0,
+            cliff_locked: entry.cliff_locked,
+            lock_duration_seconds: entry.lock_duration_seconds,
+        })
+        .collect()
+}
+
+/// Process the next page of investors of a registry-driven distribution
+/// 
+/// The day's cursor selects the registry page and the offset within it;
+/// the investor accounts must follow the registry in order and stay within
+/// that registry page. Payouts are then made as in `process_investor_page`.
+/// Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing the page and registry accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn process_investor_page_from_registry<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessInvestorPageFromRegistry<'info>>,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let accounts = ctx.accounts;
+    let bumps = ctx.bumps;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    // The day must have been started from this registry, which must not have changed since
+    let daily_state = &accounts.page.daily_distribution_state;
+    require!(daily_state.registry_driven, FeeRouterError::RegistryPageMismatch);
+    require!(
+        daily_state.total_investors == accounts.investor_registry.investor_count,
+        FeeRouterError::RegistryPageMismatch
+    );
+
+    let (page_index, offset) = InvestorRegistryState::position_of(daily_state.current_cursor);
+    require!(
+        accounts.registry_page.page_index == page_index,
+        FeeRouterError::InvalidPageIndex
+    );
+
+    let investors_in_page = remaining_accounts.len() / ACCOUNTS_PER_INVESTOR;
+    let entries = accounts.registry_page.entries
+        .get(offset..offset.saturating_add(investors_in_page))
+        .ok_or(FeeRouterError::RegistryPageMismatch)?;
+    for (entry, investor_accounts) in entries.iter().zip(remaining_accounts.chunks(ACCOUNTS_PER_INVESTOR)) {
+        require!(
+            investor_accounts[0].key() == entry.stream_account && investor_accounts[1].key() == entry.investor,
+            FeeRouterError::RegistryPageMismatch
+        );
+    }
+
+    // The registry page already pins this page to the cursor
+    let expected_page_index = accounts.page.daily_distribution_state.pages_processed;
+
+    msg!("Processing registry page {} from offset {}", page_index, offset);
+    pay_investor_page(
+        Context::new(
+            program_id,
+            &mut accounts.page,
+            remaining_accounts,
+            bumps.page,
+        ),
+        expected_page_index,
+        None,
+    )
+}
+
+/// Change the page size for the remaining pages of a running distribution
+/// 
+/// Lets the operator throttle pages during network congestion without
+/// restarting the day. Only the policy authority can call this.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `new_size` - Maximum investors per page (1 to `MAX_INVESTORS_PER_PAGE`)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn adjust_effective_page_size(ctx: Context<AdjustEffectivePageSize>, new_size: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let daily_state = &mut ctx.accounts.daily_distribution_state;
+    let old_size = daily_state.effective_page_size;
+    daily_state.set_effective_page_size(new_size)?;
+
+    emit!(EffectivePageSizeAdjusted {
+        distribution_day: daily_state.distribution_day,
+        old_size,
+        new_size,
+        expected_page_count: daily_state.expected_page_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Page size adjusted: {} -> {} investors", old_size, new_size);
+    Ok(())
+}
+
+/// Claim fees and pay them to every snapshot investor in one instruction
+/// 
+/// Only available when the policy uses `FeeDistributionMode::Instant`, so small
+/// protocols can skip the daily crank. The freshly claimed amount (less the
+/// treasury reserve) is split between the snapshot investors and the creator
+/// in a single pass, against today's snapshot only. Pauses, the sunset and
+/// the blacklist apply as on the paged path.
+/// 
+/// The day's first instant distribution creates its daily distribution
+/// state; later ones on the same day add to it, so the daily cap holds across
+/// claims. Each one is recorded in the global state. Dust and any protocol
+/// cut stay in the treasury.
+/// 
+/// Remaining accounts are passed per snapshot entry as
+/// `[investor_wallet, investor_ata]`, limited to `INSTANT_DISTRIBUTION_MAX_INVESTORS`
+/// investors so everything fits in one transaction.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing the `ClaimFees` accounts and the payout accounts
+/// * `distribution_day` - Day of the investor snapshot to distribute against
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn claim_and_distribute_instant<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimAndDistributeInstant<'info>>,
+    distribution_day: i64,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let accounts = ctx.accounts;
+    let bumps = ctx.bumps;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    require!(
+        accounts.claim.policy_state.fee_distribution_mode == FeeDistributionMode::Instant,
+        FeeRouterError::InstantDistributionDisabled
+    );
+
+    // Only today's snapshot can be paid against
+    let clock = Clock::get()?;
+    require!(
+        distribution_day == DailyDistributionState::get_day_start(clock.unix_timestamp),
+        FeeRouterError::InvalidDistributionDay
+    );
+    if accounts.claim.policy_state.is_sunsetted(clock.unix_timestamp) {
+        return Err(FeeRouterError::PolicySunsetted
+            .with_context(format!("sunset: {}", accounts.claim.policy_state.sunset_timestamp))
+            .into());
+    }
+    require_blacklist_if_enabled(&accounts.claim.policy_state, accounts.investor_blacklist.is_some())?;
+
+    let investor_count = accounts.investor_snapshot.investor_count;
+    require!(
+        investor_count <= INSTANT_DISTRIBUTION_MAX_INVESTORS,
+        FeeRouterError::InstantDistributionTooManyInvestors
+    );
+    require!(
+        remaining_accounts.len() == investor_count as usize * INSTANT_ACCOUNTS_PER_INVESTOR,
+        FeeRouterError::PaginationError
+    );
+
+    // Step 1: Claim into the treasury and measure what arrived
+    let balance_before_claim = accounts.claim.treasury_ata.amount;
+    claiming_instructions::claim_fees(Context::new(
+        program_id,
+        &mut accounts.claim,
+        &[],
+        bumps.claim,
+    ))?;
+    accounts.claim.exit(program_id)?;
+    accounts.claim.treasury_ata.reload()?;
+
+    let claimed_amount = accounts.claim.treasury_ata.amount.saturating_sub(balance_before_claim);
+    let distributable_amount = accounts.claim.treasury_state.calculate_distributable_amount(claimed_amount);
+
+    // Step 2: Load the day's state, creating it on the day's first instant distribution
+    let quote_mint_key = accounts.claim.quote_mint.key();
+    let daily_account = accounts.daily_distribution_state.to_account_info();
+    let mut daily_state = if daily_account.data_is_empty() {
+        let daily_state = new_instant_day_state(
+            &accounts.claim.policy_state,
+            &accounts.investor_snapshot,
+            &accounts.claim.treasury_state,
+            accounts.claim.treasury_ata.key(),
+            clock.unix_timestamp,
+            clock.slot,
+        );
+        create_pda_account(
+            &daily_account,
+            &accounts.authority.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            program_id,
+            &[
+                b"daily_distribution",
+                distribution_day.to_string().as_bytes(),
+                quote_mint_key.as_ref(),
+                &[bumps.daily_distribution_state],
+            ],
+            8 + DailyDistributionState::space_for(investor_count),
+            &daily_state,
+        )?;
+        daily_state
+    } else {
+        require_keys_eq!(*daily_account.owner, *program_id, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+        DailyDistributionState::try_deserialize(&mut daily_account.data.borrow().as_ref())?
+    };
+
+    // Step 3: Calculate investor payouts from the snapshot
+    let policy_state = &accounts.claim.policy_state;
+    let mut investor_data = snapshot_investor_data(&accounts.investor_snapshot.entries, &quote_mint_key);
+    let total_locked = investor_data
+        .iter()
+        .fold(0u64, |acc, data| acc.saturating_add(data.locked_amount));
+    let total_locked = apply_cliff_weighting(&mut investor_data, total_locked, policy_state.include_cliff_in_weight);
+
+    // Blacklisted investors keep their weight so their share stays in the treasury
+    skip_blacklisted_investors(
+        &mut investor_data,
+        accounts.investor_blacklist.as_deref().map(|blacklist| &**blacklist),
+        &mut daily_state,
+    );
+
+    let distribution_calc = streamflow::calculations::calculate_distribution(
+        distributable_amount,
+        &investor_data,
+        total_locked,
+        daily_state.initial_total_deposit,
+        policy_state.fee_share_ramp(clock.unix_timestamp),
+        daily_state.min_payout_threshold,
+        policy_state.weighting_options(),
+    )?;
+
+    // The cap is shared by every instant distribution of the day
+    let final_calc = streamflow::calculations::apply_daily_cap(
+        distribution_calc,
+        daily_state.daily_cap_remaining,
+        daily_state.min_payout_threshold,
+        policy_state.cap_application_mode,
+    );
+    streamflow::calculations::validate_distribution(&final_calc, distributable_amount)?;
+
+    // Step 4: Validate every payout before moving any tokens
+    let mut planned_payouts: Vec<(Pubkey, u64, &[AccountInfo<'info>])> = Vec::new();
+    let mut missing_atas: Vec<Pubkey> = Vec::new();
+    for payout in final_calc.investor_payouts.iter().filter(|payout| payout.payout_amount > 0 && payout.meets_minimum) {
+        let investor_accounts = remaining_accounts
+            .chunks(INSTANT_ACCOUNTS_PER_INVESTOR)
+            .find(|investor_accounts| investor_accounts[0].key() == payout.investor)
+            .filter(|investor_accounts| investor_accounts[1].key() == payout.investor_ata);
+        match investor_accounts {
+            Some(investor_accounts) => planned_payouts.push((payout.investor, payout.payout_amount, investor_accounts)),
+            None => missing_atas.push(payout.investor_ata),
+        }
+    }
+    let total_investor_payouts = planned_payouts
+        .iter()
+        .fold(0u64, |acc, (_, amount, _)| acc.saturating_add(*amount));
+    streamflow::calculations::validate_page_payable(
+        &missing_atas,
+        planned_payouts.len() + missing_atas.len(),
+        total_investor_payouts,
+        accounts.claim.treasury_ata.amount,
+    )?;
+
+    // Step 5: Pay investors
+    let treasury_seeds = &[
+        b"treasury_authority",
+        quote_mint_key.as_ref(),
+        &[bumps.treasury_authority],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let investors_paid = planned_payouts.len() as u32;
+    for (investor, payout_amount, investor_accounts) in planned_payouts {
+        associated_token::create_idempotent(CpiContext::new(
+            accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: accounts.authority.to_account_info(),
+                associated_token: investor_accounts[1].clone(),
+                authority: investor_accounts[0].clone(),
+                mint: accounts.claim.quote_mint.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                token_program: accounts.claim.token_program.to_account_info(),
+            },
+        ))?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.claim.token_program.to_account_info(),
+                token::Transfer {
+                    from: accounts.claim.treasury_ata.to_account_info(),
+                    to: investor_accounts[1].clone(),
+                    authority: accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payout_amount,
+        )?;
+
+        msg!("Paid {} tokens to investor {}", payout_amount, investor);
+    }
+
+    // Step 6: Pay the explicit creator share
+    let creator_amount = std::cmp::min(
+        accounts.claim.policy_state.calculate_creator_amount(distributable_amount),
+        distributable_amount.saturating_sub(total_investor_payouts),
+    );
+    if creator_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.claim.token_program.to_account_info(),
+                token::Transfer {
+                    from: accounts.claim.treasury_ata.to_account_info(),
+                    to: accounts.creator_ata.to_account_info(),
+                    authority: accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_amount,
+        )?;
+    }
+
+    // Step 7: Record the distribution on the day and globally
+    daily_state.total_amount_to_distribute = daily_state.total_amount_to_distribute.saturating_add(distributable_amount);
+    // Every instant distribution of the day pays the same snapshot
+    daily_state.update_progress(0, total_investor_payouts, daily_state.total_investors);
+    daily_state.investors_processed = daily_state.investors_processed.max(investors_paid);
+    daily_state.update_daily_cap(total_investor_payouts);
+    daily_state.add_dust(final_calc.dust_amount);
+    daily_state.creator_remainder = daily_state
+        .get_effective_distribution_amount()
+        .saturating_sub(daily_state.amount_distributed);
+    daily_state.mark_complete(clock.unix_timestamp);
+    daily_state.try_serialize(&mut &mut daily_account.try_borrow_mut_data()?[..])?;
+
+    let global_state = &mut accounts.global_distribution_state;
+    global_state.update_after_distribution(
+        clock.unix_timestamp,
+        total_investor_payouts,
+        creator_amount,
+        0, // Protocol cut stays in treasury
+    );
+
+    emit!(InstantDistributionCompleted {
+        distribution_day,
+        quote_mint: quote_mint_key,
+        claimed_amount,
+        investors_paid,
+        total_investor_payouts,
+        creator_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Instantly distributed {} claimed tokens: {} to {} investors, {} to creator",
+         claimed_amount, total_investor_payouts, investors_paid, creator_amount);
+    Ok(())
+}
+
+/// Daily distribution state for the day's first instant distribution
+/// 
+/// Amounts start at zero and are added by every instant distribution of the
+/// day; the daily cap starts full.
+fn new_instant_day_state(
+    policy_state: &PolicyState,
+    investor_snapshot: &InvestorSnapshot,
+    treasury_state: &TreasuryState,
+    treasury_ata: Pubkey,
+    timestamp: i64,
+    slot: u64,
+) -> DailyDistributionState {
+    let daily_cap = policy_state.daily_cap_for_distribution();
+    let total_investors = investor_snapshot.investor_count;
+    DailyDistributionState {
+        distribution_day: investor_snapshot.distribution_day,
+        quote_mint: policy_state.quote_mint,
+        treasury_ata,
+        total_amount_to_distribute: 0,
+        amount_distributed: 0,
+        current_cursor: 0,
+        total_investors,
+        investors_processed: 0,
+        is_complete: false,
+        started_at: timestamp,
+        completed_at: 0,
+        dust_carried_over: 0,
+        daily_cap_total: daily_cap,
+        daily_cap_remaining: daily_cap,
+        min_payout_threshold: policy_state.min_payout_lamports,
+        initial_total_deposit: policy_state.y0_total_allocation,
+        investor_fee_share_bps: policy_state.fee_share_ramp(timestamp),
+        last_page_hash: [0; 32],
+        pages_processed: 0,
+        streams_below_min_duration: 0,
+        ata_creation_fees_lamports: 0,
+        processed_bitmap: vec![0; DailyDistributionState::bitmap_len(total_investors)],
+        treasury_balance_at_start: 0,
+        lock_snapshot_timestamp: investor_snapshot.taken_at,
+        last_page_completed_at: 0,
+        total_fees_claimed_at_distribution_start: treasury_state.total_fees_claimed,
+        max_page_size_used: 0,
+        min_page_size_used: 0,
+        skipped_blacklisted: 0,
+        execution_slot: slot,
+        current_page_slot: 0,
+        expected_page_count: 0,
+        page_cursor_history: [0; 8],
+        page_hash_history: [[0; 32]; 4],
+        effective_page_size: policy_state.initial_page_size(),
+        failed_payouts_count: 0,
+        dust_rolled_over: 0,
+        registry_driven: false,
+        creator_remainder: 0,
+    }
+}
+
+/// Complete the daily distribution
+/// 
+/// This marks the daily distribution as complete, pays the explicit creator
+/// share, and updates the global state. Can only be called after all investors 
+/// have been processed.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn complete_daily_distribution(ctx: Context<CompleteDailyDistribution>) -> Result<()> {
+    msg!("Completing daily distribution for day: {}", 
+         ctx.accounts.daily_distribution_state.distribution_day);
+
+    let clock = Clock::get()?;
+
+    let treasury_authority_bump = ctx.bumps.treasury_authority;
+    let quote_mint_key = ctx.accounts.quote_mint.key();
+    let treasury_seeds = &[
+        b"treasury_authority",
+        quote_mint_key.as_ref(),
+        &[treasury_authority_bump],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let creator_transfer = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Transfer {
+            from: ctx.accounts.treasury_ata.to_account_info(),
+            to: ctx.accounts.creator_ata.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    let protocol_transfer = ctx.accounts.protocol_fee_ata.as_ref().map(|protocol_fee_ata| {
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.treasury_ata.to_account_info(),
+                to: protocol_fee_ata.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+    });
+    let protocol_share_transfer = ctx.accounts.protocol_share_ata.as_ref().map(|protocol_share_ata| {
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.treasury_ata.to_account_info(),
+                to: protocol_share_ata.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+    });
+    let creator = ctx.accounts.creator_ata.owner;
+
+    let accounts = &mut *ctx.accounts;
+    let settlement = settle_daily_distribution(
+        &mut accounts.daily_distribution_state,
+        &mut accounts.global_distribution_state,
+        &mut accounts.treasury_state,
+        &mut accounts.policy_state,
+        SettlementTransfers {
+            creator: creator_transfer,
+            protocol_dust: protocol_transfer,
+            protocol_share: protocol_share_transfer,
+        },
+        creator,
+        clock.unix_timestamp,
+    )?;
+    emit!(accounts.daily_distribution_state.to_summary());
+
+    accounts.treasury_ata.reload()?;
+    emit!(TreasuryBalancePostDistribution {
+        final_balance: accounts.treasury_ata.amount,
+        distributed_to_investors: accounts.daily_distribution_state.amount_distributed,
+        distributed_to_creator: settlement.creator_amount,
+        dust_remaining: settlement.dust_remaining,
+        timestamp: clock.unix_timestamp,
     });
 
     msg!("✅ Daily distribution completed successfully with creator payout");
     Ok(())
 }
+
+/// Force completion of a stuck daily distribution
+/// 
+/// Anyone can force-complete a distribution once it has been open for longer
+/// than `FORCE_COMPLETE_TIMEOUT_SECONDS`, even if not every investor page was
+/// processed. The creator share is still paid and global state updated.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn force_complete_distribution(ctx: Context<ForceCompleteDailyDistribution>) -> Result<()> {
+    msg!("Force completing daily distribution for day: {}", 
+         ctx.accounts.daily_distribution_state.distribution_day);
+
+    let clock = Clock::get()?;
+
+    // Only allowed once the distribution has been open past the timeout
+    require!(
+        clock.unix_timestamp > ctx.accounts.daily_distribution_state.started_at + FORCE_COMPLETE_TIMEOUT_SECONDS,
+        FeeRouterError::ForceCompleteNotYetAllowed
+    );
+
+    let treasury_authority_bump = ctx.bumps.treasury_authority;
+    let quote_mint_key = ctx.accounts.quote_mint.key();
+    let treasury_seeds = &[
+        b"treasury_authority",
+        quote_mint_key.as_ref(),
+        &[treasury_authority_bump],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let creator_transfer = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Transfer {
+            from: ctx.accounts.treasury_ata.to_account_info(),
+            to: ctx.accounts.creator_ata.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    let protocol_transfer = ctx.accounts.protocol_fee_ata.as_ref().map(|protocol_fee_ata| {
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.treasury_ata.to_account_info(),
+                to: protocol_fee_ata.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+    });
+    let protocol_share_transfer = ctx.accounts.protocol_share_ata.as_ref().map(|protocol_share_ata| {
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.treasury_ata.to_account_info(),
+                to: protocol_share_ata.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+    });
+    let creator = ctx.accounts.creator_ata.owner;
+
+    let accounts = &mut *ctx.accounts;
+    settle_daily_distribution(
+        &mut accounts.daily_distribution_state,
+        &mut accounts.global_distribution_state,
+        &mut accounts.treasury_state,
+        &mut accounts.policy_state,
+        SettlementTransfers {
+            creator: creator_transfer,
+            protocol_dust: protocol_transfer,
+            protocol_share: protocol_share_transfer,
+        },
+        creator,
+        clock.unix_timestamp,
+    )?;
+
+    emit!(DistributionForceCompleted {
+        distribution_day: accounts.daily_distribution_state.distribution_day,
+        investors_processed: accounts.daily_distribution_state.investors_processed,
+        amount_distributed: accounts.daily_distribution_state.amount_distributed,
+        reason: "timeout".to_string(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Daily distribution force completed after timeout");
+    Ok(())
+}
+
+/// Audit a completed daily distribution
+/// 
+/// Recomputes the investor fee quote from the policy recorded at the start
+/// of the day and checks the day's accounting against it. Does not fail on
+/// a discrepancy - the result is reported in `DistributionIntegrityReport`.
+/// Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn verify_distribution_integrity(ctx: Context<VerifyDistributionIntegrity>) -> Result<()> {
+    let daily_state = &ctx.accounts.daily_distribution_state;
+    msg!("Verifying distribution integrity for day: {}", daily_state.distribution_day);
+
+    let clock = Clock::get()?;
+
+    // Step 1: Expected investor fee quote from the recorded policy
+    let investor_fee_quote = ctx.accounts.policy_snapshot.expected_investor_fee_quote(
+        daily_state.get_effective_distribution_amount(),
+        daily_state.investor_fee_share_bps,
+    );
+
+    // Step 2: Run the accounting checks
+    let (check_results, discrepancy_amount) = daily_state.integrity_checks(
+        investor_fee_quote,
+        ctx.accounts.global_distribution_state.last_distribution_timestamp,
+    );
+    let is_valid = check_results.iter().all(|passed| *passed);
+
+    emit!(DistributionIntegrityReport {
+        is_valid,
+        discrepancy_amount,
+        check_results,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if is_valid {
+        msg!("✅ Distribution integrity verified");
+    } else {
+        msg!("⚠️ Distribution integrity check failed: {:?}, discrepancy {}", check_results, discrepancy_amount);
+    }
+    Ok(())
+}
+
+/// Audit a completed daily distribution from its payout summaries
+/// 
+/// Checks the day against the `PolicySnapshot` recorded when it started and
+/// against the day's `InvestorPayoutSummary` accounts, which must all be
+/// passed as remaining accounts. Does not fail on a violation - the result
+/// is reported in `AuditResult`. Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `distribution_day` - The day timestamp (start of day)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn audit_completed_distribution(ctx: Context<AuditCompletedDistribution>, distribution_day: i64) -> Result<()> {
+    msg!("Auditing completed distribution for day: {}", distribution_day);
+
+    let clock = Clock::get()?;
+
+    // Step 1: Total the day's payout summaries
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        FeeRouterError::PayoutSummariesRequired
+    );
+    let payouts = read_payout_summaries(ctx.remaining_accounts, distribution_day, ctx.program_id)?;
+    let payout_summary_total = payouts.iter().fold(0u64, |acc, amount| acc.saturating_add(*amount));
+
+    // Step 2: Check the invariants
+    let violations = ctx.accounts.daily_distribution_state.audit_violations(
+        &ctx.accounts.policy_snapshot,
+        payout_summary_total,
+        ctx.accounts.global_distribution_state.last_distribution_timestamp,
+    );
+    let passed = violations.is_empty();
+    for violation in violations.iter() {
+        msg!("⚠️ Audit violation: {}", violation);
+    }
+
+    emit!(AuditResult {
+        passed,
+        violations,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if passed {
+        msg!("✅ Distribution audit passed");
+    }
+    Ok(())
+}
+
+/// Report per-investor payout statistics for a completed daily distribution
+/// 
+/// Reads the day's `InvestorPayoutSummary` accounts, passed as remaining
+/// accounts, and emits their statistics in `DistributionReport`. Each
+/// investor is counted once. Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `distribution_day` - The day timestamp (start of day)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn generate_distribution_report(ctx: Context<GenerateDistributionReport>, distribution_day: i64) -> Result<()> {
+    msg!("Generating distribution report for day: {}", distribution_day);
+
+    let clock = Clock::get()?;
+
+    // Step 1: Collect each investor's payout for the day
+    let mut payouts = read_payout_summaries(ctx.remaining_accounts, distribution_day, ctx.program_id)?;
+
+    // Step 2: Summarize the payouts
+    let statistics = streamflow::calculations::calculate_payout_statistics(&mut payouts);
+
+    emit!(DistributionReport {
+        distribution_day,
+        investor_count: statistics.investor_count,
+        total_distributed: statistics.total_distributed,
+        median_payout: statistics.median_payout,
+        max_payout: statistics.max_payout,
+        min_payout: statistics.min_payout,
+        gini_coefficient_bps: statistics.gini_coefficient_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if statistics.total_distributed != ctx.accounts.daily_distribution_state.amount_distributed {
+        msg!("⚠️ Reported {} of {} distributed - not every payout summary was passed",
+             statistics.total_distributed, ctx.accounts.daily_distribution_state.amount_distributed);
+    }
+    msg!("✅ Distribution report: {} investors, median {}, Gini {} bps",
+         statistics.investor_count, statistics.median_payout, statistics.gini_coefficient_bps);
+    Ok(())
+}
+
+/// Report how much of a Streamflow stream is still locked
+/// 
+/// Read-only view for frontends: the stream account is passed as
+/// `remaining_accounts[0]` and the result is emitted in
+/// `StreamLockedAmountQueried`, so a simulated transaction is enough.
+/// Can be called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn get_stream_locked_amount(ctx: Context<GetStreamLockedAmount>) -> Result<()> {
+    let stream_account = ctx.remaining_accounts
+        .first()
+        .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+    require!(
+        stream_account.owner == &streamflow::STREAMFLOW_PROGRAM_ID,
+        anchor_lang::error::ErrorCode::ConstraintOwner
+    );
+    let stream = streamflow::cpi::read_stream_data(stream_account)?;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp as u64;
+    let locked_amount = stream.locked_amount(now);
+
+    emit!(StreamLockedAmountQueried {
+        stream_account: stream_account.key(),
+        investor: stream.recipient,
+        locked_amount,
+        total_deposited: stream.deposited_amount,
+        lock_fraction_pct: stream.locked_fraction_pct(now),
+        is_active: stream.is_active(now),
+        estimated_unlock_date: stream.end_time as i64,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Stream {}: {} of {} locked until {}",
+         stream_account.key(), locked_amount, stream.deposited_amount, stream.end_time);
+    Ok(())
+}
+
+/// Treasury transfers available when settling a daily distribution
+struct SettlementTransfers<'a, 'b, 'c, 'info> {
+    /// Transfer to the creator ATA
+    creator: CpiContext<'a, 'b, 'c, 'info, token::Transfer<'info>>,
+    /// Transfer to the global protocol fee wallet (dust routing)
+    protocol_dust: Option<CpiContext<'a, 'b, 'c, 'info, token::Transfer<'info>>>,
+    /// Transfer to the policy's protocol fee wallet (protocol cut)
+    protocol_share: Option<CpiContext<'a, 'b, 'c, 'info, token::Transfer<'info>>>,
+}
+
+/// Amounts paid out when settling a daily distribution
+struct SettlementAmounts {
+    /// Amount paid to the creator, including any dust routed to them
+    creator_amount: u64,
+    /// Rounding dust left in the treasury
+    dust_remaining: u64,
+}
+
+/// Pay the protocol and creator shares, mark the day complete and update global state
+/// 
+/// Shared by `complete_daily_distribution`, `force_complete_distribution` and
+/// the page auto-completion. Fails unless the treasury still holds what the
+/// investors were not paid. When the policy auto-extends its sunset, the
+/// sunset is pushed back by one distribution interval. Returns what the
+/// creator was paid and the dust left in the treasury.
+fn settle_daily_distribution(
+    daily_state: &mut DailyDistributionState,
+    global_state: &mut GlobalDistributionState,
+    treasury_state: &mut TreasuryState,
+    policy_state: &mut PolicyState,
+    transfers: SettlementTransfers,
+    creator: Pubkey,
+    timestamp: i64,
+) -> Result<SettlementAmounts> {
+    // Investor transfers must have left the undistributed amount in the treasury
+    let treasury_balance = token::accessor::amount(&transfers.creator.accounts.from)?;
+    daily_state.verify_treasury_balance(treasury_balance, treasury_state.total_fees_claimed)?;
+
+    // Step 1: Take the protocol cut before the creator is paid
+    // protocol_fee_amount = floor(claimed_quote * protocol_fee_bps / 10000)
+    let total_available = daily_state.get_effective_distribution_amount();
+    let total_investor_payouts = daily_state.amount_distributed;
+    let dust_amount = daily_state.dust_carried_over;
+
+    let protocol_fee_amount = std::cmp::min(
+        policy_state.calculate_protocol_fee_amount(total_available),
+        total_available.saturating_sub(total_investor_payouts),
+    );
+    if protocol_fee_amount > 0 {
+        let protocol_share_transfer = transfers.protocol_share.ok_or(FeeRouterError::ProtocolFeeWalletNotSet)?;
+        token::transfer(protocol_share_transfer, protocol_fee_amount)?;
+
+        emit!(ProtocolFeeCollected {
+            amount: protocol_fee_amount,
+            wallet: policy_state.protocol_fee_wallet,
+            distribution_day: daily_state.distribution_day,
+            timestamp,
+        });
+
+        msg!("✅ Transferred {} tokens to protocol fee wallet", protocol_fee_amount);
+    }
+
+    // Step 2: Calculate explicit creator share
+    // creator_amount = floor(claimed_quote * creator_fee_share_bps / 10000)
+    // Anything beyond investor payouts, the protocol cut and the creator share stays in treasury as reserve
+    let mut creator_amount = std::cmp::min(
+        policy_state.calculate_creator_amount(total_available),
+        total_available
+            .saturating_sub(total_investor_payouts)
+            .saturating_sub(protocol_fee_amount),
+    );
+
+    // Route rounding dust according to the policy
+    let dust_to_route = std::cmp::min(
+        dust_amount,
+        total_available
+            .saturating_sub(total_investor_payouts)
+            .saturating_sub(protocol_fee_amount)
+            .saturating_sub(creator_amount),
+    );
+    let mut protocol_amount = protocol_fee_amount;
+    let mut dust_remaining = dust_amount;
+    match policy_state.dust_destination {
+        DustDestination::CarryToNextDay => {
+            // Dust stays in treasury and is picked up by the next distribution
+        }
+        DustDestination::ToCreator => {
+            creator_amount = creator_amount.saturating_add(dust_to_route);
+            dust_remaining = dust_amount.saturating_sub(dust_to_route);
+        }
+        DustDestination::ToProtocol if global_state.protocol_fee_wallet.is_none() => {
+            // Nowhere to send it yet - keep it in treasury like CarryToNextDay
+            msg!("No protocol fee wallet set, carrying {} dust forward", dust_amount);
+        }
+        DustDestination::ToProtocol => {
+            if dust_to_route > 0 {
+                let protocol_transfer = transfers.protocol_dust.ok_or(FeeRouterError::ProtocolFeeWalletNotSet)?;
+                token::transfer(protocol_transfer, dust_to_route)?;
+                protocol_amount = protocol_amount.saturating_add(dust_to_route);
+                dust_remaining = dust_amount.saturating_sub(dust_to_route);
+                msg!("✅ Transferred {} dust to protocol fee wallet", dust_to_route);
+            }
+        }
+    }
+
+    let reserve_amount = total_available
+        .saturating_sub(total_investor_payouts)
+        .saturating_sub(creator_amount)
+        .saturating_sub(protocol_amount);
+    
+    msg!("Creator share calculation: {} total, {} to investors, {} to protocol, {} to creator, {} kept in reserve", 
+         total_available, total_investor_payouts, protocol_amount, creator_amount, reserve_amount);
+    daily_state.creator_remainder = creator_amount
+        .saturating_add(protocol_amount)
+        .saturating_add(reserve_amount);
+
+    // Step 3: Transfer creator share
+    if creator_amount > 0 {
+        token::transfer(transfers.creator, creator_amount)?;
+        
+        msg!("✅ Transferred {} tokens to creator", creator_amount);
+
+        // Emit creator payout event
+        emit!(CreatorPayoutCompleted {
+            distribution_day: daily_state.distribution_day,
+            quote_mint: daily_state.quote_mint,
+            creator,
+            creator_remainder: creator_amount,
+            total_distributed_amount: total_available,
+            total_investor_payouts,
+            dust_amount,
+            timestamp,
+        });
+    } else {
+        msg!("No creator share to distribute");
+    }
+
+    // Step 4: Mark daily distribution as complete
+    daily_state.mark_complete(timestamp);
+    record_lifetime_dust(treasury_state, daily_state);
+
+    // Step 5: Update global distribution state
+    global_state.record_dust_rollover(dust_remaining);
+    let total_paid_out = total_investor_payouts
+        .saturating_add(creator_amount)
+        .saturating_add(protocol_amount);
+    global_state.update_rolling_average(
+        daily_state.distribution_ratio_bps(total_paid_out),
+        policy_state.avg_alpha_bps,
+    );
+    global_state.update_avg_seconds_per_page(
+        daily_state.seconds_per_page(),
+        daily_state.pages_processed,
+        policy_state.avg_alpha_bps,
+    );
+    let (health_score, health_reason_flags) = GlobalDistributionState::calculate_health_score(
+        daily_state,
+        global_state.last_distribution_timestamp,
+        total_paid_out,
+    );
+    let old_health_score = std::mem::replace(&mut global_state.health_score, health_score);
+    global_state.update_after_distribution(
+        timestamp, // Use current timestamp instead of day
+        total_investor_payouts,
+        creator_amount,
+        protocol_amount, // Reserve stays in treasury
+    );
+
+    // Continuous distributions keep the policy alive
+    if let Some(new_sunset_timestamp) = policy_state.auto_extended_sunset() {
+        policy_state.sunset_timestamp = new_sunset_timestamp;
+        global_state.auto_extension_count = global_state.auto_extension_count.saturating_add(1);
+        emit!(SunsetAutoExtended {
+            new_sunset_timestamp,
+            extensions_count: global_state.auto_extension_count,
+            timestamp,
+        });
+        msg!("Policy sunset auto-extended to {}", new_sunset_timestamp);
+    }
+
+    // Step 6: Emit completion events
+    emit!(DailyDistributionCompleted {
+        distribution_day: daily_state.distribution_day,
+        quote_mint: daily_state.quote_mint,
+        total_amount_distributed: total_paid_out,
+        total_investors_processed: daily_state.investors_processed,
+        max_page_size_used: daily_state.max_page_size_used,
+        min_page_size_used: daily_state.min_page_size_used,
+        actual_page_count: daily_state.pages_processed,
+        timestamp,
+    });
+
+    emit!(GlobalDistributionUpdated {
+        quote_mint: global_state.quote_mint,
+        last_distribution_day: global_state.last_distribution_timestamp,
+        total_distributions: global_state.total_distributions,
+        total_amount_distributed: global_state.total_amount_distributed,
+        total_investor_payouts: global_state.total_investor_payouts,
+        total_creator_payouts: global_state.total_creator_payouts,
+        position_age_days: global_state.position_age_days(timestamp),
+        timestamp,
+    });
+
+    emit!(DistributionRateUpdated {
+        new_average_bps: global_state.rolling_avg_distribution_bps_of_treasury,
+        distribution_day: daily_state.distribution_day,
+    });
+
+    emit!(HealthScoreUpdated {
+        old_score: old_health_score,
+        new_score: health_score,
+        reason_flags: health_reason_flags,
+        timestamp,
+    });
+
+    Ok(SettlementAmounts {
+        creator_amount,
+        dust_remaining,
+    })
+}
+
+/// Add the day's resolved dust to the treasury's lifetime total
+/// 
+/// Counts the day's new dust whether it was routed out or carried forward.
+/// Dust rolled over from earlier days was counted on the day it arose.
+fn record_lifetime_dust(treasury_state: &mut TreasuryState, daily_state: &DailyDistributionState) {
+    let new_total = treasury_state.record_lifetime_dust(daily_state.new_dust());
+    emit!(LifetimeDustUpdated {
+        new_total,
+        distribution_day: daily_state.distribution_day,
+    });
+}
+
+/// Read an investor aggregate cache account, if it has been created
+fn load_investor_cache(cache_account: &AccountInfo, program_id: &Pubkey) -> Option<InvestorAggregateCache> {
+    if cache_account.data_is_empty() || cache_account.owner != program_id {
+        return None;
+    }
+    InvestorAggregateCache::try_deserialize(&mut cache_account.data.borrow().as_ref()).ok()
+}
+
+/// Write an investor aggregate cache account, creating the PDA on first use
+fn write_investor_cache<'info>(
+    cache_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    cache: InvestorAggregateCache,
+) -> Result<()> {
+    if cache_account.data_is_empty() {
+        let day_string = cache.distribution_day.to_string();
+        let (_, bump) = InvestorAggregateCache::derive_pda(cache.distribution_day, &cache.quote_mint, &cache.investor, program_id);
+        let cache_seeds: &[&[u8]] = &[
+            b"investor_cache",
+            day_string.as_bytes(),
+            cache.quote_mint.as_ref(),
+            cache.investor.as_ref(),
+            &[bump],
+        ];
+        let space = 8 + InvestorAggregateCache::INIT_SPACE;
+
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: cache_account.clone(),
+                },
+                &[cache_seeds],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            program_id,
+        )?;
+    }
+
+    let mut data = cache_account.try_borrow_mut_data()?;
+    cache.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Emit the stream errors recorded during a completed distribution day
+/// 
+/// Emits one `StreamErrorReported` per recorded error. Read-only; can be
+/// called by anyone.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn get_stream_errors(ctx: Context<GetStreamErrors>) -> Result<()> {
+    let stream_error_log = &ctx.accounts.stream_error_log;
+
+    for (index, entry) in stream_error_log.entries.iter().enumerate() {
+        emit!(StreamErrorReported {
+            distribution_day: stream_error_log.distribution_day,
+            quote_mint: stream_error_log.quote_mint,
+            index: index as u32,
+            stream_account: entry.stream_account,
+            error_type: entry.error_type,
+        });
+    }
+
+    msg!("📋 {} stream errors recorded for day {} ({} dropped)",
+         stream_error_log.entries.len(), stream_error_log.distribution_day, stream_error_log.dropped_errors());
+    Ok(())
+}
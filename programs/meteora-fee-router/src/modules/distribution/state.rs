@@ -1,4 +1,43 @@
 use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+use crate::integrations::streamflow::calculations::{InvestorPayout, WeightingOptions};
+use crate::integrations::streamflow::accounts::InvestorStreamData;
+use crate::integrations::streamflow::cpi::StreamErrorType;
+use crate::modules::distribution::events::DistributionSummaryView;
+use crate::shared::crypto::{compute_hash, HashAlgorithm};
+use crate::shared::constants::{ALLOCATION_Y0_BUFFER_PERCENT, BPS_DENOMINATOR, DEFAULT_MIN_INVESTORS_TO_DISTRIBUTE, DISTRIBUTION_INTERVAL_SECONDS, HEALTH_FLAG_CAP_BINDING, HEALTH_FLAG_FAILED_PAYOUTS, HEALTH_FLAG_HIGH_DUST, HEALTH_FLAG_MISSED_DAYS, INVESTOR_REGISTRY_PAGE_SIZE, MAX_BLACKLISTED_INVESTORS, MAX_DAILY_CAP_LAMPORTS, MAX_DISTRIBUTION_WINDOW_SECONDS, MAX_INVESTORS_PER_PAGE, MAX_REGISTERED_STREAMS, MAX_SNAPSHOT_INVESTORS, MAX_STREAM_ERROR_LOG_ENTRIES, MIN_CREATOR_BPS, SECONDS_PER_DAY, SECONDS_PER_YEAR, SNAPSHOT_RETENTION_SECONDS};
+
+/// Where rounding dust goes when a daily distribution completes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DustDestination {
+    /// Leave dust in the treasury for the next day's distribution
+    #[default]
+    CarryToNextDay,
+    /// Add dust to the creator payout
+    ToCreator,
+    /// Transfer dust to the protocol fee wallet (carried forward while no wallet is set)
+    ToProtocol,
+}
+
+/// How payouts are reduced when a page exceeds the remaining daily cap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CapApplicationMode {
+    /// Scale every payout down by the same factor
+    #[default]
+    ProportionalScale,
+    /// Reduce the smallest payouts first, leaving larger payouts untouched
+    TrimFromBottom,
+}
+
+/// When claimed fees reach investors
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FeeDistributionMode {
+    /// Pay investors in the same instruction that claims the fees
+    Instant,
+    /// Accumulate fees in the treasury until the daily distribution is cranked
+    #[default]
+    Batched,
+}
 
 /// Policy configuration for fee distribution
 #[account]
@@ -9,6 +48,9 @@ pub struct PolicyState {
     /// Maximum investor share in basis points (0-10000)
     pub investor_fee_share_bps: u64,
     
+    /// Explicit creator share in basis points (investor + creator <= 10000)
+    pub creator_fee_share_bps: u64,
+    
     /// Daily distribution cap in lamports (0 = no cap)
     pub daily_cap_lamports: u64,
     
@@ -21,19 +63,136 @@ pub struct PolicyState {
     /// Authority that can update this policy
     pub policy_authority: Pubkey,
     
+    /// Accept dust-level base fees on claim instead of reverting
+    pub allow_base_fee_grace_period: bool,
+    
+    /// One-time lamport fee paid to the policy authority on position creation (0 = free)
+    pub position_creation_fee_lamports: u64,
+    
+    /// Investor share at the start of the ramp in basis points
+    pub ramp_start_bps: u64,
+    
+    /// Investor share at the end of the ramp in basis points
+    pub ramp_end_bps: u64,
+    
+    /// Duration of the ramp in seconds (0 = ramp disabled)
+    pub ramp_duration_seconds: i64,
+    
+    /// Timestamp the ramp starts from
+    pub ramp_start_timestamp: i64,
+    
+    /// Give long-term lockers extra weight in the distribution
+    pub duration_bonus_enabled: bool,
+    
+    /// Maximum lock duration multiplier in basis points (e.g. 20000 = 2x)
+    pub duration_bonus_max_multiplier_bps: u64,
+    
+    /// Streams ending sooner than this are excluded from distribution (0 = no filter)
+    pub min_remaining_lock_seconds: u64,
+    
+    /// EMA smoothing factor for the rolling distribution rate in basis points
+    pub avg_alpha_bps: u64,
+    
+    /// Where rounding dust goes on completion
+    pub dust_destination: DustDestination,
+    
+    /// Minimum snapshot investors required to start a distribution (0 = `DEFAULT_MIN_INVESTORS_TO_DISTRIBUTE`)
+    pub min_investors_to_distribute: u32,
+    
+    /// Count unreleased cliff amounts towards investor weight
+    pub include_cliff_in_weight: bool,
+    
+    /// No new distributions can start at or after this timestamp (0 = no sunset)
+    pub sunset_timestamp: i64,
+    
+    /// Protocol cut of each distribution in basis points (0 = no cut)
+    pub protocol_fee_bps: u64,
+    
+    /// Wallet receiving the protocol cut
+    pub protocol_fee_wallet: Pubkey,
+    
+    /// Complete the day inline once this many pages are processed (0 = disabled)
+    pub max_pages_before_auto_complete: u32,
+    
+    /// How payouts are reduced to fit the daily cap
+    pub cap_application_mode: CapApplicationMode,
+    
+    /// Claim position fees into the treasury when a distribution starts
+    pub auto_claim_before_distribution: bool,
+    
+    /// Scale each investor's weight by their remaining lock duration
+    pub use_duration_weighting: bool,
+    
+    /// Distribute fees as they are claimed or in the daily crank
+    pub fee_distribution_mode: FeeDistributionMode,
+    
+    /// Cached PDA bump so constraints skip `find_program_address`
+    pub bump: u8,
+    
+    /// Account layout version (0 on policies created before versioning, read as v1)
+    pub version: u8,
+    
+    /// Hash algorithm for page hashes (zeroed on older policies, read as SHA-256)
+    pub page_hash_algorithm: HashAlgorithm,
+    
+    /// Investors per page for new distributions (0 = MAX_INVESTORS_PER_PAGE)
+    pub target_page_size: u32,
+    
+    /// Longest time since the last fee claim a distribution may start after (0 = disabled)
+    pub max_claim_interval_seconds: i64,
+    
+    /// Push the sunset back by one distribution interval on each completed distribution
+    pub auto_extend_sunset: bool,
+    
+    /// Set once the investor blacklist exists; pages must then pass it
+    pub blacklist_enabled: bool,
+    
+    /// SPL governance whose completed proposals must back policy updates (default = direct authority mode)
+    pub governance_authority: Pubkey,
+    
     /// Reserved for future use
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 15],
 }
 
 impl PolicyState {
     pub const INIT_SPACE: usize = 8 +   // discriminator
                                    32 +  // quote_mint
                                    8 +   // investor_fee_share_bps
+                                   8 +   // creator_fee_share_bps
                                    8 +   // daily_cap_lamports
                                    8 +   // min_payout_lamports
                                    8 +   // y0_total_allocation
                                    32 +  // policy_authority
-                                   64;   // reserved
+                                   1 +   // allow_base_fee_grace_period
+                                   8 +   // position_creation_fee_lamports
+                                   8 +   // ramp_start_bps
+                                   8 +   // ramp_end_bps
+                                   8 +   // ramp_duration_seconds
+                                   8 +   // ramp_start_timestamp
+                                   1 +   // duration_bonus_enabled
+                                   8 +   // duration_bonus_max_multiplier_bps
+                                   8 +   // min_remaining_lock_seconds
+                                   8 +   // avg_alpha_bps
+                                   1 +   // dust_destination
+                                   4 +   // min_investors_to_distribute
+                                   1 +   // include_cliff_in_weight
+                                   8 +   // sunset_timestamp
+                                   8 +   // protocol_fee_bps
+                                   32 +  // protocol_fee_wallet
+                                   4 +   // max_pages_before_auto_complete
+                                   1 +   // cap_application_mode
+                                   1 +   // auto_claim_before_distribution
+                                   1 +   // use_duration_weighting
+                                   1 +   // fee_distribution_mode
+                                   1 +   // bump
+                                   1 +   // version
+                                   1 +   // page_hash_algorithm
+                                   4 +   // target_page_size
+                                   8 +   // max_claim_interval_seconds
+                                   1 +   // auto_extend_sunset
+                                   1 +   // blacklist_enabled
+                                   32 +  // governance_authority
+                                   15;   // reserved
 
     /// Derive the PDA for policy state
     pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
@@ -46,18 +205,307 @@ impl PolicyState {
         )
     }
 
+    /// Layout version of this account
+    pub fn format_version(&self) -> u8 {
+        // This layout is v2; the first policies written in it left the byte zeroed
+        std::cmp::max(self.version, 2)
+    }
+
+    /// Layout version of raw policy account data
+    /// 
+    /// v1 policies use the original 168-byte layout and cannot be loaded as
+    /// `PolicyState` until they are migrated.
+    pub fn stored_format_version(data: &[u8]) -> Result<u8> {
+        if data.len() == PolicyStateV1::LEN {
+            return Ok(1);
+        }
+        Ok(Self::try_deserialize(&mut &data[..])?.format_version())
+    }
+
     /// Validate policy parameters
     pub fn validate(&self) -> Result<()> {
         require!(
-            self.investor_fee_share_bps <= 10000,
+            self.investor_fee_share_bps <= BPS_DENOMINATOR,
             anchor_lang::error::ErrorCode::ConstraintRaw
         );
         require!(
             self.y0_total_allocation > 0,
             anchor_lang::error::ErrorCode::ConstraintRaw
         );
+        require!(
+            self.investor_fee_share_bps + MIN_CREATOR_BPS <= BPS_DENOMINATOR,
+            FeeRouterError::CreatorShareBelowMinimum
+        );
+        // A daily cap of 0 means no cap
+        require!(
+            self.daily_cap_lamports < MAX_DAILY_CAP_LAMPORTS,
+            FeeRouterError::DailyCapTooLarge
+        );
+        require!(
+            self.daily_cap_lamports == 0 || self.min_payout_lamports < self.daily_cap_lamports,
+            FeeRouterError::MinPayoutExceedsDailyCap
+        );
+        require!(
+            self.y0_total_allocation >= self.min_payout_lamports,
+            FeeRouterError::Y0BelowMinPayout
+        );
+        // Anything not claimed by investors, creator or protocol stays in treasury as reserve
+        require!(
+            self.investor_fee_share_bps
+                .saturating_add(self.creator_fee_share_bps)
+                .saturating_add(self.protocol_fee_bps) <= BPS_DENOMINATOR,
+            FeeRouterError::InvalidFeeShareBps
+        );
+        require!(
+            self.protocol_fee_bps == 0 || self.protocol_fee_wallet != Pubkey::default(),
+            FeeRouterError::ProtocolFeeWalletNotSet
+        );
+        require!(
+            self.ramp_start_bps <= BPS_DENOMINATOR && self.ramp_end_bps <= BPS_DENOMINATOR,
+            FeeRouterError::InvalidFeeShareBps
+        );
+        require!(
+            self.ramp_duration_seconds >= 0,
+            FeeRouterError::InvalidRampDuration
+        );
+        require!(
+            !self.duration_bonus_enabled || self.duration_bonus_max_multiplier_bps >= BPS_DENOMINATOR,
+            FeeRouterError::InvalidDurationBonusMultiplier
+        );
+        require!(
+            self.avg_alpha_bps <= BPS_DENOMINATOR,
+            FeeRouterError::InvalidFeeShareBps
+        );
+        require!(
+            self.target_page_size <= MAX_INVESTORS_PER_PAGE,
+            FeeRouterError::InvalidPageSize
+        );
+        require!(
+            self.max_claim_interval_seconds >= 0,
+            FeeRouterError::InvalidClaimInterval
+        );
         Ok(())
     }
+
+    /// Investors per page a new distribution starts with
+    pub fn initial_page_size(&self) -> u32 {
+        if self.target_page_size == 0 {
+            MAX_INVESTORS_PER_PAGE
+        } else {
+            std::cmp::min(self.target_page_size, MAX_INVESTORS_PER_PAGE)
+        }
+    }
+
+    /// Get the investor fee share for a timestamp, following the configured ramp
+    /// 
+    /// Linearly interpolates from `ramp_start_bps` to `ramp_end_bps` over
+    /// `ramp_duration_seconds`, capped by `investor_fee_share_bps`. With the ramp
+    /// disabled this is just `investor_fee_share_bps`.
+    pub fn fee_share_ramp(&self, current_timestamp: i64) -> u64 {
+        if self.ramp_duration_seconds == 0 {
+            return self.investor_fee_share_bps;
+        }
+
+        let elapsed = current_timestamp
+            .saturating_sub(self.ramp_start_timestamp)
+            .clamp(0, self.ramp_duration_seconds);
+
+        let start = self.ramp_start_bps as i128;
+        let end = self.ramp_end_bps as i128;
+        let ramped = start + (end - start) * elapsed as i128 / self.ramp_duration_seconds as i128;
+
+        std::cmp::min(ramped as u64, self.investor_fee_share_bps)
+    }
+
+    /// Check if the policy has passed its sunset date
+    pub fn is_sunsetted(&self, current_timestamp: i64) -> bool {
+        self.sunset_timestamp > 0 && current_timestamp >= self.sunset_timestamp
+    }
+
+    /// Sunset after a completed distribution when the policy auto-extends it
+    /// 
+    /// `None` when auto-extension is off or there is no sunset to extend.
+    pub fn auto_extended_sunset(&self) -> Option<i64> {
+        (self.auto_extend_sunset && self.sunset_timestamp > 0)
+            .then(|| self.sunset_timestamp.saturating_add(DISTRIBUTION_INTERVAL_SECONDS))
+    }
+
+    /// Check if the treasury's last claim is too old to start a distribution from
+    pub fn is_claim_stale(&self, last_claim_timestamp: i64, current_timestamp: i64) -> bool {
+        self.max_claim_interval_seconds > 0
+            && current_timestamp.saturating_sub(last_claim_timestamp) > self.max_claim_interval_seconds
+    }
+
+    /// Check if a new sunset timestamp pushes the current one later
+    /// 
+    /// 0 removes the sunset entirely.
+    pub fn is_valid_sunset_extension(&self, new_sunset_timestamp: i64) -> bool {
+        self.sunset_timestamp > 0
+            && (new_sunset_timestamp == 0 || new_sunset_timestamp > self.sunset_timestamp)
+    }
+
+    /// Check if a minimum payout is non-zero and fits within the daily cap
+    /// 
+    /// A daily cap of 0 means no cap, so only the lower bound applies.
+    pub fn is_valid_min_payout(&self, min_payout_lamports: u64) -> bool {
+        min_payout_lamports > 0
+            && (self.daily_cap_lamports == 0 || min_payout_lamports <= self.daily_cap_lamports)
+    }
+
+    /// Daily cap applied to a distribution (a policy cap of 0 means no cap)
+    pub fn daily_cap_for_distribution(&self) -> u64 {
+        if self.daily_cap_lamports == 0 {
+            u64::MAX
+        } else {
+            self.daily_cap_lamports
+        }
+    }
+
+    /// Check if enough investors are eligible to start a distribution
+    /// 
+    /// A minimum of 0 means the default, so an empty snapshot never starts one.
+    pub fn has_enough_investors(&self, total_investors: u32) -> bool {
+        let min_investors = if self.min_investors_to_distribute == 0 {
+            DEFAULT_MIN_INVESTORS_TO_DISTRIBUTE
+        } else {
+            self.min_investors_to_distribute
+        };
+        total_investors >= min_investors
+    }
+
+    /// Check if a distribution should be completed inline after this many pages
+    pub fn should_auto_complete(&self, pages_processed: u32) -> bool {
+        self.max_pages_before_auto_complete > 0 && pages_processed >= self.max_pages_before_auto_complete
+    }
+
+    /// Get the max duration multiplier to apply (10000 = no bonus when disabled)
+    pub fn duration_bonus_multiplier_bps(&self) -> u64 {
+        if self.duration_bonus_enabled {
+            self.duration_bonus_max_multiplier_bps
+        } else {
+            BPS_DENOMINATOR
+        }
+    }
+
+    /// Weighting options for `calculate_distribution`
+    pub fn weighting_options(&self) -> WeightingOptions {
+        WeightingOptions {
+            duration_bonus_max_multiplier_bps: self.duration_bonus_multiplier_bps(),
+            use_duration_weighting: self.use_duration_weighting,
+        }
+    }
+
+    /// Calculate the explicit creator amount for a claimed quote total
+    /// creator_amount = floor(claimed_quote * creator_fee_share_bps / 10000)
+    pub fn calculate_creator_amount(&self, claimed_quote: u64) -> u64 {
+        ((claimed_quote as u128 * self.creator_fee_share_bps as u128) / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Calculate the protocol cut for a distribution total
+    /// protocol_amount = floor(total_amount * protocol_fee_bps / 10000)
+    pub fn calculate_protocol_fee_amount(&self, total_amount: u64) -> u64 {
+        ((total_amount as u128 * self.protocol_fee_bps as u128) / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/// Original (v1) policy account layout
+/// 
+/// Shares the `PolicyState` discriminator; later fields were inserted before
+/// the reserved bytes, so v1 data has to be decoded with this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyStateV1 {
+    /// Quote mint this policy applies to
+    pub quote_mint: Pubkey,
+    
+    /// Maximum investor share in basis points (0-10000)
+    pub investor_fee_share_bps: u64,
+    
+    /// Daily distribution cap in lamports (0 = no cap)
+    pub daily_cap_lamports: u64,
+    
+    /// Minimum payout threshold in lamports
+    pub min_payout_lamports: u64,
+    
+    /// Total investor allocation at TGE (Y0)
+    pub y0_total_allocation: u64,
+    
+    /// Authority that can update this policy
+    pub policy_authority: Pubkey,
+    
+    /// Reserved for future use
+    pub reserved: [u8; 64],
+}
+
+impl PolicyStateV1 {
+    pub const LEN: usize = 8 +   // discriminator
+                           32 +  // quote_mint
+                           8 +   // investor_fee_share_bps
+                           8 +   // daily_cap_lamports
+                           8 +   // min_payout_lamports
+                           8 +   // y0_total_allocation
+                           32 +  // policy_authority
+                           64;   // reserved
+
+    /// Decode v1 policy account data, discriminator included
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() == Self::LEN,
+            FeeRouterError::UnsupportedPolicyVersion
+        );
+        require!(
+            data[..8] == <PolicyState as anchor_lang::Discriminator>::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// Map a v1 policy onto the v2 layout
+/// 
+/// v1 fields keep their values. The creator gets the share v1 gave it
+/// implicitly, everything investors do not receive; every other new field
+/// takes the value that reproduces v1 behavior (features off, no sunset, no
+/// protocol cut, batched distribution).
+pub fn migrate_v1_to_v2(policy: &PolicyStateV1, bump: u8) -> PolicyState {
+    PolicyState {
+        quote_mint: policy.quote_mint,
+        investor_fee_share_bps: policy.investor_fee_share_bps,
+        creator_fee_share_bps: BPS_DENOMINATOR.saturating_sub(policy.investor_fee_share_bps),
+        daily_cap_lamports: policy.daily_cap_lamports,
+        min_payout_lamports: policy.min_payout_lamports,
+        y0_total_allocation: policy.y0_total_allocation,
+        policy_authority: policy.policy_authority,
+        allow_base_fee_grace_period: false,
+        position_creation_fee_lamports: 0,
+        ramp_start_bps: 0,
+        ramp_end_bps: 0,
+        ramp_duration_seconds: 0,
+        ramp_start_timestamp: 0,
+        duration_bonus_enabled: false,
+        duration_bonus_max_multiplier_bps: 0,
+        min_remaining_lock_seconds: 0,
+        avg_alpha_bps: 0,
+        dust_destination: DustDestination::CarryToNextDay,
+        min_investors_to_distribute: 0,
+        include_cliff_in_weight: false,
+        sunset_timestamp: 0,
+        protocol_fee_bps: 0,
+        protocol_fee_wallet: Pubkey::default(),
+        max_pages_before_auto_complete: 0,
+        cap_application_mode: CapApplicationMode::ProportionalScale,
+        auto_claim_before_distribution: false,
+        use_duration_weighting: false,
+        fee_distribution_mode: FeeDistributionMode::Batched,
+        bump,
+        version: 2,
+        page_hash_algorithm: HashAlgorithm::Sha256,
+        target_page_size: 0,
+        max_claim_interval_seconds: 0,
+        auto_extend_sunset: false,
+        blacklist_enabled: false,
+        governance_authority: Pubkey::default(),
+        reserved: [0; 15],
+    }
 }
 
 /// Daily distribution state to track progress within a 24-hour period
@@ -120,11 +568,68 @@ pub struct DailyDistributionState {
     /// Number of pages processed so far
     pub pages_processed: u32,
     
-    /// Number of failed payouts (for retry tracking)
+    /// Number of streams skipped for ending before the minimum remaining lock
+    pub streams_below_min_duration: u32,
+    
+    /// Lamports spent creating missing investor ATAs (paid by the cranker)
+    pub ata_creation_fees_lamports: u64,
+    
+    /// Bitmap of processed investor indices (bit i set = investor i paid),
+    /// one bit per investor in `total_investors`
+    pub processed_bitmap: Vec<u8>,
+    
+    /// Treasury ATA balance when the distribution started (before reserve)
+    pub treasury_balance_at_start: u64,
+    
+    /// Timestamp of the investor snapshot locked amounts are based on
+    pub lock_snapshot_timestamp: i64,
+    
+    /// Timestamp when the last page finished processing (0 if none yet)
+    pub last_page_completed_at: i64,
+    
+    /// Treasury's lifetime claimed fees when the distribution started
+    pub total_fees_claimed_at_distribution_start: u64,
+    
+    /// Largest page (in investors) processed so far
+    pub max_page_size_used: u32,
+    
+    /// Smallest page (in investors) processed so far (0 before the first page)
+    pub min_page_size_used: u32,
+    
+    /// Number of investors skipped for being on the blacklist
+    pub skipped_blacklisted: u32,
+    
+    /// Slot in which the distribution was started
+    pub execution_slot: u64,
+    
+    /// Slot in which the most recent page was processed (0 before the first page)
+    pub current_page_slot: u64,
+    
+    /// Number of full-size pages needed to process every investor
+    pub expected_page_count: u32,
+    
+    /// Starting cursors of the last 8 processed pages, most recent first
+    pub page_cursor_history: [u32; 8],
+    
+    /// Hashes of the last 4 processed pages, most recent first (for idempotency)
+    pub page_hash_history: [[u8; 32]; 4],
+    
+    /// Maximum investors accepted per page (can be lowered mid-distribution)
+    pub effective_page_size: u32,
+    
+    /// Number of investor payouts skipped because the investor's ATA was frozen
     pub failed_payouts_count: u32,
     
-    /// Reserved for future use
-    pub reserved: [u8; 20],
+    /// Dust carried over from earlier days when the distribution started
+    pub dust_rolled_over: u64,
+    
+    /// Whether the day was started from the investor registry (pages must
+    /// then go through `process_investor_page_from_registry`)
+    pub registry_driven: bool,
+    
+    /// Quote left for the creator, protocol and reserve after investor payouts,
+    /// recorded on completion
+    pub creator_remainder: u64,
 }
 
 impl DailyDistributionState {
@@ -147,8 +652,36 @@ impl DailyDistributionState {
                                    8 +   // investor_fee_share_bps
                                    32 +  // last_page_hash
                                    4 +   // pages_processed
+                                   4 +   // streams_below_min_duration
+                                   8 +   // ata_creation_fees_lamports
+                                   4 +   // processed_bitmap (bits sized by space_for)
+                                   8 +   // treasury_balance_at_start
+                                   8 +   // lock_snapshot_timestamp
+                                   8 +   // last_page_completed_at
+                                   8 +   // total_fees_claimed_at_distribution_start
+                                   4 +   // max_page_size_used
+                                   4 +   // min_page_size_used
+                                   4 +   // skipped_blacklisted
+                                   8 +   // execution_slot
+                                   8 +   // current_page_slot
+                                   4 +   // expected_page_count
+                                   4 * 8 + // page_cursor_history
+                                   32 * 4 + // page_hash_history
+                                   4 +   // effective_page_size
                                    4 +   // failed_payouts_count
-                                   20;   // reserved
+                                   8 +   // dust_rolled_over
+                                   1 +   // registry_driven
+                                   8;    // creator_remainder
+
+    /// Account space for a day of `total_investors` investors
+    pub fn space_for(total_investors: u32) -> usize {
+        Self::INIT_SPACE + Self::bitmap_len(total_investors)
+    }
+
+    /// Bytes of `processed_bitmap` needed to track `total_investors` investors
+    pub fn bitmap_len(total_investors: u32) -> usize {
+        (total_investors as usize).div_ceil(8)
+    }
 
     /// Derive the PDA for daily distribution state
     pub fn derive_pda(distribution_day: i64, quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
@@ -162,6 +695,31 @@ impl DailyDistributionState {
         )
     }
 
+    /// Number of pages of at most `page_size` investors needed to process `total_investors`
+    pub fn calculate_expected_page_count(total_investors: u32, page_size: u32) -> u32 {
+        if page_size == 0 {
+            return 0;
+        }
+        total_investors.div_ceil(page_size)
+    }
+
+    /// Change the page size for the remaining pages
+    /// 
+    /// The size must be between 1 and `MAX_INVESTORS_PER_PAGE`. The expected
+    /// page count is recomputed for the investors still to be processed.
+    pub fn set_effective_page_size(&mut self, new_size: u32) -> Result<()> {
+        require!(
+            new_size > 0 && new_size <= MAX_INVESTORS_PER_PAGE,
+            FeeRouterError::InvalidPageSize
+        );
+        self.effective_page_size = new_size;
+        let investors_remaining = self.total_investors.saturating_sub(self.current_cursor);
+        self.expected_page_count = self
+            .pages_processed
+            .saturating_add(Self::calculate_expected_page_count(investors_remaining, new_size));
+        Ok(())
+    }
+
     /// Get the day timestamp (start of day) from a given timestamp
     pub fn get_day_start(timestamp: i64) -> i64 {
         // Round down to start of day (86400 seconds = 24 hours)
@@ -187,14 +745,225 @@ impl DailyDistributionState {
         self.completed_at = timestamp;
     }
 
+    /// Seconds from the distribution start to the last processed page
+    pub fn page_processing_latency_seconds(&self) -> i64 {
+        if self.last_page_completed_at == 0 {
+            return 0;
+        }
+        self.last_page_completed_at.saturating_sub(self.started_at)
+    }
+
+    /// Summarize the distribution's progress for off-chain monitoring
+    /// 
+    /// The completion estimate extrapolates the time taken so far (up to the
+    /// last processed page) over the remaining investors. The effective APR
+    /// annualizes the amount paid out today against Y0.
+    pub fn to_summary(&self) -> DistributionSummaryView {
+        let progress_pct = if self.total_investors == 0 {
+            100
+        } else {
+            (self.investors_processed.min(self.total_investors) as u64 * 100 / self.total_investors as u64) as u8
+        };
+
+        let estimated_completion_time = if self.is_complete {
+            self.completed_at
+        } else if progress_pct == 0 {
+            0
+        } else {
+            let elapsed = self.page_processing_latency_seconds();
+            self.started_at.saturating_add(elapsed.saturating_mul(100) / progress_pct as i64)
+        };
+
+        let effective_apr_bps = if self.initial_total_deposit == 0 {
+            0
+        } else {
+            let days_per_year = (SECONDS_PER_YEAR / SECONDS_PER_DAY) as u128;
+            (self.amount_distributed as u128 * BPS_DENOMINATOR as u128 * days_per_year / self.initial_total_deposit as u128)
+                .min(u64::MAX as u128) as u64
+        };
+
+        DistributionSummaryView {
+            distribution_day: self.distribution_day,
+            quote_mint: self.quote_mint,
+            investors_processed: self.investors_processed,
+            total_investors: self.total_investors,
+            amount_distributed: self.amount_distributed,
+            pages_processed: self.pages_processed,
+            is_complete: self.is_complete,
+            progress_pct,
+            estimated_completion_time,
+            effective_apr_bps,
+        }
+    }
+
+    /// Average seconds spent per processed page (0 before the first page)
+    pub fn seconds_per_page(&self) -> u32 {
+        if self.pages_processed == 0 {
+            return 0;
+        }
+        let average = self.page_processing_latency_seconds() / self.pages_processed as i64;
+        std::cmp::min(average.max(0), u32::MAX as i64) as u32
+    }
+
+    /// Verify the treasury still holds what the distribution has not paid out
+    /// 
+    /// Fees claimed after the distribution started are excluded, so a claim
+    /// cannot mask missing funds.
+    pub fn verify_treasury_balance(&self, treasury_balance: u64, total_fees_claimed: u64) -> Result<()> {
+        let claimed_since_start = total_fees_claimed.saturating_sub(self.total_fees_claimed_at_distribution_start);
+        let expected_balance = self.total_amount_to_distribute.saturating_sub(self.amount_distributed);
+        require!(
+            treasury_balance.saturating_sub(claimed_since_start) >= expected_balance,
+            FeeRouterError::TreasuryBalanceMismatch
+        );
+        Ok(())
+    }
+
+    /// Run the post-distribution integrity checks
+    /// 
+    /// Checks, in order: completed and recorded globally, investor payouts
+    /// within the investor fee quote, payouts plus the creator remainder
+    /// adding up to the amount to distribute plus carried dust, processed and
+    /// failed investors within the total, and payouts within the daily cap.
+    /// Returns the check results and the largest discrepancy found.
+    pub fn integrity_checks(
+        &self,
+        investor_fee_quote: u64,
+        global_last_distribution_timestamp: i64,
+    ) -> ([bool; 5], u64) {
+        let settled_amount = self.amount_distributed as u128 + self.creator_remainder as u128;
+        let expected_amount = self.total_amount_to_distribute as u128 + self.dust_carried_over as u128;
+        let processed_investors = self.investors_processed as u64 + self.failed_payouts_count as u64;
+        let check_results = [
+            self.is_complete && global_last_distribution_timestamp >= self.completed_at,
+            self.amount_distributed <= investor_fee_quote,
+            settled_amount == expected_amount,
+            processed_investors <= self.total_investors as u64,
+            self.amount_distributed <= self.daily_cap_total,
+        ];
+        let discrepancy_amount = [
+            self.amount_distributed.saturating_sub(investor_fee_quote),
+            settled_amount.abs_diff(expected_amount) as u64,
+            self.amount_distributed.saturating_sub(self.daily_cap_total),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+        (check_results, discrepancy_amount)
+    }
+
+    /// Audit a completed distribution against the policy recorded at its start
+    /// 
+    /// Returns a description of every violated invariant: investor payouts
+    /// within the investor fee quote, payout summaries adding up to the amount
+    /// distributed, the creator remainder covering the rest of the day's
+    /// amount, and the day recorded globally. Empty when the audit passed.
+    pub fn audit_violations(
+        &self,
+        policy_snapshot: &PolicySnapshot,
+        payout_summary_total: u64,
+        global_last_distribution_timestamp: i64,
+    ) -> Vec<String> {
+        let effective_amount = self.get_effective_distribution_amount();
+        let investor_fee_quote = policy_snapshot.expected_investor_fee_quote(effective_amount, self.investor_fee_share_bps);
+        let expected_remainder = effective_amount.checked_sub(self.amount_distributed);
+        let mut violations = Vec::new();
+        if self.amount_distributed > investor_fee_quote {
+            violations.push(format!(
+                "amount distributed {} exceeds investor fee quote {}",
+                self.amount_distributed, investor_fee_quote
+            ));
+        }
+        if payout_summary_total != self.amount_distributed {
+            violations.push(format!(
+                "payout summaries total {} but amount distributed is {}",
+                payout_summary_total, self.amount_distributed
+            ));
+        }
+        if expected_remainder != Some(self.creator_remainder) {
+            violations.push(format!(
+                "creator remainder {} does not match {} total minus {} distributed",
+                self.creator_remainder, effective_amount, self.amount_distributed
+            ));
+        }
+        if global_last_distribution_timestamp < self.completed_at {
+            violations.push(format!(
+                "completion at {} not recorded globally (last distribution {})",
+                self.completed_at, global_last_distribution_timestamp
+            ));
+        }
+        violations
+    }
+
+    /// Check if the day's investor snapshot can be closed
+    /// 
+    /// The snapshot is kept for `SNAPSHOT_RETENTION_SECONDS` after completion.
+    pub fn can_close_snapshot(&self, current_timestamp: i64) -> bool {
+        self.is_complete
+            && self.completed_at < current_timestamp.saturating_sub(SNAPSHOT_RETENTION_SECONDS)
+    }
+
+    /// Check if the distribution still accepts investor pages
+    /// 
+    /// Open until completed or until `MAX_DISTRIBUTION_WINDOW_SECONDS` after it
+    /// started; after that it can only be force-completed.
+    pub fn is_open(&self, current_timestamp: i64) -> bool {
+        !self.is_complete
+            && current_timestamp.saturating_sub(self.started_at) < MAX_DISTRIBUTION_WINDOW_SECONDS
+    }
+
+    /// Ratio of paid out amount to the starting treasury balance in basis points
+    pub fn distribution_ratio_bps(&self, amount_paid_out: u64) -> u64 {
+        if self.treasury_balance_at_start == 0 {
+            return 0;
+        }
+        ((amount_paid_out as u128 * BPS_DENOMINATOR as u128) / self.treasury_balance_at_start as u128) as u64
+    }
+
     /// Calculate remaining amount to distribute
     pub fn remaining_amount(&self) -> u64 {
         self.total_amount_to_distribute.saturating_sub(self.amount_distributed)
     }
 
     /// Check if there are more investors to process
+    /// 
+    /// True while any investor index below `total_investors` has its bit unset.
+    /// Investors the bitmap cannot hold are never processed, so they keep the
+    /// day open rather than ending it early.
     pub fn has_more_investors(&self) -> bool {
-        self.investors_processed < self.total_investors
+        // Compare whole bytes so large days stay cheap to check on every page
+        let full_bytes = (self.total_investors / 8) as usize;
+        let trailing_bits = self.total_investors % 8;
+        let bitmap = &self.processed_bitmap;
+        bitmap.len() < Self::bitmap_len(self.total_investors)
+            || bitmap[..full_bytes].iter().any(|byte| *byte != u8::MAX)
+            || (trailing_bits > 0 && bitmap[full_bytes] != (1u8 << trailing_bits) - 1)
+    }
+
+    /// Check if the investor at `index` has already been processed
+    pub fn is_investor_processed(&self, index: u32) -> bool {
+        let index = index as usize;
+        self.processed_bitmap
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Mark the investor at `index` as processed
+    /// 
+    /// Returns false if the bit was already set (or the index is out of range),
+    /// so a retried investor is never counted twice.
+    pub fn mark_investor_processed(&mut self, index: u32) -> bool {
+        if index >= self.total_investors {
+            return false;
+        }
+        let index = index as usize;
+        match self.processed_bitmap.get_mut(index / 8) {
+            Some(byte) if *byte & (1 << (index % 8)) == 0 => {
+                *byte |= 1 << (index % 8);
+                true
+            }
+            _ => false,
+        }
     }
 
     /// Update daily cap after distribution
@@ -207,6 +976,16 @@ impl DailyDistributionState {
         self.dust_carried_over = self.dust_carried_over.saturating_add(dust_amount);
     }
 
+    /// Dust that arose during this day, excluding dust rolled over from earlier days
+    pub fn new_dust(&self) -> u64 {
+        self.dust_carried_over.saturating_sub(self.dust_rolled_over)
+    }
+
+    /// Count investor payouts that were skipped instead of transferred
+    pub fn add_failed_payouts(&mut self, count: u32) {
+        self.failed_payouts_count = self.failed_payouts_count.saturating_add(count);
+    }
+
     /// Check if daily cap allows for distribution
     pub fn can_distribute(&self, amount: u64) -> bool {
         amount <= self.daily_cap_remaining
@@ -218,44 +997,98 @@ impl DailyDistributionState {
     }
 
     /// Calculate hash for a page of investor accounts (for idempotency)
-    pub fn calculate_page_hash(investor_accounts: &[Pubkey]) -> [u8; 32] {
-        use anchor_lang::solana_program::hash::hash;
-        
+    pub fn calculate_page_hash(investor_accounts: &[Pubkey], algorithm: HashAlgorithm) -> [u8; 32] {
         let mut data = Vec::new();
         for account in investor_accounts {
             data.extend_from_slice(account.as_ref());
         }
         
-        let hash_result = hash(&data);
-        hash_result.to_bytes()
+        compute_hash(&data, algorithm)
+    }
+
+    /// Calculate hash of the payouts transferred in a page (for external verification)
+    /// 
+    /// Hashes `investor || payout_amount (u64 LE)` for each payout that is
+    /// paid (non-zero and above the minimum), in page order.
+    pub fn calculate_payouts_hash(payouts: &[InvestorPayout], algorithm: HashAlgorithm) -> [u8; 32] {
+        let mut data = Vec::new();
+        for payout in payouts.iter().filter(|payout| payout.payout_amount > 0 && payout.meets_minimum) {
+            data.extend_from_slice(payout.investor.as_ref());
+            data.extend_from_slice(&payout.payout_amount.to_le_bytes());
+        }
+
+        compute_hash(&data, algorithm)
     }
 
     /// Check if this page has already been processed (idempotency check)
+    /// 
+    /// Matches against the last 4 pages so a re-submitted earlier page is
+    /// rejected as well.
     pub fn is_page_already_processed(&self, page_hash: &[u8; 32]) -> bool {
-        self.last_page_hash == *page_hash
+        self.last_page_hash == *page_hash || self.page_hash_history.contains(page_hash)
     }
 
     /// Update page processing state
+    /// 
+    /// Marks the investors in `[current_cursor, current_cursor + investors_in_page)`
+    /// as processed in the bitmap and records the page in the cursor and hash
+    /// histories.
     pub fn update_page_state(&mut self, page_hash: [u8; 32], investors_in_page: u32, amount_distributed: u64) {
+        for index in self.current_cursor..self.current_cursor.saturating_add(investors_in_page) {
+            self.mark_investor_processed(index);
+        }
+        self.page_cursor_history.rotate_right(1);
+        self.page_cursor_history[0] = self.current_cursor;
+        self.page_hash_history.rotate_right(1);
+        self.page_hash_history[0] = page_hash;
         self.last_page_hash = page_hash;
         self.pages_processed = self.pages_processed.saturating_add(1);
         self.update_progress(investors_in_page, amount_distributed, self.current_cursor + investors_in_page);
     }
 
-    /// Track failed payouts for retry purposes
-    pub fn add_failed_payouts(&mut self, failed_count: u32) {
-        self.failed_payouts_count = self.failed_payouts_count.saturating_add(failed_count);
+    /// Record the size of a processed page
+    pub fn record_page_stats(&mut self, page_size: u32) {
+        self.max_page_size_used = std::cmp::max(self.max_page_size_used, page_size);
+        self.min_page_size_used = if self.min_page_size_used == 0 {
+            page_size
+        } else {
+            std::cmp::min(self.min_page_size_used, page_size)
+        };
+    }
+
+    /// Record the slot a page is processed in, rejecting pages from an earlier slot
+    pub fn record_page_slot(&mut self, slot: u64) -> Result<()> {
+        require!(
+            slot >= self.execution_slot && slot >= self.current_page_slot,
+            FeeRouterError::PageSlotOutOfOrder
+        );
+        self.current_page_slot = slot;
+        Ok(())
     }
 
-    /// Check if there were any failed payouts that might need retry
-    pub fn has_failed_payouts(&self) -> bool {
-        self.failed_payouts_count > 0
+    /// Check that a page is the next one in order and stays within the investor set
+    /// 
+    /// Pages are numbered by `pages_processed`, so a reordered, skipped or
+    /// replayed page fails before it can move the cursor.
+    pub fn validate_page_order(&self, expected_page_index: u32, investors_in_page: u32) -> Result<()> {
+        if expected_page_index != self.pages_processed {
+            return Err(FeeRouterError::InvalidPageIndex
+                .with_context(format!("expected page: {}, next page: {}", expected_page_index, self.pages_processed))
+                .into());
+        }
+        let page_end = self.current_cursor.saturating_add(investors_in_page);
+        if page_end > self.total_investors {
+            return Err(FeeRouterError::PaginationError
+                .with_context(format!("page end: {}, total investors: {}", page_end, self.total_investors))
+                .into());
+        }
+        Ok(())
     }
 
     /// Validate page for retry safety
-    pub fn validate_page_for_retry(&self, investor_accounts: &[Pubkey]) -> Result<()> {
+    pub fn validate_page_for_retry(&self, investor_accounts: &[Pubkey], algorithm: HashAlgorithm) -> Result<()> {
         // Calculate hash for this page
-        let page_hash = Self::calculate_page_hash(investor_accounts);
+        let page_hash = Self::calculate_page_hash(investor_accounts, algorithm);
         
         // If this exact page was already processed, it's a retry attempt
         if self.is_page_already_processed(&page_hash) {
@@ -282,8 +1115,41 @@ pub struct GlobalDistributionState {
     /// Total amount distributed across all time
     pub total_amount_distributed: u64,
     
+    /// Rolling (EMA) average of amount paid out / treasury balance at start, in basis points
+    pub rolling_avg_distribution_bps_of_treasury: u64,
+    
+    /// Wallet that owns the creator ATA receiving the creator share
+    pub creator_wallet: Pubkey,
+    
+    /// Wallet receiving dust when the policy routes it to the protocol
+    pub protocol_fee_wallet: Option<Pubkey>,
+    
+    /// Total paid to investors across all time
+    pub total_investor_payouts: u64,
+    
+    /// Total paid to the creator across all time
+    pub total_creator_payouts: u64,
+    
+    /// Rolling (EMA) average of seconds spent per investor page
+    pub avg_seconds_per_page: u32,
+    
+    /// Cached PDA bump so constraints skip `find_program_address`
+    pub bump: u8,
+    
+    /// Creation timestamp of the linked honorary position (0 = not linked)
+    pub position_initialized_at: i64,
+    
+    /// Health (0-100) of the most recent distribution
+    pub health_score: u8,
+    
+    /// Number of times completed distributions auto-extended the policy sunset
+    pub auto_extension_count: u32,
+    
+    /// Dust left in the treasury by completed distributions, seeded into the next day
+    pub dust_rollover: u64,
+    
     /// Reserved for future use
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 2],
 }
 
 impl GlobalDistributionState {
@@ -291,7 +1157,18 @@ impl GlobalDistributionState {
                                    8 +   // last_distribution_timestamp
                                    8 +   // total_distributions
                                    8 +   // total_amount_distributed
-                                   64;   // reserved
+                                   8 +   // rolling_avg_distribution_bps_of_treasury
+                                   32 +  // creator_wallet
+                                   33 +  // protocol_fee_wallet
+                                   8 +   // total_investor_payouts
+                                   8 +   // total_creator_payouts
+                                   4 +   // avg_seconds_per_page
+                                   1 +   // bump
+                                   8 +   // position_initialized_at
+                                   1 +   // health_score
+                                   4 +   // auto_extension_count
+                                   8 +   // dust_rollover
+                                   2;    // reserved
 
     /// Derive the PDA for global distribution state
     pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
@@ -301,10 +1178,881 @@ impl GlobalDistributionState {
         )
     }
 
+    /// Take the dust rolled over from earlier distributions, resetting it to zero
+    pub fn take_dust_rollover(&mut self) -> u64 {
+        std::mem::take(&mut self.dust_rollover)
+    }
+
+    /// Keep dust a completed distribution left in the treasury for the next day
+    /// 
+    /// Accumulates until a new day takes it, so days without a distribution
+    /// do not drop it.
+    pub fn record_dust_rollover(&mut self, dust_remaining: u64) {
+        self.dust_rollover = self.dust_rollover.saturating_add(dust_remaining);
+    }
+
     /// Update after completing a daily distribution
-    pub fn update_after_distribution(&mut self, distribution_timestamp: i64, amount_distributed: u64) {
+    /// 
+    /// `total_amount_distributed` grows by investor + creator + protocol amounts;
+    /// investor and creator amounts are also tracked separately.
+    pub fn update_after_distribution(
+        &mut self,
+        distribution_timestamp: i64,
+        investor_amount: u64,
+        creator_amount: u64,
+        protocol_amount: u64,
+    ) {
         self.last_distribution_timestamp = distribution_timestamp;
         self.total_distributions = self.total_distributions.saturating_add(1);
-        self.total_amount_distributed = self.total_amount_distributed.saturating_add(amount_distributed);
+        self.total_amount_distributed = self.total_amount_distributed
+            .saturating_add(investor_amount)
+            .saturating_add(creator_amount)
+            .saturating_add(protocol_amount);
+        self.total_investor_payouts = self.total_investor_payouts.saturating_add(investor_amount);
+        self.total_creator_payouts = self.total_creator_payouts.saturating_add(creator_amount);
+    }
+
+    /// Score (0-100) the health of a completed daily distribution
+    /// 
+    /// Starts at 100 and loses 10 per day missed since `previous_distribution_timestamp`,
+    /// 20 when less than 10% of the daily cap was left, 10 when investors were
+    /// left unpaid, and 5 when dust exceeds 1% of `total_distributed`.
+    /// Returns the score with the `HEALTH_FLAG_*` reasons it was lowered.
+    pub fn calculate_health_score(
+        daily_state: &DailyDistributionState,
+        previous_distribution_timestamp: i64,
+        total_distributed: u64,
+    ) -> (u8, u8) {
+        let mut score = 100u8;
+        let mut reason_flags = 0u8;
+
+        let missed_days = if previous_distribution_timestamp == 0 {
+            0
+        } else {
+            let previous_day = DailyDistributionState::get_day_start(previous_distribution_timestamp);
+            ((daily_state.distribution_day - previous_day) / SECONDS_PER_DAY - 1).max(0)
+        };
+        if missed_days > 0 {
+            score = score.saturating_sub(missed_days.saturating_mul(10).min(100) as u8);
+            reason_flags |= HEALTH_FLAG_MISSED_DAYS;
+        }
+
+        if daily_state.daily_cap_remaining < daily_state.daily_cap_total / 10 {
+            score = score.saturating_sub(20);
+            reason_flags |= HEALTH_FLAG_CAP_BINDING;
+        }
+
+        if daily_state.investors_processed < daily_state.total_investors {
+            score = score.saturating_sub(10);
+            reason_flags |= HEALTH_FLAG_FAILED_PAYOUTS;
+        }
+
+        if daily_state.dust_carried_over > total_distributed / 100 {
+            score = score.saturating_sub(5);
+            reason_flags |= HEALTH_FLAG_HIGH_DUST;
+        }
+
+        (score, reason_flags)
+    }
+
+    /// Fold a day's distribution ratio into the rolling average
+    /// new_avg = alpha * current_ratio + (1 - alpha) * old_avg
+    /// 
+    /// The first distribution seeds the average with its own ratio.
+    pub fn update_rolling_average(&mut self, current_ratio_bps: u64, alpha_bps: u64) {
+        if self.total_distributions == 0 {
+            self.rolling_avg_distribution_bps_of_treasury = current_ratio_bps;
+            return;
+        }
+        let alpha = std::cmp::min(alpha_bps, BPS_DENOMINATOR) as u128;
+        let new_avg = (alpha * current_ratio_bps as u128
+            + (BPS_DENOMINATOR as u128 - alpha) * self.rolling_avg_distribution_bps_of_treasury as u128)
+            / BPS_DENOMINATOR as u128;
+        self.rolling_avg_distribution_bps_of_treasury = new_avg as u64;
+    }
+
+    /// Fold a day's seconds-per-page figure into the rolling average
+    /// 
+    /// Uses the same smoothing as the distribution rate; days without any
+    /// processed page leave the average unchanged.
+    pub fn update_avg_seconds_per_page(&mut self, seconds_per_page: u32, pages_processed: u32, alpha_bps: u64) {
+        if pages_processed == 0 {
+            return;
+        }
+        if self.avg_seconds_per_page == 0 {
+            self.avg_seconds_per_page = seconds_per_page;
+            return;
+        }
+        let alpha = std::cmp::min(alpha_bps, BPS_DENOMINATOR) as u128;
+        let new_avg = (alpha * seconds_per_page as u128
+            + (BPS_DENOMINATOR as u128 - alpha) * self.avg_seconds_per_page as u128)
+            / BPS_DENOMINATOR as u128;
+        self.avg_seconds_per_page = new_avg as u32;
+    }
+
+    /// Whole days since the linked honorary position was created (0 if not linked)
+    pub fn position_age_days(&self, current_timestamp: i64) -> u64 {
+        if self.position_initialized_at == 0 {
+            return 0;
+        }
+        (current_timestamp.saturating_sub(self.position_initialized_at).max(0) / SECONDS_PER_DAY) as u64
+    }
+
+    /// Estimate the next distribution amount for a treasury balance using the rolling average
+    pub fn average_distribution_amount(&self, treasury_balance: u64) -> u64 {
+        ((treasury_balance as u128 * self.rolling_avg_distribution_bps_of_treasury as u128) / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/// A single investor entry captured in an investor snapshot
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    /// The investor's wallet address
+    pub investor: Pubkey,
+    
+    /// The stream account the locked amount was read from
+    pub stream_account: Pubkey,
+    
+    /// Amount locked at snapshot time
+    pub locked_amount: u64,
+    
+    /// Part of `locked_amount` still behind the stream's cliff at snapshot time
+    pub cliff_locked: u64,
+    
+    /// Remaining lock time in seconds at snapshot time
+    pub lock_duration_seconds: u64,
+}
+
+impl SnapshotEntry {
+    pub const SIZE: usize = 32 + // investor
+                            32 + // stream_account
+                            8 +  // locked_amount
+                            8 +  // cliff_locked
+                            8;   // lock_duration_seconds
+}
+
+/// Snapshot of eligible investors taken before a daily distribution starts
+/// 
+/// Fixing the investor set and locked amounts up front makes every cranker
+/// compute the same distribution for the day.
+#[account]
+pub struct InvestorSnapshot {
+    /// The distribution day this snapshot belongs to
+    pub distribution_day: i64,
+    
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Number of investors in the snapshot
+    pub investor_count: u32,
+    
+    /// Total locked amount across all snapshot entries
+    pub total_locked: u64,
+    
+    /// Timestamp when the snapshot was taken
+    pub taken_at: i64,
+    
+    /// Authority that paid the snapshot's rent (receives it back on close)
+    pub payer: Pubkey,
+    
+    /// Investor entries (bounded by MAX_SNAPSHOT_INVESTORS)
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl InvestorSnapshot {
+    pub const INIT_SPACE: usize = 8 +   // distribution_day
+                                   32 +  // quote_mint
+                                   4 +   // investor_count
+                                   8 +   // total_locked
+                                   8 +   // taken_at
+                                   32 +  // payer
+                                   4 + MAX_SNAPSHOT_INVESTORS * SnapshotEntry::SIZE; // entries
+
+    /// Derive the PDA for an investor snapshot
+    pub fn derive_pda(distribution_day: i64, quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"snapshot",
+                distribution_day.to_string().as_bytes(),
+                quote_mint.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Investor index of the first entry in a page
+    pub fn page_start(page_index: u32, page_size: u32) -> u32 {
+        page_index.saturating_mul(page_size)
+    }
+
+    /// Entries belonging to a page of `page_size` investors
+    /// 
+    /// Returns None if the page starts past the last entry.
+    pub fn page_entries(&self, page_index: u32, page_size: u32) -> Option<&[SnapshotEntry]> {
+        let start = Self::page_start(page_index, page_size) as usize;
+        if page_size == 0 || start >= self.entries.len() {
+            return None;
+        }
+        let end = std::cmp::min(start.saturating_add(page_size as usize), self.entries.len());
+        Some(&self.entries[start..end])
+    }
+}
+
+/// Per-investor aggregate of the streams paid over a distribution day
+/// 
+/// Written by `process_investor_page` after reading an investor's streams.
+/// An investor's streams can be spread over several pages; each page adds the
+/// streams it was given, so every stream is counted and paid exactly once.
+/// The cache is a record of the day, not a source of locked amounts: those
+/// come from the day's `InvestorSnapshot`.
+#[account]
+pub struct InvestorAggregateCache {
+    /// The distribution day this cache belongs to
+    pub distribution_day: i64,
+    
+    /// Quote mint of the distribution
+    pub quote_mint: Pubkey,
+    
+    /// The investor's wallet address
+    pub investor: Pubkey,
+    
+    /// Total locked across the investor's streams
+    pub total_locked: u64,
+    
+    /// Total deposited across the investor's streams
+    pub total_deposited: u64,
+    
+    /// Number of streams aggregated
+    pub stream_count: u32,
+    
+    /// Earliest start time of the aggregated streams
+    pub start_time: u64,
+    
+    /// Latest end time of the aggregated streams
+    pub end_time: u64,
+    
+    /// Locked-weighted average remaining lock in seconds (for duration weighting)
+    pub lock_duration_seconds: u64,
+    
+    /// Unreleased cliff amounts included in `total_locked`
+    pub cliff_locked: u64,
+    
+    /// Timestamp when the cache was last written
+    pub last_updated: i64,
+    
+    /// Cranker that paid the rent (receives it back on close)
+    pub payer: Pubkey,
+}
+
+impl InvestorAggregateCache {
+    pub const INIT_SPACE: usize = 8 +   // distribution_day
+                                   32 +  // quote_mint
+                                   32 +  // investor
+                                   8 +   // total_locked
+                                   8 +   // total_deposited
+                                   4 +   // stream_count
+                                   8 +   // start_time
+                                   8 +   // end_time
+                                   8 +   // lock_duration_seconds
+                                   8 +   // cliff_locked
+                                   8 +   // last_updated
+                                   32;   // payer
+
+    /// Empty cache for an investor's first page of the day
+    pub fn new(distribution_day: i64, quote_mint: Pubkey, investor: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            distribution_day,
+            quote_mint,
+            investor,
+            total_locked: 0,
+            total_deposited: 0,
+            stream_count: 0,
+            start_time: 0,
+            end_time: 0,
+            lock_duration_seconds: 0,
+            cliff_locked: 0,
+            last_updated: 0,
+            payer,
+        }
+    }
+
+    /// Derive the PDA for an investor aggregate cache
+    pub fn derive_pda(distribution_day: i64, quote_mint: &Pubkey, investor: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"investor_cache",
+                distribution_day.to_string().as_bytes(),
+                quote_mint.as_ref(),
+                investor.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Add freshly read streams of the investor to the aggregate
+    pub fn add_streams(&mut self, investor_streams: &[&InvestorStreamData], timestamp: i64) {
+        let mut weighted_remaining = self.lock_duration_seconds as u128 * self.total_locked as u128;
+        for data in investor_streams {
+            self.start_time = if self.stream_count == 0 {
+                data.start_time
+            } else {
+                self.start_time.min(data.start_time)
+            };
+            self.end_time = self.end_time.max(data.end_time);
+            self.total_locked = self.total_locked.saturating_add(data.locked_amount);
+            self.total_deposited = self.total_deposited.saturating_add(data.total_deposited);
+            self.cliff_locked = self.cliff_locked.saturating_add(data.cliff_locked);
+            self.stream_count = self.stream_count.saturating_add(1);
+            weighted_remaining += data.locked_amount as u128 * data.lock_duration_seconds as u128;
+        }
+        self.lock_duration_seconds = if self.total_locked == 0 {
+            0
+        } else {
+            (weighted_remaining / self.total_locked as u128) as u64
+        };
+        self.last_updated = timestamp;
+    }
+
+    /// Check if the cache can be used for a distribution day
+    /// 
+    /// The cache must belong to the day and be written at or after the
+    /// distribution's lock snapshot.
+    pub fn is_valid_for(&self, distribution_day: i64, lock_snapshot_timestamp: i64) -> bool {
+        self.distribution_day == distribution_day && self.last_updated >= lock_snapshot_timestamp
+    }
+}
+
+/// Quote paid to one investor over a distribution day
+/// 
+/// Written by the page instructions when the investor is paid, so the day
+/// can be reported on per investor after it completes.
+#[account]
+pub struct InvestorPayoutSummary {
+    /// The distribution day this summary belongs to
+    pub distribution_day: i64,
+    
+    /// The investor's wallet address
+    pub investor: Pubkey,
+    
+    /// Total quote paid to the investor this day
+    pub amount_paid: u64,
+    
+    /// Number of payouts made to the investor this day
+    pub payout_count: u32,
+    
+    /// Timestamp of the most recent payout
+    pub last_paid_at: i64,
+    
+    /// Cranker that paid the rent (receives it back on close)
+    pub payer: Pubkey,
+}
+
+impl InvestorPayoutSummary {
+    pub const INIT_SPACE: usize = 8 +   // distribution_day
+                                   32 +  // investor
+                                   8 +   // amount_paid
+                                   4 +   // payout_count
+                                   8 +   // last_paid_at
+                                   32;   // payer
+
+    /// Empty summary for an investor's first payout of the day
+    pub fn new(distribution_day: i64, investor: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            distribution_day,
+            investor,
+            amount_paid: 0,
+            payout_count: 0,
+            last_paid_at: 0,
+            payer,
+        }
+    }
+
+    /// Derive the PDA for an investor payout summary
+    pub fn derive_pda(distribution_day: i64, investor: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"payout_summary",
+                distribution_day.to_string().as_bytes(),
+                investor.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Add a payout made to the investor
+    pub fn record_payout(&mut self, amount: u64, timestamp: i64) {
+        self.amount_paid = self.amount_paid.saturating_add(amount);
+        self.payout_count = self.payout_count.saturating_add(1);
+        self.last_paid_at = timestamp;
+    }
+}
+
+/// Policy parameters in effect when a daily distribution started
+/// 
+/// Recorded by `start_daily_distribution` so payouts can be audited against
+/// the policy used, even if the policy is updated later.
+#[account]
+pub struct PolicySnapshot {
+    /// The distribution day this snapshot belongs to
+    pub distribution_day: i64,
+    
+    /// The quote mint of the distribution
+    pub quote_mint: Pubkey,
+    
+    /// Investor fee share (basis points) configured at the time
+    pub investor_fee_share_bps: u64,
+    
+    /// Daily cap configured at the time
+    pub daily_cap_lamports: u64,
+    
+    /// Minimum payout threshold configured at the time
+    pub min_payout_lamports: u64,
+    
+    /// Total investor allocation at TGE (Y0) configured at the time
+    pub y0_total_allocation: u64,
+    
+    /// Timestamp when the snapshot was recorded
+    pub recorded_at: i64,
+}
+
+impl PolicySnapshot {
+    pub const INIT_SPACE: usize = 8 +   // distribution_day
+                                   32 +  // quote_mint
+                                   8 +   // investor_fee_share_bps
+                                   8 +   // daily_cap_lamports
+                                   8 +   // min_payout_lamports
+                                   8 +   // y0_total_allocation
+                                   8;    // recorded_at
+
+    /// Copy the auditable parameters out of the policy
+    pub fn from_policy(policy: &PolicyState, distribution_day: i64, timestamp: i64) -> Self {
+        Self {
+            distribution_day,
+            quote_mint: policy.quote_mint,
+            investor_fee_share_bps: policy.investor_fee_share_bps,
+            daily_cap_lamports: policy.daily_cap_lamports,
+            min_payout_lamports: policy.min_payout_lamports,
+            y0_total_allocation: policy.y0_total_allocation,
+            recorded_at: timestamp,
+        }
+    }
+
+    /// Upper bound on the investor fee quote for a distribution amount
+    /// investor_fee_quote = floor(amount * min(snapshot_share, day_share) / 10000)
+    /// 
+    /// The day's share already reflects the ramp; the locked fraction can
+    /// only lower it further.
+    pub fn expected_investor_fee_quote(&self, distribution_amount: u64, day_fee_share_bps: u64) -> u64 {
+        let share_bps = std::cmp::min(self.investor_fee_share_bps, day_fee_share_bps);
+        ((distribution_amount as u128 * share_bps as u128) / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Derive the PDA for a policy snapshot
+    pub fn derive_pda(distribution_day: i64, quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"policy_snapshot",
+                distribution_day.to_string().as_bytes(),
+                quote_mint.as_ref(),
+            ],
+            program_id,
+        )
+    }
+}
+
+/// Program-wide configuration, initialized once at deployment
+/// 
+/// Holds the emergency pause, which is controlled by its own authority so a
+/// security team can pause distributions without holding policy keys.
+#[account]
+pub struct ProgramConfig {
+    /// Authority allowed to pause distributions and hand over the pause role
+    pub program_pause_authority: Pubkey,
+    
+    /// Whether new distribution work is currently paused
+    pub is_paused: bool,
+    
+    /// Number of times distributions were paused
+    pub pause_count: u32,
+    
+    /// Number of times distributions were resumed
+    pub resume_count: u32,
+    
+    /// Timestamp of the most recent pause (0 if never paused)
+    pub last_paused_at: i64,
+    
+    /// Total seconds spent paused across completed pause periods
+    pub total_paused_seconds: i64,
+    
+    /// Reserved for future use
+    pub reserved: [u8; 8],
+}
+
+impl ProgramConfig {
+    pub const INIT_SPACE: usize = 32 +  // program_pause_authority
+                                   1 +   // is_paused
+                                   4 +   // pause_count
+                                   4 +   // resume_count
+                                   8 +   // last_paused_at
+                                   8 +   // total_paused_seconds
+                                   8;    // reserved
+
+    /// Derive the PDA for the program config
+    pub fn derive_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"program_config"], program_id)
+    }
+
+    /// Record distributions being paused
+    pub fn record_pause(&mut self, current_timestamp: i64) {
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.last_paused_at = current_timestamp;
+    }
+
+    /// Record distributions being resumed, closing the open pause period
+    pub fn record_resume(&mut self, current_timestamp: i64) {
+        if self.pause_count > self.resume_count {
+            let paused_seconds = current_timestamp.saturating_sub(self.last_paused_at).max(0);
+            self.total_paused_seconds = self.total_paused_seconds.saturating_add(paused_seconds);
+        }
+        self.resume_count = self.resume_count.saturating_add(1);
+    }
+}
+
+/// Investors that must not receive payouts (e.g. sanctioned wallets)
+/// 
+/// Optional per quote mint. When passed to `process_investor_page`,
+/// blacklisted investors are skipped and their share stays in the treasury.
+#[account]
+pub struct InvestorBlacklist {
+    /// Quote mint this blacklist applies to
+    pub quote_mint: Pubkey,
+    
+    /// Blacklisted investor wallets (bounded by MAX_BLACKLISTED_INVESTORS)
+    pub blacklisted: Vec<Pubkey>,
+    
+    /// Cached PDA bump
+    pub bump: u8,
+}
+
+impl InvestorBlacklist {
+    pub const INIT_SPACE: usize = 32 +  // quote_mint
+                                   4 + MAX_BLACKLISTED_INVESTORS * 32 + // blacklisted
+                                   1;    // bump
+
+    /// Derive the PDA for an investor blacklist
+    pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"blacklist", quote_mint.as_ref()], program_id)
+    }
+
+    /// Check if an investor is blacklisted
+    pub fn is_blacklisted(&self, investor: &Pubkey) -> bool {
+        self.blacklisted.contains(investor)
+    }
+
+    /// Add an investor to the blacklist
+    pub fn add(&mut self, investor: Pubkey) -> Result<()> {
+        require!(
+            !self.is_blacklisted(&investor),
+            FeeRouterError::InvestorAlreadyBlacklisted
+        );
+        require!(
+            self.blacklisted.len() < MAX_BLACKLISTED_INVESTORS,
+            FeeRouterError::BlacklistFull
+        );
+        self.blacklisted.push(investor);
+        Ok(())
+    }
+
+    /// Remove an investor from the blacklist
+    pub fn remove(&mut self, investor: Pubkey) -> Result<()> {
+        let index = self
+            .blacklisted
+            .iter()
+            .position(|entry| *entry == investor)
+            .ok_or(FeeRouterError::InvestorNotBlacklisted)?;
+        self.blacklisted.swap_remove(index);
+        Ok(())
+    }
+}
+
+/// Investor allocations (their streams' total deposits) registered for a quote mint
+/// 
+/// One per quote mint, required by `start_daily_distribution`, which checks
+/// the registered total against the policy's Y0.
+#[account]
+pub struct AllocationRegistry {
+    /// Quote mint this registry applies to
+    pub quote_mint: Pubkey,
+    
+    /// Sum of the deposited amounts of every registered stream
+    pub total_registered_allocation: u64,
+    
+    /// Streams already counted (bounded by MAX_REGISTERED_STREAMS)
+    pub registered_streams: Vec<Pubkey>,
+    
+    /// Cached PDA bump
+    pub bump: u8,
+}
+
+impl AllocationRegistry {
+    pub const INIT_SPACE: usize = 32 +  // quote_mint
+                                   8 +   // total_registered_allocation
+                                   4 + MAX_REGISTERED_STREAMS * 32 + // registered_streams
+                                   1;    // bump
+
+    /// Derive the PDA for an allocation registry
+    pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"allocation_registry", quote_mint.as_ref()], program_id)
+    }
+
+    /// Check if a stream's allocation is already counted
+    pub fn is_registered(&self, stream: &Pubkey) -> bool {
+        self.registered_streams.contains(stream)
+    }
+
+    /// Count a stream's deposited amount towards the registered allocation
+    pub fn register(&mut self, stream: Pubkey, deposited_amount: u64) -> Result<()> {
+        require!(
+            !self.is_registered(&stream),
+            FeeRouterError::StreamAlreadyRegistered
+        );
+        require!(
+            self.registered_streams.len() < MAX_REGISTERED_STREAMS,
+            FeeRouterError::AllocationRegistryFull
+        );
+        self.registered_streams.push(stream);
+        self.total_registered_allocation = self.total_registered_allocation.saturating_add(deposited_amount);
+        Ok(())
+    }
+
+    /// Check whether the registered allocation exceeds Y0 beyond the rounding buffer
+    /// 
+    /// Up to `ALLOCATION_Y0_BUFFER_PERCENT` above Y0 is tolerated.
+    pub fn exceeds_y0(&self, y0_total_allocation: u64) -> bool {
+        let limit = y0_total_allocation as u128 * (100 + ALLOCATION_Y0_BUFFER_PERCENT) as u128 / 100;
+        self.total_registered_allocation as u128 > limit
+    }
+
+    /// Registered allocation above Y0 in basis points of Y0 (0 when within Y0)
+    pub fn overage_bps(&self, y0_total_allocation: u64) -> u64 {
+        if y0_total_allocation == 0 {
+            return 0;
+        }
+        let overage = self.total_registered_allocation.saturating_sub(y0_total_allocation);
+        (overage as u128 * BPS_DENOMINATOR as u128 / y0_total_allocation as u128).min(u64::MAX as u128) as u64
+    }
+}
+
+/// A stream that could not be read while processing a page
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamErrorEntry {
+    /// The Streamflow stream account that failed
+    pub stream_account: Pubkey,
+    
+    /// Why the stream failed
+    pub error_type: StreamErrorType,
+}
+
+/// Streams that failed during a day's distribution
+/// 
+/// Created by `start_daily_distribution` and required by
+/// `process_investor_page`, which records failed streams so they can be
+/// reviewed with `get_stream_errors` once the day is complete.
+#[account]
+pub struct StreamErrorLog {
+    /// Distribution day (Unix timestamp / 86400)
+    pub distribution_day: i64,
+    
+    /// Quote mint being distributed
+    pub quote_mint: Pubkey,
+    
+    /// Recorded errors (bounded by MAX_STREAM_ERROR_LOG_ENTRIES)
+    pub entries: Vec<StreamErrorEntry>,
+    
+    /// Errors seen, including those dropped once the log was full
+    pub total_errors: u32,
+    
+    /// Cranker that paid the log's rent (receives it back on close)
+    pub payer: Pubkey,
+    
+    /// Cached PDA bump
+    pub bump: u8,
+}
+
+impl StreamErrorLog {
+    pub const INIT_SPACE: usize = 8 +   // distribution_day
+                                   32 +  // quote_mint
+                                   4 + MAX_STREAM_ERROR_LOG_ENTRIES * (32 + 1) + // entries
+                                   4 +   // total_errors
+                                   32 +  // payer
+                                   1;    // bump
+
+    /// Derive the PDA for a day's stream error log
+    pub fn derive_pda(distribution_day: i64, quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"stream_errors",
+                distribution_day.to_string().as_bytes(),
+                quote_mint.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Record a failed stream
+    /// 
+    /// Once the log is full only `total_errors` is incremented.
+    /// Returns whether the entry was stored.
+    pub fn record(&mut self, stream_account: Pubkey, error_type: StreamErrorType) -> bool {
+        self.total_errors = self.total_errors.saturating_add(1);
+        if self.entries.len() >= MAX_STREAM_ERROR_LOG_ENTRIES {
+            return false;
+        }
+        self.entries.push(StreamErrorEntry { stream_account, error_type });
+        true
+    }
+
+    /// Number of errors that did not fit in the log
+    pub fn dropped_errors(&self) -> u32 {
+        self.total_errors.saturating_sub(self.entries.len() as u32)
+    }
+}
+
+/// Investors registered for distributions of a quote mint
+/// 
+/// Entries live in a linked list of `InvestorRegistryPage` accounts so the
+/// registry can grow past the size of a single account. Days started with
+/// the registry process it one registry page at a time.
+#[account]
+pub struct InvestorRegistryState {
+    /// Quote mint this registry applies to
+    pub quote_mint: Pubkey,
+    
+    /// Maximum number of investors (bounded by MAX_REGISTRY_INVESTORS)
+    pub investor_capacity: u32,
+    
+    /// Number of registered investors
+    pub investor_count: u32,
+    
+    /// Number of registry pages created
+    pub page_count: u32,
+    
+    /// Index of the page new investors are appended to
+    pub last_page_index: u32,
+    
+    /// Cached PDA bump
+    pub bump: u8,
+}
+
+impl InvestorRegistryState {
+    pub const INIT_SPACE: usize = 32 +  // quote_mint
+                                   4 +   // investor_capacity
+                                   4 +   // investor_count
+                                   4 +   // page_count
+                                   4 +   // last_page_index
+                                   1;    // bump
+
+    /// Derive the PDA for an investor registry
+    pub fn derive_pda(quote_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"investor_registry", quote_mint.as_ref()], program_id)
+    }
+
+    /// Check if another investor fits in the registry
+    pub fn has_capacity(&self) -> bool {
+        self.investor_count < self.investor_capacity
+    }
+
+    /// Index of the page the next investor goes to
+    /// 
+    /// `last_page_len` is the number of entries in the current last page.
+    pub fn next_entry_page_index(&self, last_page_len: usize) -> u32 {
+        if self.page_count == 0 {
+            0
+        } else if last_page_len >= INVESTOR_REGISTRY_PAGE_SIZE {
+            self.last_page_index.saturating_add(1)
+        } else {
+            self.last_page_index
+        }
+    }
+
+    /// Record an investor appended to page `page_index`
+    pub fn record_added(&mut self, page_index: u32) {
+        if self.page_count == 0 || page_index > self.last_page_index {
+            self.page_count = self.page_count.saturating_add(1);
+        }
+        self.last_page_index = page_index;
+        self.investor_count = self.investor_count.saturating_add(1);
+    }
+
+    /// Registry page and offset within it of the investor at `cursor`
+    pub fn position_of(cursor: u32) -> (u32, usize) {
+        let page_size = INVESTOR_REGISTRY_PAGE_SIZE as u32;
+        (cursor / page_size, (cursor % page_size) as usize)
+    }
+}
+
+/// A registered investor
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvestorEntry {
+    /// The investor's wallet address
+    pub investor: Pubkey,
+    
+    /// The investor's Streamflow stream account
+    pub stream_account: Pubkey,
+}
+
+impl InvestorEntry {
+    pub const SIZE: usize = 32 + // investor
+                            32;  // stream_account
+}
+
+/// One page of an investor registry
+#[account]
+pub struct InvestorRegistryPage {
+    /// Registry this page belongs to
+    pub registry: Pubkey,
+    
+    /// Position of the page in the registry
+    pub page_index: u32,
+    
+    /// Following page (default until one is created)
+    pub next_page: Pubkey,
+    
+    /// Registered investors (bounded by INVESTOR_REGISTRY_PAGE_SIZE)
+    pub entries: Vec<InvestorEntry>,
+    
+    /// Cached PDA bump
+    pub bump: u8,
+}
+
+impl InvestorRegistryPage {
+    pub const INIT_SPACE: usize = 32 +  // registry
+                                   4 +   // page_index
+                                   32 +  // next_page
+                                   4 + INVESTOR_REGISTRY_PAGE_SIZE * InvestorEntry::SIZE + // entries
+                                   1;    // bump
+
+    /// Derive the PDA for a registry page
+    pub fn derive_pda(registry: &Pubkey, page_index: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"registry_page",
+                registry.as_ref(),
+                &page_index.to_le_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Check if the page has room for another investor
+    pub fn has_room(&self) -> bool {
+        self.entries.len() < INVESTOR_REGISTRY_PAGE_SIZE
+    }
+
+    /// Append an investor to the page
+    pub fn push(&mut self, entry: InvestorEntry) -> Result<()> {
+        require!(self.has_room(), FeeRouterError::InvestorRegistryFull);
+        self.entries.push(entry);
+        Ok(())
     }
 }
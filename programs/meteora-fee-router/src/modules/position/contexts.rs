@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_spl::token_2022::Token2022;
-use crate::integrations::meteora::POOL_AUTHORITY;
+use crate::integrations::meteora::{METEORA_CP_AMM_PROGRAM_ID, POOL_AUTHORITY};
 use crate::modules::position::state::PositionMetadata;
+use crate::modules::distribution::state::PolicyState;
+use crate::modules::claiming::state::TreasuryState;
 use crate::shared::constants::*;
+use crate::errors::FeeRouterError;
 
 /// Accounts required to initialize the honorary fee position
 #[derive(Accounts)]
@@ -59,8 +62,8 @@ pub struct InitializePosition<'info> {
     /// CHECK: Derived by Meteora program
     pub event_authority: UncheckedAccount<'info>,
 
-    /// Meteora CP-AMM program
-    /// CHECK: Meteora program ID
+    /// Meteora CP-AMM program (Raydium CLMM program for Raydium pools)
+    /// CHECK: Meteora program ID, or verified against the Raydium program ID
     pub meteora_program: UncheckedAccount<'info>,
 
     /// System program
@@ -91,6 +94,19 @@ pub struct InitializePosition<'info> {
     #[account(mut)]
     pub token_b_vault: UncheckedAccount<'info>,
 
+    /// Policy state (provides the position creation fee)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Policy authority receiving the position creation fee
+    /// CHECK: Verified by address constraint
+    #[account(mut, address = policy_state.policy_authority)]
+    pub policy_authority: UncheckedAccount<'info>,
+
     /// Position metadata account to store position information
     #[account(
         init,
@@ -104,3 +120,170 @@ pub struct InitializePosition<'info> {
     /// Rent sysvar
     pub rent: Sysvar<'info, Rent>,
 }
+
+/// Accounts required to set the position value used for the fee APR
+#[derive(Accounts)]
+pub struct UpdatePositionValue<'info> {
+    /// The policy authority
+    pub authority: Signer<'info>,
+
+    /// The quote mint of the position
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Position NFT mint
+    pub position_nft_mint: Account<'info, Mint>,
+
+    /// Policy state (holds the authority allowed to set the value)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.policy_authority == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Position metadata to update
+    #[account(
+        mut,
+        seeds = [b"position_metadata", position_nft_mint.key().as_ref()],
+        bump,
+        constraint = position_metadata.quote_mint == quote_mint.key() @ FeeRouterError::PositionMetadataMismatch,
+    )]
+    pub position_metadata: Account<'info, PositionMetadata>,
+}
+
+/// Accounts required to retire the honorary position
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    /// The authority that created the position (receives the rent and liquidity)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The vault account (used for PDA derivation)
+    /// CHECK: Used as seed for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+
+    /// PDA that owns the honorary position
+    /// Seeds: [VAULT_SEED, vault, "investor_fee_pos_owner"]
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POSITION_OWNER_SEED],
+        bump = position_metadata.position_owner_bump,
+    )]
+    /// CHECK: PDA owner of the position
+    pub position_owner_pda: UncheckedAccount<'info>,
+
+    /// Position NFT mint (closed by Meteora)
+    /// CHECK: Seed of the position metadata PDA, validated by Meteora
+    #[account(mut)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    /// Position NFT account (closed by Meteora)
+    /// CHECK: Derived by Meteora program
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// The Meteora pool account
+    /// CHECK: Validated against position metadata
+    #[account(
+        mut,
+        constraint = pool.key() == position_metadata.pool @ FeeRouterError::PositionMetadataMismatch,
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// The Meteora position account
+    /// CHECK: Validated against position metadata
+    #[account(
+        mut,
+        constraint = position.key() == position_metadata.position @ FeeRouterError::PositionMetadataMismatch,
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    /// Quote mint
+    #[account(constraint = quote_mint.key() == position_metadata.quote_mint @ FeeRouterError::PositionMetadataMismatch)]
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Base mint
+    #[account(constraint = base_mint.key() == position_metadata.base_mint @ FeeRouterError::PositionMetadataMismatch)]
+    pub base_mint: Account<'info, Mint>,
+
+    /// Authority's quote token account (receives the quote liquidity)
+    #[account(
+        mut,
+        constraint = authority_quote_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = authority_quote_ata.owner == authority.key(),
+    )]
+    pub authority_quote_ata: Account<'info, TokenAccount>,
+
+    /// Authority's base token account (receives any base liquidity)
+    #[account(
+        mut,
+        constraint = authority_base_ata.mint == base_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+        constraint = authority_base_ata.owner == authority.key(),
+    )]
+    pub authority_base_ata: Account<'info, TokenAccount>,
+
+    /// Treasury state account (records the final fees)
+    #[account(
+        mut,
+        seeds = [b"treasury_state", quote_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.quote_mint == quote_mint.key(),
+        constraint = treasury_state.is_authorized_claimer(&position_owner_pda.key()) @ FeeRouterError::PositionOwnerMismatch,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// Treasury ATA (receives the final fees)
+    #[account(
+        mut,
+        constraint = treasury_ata.key() == treasury_state.treasury_ata,
+        constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    /// Policy state (its authority closes positions that predate the recorded authority)
+    #[account(
+        seeds = [b"policy", quote_mint.key().as_ref()],
+        bump = policy_state.bump,
+        constraint = policy_state.quote_mint == quote_mint.key(),
+    )]
+    pub policy_state: Account<'info, PolicyState>,
+
+    /// Pool vault holding the quote token
+    /// CHECK: Validated against the Meteora pool in the handler
+    #[account(mut)]
+    pub quote_vault: UncheckedAccount<'info>,
+
+    /// Pool vault holding the base token
+    /// CHECK: Validated against the Meteora pool in the handler
+    #[account(mut)]
+    pub base_vault: UncheckedAccount<'info>,
+
+    /// Position metadata, closed to the authority
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"position_metadata", position_nft_mint.key().as_ref()],
+        bump,
+        constraint = position_metadata.close_authority(&policy_state.policy_authority) == authority.key() @ FeeRouterError::Unauthorized,
+    )]
+    pub position_metadata: Account<'info, PositionMetadata>,
+
+    /// Meteora pool authority
+    /// CHECK: Verified by address constraint
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Event authority PDA (required by Meteora)
+    /// CHECK: Derived by Meteora program
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// Meteora CP-AMM program
+    /// CHECK: Verified by address constraint
+    #[account(address = METEORA_CP_AMM_PROGRAM_ID)]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// Token program (for the pool tokens)
+    pub token_program: Program<'info, Token>,
+
+    /// Token2022 program (for the position NFT)
+    pub position_nft_token_program: Program<'info, Token2022>,
+}
@@ -34,3 +34,54 @@ pub struct PositionInitializationFailed {
     /// Timestamp of failure
     pub timestamp: i64,
 }
+
+/// Event emitted when the position creation fee is paid to the policy authority
+#[event]
+pub struct PositionCreationFeeCollected {
+    /// Lamports paid
+    pub amount: u64,
+    
+    /// The account that paid the fee
+    pub payer: Pubkey,
+    
+    /// Timestamp of payment
+    pub timestamp: i64,
+}
+
+/// Event emitted when the position value used for the fee APR is updated
+#[event]
+pub struct PositionValueUpdated {
+    /// The position account pubkey
+    pub position: Pubkey,
+    
+    /// Previous position value in quote tokens
+    pub old_position_value_quote: u64,
+    
+    /// New position value in quote tokens
+    pub new_position_value_quote: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when the honorary position is retired
+#[event]
+pub struct PositionClosed {
+    /// The closed position account pubkey
+    pub position: Pubkey,
+    
+    /// The pool the position belonged to
+    pub pool: Pubkey,
+    
+    /// The authority that closed the position
+    pub authority: Pubkey,
+    
+    /// Liquidity removed before closing
+    pub liquidity_removed: u128,
+    
+    /// Quote fees claimed into the treasury before closing
+    pub final_fees_claimed: u64,
+    
+    /// Timestamp of closing
+    pub timestamp: i64,
+}
@@ -1,36 +1,92 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::modules::position::contexts::*;
 use crate::modules::position::events::*;
-use crate::modules::position::state::PositionMetadata;
+use crate::modules::position::state::{PoolType, PositionMetadata};
 use crate::integrations::meteora;
+use crate::integrations::raydium_clmm;
 use crate::shared::constants::*;
+use crate::shared::rent;
+use crate::errors::FeeRouterError;
 
 /// Initialize the honorary fee position
 /// 
-/// This creates a DAMM V2 LP position owned by our program PDA that:
+/// This creates a DAMM V2 (or Raydium CLMM) LP position owned by our program PDA that:
 /// - Accrues fees exclusively in the quote mint
 /// - Is owned by the InvestorFeePositionOwnerPda
 /// - Validates pool configuration for quote-only fees
+/// - Charges the policy's one-time position creation fee (if non-zero)
+/// 
+/// For Raydium CLMM pools `meteora_program` must be the Raydium CLMM program,
+/// token A/B accounts are token 0/1 of the pool, and the remaining accounts are
+/// `[metadata_account, protocol_position, tick_array_lower, tick_array_upper,
+/// associated_token_program, metadata_program, spl_token_program]`.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
+/// * `pool_type` - The AMM backend of the pool
 /// 
 /// # Returns
 /// * `Result<()>` - Success or error
-pub fn initialize_position(ctx: Context<InitializePosition>) -> Result<()> {
-    msg!("Initializing honorary fee position");
+pub fn initialize_position<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializePosition<'info>>,
+    pool_type: PoolType,
+) -> Result<()> {
+    msg!("Initializing honorary fee position ({:?})", pool_type);
 
     // Step 1 - Deserialize and validate pool
-    let pool_data = ctx.accounts.pool.try_borrow_data()?;
-    let pool = bytemuck::from_bytes::<meteora::Pool>(&pool_data[8..]); // Skip 8-byte discriminator
-    
-    meteora::validation::preflight_validation(
-        pool,
-        &ctx.accounts.base_mint.key(),
-        &ctx.accounts.quote_mint.key(),
-    )?;
+    let raydium_params = match pool_type {
+        PoolType::MeteoraDAMM => {
+            let pool_data = ctx.accounts.pool.try_borrow_data()?;
+            let pool = bytemuck::from_bytes::<meteora::Pool>(&pool_data[8..]); // Skip 8-byte discriminator
+
+            meteora::validation::preflight_validation(
+                pool,
+                &ctx.accounts.base_mint.key(),
+                &ctx.accounts.quote_mint.key(),
+            )?;
+            None
+        }
+        PoolType::RaydiumCLMM => Some(raydium_position_params(ctx.accounts)?),
+    };
+
+    // The payer funds the position account created by the AMM
+    // (Raydium sizes its accounts inside open_position_v2)
+    if pool_type == PoolType::MeteoraDAMM {
+        let position_rent = rent::required_rent(meteora::POSITION_ACCOUNT_SIZE)?;
+        rent::require_rent_lamports(&ctx.accounts.authority, position_rent)?;
+    }
+
+    // Step 2 - Collect the position creation fee (if configured)
+    let clock = Clock::get()?;
+    let creation_fee = ctx.accounts.policy_state.position_creation_fee_lamports;
+    if creation_fee > 0 {
+        require!(
+            ctx.accounts.authority.lamports() >= creation_fee,
+            FeeRouterError::InsufficientPositionCreationFee
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.policy_authority.to_account_info(),
+                },
+            ),
+            creation_fee,
+        )?;
+
+        emit!(PositionCreationFeeCollected {
+            amount: creation_fee,
+            payer: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Collected position creation fee: {} lamports", creation_fee);
+    }
 
-    // Step 2 - Create DAMM V2 position via CPI
+    // Step 3 - Create the position via CPI
     // The position will be owned by our position_owner_pda
     let vault_key = ctx.accounts.vault.key();
     let bump = ctx.bumps.position_owner_pda;
@@ -42,51 +98,59 @@ pub fn initialize_position(ctx: Context<InitializePosition>) -> Result<()> {
     ];
     let signer_seeds = &[&owner_seeds[..]];
 
-    meteora::cpi::create_position(
-        ctx.accounts.position_owner_pda.to_account_info(),
-        ctx.accounts.position_nft_mint.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
-        ctx.accounts.pool.to_account_info(),
-        ctx.accounts.position.to_account_info(),
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.authority.to_account_info(), // payer
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.event_authority.to_account_info(),
-        ctx.accounts.meteora_program.to_account_info(),
-        Some(signer_seeds),
-    )?;
+    match raydium_params {
+        None => {
+            // DAMM V2: create the position, then add liquidity
+            meteora::cpi::create_position(
+                ctx.accounts.position_owner_pda.to_account_info(),
+                ctx.accounts.position_nft_mint.to_account_info(),
+                ctx.accounts.position_nft_account.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.position.to_account_info(),
+                ctx.accounts.pool_authority.to_account_info(),
+                ctx.accounts.authority.to_account_info(), // payer
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.event_authority.to_account_info(),
+                ctx.accounts.meteora_program.to_account_info(),
+                Some(signer_seeds),
+            )?;
 
-    // Step 3 - Add minimal liquidity to activate fee collection
-    msg!("Adding minimal liquidity to activate position");
-    
-    // Determine quote amount for minimal liquidity (e.g., 1000 units)
-    let minimal_quote_amount = 1000u64;
-    let liquidity_params = meteora::AddLiquidityParameters::minimal_quote_only(minimal_quote_amount);
-
-    meteora::cpi::add_liquidity(
-        ctx.accounts.pool.to_account_info(),
-        ctx.accounts.position.to_account_info(),
-        ctx.accounts.authority_token_a.to_account_info(),
-        ctx.accounts.authority_token_b.to_account_info(),
-        ctx.accounts.token_a_vault.to_account_info(),
-        ctx.accounts.token_b_vault.to_account_info(),
-        ctx.accounts.base_mint.to_account_info(),
-        ctx.accounts.quote_mint.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
-        ctx.accounts.position_owner_pda.to_account_info(),
-        ctx.accounts.token_a_program.to_account_info(),
-        ctx.accounts.token_b_program.to_account_info(),
-        ctx.accounts.event_authority.to_account_info(),
-        ctx.accounts.meteora_program.to_account_info(),
-        liquidity_params,
-        Some(signer_seeds),
-    )?;
+            // Step 4 - Add minimal liquidity to activate fee collection
+            msg!("Adding minimal liquidity to activate position");
+            
+            // Determine quote amount for minimal liquidity (e.g., 1000 units)
+            let minimal_quote_amount = 1000u64;
+            let liquidity_params = meteora::AddLiquidityParameters::minimal_quote_only(minimal_quote_amount);
 
-    // Step 4 - Initialize position metadata
+            meteora::cpi::add_liquidity(
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.position.to_account_info(),
+                ctx.accounts.authority_token_a.to_account_info(),
+                ctx.accounts.authority_token_b.to_account_info(),
+                ctx.accounts.token_a_vault.to_account_info(),
+                ctx.accounts.token_b_vault.to_account_info(),
+                ctx.accounts.base_mint.to_account_info(),
+                ctx.accounts.quote_mint.to_account_info(),
+                ctx.accounts.position_nft_account.to_account_info(),
+                ctx.accounts.position_owner_pda.to_account_info(),
+                ctx.accounts.token_a_program.to_account_info(),
+                ctx.accounts.token_b_program.to_account_info(),
+                ctx.accounts.event_authority.to_account_info(),
+                ctx.accounts.meteora_program.to_account_info(),
+                liquidity_params,
+                Some(signer_seeds),
+            )?;
+        }
+        Some(params) => {
+            // Raydium CLMM: open_position_v2 creates the position and adds liquidity
+            create_raydium_position(&ctx, params, signer_seeds)?;
+        }
+    }
+
+    // Step 5 - Initialize position metadata
     msg!("Storing position metadata");
     
-    let clock = Clock::get()?;
     ctx.accounts.position_metadata.set_inner(PositionMetadata {
         position: ctx.accounts.position.key(),
         pool: ctx.accounts.pool.key(),
@@ -94,10 +158,15 @@ pub fn initialize_position(ctx: Context<InitializePosition>) -> Result<()> {
         base_mint: ctx.accounts.base_mint.key(),
         created_at: clock.unix_timestamp,
         position_owner_bump: bump,
-        reserved: [0; 64],
+        pool_type,
+        last_claim_timestamp: 0,
+        total_fees_claimed: 0,
+        position_value_quote: 0,
+        authority: ctx.accounts.authority.key(),
+        reserved: [0; 8],
     });
 
-    // Step 5 - Emit event
+    // Step 6 - Emit event
     emit!(HonoraryPositionInitialized {
         position: ctx.accounts.position.key(),
         pool: ctx.accounts.pool.key(),
@@ -110,3 +179,275 @@ pub fn initialize_position(ctx: Context<InitializePosition>) -> Result<()> {
     msg!("✅ Honorary position initialized with liquidity and metadata successfully");
     Ok(())
 }
+
+/// Number of extra accounts required to open a Raydium CLMM position
+const RAYDIUM_OPEN_POSITION_REMAINING_ACCOUNTS: usize = 7;
+
+/// Validate a Raydium CLMM pool and build the quote-only position parameters
+fn raydium_position_params(accounts: &InitializePosition) -> Result<raydium_clmm::RaydiumOpenPositionParameters> {
+    require!(
+        accounts.meteora_program.key() == raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
+        anchor_lang::error::ErrorCode::InvalidProgramId
+    );
+    require!(
+        *accounts.pool.owner == raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
+        FeeRouterError::InvalidPoolConfig
+    );
+
+    let pool_data = accounts.pool.try_borrow_data()?;
+    let pool = raydium_clmm::PoolState::from_account_data(&pool_data)
+        .ok_or(FeeRouterError::InvalidPoolConfig)?;
+    let quote_mint = accounts.quote_mint.key();
+
+    raydium_clmm::validation::preflight_validation(pool, &accounts.base_mint.key(), &quote_mint)?;
+
+    let (tick_lower, tick_upper) = raydium_clmm::validation::quote_only_tick_range(pool, &quote_mint)?;
+    let tick_spacing = pool.tick_spacing;
+    Ok(raydium_clmm::RaydiumOpenPositionParameters::minimal_quote_only(
+        (tick_lower, tick_upper),
+        (
+            raydium_clmm::tick_array_start_index(tick_lower, tick_spacing),
+            raydium_clmm::tick_array_start_index(tick_upper, tick_spacing),
+        ),
+        pool.is_quote_token_0(&quote_mint),
+        1000, // Minimal quote amount, as for DAMM V2
+    ))
+}
+
+/// Open the honorary position in a Raydium CLMM pool
+fn create_raydium_position<'info>(
+    ctx: &Context<'_, '_, '_, 'info, InitializePosition<'info>>,
+    params: raydium_clmm::RaydiumOpenPositionParameters,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() >= RAYDIUM_OPEN_POSITION_REMAINING_ACCOUNTS,
+        anchor_lang::error::ErrorCode::AccountNotEnoughKeys
+    );
+
+    // Vault mints follow the pool's token 0 / token 1 order
+    let quote_is_token_0 = params.base_flag == Some(true);
+    let (vault_0_mint, vault_1_mint) = if quote_is_token_0 {
+        (ctx.accounts.quote_mint.to_account_info(), ctx.accounts.base_mint.to_account_info())
+    } else {
+        (ctx.accounts.base_mint.to_account_info(), ctx.accounts.quote_mint.to_account_info())
+    };
+
+    raydium_clmm::cpi::raydium_create_position(
+        raydium_clmm::RaydiumOpenPositionAccounts {
+            payer: ctx.accounts.authority.to_account_info(),
+            position_nft_owner: ctx.accounts.position_owner_pda.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            metadata_account: remaining_accounts[0].clone(),
+            pool_state: ctx.accounts.pool.to_account_info(),
+            protocol_position: remaining_accounts[1].clone(),
+            tick_array_lower: remaining_accounts[2].clone(),
+            tick_array_upper: remaining_accounts[3].clone(),
+            personal_position: ctx.accounts.position.to_account_info(),
+            token_account_0: ctx.accounts.authority_token_a.to_account_info(),
+            token_account_1: ctx.accounts.authority_token_b.to_account_info(),
+            token_vault_0: ctx.accounts.token_a_vault.to_account_info(),
+            token_vault_1: ctx.accounts.token_b_vault.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: remaining_accounts[6].clone(),
+            associated_token_program: remaining_accounts[4].clone(),
+            metadata_program: remaining_accounts[5].clone(),
+            token_program_2022: ctx.accounts.token_program.to_account_info(),
+            vault_0_mint,
+            vault_1_mint,
+            raydium_program: ctx.accounts.meteora_program.to_account_info(),
+        },
+        params,
+        Some(signer_seeds),
+    )
+}
+
+/// Set the quote value of the honorary position
+/// 
+/// The value is the denominator of the fee APR reported on each claim. Only
+/// the policy authority can set it.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `position_value_quote` - Position value in quote tokens (0 = unknown)
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn update_position_value(ctx: Context<UpdatePositionValue>, position_value_quote: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let metadata = &mut ctx.accounts.position_metadata;
+    let old_position_value_quote = metadata.position_value_quote;
+    metadata.position_value_quote = position_value_quote;
+
+    emit!(PositionValueUpdated {
+        position: metadata.position,
+        old_position_value_quote,
+        new_position_value_quote: position_value_quote,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Position value updated: {} -> {}", old_position_value_quote, position_value_quote);
+    Ok(())
+}
+
+/// Retire the honorary position
+/// 
+/// Only the authority that created the position can close it (the policy
+/// authority for positions that predate the recorded authority). The handler:
+/// 1. Removes the position's unlocked liquidity
+/// 2. Claims the final fees into the treasury (if requested)
+/// 3. Closes the Meteora position via CPI
+/// 4. Closes the position metadata, returning its rent to the authority
+/// 
+/// The liquidity is paid to the authority's token accounts. The final quote
+/// fees belong to the investors, so they go to the treasury ATA and are
+/// recorded in the treasury state. Only DAMM V2 positions can be closed.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `claim_final_fees` - Whether to claim the outstanding fees before closing
+/// 
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn close_position(ctx: Context<ClosePosition>, claim_final_fees: bool) -> Result<()> {
+    msg!("Closing honorary fee position");
+
+    require!(
+        ctx.accounts.position_metadata.pool_type == PoolType::MeteoraDAMM,
+        FeeRouterError::InvalidPoolConfig
+    );
+
+    let pool_state = meteora::cpi::read_pool(&ctx.accounts.pool.to_account_info())?;
+    let (token_a, token_b) = meteora::cpi::order_claim_token_sides(
+        &pool_state,
+        meteora::cpi::ClaimTokenSide {
+            token_account: ctx.accounts.authority_quote_ata.to_account_info(),
+            vault: ctx.accounts.quote_vault.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+        },
+        meteora::cpi::ClaimTokenSide {
+            token_account: ctx.accounts.authority_base_ata.to_account_info(),
+            vault: ctx.accounts.base_vault.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
+        },
+    )?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let bump = ctx.accounts.position_metadata.position_owner_bump;
+    let owner_seeds = &[
+        VAULT_SEED,
+        vault_key.as_ref(),
+        POSITION_OWNER_SEED,
+        &[bump],
+    ];
+    let signer_seeds = &[&owner_seeds[..]];
+    let clock = Clock::get()?;
+
+    // Step 1 - Pull the seeded liquidity
+    let liquidity_removed = meteora::cpi::unlocked_liquidity(&ctx.accounts.position.to_account_info())?;
+    if liquidity_removed > 0 {
+        meteora::cpi::remove_liquidity(
+            meteora::cpi::RemoveLiquidityAccounts {
+                pool_authority: ctx.accounts.pool_authority.to_account_info(),
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                token_a_account: token_a.token_account.clone(),
+                token_b_account: token_b.token_account.clone(),
+                token_a_vault: token_a.vault.clone(),
+                token_b_vault: token_b.vault.clone(),
+                token_a_mint: token_a.mint.clone(),
+                token_b_mint: token_b.mint.clone(),
+                position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+                owner: ctx.accounts.position_owner_pda.to_account_info(),
+                token_a_program: ctx.accounts.token_program.to_account_info(),
+                token_b_program: ctx.accounts.token_program.to_account_info(),
+                event_authority: ctx.accounts.event_authority.to_account_info(),
+                meteora_program: ctx.accounts.meteora_program.to_account_info(),
+            },
+            meteora::RemoveLiquidityParameters {
+                liquidity_delta: liquidity_removed,
+                token_a_amount_threshold: 0,
+                token_b_amount_threshold: 0,
+            },
+            Some(signer_seeds),
+        ).map_err(|_| FeeRouterError::MeteoraCpiFailed)?;
+    }
+
+    // Step 2 - Claim the final quote fees into the treasury
+    let mut final_fees_claimed = 0;
+    if claim_final_fees {
+        let (fee_token_a, fee_token_b) = meteora::cpi::order_claim_token_sides(
+            &pool_state,
+            meteora::cpi::ClaimTokenSide {
+                token_account: ctx.accounts.treasury_ata.to_account_info(),
+                vault: ctx.accounts.quote_vault.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+            },
+            meteora::cpi::ClaimTokenSide {
+                token_account: ctx.accounts.authority_base_ata.to_account_info(),
+                vault: ctx.accounts.base_vault.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+            },
+        )?;
+        let treasury_balance_before = ctx.accounts.treasury_ata.amount;
+
+        meteora::cpi::claim_position_fee(
+            ctx.accounts.pool_authority.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.position.to_account_info(),
+            fee_token_a.token_account,
+            fee_token_b.token_account,
+            fee_token_a.vault,
+            fee_token_b.vault,
+            fee_token_a.mint,
+            fee_token_b.mint,
+            ctx.accounts.position_nft_account.to_account_info(),
+            ctx.accounts.position_owner_pda.to_account_info(),
+            ctx.accounts.token_program.to_account_info(), // token_a_program
+            ctx.accounts.token_program.to_account_info(), // token_b_program
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.accounts.meteora_program.to_account_info(),
+            Some(signer_seeds),
+        ).map_err(|_| FeeRouterError::MeteoraCpiFailed)?;
+
+        ctx.accounts.treasury_ata.reload()?;
+        final_fees_claimed = ctx.accounts.treasury_ata.amount.saturating_sub(treasury_balance_before);
+        if final_fees_claimed > 0 {
+            ctx.accounts.treasury_state.require_allowed_mint(&ctx.accounts.quote_mint.key())?;
+            ctx.accounts.treasury_state.record_claim(final_fees_claimed, clock.unix_timestamp);
+        }
+    }
+
+    // Step 3 - Close the Meteora position (its rent goes to the authority)
+    meteora::cpi::close_position(
+        meteora::cpi::ClosePositionAccounts {
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            rent_receiver: ctx.accounts.authority.to_account_info(),
+            owner: ctx.accounts.position_owner_pda.to_account_info(),
+            token_program: ctx.accounts.position_nft_token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            meteora_program: ctx.accounts.meteora_program.to_account_info(),
+        },
+        Some(signer_seeds),
+    ).map_err(|_| FeeRouterError::MeteoraCpiFailed)?;
+
+    // Step 4 - The metadata account is closed to the authority on exit
+    emit!(PositionClosed {
+        position: ctx.accounts.position.key(),
+        pool: ctx.accounts.pool.key(),
+        authority: ctx.accounts.authority.key(),
+        liquidity_removed,
+        final_fees_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Honorary position closed (liquidity removed: {}, final fees: {})", liquidity_removed, final_fees_claimed);
+    Ok(())
+}
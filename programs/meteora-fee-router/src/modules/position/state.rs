@@ -1,4 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+use crate::shared::constants::{BPS_DENOMINATOR, POSITION_OWNER_SEED, SECONDS_PER_YEAR, VAULT_SEED};
+
+/// AMM backend the honorary position lives in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PoolType {
+    /// Meteora DAMM V2 (CP-AMM)
+    #[default]
+    MeteoraDAMM,
+    /// Raydium Concentrated Liquidity
+    RaydiumCLMM,
+}
 
 /// Optional: Position metadata account
 /// 
@@ -24,8 +36,23 @@ pub struct PositionMetadata {
     /// The bump seed for the position owner PDA
     pub position_owner_bump: u8,
     
+    /// The AMM backend of the pool
+    pub pool_type: PoolType,
+    
+    /// Last time fees were claimed from this position
+    pub last_claim_timestamp: i64,
+    
+    /// Total quote fees claimed from this position since creation
+    pub total_fees_claimed: u64,
+    
+    /// Quote value of the position used as the fee APR denominator (0 = unknown)
+    pub position_value_quote: u64,
+    
+    /// The authority that created the position (the only one allowed to close it, default = policy authority)
+    pub authority: Pubkey,
+    
     /// Reserved for future use
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 8],
 }
 
 impl PositionMetadata {
@@ -35,7 +62,12 @@ impl PositionMetadata {
                                    32 + // base_mint
                                    8 +  // created_at
                                    1 +  // position_owner_bump
-                                   64;  // reserved
+                                   1 +  // pool_type
+                                   8 +  // last_claim_timestamp
+                                   8 +  // total_fees_claimed
+                                   8 +  // position_value_quote
+                                   32 + // authority
+                                   8;   // reserved
 
     /// Derive the PDA for position metadata
     pub fn derive_pda(position_nft_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
@@ -45,6 +77,18 @@ impl PositionMetadata {
         )
     }
 
+    /// Authority allowed to close this position
+    /// 
+    /// Positions created before the creating authority was recorded read the
+    /// default key from the reserved bytes and fall back to the policy authority.
+    pub fn close_authority(&self, policy_authority: &Pubkey) -> Pubkey {
+        if self.authority == Pubkey::default() {
+            *policy_authority
+        } else {
+            self.authority
+        }
+    }
+
     /// Check if this position is configured for quote-only fees
     pub fn is_quote_only(&self) -> bool {
         // In our system, all positions should be quote-only
@@ -52,6 +96,49 @@ impl PositionMetadata {
         true
     }
 
+    /// Verify a position owner PDA against the stored canonical bump
+    /// 
+    /// Re-derives `[VAULT_SEED, vault, POSITION_OWNER_SEED, bump]` with the bump
+    /// recorded at creation so a PDA derived with any other bump is rejected.
+    pub fn verify_position_owner(&self, vault: &Pubkey, position_owner: &Pubkey, program_id: &Pubkey) -> Result<()> {
+        let expected = Pubkey::create_program_address(
+            &[
+                VAULT_SEED,
+                vault.as_ref(),
+                POSITION_OWNER_SEED,
+                &[self.position_owner_bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| FeeRouterError::PositionOwnerMismatch)?;
+
+        require!(
+            expected == *position_owner,
+            FeeRouterError::PositionOwnerMismatch
+        );
+        Ok(())
+    }
+
+    /// Update claim history after a successful claim
+    pub fn record_claim(&mut self, amount_claimed: u64, timestamp: i64) {
+        self.total_fees_claimed = self.total_fees_claimed.saturating_add(amount_claimed);
+        self.last_claim_timestamp = timestamp;
+    }
+
+    /// Annualised fee yield in basis points of the position value
+    /// position_fee_apr = floor(total_fees_claimed * 10000 * SECONDS_PER_YEAR / (position_value * age))
+    /// 
+    /// Returns 0 while the position value is unknown or the position has no age.
+    pub fn calculate_fee_apr_bps(&self, current_timestamp: i64) -> u64 {
+        let age = self.age_seconds(current_timestamp);
+        if self.position_value_quote == 0 || age <= 0 {
+            return 0;
+        }
+        let annualised = self.total_fees_claimed as u128 * BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128;
+        let apr_bps = annualised / (self.position_value_quote as u128 * age as u128);
+        std::cmp::min(apr_bps, u64::MAX as u128) as u64
+    }
+
     /// Get the age of the position in seconds
     pub fn age_seconds(&self, current_timestamp: i64) -> i64 {
         current_timestamp - self.created_at
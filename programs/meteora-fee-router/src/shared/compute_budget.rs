@@ -0,0 +1,70 @@
+// Compute budget estimates for heavy instructions
+//
+// The per-page figures are estimates of the work `process_investor_page`
+// does, not measurements; replace them with the numbers from the SBF
+// benchmark in `compute_tests` once it has run against a program build.
+// Callers processing large pages should prepend
+// `ComputeBudgetInstruction::set_compute_unit_limit(400_000)` to the
+// transaction; the default limit only fits `MAX_SAFE_INVESTORS_PER_PAGE`.
+
+use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+use crate::shared::constants::{ACCOUNTS_PER_INVESTOR, MAX_INVESTORS_PER_PAGE};
+
+/// Default compute unit limit for a single instruction
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+/// Estimated fixed cost of a page: account deserialization, hashing, distribution math
+pub const PROCESS_PAGE_BASE_COMPUTE: u64 = 25_000;
+
+/// Estimated cost per investor: stream read, cache write, idempotent ATA check and transfer
+pub const PROCESS_PAGE_PER_INVESTOR_COMPUTE: u64 = 14_000;
+
+/// Largest page that fits within the default compute unit limit
+pub const MAX_SAFE_INVESTORS_PER_PAGE: u32 =
+    ((DEFAULT_COMPUTE_UNIT_LIMIT - PROCESS_PAGE_BASE_COMPUTE) / PROCESS_PAGE_PER_INVESTOR_COMPUTE) as u32;
+
+/// Most accounts a single transaction may lock
+pub const MAX_TRANSACTION_ACCOUNT_LOCKS: usize = 128;
+
+/// Accounts of a `process_investor_page` call besides the investors':
+/// the 16 named accounts plus the shared associated token program
+pub const PROCESS_PAGE_FIXED_ACCOUNTS: usize = 17;
+
+/// Largest page whose accounts fit in one transaction
+/// 
+/// Each investor adds `ACCOUNTS_PER_INVESTOR` accounts, one of them the
+/// shared associated token program. A page of 50 needs 267 accounts.
+pub const MAX_INVESTORS_PER_TRANSACTION: u32 =
+    ((MAX_TRANSACTION_ACCOUNT_LOCKS - PROCESS_PAGE_FIXED_ACCOUNTS) / (ACCOUNTS_PER_INVESTOR - 1)) as u32;
+
+/// Estimate the compute units consumed by `process_investor_page`
+pub fn estimate_process_page_compute(investor_count: u32) -> u64 {
+    PROCESS_PAGE_BASE_COMPUTE
+        .saturating_add(PROCESS_PAGE_PER_INVESTOR_COMPUTE.saturating_mul(investor_count as u64))
+}
+
+/// Validate the remaining accounts of a `process_investor_page` call up front
+/// 
+/// Checks the count against the page limits and the compute estimate before
+/// any account is deserialized. Returns the number of investors in the page.
+pub fn validate_remaining_accounts_length(account_count: usize) -> Result<u32> {
+    require!(account_count > 0, FeeRouterError::NoInvestors);
+    require!(
+        account_count <= MAX_INVESTORS_PER_PAGE as usize * ACCOUNTS_PER_INVESTOR,
+        FeeRouterError::TooManyAccountsInPage
+    );
+
+    let investor_count = account_count / ACCOUNTS_PER_INVESTOR;
+    require!(
+        investor_count * ACCOUNTS_PER_INVESTOR == account_count,
+        FeeRouterError::PaginationError
+    );
+
+    let investor_count = investor_count as u32;
+    require!(
+        investor_count <= MAX_SAFE_INVESTORS_PER_PAGE,
+        FeeRouterError::PageTooLarge
+    );
+    Ok(investor_count)
+}
@@ -7,19 +7,60 @@ pub const POLICY_SEED: &[u8] = b"policy";
 pub const TREASURY_SEED: &[u8] = b"treasury";
 
 // Program limits
-pub const MAX_INVESTORS_PER_PAGE: u32 = 50;
+// 50 investors would need 267 accounts, past the 22 that fit in a transaction
+// (compute_budget::MAX_INVESTORS_PER_TRANSACTION); 12 is the compute-limited size
+pub const MAX_INVESTORS_PER_PAGE: u32 = 12; // must not exceed compute_budget::MAX_SAFE_INVESTORS_PER_PAGE
+pub const MAX_SNAPSHOT_INVESTORS: usize = 100;
+pub const MAX_BLACKLISTED_INVESTORS: usize = 100;
+pub const MAX_REGISTERED_STREAMS: usize = 100;
+pub const MAX_STREAM_ERROR_LOG_ENTRIES: usize = 32;
+pub const INVESTOR_REGISTRY_PAGE_SIZE: usize = 50; // entries per InvestorRegistryPage
+pub const MAX_REGISTRY_INVESTORS: u32 = 10_000;
+pub const ACCOUNTS_PER_INVESTOR: usize = 6; // [stream, investor_wallet, investor_ata, associated_token_program, investor_cache, payout_summary]
+pub const SNAPSHOT_ACCOUNTS_PER_INVESTOR: usize = 3; // [investor_wallet, investor_ata, payout_summary]
+pub const INSTANT_DISTRIBUTION_MAX_INVESTORS: u32 = 19; // all investors are paid in the claim transaction
+pub const INSTANT_ACCOUNTS_PER_INVESTOR: usize = 2; // [investor_wallet, investor_ata]
 
 // Distribution constants
 pub const DEFAULT_MIN_PAYOUT_LAMPORTS: u64 = 1000; // 0.001 SOL equivalent
 pub const DEFAULT_DAILY_CAP_LAMPORTS: u64 = 1_000_000_000; // 1 SOL equivalent
 pub const DEFAULT_INVESTOR_FEE_SHARE_BPS: u64 = 5000; // 50% max to investors
+pub const DEFAULT_MIN_INVESTORS_TO_DISTRIBUTE: u32 = 1; // policies initialized without a minimum
 pub const MAX_BASIS_POINTS: u64 = 10000; // 100%
+pub const ALLOCATION_Y0_BUFFER_PERCENT: u64 = 10; // registered allocation tolerated above Y0 for rounding
+
+// Account layout versions
+pub const CURRENT_POLICY_VERSION: u8 = 2;
+
+// Health score reason flags (HealthScoreUpdated::reason_flags)
+pub const HEALTH_FLAG_MISSED_DAYS: u8 = 1 << 0;
+pub const HEALTH_FLAG_CAP_BINDING: u8 = 1 << 1;
+pub const HEALTH_FLAG_FAILED_PAYOUTS: u8 = 1 << 2;
+pub const HEALTH_FLAG_HIGH_DUST: u8 = 1 << 3;
 
 // Time constants
 pub const SECONDS_PER_DAY: i64 = 86400;
+pub const SECONDS_PER_YEAR: i64 = SECONDS_PER_DAY * 365;
+pub const FORCE_COMPLETE_TIMEOUT_SECONDS: i64 = SECONDS_PER_DAY * 2; // stuck distribution timeout
+pub const MAX_DISTRIBUTION_WINDOW_SECONDS: i64 = SECONDS_PER_DAY * 2; // pages accepted after start
+pub const SNAPSHOT_RETENTION_SECONDS: i64 = SECONDS_PER_DAY; // snapshot kept after completion
+pub const DISTRIBUTION_INTERVAL_SECONDS: i64 = SECONDS_PER_DAY; // one distribution per day
+pub const DEFAULT_MIN_CLAIM_INTERVAL_SECONDS: i64 = 3600; // treasuries initialized without an interval
+pub const DURATION_BONUS_MIN_LOCK_SECONDS: u64 = 86400 * 182; // ~6 months, no bonus
+pub const DURATION_BONUS_MAX_LOCK_SECONDS: u64 = 86400 * 365 * 4; // 4 years, full bonus
+pub const MAX_LOCK_DURATION_SECONDS: u64 = 4 * 365 * 86400; // remaining lock for full weight under duration weighting
 
 /// Basis points denominator (10000 = 100%)
 pub const BPS_DENOMINATOR: u64 = 10_000;
 
+/// Smallest creator share a policy may leave (1%)
+pub const MIN_CREATOR_BPS: u64 = 100;
+
+/// Largest daily cap a policy accepts, leaving headroom in cap calculations
+pub const MAX_DAILY_CAP_LAMPORTS: u64 = u64::MAX / 2;
+
 /// Minimum payout threshold in lamports (to avoid dust)
 pub const MIN_PAYOUT_LAMPORTS: u64 = 1_000;
+
+/// Maximum base fee amount (exclusive) that can be accepted under the grace period
+pub const GRACE_AMOUNT_THRESHOLD: u64 = 100;
@@ -0,0 +1,25 @@
+// Hashing used for page idempotency and verification proofs
+//
+// The algorithm is chosen per policy so proofs can be checked by verifiers
+// that only support one of them (e.g. Keccak-256 on Ethereum).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash, keccak};
+
+/// Hash algorithm used for page hashes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    /// SHA-256 (Solana's native hash)
+    #[default]
+    Sha256,
+    /// Keccak-256 (Ethereum compatible)
+    Keccak256,
+}
+
+/// Hash `data` with the given algorithm
+pub fn compute_hash(data: &[u8], algorithm: HashAlgorithm) -> [u8; 32] {
+    match algorithm {
+        HashAlgorithm::Sha256 => hash::hash(data).to_bytes(),
+        HashAlgorithm::Keccak256 => keccak::hash(data).to_bytes(),
+    }
+}
@@ -0,0 +1,133 @@
+// SPL governance proposal validation for DAO-controlled policies
+//
+// A policy with a governance authority only accepts updates carried by a
+// completed proposal of that governance. `Pubkey::default()` as the
+// governance authority keeps the policy in direct authority mode.
+
+use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+
+/// SPL Governance Program ID: GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw
+pub const SPL_GOVERNANCE_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0xea, 0xe4, 0x35, 0xbd, 0xee, 0x75, 0xb7, 0x34,
+    0xcd, 0x59, 0x3e, 0xcf, 0x9a, 0x30, 0x4b, 0x80,
+    0x24, 0xba, 0x28, 0x98, 0x67, 0xb7, 0x69, 0xb1,
+    0xf9, 0x3c, 0xa7, 0xbb, 0xb8, 0x8e, 0x46, 0xfe,
+]);
+
+/// `GovernanceAccountType::ProposalTransactionV2`
+pub const PROPOSAL_TRANSACTION_ACCOUNT_TYPE: u8 = 13;
+
+/// `GovernanceAccountType::ProposalV2`
+pub const PROPOSAL_ACCOUNT_TYPE: u8 = 14;
+
+/// `ProposalState::Completed`
+pub const PROPOSAL_STATE_COMPLETED: u8 = 5;
+
+/// SPL governance `ProposalV2` account (leading fields only)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GovernanceProposal {
+    pub account_type: u8,
+    /// Governance the proposal belongs to
+    pub governance: Pubkey,
+    pub governing_token_mint: Pubkey,
+    /// `ProposalState` discriminant
+    pub state: u8,
+}
+
+/// Account meta of an instruction stored in a proposal transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Instruction stored in a proposal transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalInstruction {
+    /// Program the instruction targets
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// SPL governance `ProposalTransactionV2` account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalTransaction {
+    pub account_type: u8,
+    /// Proposal the transaction belongs to
+    pub proposal: Pubkey,
+    pub option_index: u8,
+    pub transaction_index: u16,
+    pub hold_up_time: u32,
+    pub instructions: Vec<ProposalInstruction>,
+    pub executed_at: Option<i64>,
+    pub execution_status: u8,
+    pub reserved_v2: [u8; 8],
+}
+
+/// Read an SPL governance account owned by the governance program
+fn read_governance_account<T: AnchorDeserialize>(account: &AccountInfo, account_type: u8) -> Result<T> {
+    require!(
+        *account.owner == SPL_GOVERNANCE_PROGRAM_ID,
+        FeeRouterError::InvalidGovernanceProposal
+    );
+    let data = account.try_borrow_data()?;
+    require!(
+        data.first() == Some(&account_type),
+        FeeRouterError::InvalidGovernanceProposal
+    );
+    T::deserialize(&mut &data[..]).map_err(|_| FeeRouterError::InvalidGovernanceProposal.into())
+}
+
+/// Validation of policy updates against a governance proposal
+pub struct ValidateGovernanceProposal;
+
+impl ValidateGovernanceProposal {
+    /// Check that a policy update is authorized by governance
+    /// 
+    /// Skipped entirely in direct authority mode. Otherwise the proposal must
+    /// belong to `governance_authority` and be completed, and its transaction
+    /// must carry an instruction to this program with exactly `instruction_data`.
+    /// 
+    /// # Arguments
+    /// * `governance_authority` - The policy's governance (default = direct authority mode)
+    /// * `proposal` - The SPL governance proposal account
+    /// * `proposal_transaction` - The proposal transaction holding the update
+    /// * `program_id` - This program's ID
+    /// * `instruction_data` - Data of the update instruction being executed
+    /// 
+    /// # Returns
+    /// * `Result<()>` - Success if the update may be applied
+    pub fn validate(
+        governance_authority: &Pubkey,
+        proposal: Option<&AccountInfo>,
+        proposal_transaction: Option<&AccountInfo>,
+        program_id: &Pubkey,
+        instruction_data: &[u8],
+    ) -> Result<()> {
+        if *governance_authority == Pubkey::default() {
+            return Ok(());
+        }
+
+        let (Some(proposal_account), Some(transaction_account)) = (proposal, proposal_transaction) else {
+            return Err(FeeRouterError::InvalidGovernanceProposal.into());
+        };
+        let proposal: GovernanceProposal = read_governance_account(proposal_account, PROPOSAL_ACCOUNT_TYPE)?;
+        let transaction: ProposalTransaction = read_governance_account(transaction_account, PROPOSAL_TRANSACTION_ACCOUNT_TYPE)?;
+
+        require!(
+            proposal.governance == *governance_authority
+                && proposal.state == PROPOSAL_STATE_COMPLETED
+                && transaction.proposal == proposal_account.key(),
+            FeeRouterError::InvalidGovernanceProposal
+        );
+        require!(
+            transaction.instructions.iter()
+                .any(|instruction| instruction.program_id == *program_id && instruction.data == instruction_data),
+            FeeRouterError::InvalidGovernanceProposal
+        );
+        Ok(())
+    }
+}
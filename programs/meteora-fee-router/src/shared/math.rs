@@ -0,0 +1,28 @@
+// Checked arithmetic for the distribution formulas
+//
+// Saturating or truncating here would silently shift amounts between
+// investors and the creator, so failures surface as explicit errors.
+
+use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+
+/// floor(value * numerator / denominator) with a u128 intermediate
+pub fn checked_mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let product = (value as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(FeeRouterError::ArithmeticOverflow)?;
+    let quotient = product
+        .checked_div(denominator as u128)
+        .ok_or(FeeRouterError::DivisionByZero)?;
+    u64::try_from(quotient).map_err(|_| FeeRouterError::ArithmeticOverflow.into())
+}
+
+/// `a + b`, failing with `ArithmeticOverflow`
+pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| FeeRouterError::ArithmeticOverflow.into())
+}
+
+/// `a - b`, failing with `ArithmeticUnderflow`
+pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or_else(|| FeeRouterError::ArithmeticUnderflow.into())
+}
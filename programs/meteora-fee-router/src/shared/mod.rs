@@ -1,6 +1,10 @@
 // Shared utilities used across all modules
 
 pub mod constants;
-// pub mod math;
+pub mod compute_budget;
+pub mod crypto;
+pub mod rent;
+pub mod pda;
+pub mod math;
+pub mod governance;
 // pub mod time;
-// pub mod pda;
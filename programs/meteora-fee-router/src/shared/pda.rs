@@ -0,0 +1,19 @@
+// PDA verification with the addresses in the logs
+//
+// Anchor's `seeds` constraint only reports ConstraintSeeds. Accounts checked
+// here log the expected and actual address so a wrong seed is easy to spot.
+
+use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+
+/// Check that `actual` is the canonical PDA for `seeds` and return its bump
+pub fn verify_pda(actual: &Pubkey, seeds: &[&[u8]], program_id: &Pubkey) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(seeds, program_id);
+    if expected != *actual {
+        msg!("PDA mismatch: expected {}, got {}", expected, actual);
+        return Err(FeeRouterError::SeedConstraintViolation
+            .with_context(format!("expected {}, got {}", expected, actual))
+            .into());
+    }
+    Ok(bump)
+}
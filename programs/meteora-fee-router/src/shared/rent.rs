@@ -0,0 +1,27 @@
+// Rent pre-flight checks for instructions that create accounts
+//
+// Without them an underfunded payer only sees the system program's generic
+// insufficient funds error once the account creation CPI fails.
+
+use anchor_lang::prelude::*;
+use crate::errors::FeeRouterError;
+
+/// Rent-exempt minimum for an account of `space` bytes
+pub fn required_rent(space: usize) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(space))
+}
+
+/// Fail with the exact amount needed if `payer` cannot fund `required_lamports` of rent
+pub fn require_rent_lamports(payer: &AccountInfo, required_lamports: u64) -> Result<()> {
+    let available_lamports = payer.lamports();
+    if available_lamports < required_lamports {
+        msg!("Need {} lamports for rent, have {}", required_lamports, available_lamports);
+        return Err(FeeRouterError::InsufficientRentForAccount
+            .with_context(format!(
+                "need {} lamports, have {}",
+                required_lamports, available_lamports
+            ))
+            .into());
+    }
+    Ok(())
+}
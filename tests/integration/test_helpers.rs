@@ -140,11 +140,13 @@ impl TestHelpers {
         use meteora_fee_router::integrations::streamflow::accounts::StreamflowStream;
         
         let stream_data = StreamflowStream {
-            magic: 0x1234567890abcdef,
+            magic: 0,
             version: 1,
             created_at: start_time - 3600,
             start_time,
             end_time,
+            cliff_time: 0,
+            cliff_amount: 0,
             deposited_amount,
             withdrawn_amount: 0,
             recipient: *recipient,
@@ -155,7 +157,6 @@ impl TestHelpers {
             can_cancel: true,
             can_transfer: false,
             cancelled: false,
-            metadata: [0; 128],
         };
 
         let rent = banks_client.get_rent().await?;
@@ -0,0 +1,125 @@
+// Static audit of the account constraints declared in the context structs
+
+/// Context sources checked by the audit
+const CONTEXT_SOURCES: [(&str, &str); 3] = [
+    ("claiming", include_str!("../../programs/meteora-fee-router/src/modules/claiming/contexts.rs")),
+    ("distribution", include_str!("../../programs/meteora-fee-router/src/modules/distribution/contexts.rs")),
+    ("position", include_str!("../../programs/meteora-fee-router/src/modules/position/contexts.rs")),
+];
+
+/// A `TokenAccount` field together with its `#[account(...)]` attribute lines
+struct TokenAccountField {
+    declaration: String,
+    attributes: Vec<String>,
+}
+
+/// Collect every `TokenAccount` field of the context structs in `source`
+fn token_account_fields(source: &str) -> Vec<TokenAccountField> {
+    let lines: Vec<&str> = source.lines().map(str::trim).collect();
+    let mut fields = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if !(line.starts_with("pub ") && line.contains("TokenAccount>")) {
+            continue;
+        }
+
+        // Attributes sit between the previous field (or struct opening) and this one
+        let attributes = lines[..index]
+            .iter()
+            .rev()
+            .take_while(|previous| !previous.starts_with("pub ") && !previous.ends_with('{'))
+            .filter(|previous| !previous.starts_with("//"))
+            .map(|previous| previous.to_string())
+            .collect();
+        fields.push(TokenAccountField {
+            declaration: line.to_string(),
+            attributes,
+        });
+    }
+    fields
+}
+
+/// Whether the field's mint is checked with a specific error
+fn has_mint_validation(field: &TokenAccountField) -> bool {
+    field.attributes.iter().any(|attribute| {
+        attribute.starts_with("associated_token::mint")
+            || attribute.starts_with("token::mint")
+            || (attribute.contains(".mint ==") && attribute.contains("@ FeeRouterError::TokenAccountMintMismatch"))
+    })
+}
+
+/// Whether the field's owner is pinned to the registered creator wallet
+fn has_creator_wallet_validation(field: &TokenAccountField) -> bool {
+    field.attributes.iter().any(|attribute| {
+        attribute.contains("creator_ata.owner")
+            && attribute.contains("creator_wallet")
+            && attribute.contains("@ FeeRouterError::CreatorWalletMismatch")
+    })
+}
+
+#[cfg(test)]
+mod constraint_tests {
+    use super::*;
+
+    #[test]
+    fn test_all_token_accounts_validate_mint() {
+        let mut audited = 0;
+        for (module, source) in CONTEXT_SOURCES {
+            for field in token_account_fields(source) {
+                assert!(
+                    has_mint_validation(&field),
+                    "{} context field `{}` has no mint constraint",
+                    module,
+                    field.declaration
+                );
+                audited += 1;
+            }
+        }
+
+        // The treasury, creator, protocol and position owner ATAs are all covered
+        assert!(audited >= 19, "only {} token accounts audited", audited);
+    }
+
+    #[test]
+    fn test_creator_ata_locked_to_creator_wallet() {
+        let mut audited = 0;
+        for (module, source) in CONTEXT_SOURCES {
+            for field in token_account_fields(source) {
+                if !field.declaration.starts_with("pub creator_ata:") {
+                    continue;
+                }
+                assert!(
+                    has_creator_wallet_validation(&field),
+                    "{} context field `{}` is not tied to the creator wallet",
+                    module,
+                    field.declaration
+                );
+                audited += 1;
+            }
+        }
+
+        // Instant claims, inline completion, completion and forced completion
+        assert!(audited >= 4, "only {} creator ATAs audited", audited);
+    }
+
+    #[test]
+    fn test_audit_flags_missing_mint_constraint() {
+        let source = "
+            pub struct Example<'info> {
+                /// Checked
+                #[account(
+                    mut,
+                    constraint = treasury_ata.mint == quote_mint.key() @ FeeRouterError::TokenAccountMintMismatch,
+                )]
+                pub treasury_ata: Account<'info, TokenAccount>,
+
+                /// Unchecked
+                #[account(mut)]
+                pub creator_ata: Account<'info, TokenAccount>,
+            }
+        ";
+        let fields = token_account_fields(source);
+        assert_eq!(fields.len(), 2);
+        assert!(has_mint_validation(&fields[0]));
+        assert!(!has_mint_validation(&fields[1]));
+    }
+}
@@ -0,0 +1,3051 @@
+use meteora_fee_router::errors::FeeRouterError;
+use meteora_fee_router::integrations::meteora::{
+    position_layout, RemoveLiquidityParameters, EVENT_AUTHORITY_SEED, METEORA_CP_AMM_PROGRAM_ID, POOL_AUTHORITY, POSITION_ACCOUNT_SIZE,
+};
+use meteora_fee_router::integrations::streamflow::accounts::StreamflowStream;
+use meteora_fee_router::modules::claiming::state::TreasuryState;
+use meteora_fee_router::modules::distribution::state::*;
+use meteora_fee_router::modules::distribution::instructions::UpdatePolicySettingsParams;
+use meteora_fee_router::modules::position::state::{PoolType, PositionMetadata};
+use meteora_fee_router::shared::constants::{BPS_DENOMINATOR, GRACE_AMOUNT_THRESHOLD, INSTANT_DISTRIBUTION_MAX_INVESTORS, INVESTOR_REGISTRY_PAGE_SIZE, MAX_INVESTORS_PER_PAGE, POSITION_OWNER_SEED, VAULT_SEED};
+use meteora_fee_router::shared::compute_budget::MAX_SAFE_INVESTORS_PER_PAGE;
+use meteora_fee_router::shared::crypto::HashAlgorithm;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use super::test_fixtures::*;
+
+// Quote tokens already in the treasury and accrued on the position
+const TREASURY_BALANCE_BEFORE_CLAIM: u64 = 250_000;
+const POSITION_FEES: u64 = 750_000;
+
+/// Run the Anchor entrypoint as a native builtin
+fn process_fee_router(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    meteora_fee_router::entry(program_id, accounts, data)
+}
+
+/// Stand-in for Meteora `claim_position_fee`: pays the whole token A and token B
+/// vaults (owned by the event authority PDA) into the owner's token accounts
+fn process_mock_claim_position_fee(program_id: &Pubkey, accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let token_program = &accounts[11];
+    let event_authority = &accounts[13];
+    let (_, bump) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id);
+
+    // Token A and token B accounts and vaults
+    for (token_account, vault) in [(&accounts[3], &accounts[5]), (&accounts[4], &accounts[6])] {
+        let amount = spl_token::state::Account::unpack(&vault.data.borrow())?.amount;
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                token_account.key,
+                event_authority.key,
+                &[],
+                amount,
+            )?,
+            &[vault.clone(), token_account.clone(), event_authority.clone(), token_program.clone()],
+            &[&[EVENT_AUTHORITY_SEED, &[bump]]],
+        )?;
+    }
+    Ok(())
+}
+
+/// Stand-in for the CP-AMM instructions used to retire a position
+/// 
+/// `remove_liquidity` is a no-op, `claim_position_fee` pays out the token A
+/// vault and `close_position` moves the position's lamports to the rent receiver.
+fn process_mock_close_position_cpis(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    const CLAIM_POSITION_FEE: [u8; 8] = [180, 38, 154, 17, 133, 33, 162, 211];
+    const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+
+    match data[..8].try_into().unwrap() {
+        RemoveLiquidityParameters::DISCRIMINATOR => Ok(()),
+        CLAIM_POSITION_FEE => process_mock_claim_position_fee(program_id, accounts, data),
+        CLOSE_POSITION => {
+            let position = &accounts[3];
+            let rent_receiver = &accounts[5];
+            **rent_receiver.lamports.borrow_mut() += position.lamports();
+            **position.lamports.borrow_mut() = 0;
+            Ok(())
+        }
+        _ => Err(anchor_lang::solana_program::program_error::ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Start a program-test bank with a claimable position and a day ready to start
+async fn setup_atomic_distribution() -> (ProgramTestContext, meteora_fee_router::accounts::InitializeAtomicDistribution, i64) {
+    let program_id = meteora_fee_router::id();
+    let mut program_test = ProgramTest::new("meteora_fee_router", program_id, processor!(process_fee_router));
+    program_test.add_program("meteora_cp_amm", METEORA_CP_AMM_PROGRAM_ID, processor!(process_mock_claim_position_fee));
+    let mut context = program_test.start_with_context().await;
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let distribution_day = DailyDistributionState::get_day_start(now);
+
+    let quote_mint = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let position_nft_mint = Pubkey::new_unique();
+    context.set_account(&quote_mint, &mint_account());
+    context.set_account(&base_mint, &mint_account());
+    context.set_account(&position_nft_mint, &mint_account());
+
+    let (program_config, _) = ProgramConfig::derive_pda(&program_id);
+    context.set_account(&program_config, &anchor_account(&ProgramConfig {
+        program_pause_authority: Pubkey::new_unique(),
+        is_paused: false,
+        pause_count: 0,
+        resume_count: 0,
+        last_paused_at: 0,
+        total_paused_seconds: 0,
+        reserved: [0; 8],
+    }, &program_id));
+
+    let (policy_state, policy_bump) = Pubkey::find_program_address(&[b"policy", quote_mint.as_ref()], &program_id);
+    context.set_account(&policy_state, &anchor_account(&PolicyState {
+        bump: policy_bump,
+        ..fixture_policy_state().with_quote_mint(quote_mint)
+    }, &program_id));
+
+    let (global_distribution_state, global_bump) =
+        Pubkey::find_program_address(&[b"global_distribution", quote_mint.as_ref()], &program_id);
+    context.set_account(&global_distribution_state, &anchor_account(&GlobalDistributionState {
+        quote_mint,
+        last_distribution_timestamp: 0,
+        total_distributions: 0,
+        total_amount_distributed: 0,
+        rolling_avg_distribution_bps_of_treasury: 0,
+        creator_wallet: Pubkey::new_unique(),
+        protocol_fee_wallet: None,
+        total_investor_payouts: 0,
+        total_creator_payouts: 0,
+        avg_seconds_per_page: 0,
+        bump: global_bump,
+        position_initialized_at: 0,
+        health_score: 100,
+        auto_extension_count: 0,
+        dust_rollover: 0,
+        reserved: [0; 2],
+    }, &program_id));
+
+    let (investor_snapshot, _) = InvestorSnapshot::derive_pda(distribution_day, &quote_mint, &program_id);
+    context.set_account(&investor_snapshot, &anchor_account(&InvestorSnapshot {
+        distribution_day,
+        quote_mint,
+        investor_count: 1,
+        total_locked: 1_000_000,
+        taken_at: now,
+        payer: Pubkey::new_unique(),
+        entries: Vec::new(),
+    }, &program_id));
+
+    // Honorary position owned by the vault's position owner PDA
+    let vault = Pubkey::new_unique();
+    let (position_owner_pda, position_owner_bump) =
+        Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), POSITION_OWNER_SEED], &program_id);
+    let pool = Pubkey::new_unique();
+    let position = Pubkey::new_unique();
+    let (position_metadata, _) = PositionMetadata::derive_pda(&position_nft_mint, &program_id);
+    context.set_account(&position_metadata, &anchor_account(&PositionMetadata {
+        position,
+        pool,
+        quote_mint,
+        base_mint,
+        created_at: now - 86_400,
+        position_owner_bump,
+        pool_type: PoolType::MeteoraDAMM,
+        last_claim_timestamp: 0,
+        total_fees_claimed: 0,
+        position_value_quote: 0,
+        authority: Pubkey::new_unique(),
+        reserved: [0; 8],
+    }, &program_id));
+
+    let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+    let quote_vault = Pubkey::new_unique();
+    let base_vault = Pubkey::new_unique();
+    context.set_account(&pool, &meteora_pool_account(&fixture_meteora_pool(quote_mint, base_mint, quote_vault, base_vault)));
+    context.set_account(&quote_vault, &token_account(&quote_mint, &event_authority, POSITION_FEES));
+    context.set_account(&base_vault, &token_account(&base_mint, &event_authority, 0));
+    let position_owner_quote_ata = Pubkey::new_unique();
+    let position_owner_base_ata = Pubkey::new_unique();
+    context.set_account(&position_owner_quote_ata, &token_account(&quote_mint, &position_owner_pda, 0));
+    context.set_account(&position_owner_base_ata, &token_account(&base_mint, &position_owner_pda, 0));
+
+    let (treasury_state, treasury_bump) = Pubkey::find_program_address(&[b"treasury_state", quote_mint.as_ref()], &program_id);
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+    let treasury_ata = Pubkey::new_unique();
+    context.set_account(&treasury_ata, &token_account(&quote_mint, &treasury_authority, TREASURY_BALANCE_BEFORE_CLAIM));
+    context.set_account(&treasury_state, &anchor_account(&TreasuryState {
+        treasury_ata,
+        bump: treasury_bump,
+        ..fixture_treasury_state()
+            .with_quote_mint(quote_mint)
+            .with_claim_authority(position_owner_pda)
+            .with_allowed_mints([quote_mint, Pubkey::default(), Pubkey::default(), Pubkey::default()])
+    }, &program_id));
+
+    let (daily_distribution_state, _) = DailyDistributionState::derive_pda(distribution_day, &quote_mint, &program_id);
+    let (policy_snapshot, _) = Pubkey::find_program_address(
+        &[b"policy_snapshot", distribution_day.to_string().as_bytes(), quote_mint.as_ref()],
+        &program_id,
+    );
+    let (stream_error_log, _) = StreamErrorLog::derive_pda(distribution_day, &quote_mint, &program_id);
+    let (allocation_registry, allocation_registry_bump) = AllocationRegistry::derive_pda(&quote_mint, &program_id);
+    context.set_account(&allocation_registry, &anchor_account(&AllocationRegistry {
+        quote_mint,
+        total_registered_allocation: 1_000_000,
+        registered_streams: Vec::new(),
+        bump: allocation_registry_bump,
+    }, &program_id));
+
+    let accounts = meteora_fee_router::accounts::InitializeAtomicDistribution {
+        claim: meteora_fee_router::accounts::ClaimFees {
+            position_metadata,
+            position_nft_mint,
+            pool,
+            position,
+            position_nft_account: Pubkey::new_unique(),
+            position_owner_pda,
+            vault,
+            treasury_state,
+            policy_state,
+            quote_mint,
+            base_mint,
+            treasury_ata,
+            position_owner_quote_ata,
+            position_owner_base_ata,
+            quote_vault,
+            base_vault,
+            pool_authority: POOL_AUTHORITY,
+            event_authority,
+            meteora_program: METEORA_CP_AMM_PROGRAM_ID,
+            raydium_program: None,
+            token_program: anchor_spl::token::ID,
+        },
+        start: meteora_fee_router::accounts::StartDailyDistribution {
+            authority: context.payer.pubkey(),
+            quote_mint,
+            program_config,
+            global_distribution_state,
+            daily_distribution_state,
+            investor_snapshot,
+            policy_snapshot,
+            stream_error_log,
+            policy_state,
+            treasury_state,
+            treasury_ata,
+            investor_registry: None,
+            allocation_registry,
+            system_program: anchor_lang::system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        },
+    };
+    (context, accounts, distribution_day)
+}
+
+/// Enable `auto_claim_before_distribution` and build a start instruction carrying the claim accounts
+async fn auto_claim_start_instruction(
+    context: &mut ProgramTestContext,
+    accounts: &meteora_fee_router::accounts::InitializeAtomicDistribution,
+    distribution_day: i64,
+) -> Instruction {
+    let program_id = meteora_fee_router::id();
+    let account = context.banks_client.get_account(accounts.start.policy_state).await.unwrap().unwrap();
+    let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    context.set_account(&accounts.start.policy_state, &anchor_account(&PolicyState {
+        auto_claim_before_distribution: true,
+        ..policy
+    }, &program_id));
+
+    let mut account_metas = accounts.start.to_account_metas(None);
+    account_metas.extend(accounts.claim.to_account_metas(None));
+    Instruction {
+        program_id,
+        accounts: account_metas,
+        data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+    }
+}
+
+/// Switch the atomic setup to instant mode with one snapshot investor per locked amount
+/// 
+/// Every investor already has an empty quote ATA.
+async fn setup_instant_distribution(
+    locked_amounts: &[u64],
+) -> (ProgramTestContext, meteora_fee_router::accounts::ClaimAndDistributeInstant, i64, Vec<SnapshotEntry>) {
+    let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+    let program_id = meteora_fee_router::id();
+    let quote_mint = accounts.claim.quote_mint;
+
+    let (_, policy_bump) = Pubkey::find_program_address(&[b"policy", quote_mint.as_ref()], &program_id);
+    context.set_account(&accounts.claim.policy_state, &anchor_account(&PolicyState {
+        fee_distribution_mode: FeeDistributionMode::Instant,
+        bump: policy_bump,
+        ..fixture_policy_state().with_quote_mint(quote_mint)
+    }, &program_id));
+
+    let entries: Vec<SnapshotEntry> = locked_amounts
+        .iter()
+        .map(|&locked_amount| {
+            let investor = Pubkey::new_unique();
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(&investor, &quote_mint);
+            context.set_account(&investor_ata, &token_account(&quote_mint, &investor, 0));
+            SnapshotEntry {
+                investor,
+                stream_account: Pubkey::new_unique(),
+                locked_amount,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            }
+        })
+        .collect();
+    context.set_account(&accounts.start.investor_snapshot, &anchor_account(&InvestorSnapshot {
+        distribution_day,
+        quote_mint,
+        investor_count: entries.len() as u32,
+        total_locked: locked_amounts.iter().sum(),
+        taken_at: distribution_day,
+        payer: Pubkey::new_unique(),
+        entries: entries.clone(),
+    }, &program_id));
+
+    let account = context.banks_client.get_account(accounts.start.global_distribution_state).await.unwrap().unwrap();
+    let global_state = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    let creator_ata = Pubkey::new_unique();
+    context.set_account(&creator_ata, &token_account(&quote_mint, &global_state.creator_wallet, 0));
+
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+    let accounts = meteora_fee_router::accounts::ClaimAndDistributeInstant {
+        claim: accounts.claim,
+        authority: context.payer.pubkey(),
+        program_config: accounts.start.program_config,
+        investor_snapshot: accounts.start.investor_snapshot,
+        daily_distribution_state: accounts.start.daily_distribution_state,
+        global_distribution_state: accounts.start.global_distribution_state,
+        treasury_authority,
+        creator_ata,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: anchor_lang::system_program::ID,
+        investor_blacklist: None,
+    };
+    (context, accounts, distribution_day, entries)
+}
+
+/// Build the `claim_and_distribute_instant` instruction paying `entries`
+fn instant_distribution_instruction(
+    accounts: &meteora_fee_router::accounts::ClaimAndDistributeInstant,
+    distribution_day: i64,
+    entries: &[SnapshotEntry],
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    for entry in entries {
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(&entry.investor, &accounts.claim.quote_mint);
+        account_metas.push(AccountMeta::new_readonly(entry.investor, false));
+        account_metas.push(AccountMeta::new(investor_ata, false));
+    }
+    Instruction {
+        program_id: meteora_fee_router::id(),
+        accounts: account_metas,
+        data: meteora_fee_router::instruction::ClaimAndDistributeInstant { distribution_day }.data(),
+    }
+}
+
+/// Point the atomic setup's start accounts at `distribution_day` and snapshot `investors`
+/// 
+/// Each investor gets a Streamflow stream of its locked amount and an empty quote ATA.
+async fn prepare_distribution_day(
+    context: &mut ProgramTestContext,
+    start: &mut meteora_fee_router::accounts::StartDailyDistribution,
+    distribution_day: i64,
+    investors: &[(Pubkey, Pubkey, u64)],
+) {
+    let program_id = meteora_fee_router::id();
+    let quote_mint = start.quote_mint;
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    for (investor, stream_account, locked_amount) in investors {
+        let stream_start = (now - 100) as u64;
+        context.set_account(stream_account, &streamflow_account(&StreamflowStream {
+            recipient: *investor,
+            mint: quote_mint,
+            ..fixture_streamflow_stream(stream_start, stream_start + 100_000_000, *locked_amount)
+        }));
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(investor, &quote_mint);
+        if context.banks_client.get_account(investor_ata).await.unwrap().is_none() {
+            context.set_account(&investor_ata, &token_account(&quote_mint, investor, 0));
+        }
+    }
+
+    let page_investors: Vec<(Pubkey, Pubkey)> = investors
+        .iter()
+        .map(|(investor, stream_account, _)| (*investor, *stream_account))
+        .collect();
+    let entries = stream_snapshot_entries(context, &page_investors).await;
+    start.investor_snapshot = InvestorSnapshot::derive_pda(distribution_day, &quote_mint, &program_id).0;
+    context.set_account(&start.investor_snapshot, &anchor_account(&InvestorSnapshot {
+        distribution_day,
+        quote_mint,
+        investor_count: investors.len() as u32,
+        total_locked: investors.iter().map(|(_, _, locked_amount)| locked_amount).sum(),
+        taken_at: now,
+        payer: Pubkey::new_unique(),
+        entries,
+    }, &program_id));
+    start.daily_distribution_state = DailyDistributionState::derive_pda(distribution_day, &quote_mint, &program_id).0;
+    start.policy_snapshot = PolicySnapshot::derive_pda(distribution_day, &quote_mint, &program_id).0;
+    start.stream_error_log = StreamErrorLog::derive_pda(distribution_day, &quote_mint, &program_id).0;
+}
+
+/// Snapshot entries for `investors`, read from their streams in the bank at the current time
+async fn stream_snapshot_entries(context: &mut ProgramTestContext, investors: &[(Pubkey, Pubkey)]) -> Vec<SnapshotEntry> {
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp as u64;
+    let mut entries = Vec::new();
+    for (investor, stream_account) in investors {
+        let account = context.banks_client.get_account(*stream_account).await.unwrap().unwrap();
+        let stream = StreamflowStream::try_from_account_data(&account.data).unwrap();
+        entries.push(SnapshotEntry {
+            investor: *investor,
+            stream_account: *stream_account,
+            locked_amount: stream.locked_amount(now),
+            cliff_locked: stream.locked_cliff_amount(now),
+            lock_duration_seconds: stream.remaining_lock_seconds(now),
+        });
+    }
+    entries
+}
+
+/// Replace the entries of the page's investor snapshot with `investors`, in order
+async fn set_page_snapshot(
+    context: &mut ProgramTestContext,
+    accounts: &meteora_fee_router::accounts::ProcessInvestorPage,
+    investors: &[(Pubkey, Pubkey)],
+) {
+    let entries = stream_snapshot_entries(context, investors).await;
+    let account = context.banks_client.get_account(accounts.investor_snapshot).await.unwrap().unwrap();
+    let snapshot = InvestorSnapshot::try_deserialize(&mut account.data.as_slice()).unwrap();
+    context.set_account(&accounts.investor_snapshot, &anchor_account(&InvestorSnapshot {
+        investor_count: entries.len() as u32,
+        total_locked: entries.iter().map(|entry| entry.locked_amount).sum(),
+        entries,
+        ..snapshot
+    }, &meteora_fee_router::id()));
+}
+
+/// Run start, a single investor page and completion for a day prepared by `prepare_distribution_day`
+/// 
+/// Returns the completed daily distribution state.
+async fn run_distribution_day(
+    context: &mut ProgramTestContext,
+    start: &meteora_fee_router::accounts::StartDailyDistribution,
+    distribution_day: i64,
+    investors: &[(Pubkey, Pubkey, u64)],
+    creator_ata: Pubkey,
+) -> DailyDistributionState {
+    let program_id = meteora_fee_router::id();
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", start.quote_mint.as_ref()], &program_id);
+
+    let instruction = Instruction {
+        program_id,
+        accounts: start.to_account_metas(None),
+        data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+    };
+    send_instruction(context, instruction).await.unwrap();
+
+    let page = meteora_fee_router::accounts::ProcessInvestorPage {
+        authority: context.payer.pubkey(),
+        quote_mint: start.quote_mint,
+        program_config: start.program_config,
+        daily_distribution_state: start.daily_distribution_state,
+        policy_state: start.policy_state,
+        treasury_ata: start.treasury_ata,
+        treasury_authority,
+        token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::system_program::ID,
+        global_distribution_state: None,
+        treasury_state: None,
+        creator_ata: None,
+        protocol_fee_ata: None,
+        protocol_share_ata: None,
+        investor_blacklist: None,
+        stream_error_log: start.stream_error_log,
+        investor_snapshot: start.investor_snapshot,
+    };
+    let page_investors: Vec<(Pubkey, Pubkey)> = investors
+        .iter()
+        .map(|(investor, stream_account, _)| (*investor, *stream_account))
+        .collect();
+    send_instruction(context, investor_page_instruction(&page, distribution_day, &page_investors)).await.unwrap();
+
+    let instruction = Instruction {
+        program_id,
+        accounts: meteora_fee_router::accounts::CompleteDailyDistribution {
+            authority: context.payer.pubkey(),
+            quote_mint: start.quote_mint,
+            global_distribution_state: start.global_distribution_state,
+            daily_distribution_state: start.daily_distribution_state,
+            policy_state: start.policy_state,
+            treasury_state: start.treasury_state,
+            treasury_ata: start.treasury_ata,
+            treasury_authority,
+            creator_ata,
+            protocol_fee_ata: None,
+            protocol_share_ata: None,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: meteora_fee_router::instruction::CompleteDailyDistribution {}.data(),
+    };
+    send_instruction(context, instruction).await.unwrap();
+
+    let account = context.banks_client.get_account(start.daily_distribution_state).await.unwrap().unwrap();
+    DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Registry of `investor_count` investors, all on page 0, for a fresh quote mint
+async fn setup_investor_registry(investor_count: u32) -> (ProgramTestContext, Pubkey, Pubkey, Pubkey) {
+    let program_id = meteora_fee_router::id();
+    let program_test = ProgramTest::new("meteora_fee_router", program_id, processor!(process_fee_router));
+    let mut context = program_test.start_with_context().await;
+
+    let quote_mint = Pubkey::new_unique();
+    context.set_account(&quote_mint, &mint_account());
+
+    let (policy_state, policy_bump) = PolicyState::derive_pda(&quote_mint, &program_id);
+    context.set_account(&policy_state, &anchor_account(&PolicyState {
+        bump: policy_bump,
+        policy_authority: context.payer.pubkey(),
+        ..fixture_policy_state().with_quote_mint(quote_mint)
+    }, &program_id));
+
+    let (investor_registry, registry_bump) = InvestorRegistryState::derive_pda(&quote_mint, &program_id);
+    context.set_account(&investor_registry, &anchor_account(&InvestorRegistryState {
+        quote_mint,
+        investor_capacity: 10_000,
+        investor_count,
+        page_count: 1,
+        last_page_index: 0,
+        bump: registry_bump,
+    }, &program_id));
+
+    // Pages are allocated for a full page of entries
+    let (first_page, page_bump) = InvestorRegistryPage::derive_pda(&investor_registry, 0, &program_id);
+    let mut data = Vec::new();
+    InvestorRegistryPage {
+        registry: investor_registry,
+        page_index: 0,
+        next_page: Pubkey::default(),
+        entries: (0..investor_count)
+            .map(|_| InvestorEntry { investor: Pubkey::new_unique(), stream_account: Pubkey::new_unique() })
+            .collect(),
+        bump: page_bump,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    data.resize(8 + InvestorRegistryPage::INIT_SPACE, 0);
+    let mut page = solana_sdk::account::Account::new(1_000_000_000, data.len(), &program_id);
+    page.data = data;
+    context.set_account(&first_page, &page.into());
+
+    (context, quote_mint, policy_state, investor_registry)
+}
+
+/// `add_investor` for the registry's current last page
+async fn add_investor_instruction(
+    context: &mut ProgramTestContext,
+    quote_mint: Pubkey,
+    policy_state: Pubkey,
+    investor_registry: Pubkey,
+    investor: Pubkey,
+) -> Instruction {
+    let program_id = meteora_fee_router::id();
+    let account = context.banks_client.get_account(investor_registry).await.unwrap().unwrap();
+    let registry = InvestorRegistryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    let (last_page, _) = InvestorRegistryPage::derive_pda(&investor_registry, registry.last_page_index, &program_id);
+    let (next_page, _) = InvestorRegistryPage::derive_pda(&investor_registry, registry.last_page_index + 1, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: meteora_fee_router::accounts::AddInvestor {
+            authority: context.payer.pubkey(),
+            quote_mint,
+            policy_state,
+            investor_registry,
+            last_page,
+            next_page,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: meteora_fee_router::instruction::AddInvestor {
+            investor,
+            stream_account: Pubkey::new_unique(),
+        }
+        .data(),
+    }
+}
+
+/// Read the registry page `page_index` of `investor_registry`
+async fn registry_page(context: &mut ProgramTestContext, investor_registry: Pubkey, page_index: u32) -> InvestorRegistryPage {
+    let (address, _) = InvestorRegistryPage::derive_pda(&investor_registry, page_index, &meteora_fee_router::id());
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    InvestorRegistryPage::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Start a bank with an open day for `investor_count` investors, each with
+/// a live stream locking `locked_amount` and an empty quote ATA
+/// 
+/// The day's snapshot holds every investor's stream in order. Returns the
+/// page accounts and the investors with their stream accounts.
+async fn setup_investor_page(
+    investor_count: u32,
+    locked_amount: u64,
+) -> (ProgramTestContext, meteora_fee_router::accounts::ProcessInvestorPage, Vec<(Pubkey, Pubkey)>) {
+    let program_id = meteora_fee_router::id();
+    let program_test = ProgramTest::new("meteora_fee_router", program_id, processor!(process_fee_router));
+    let mut context = program_test.start_with_context().await;
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let distribution_day = DailyDistributionState::get_day_start(now);
+
+    let quote_mint = Pubkey::new_unique();
+    context.set_account(&quote_mint, &mint_account());
+
+    let (program_config, _) = ProgramConfig::derive_pda(&program_id);
+    context.set_account(&program_config, &anchor_account(&ProgramConfig {
+        program_pause_authority: Pubkey::new_unique(),
+        is_paused: false,
+        pause_count: 0,
+        resume_count: 0,
+        last_paused_at: 0,
+        total_paused_seconds: 0,
+        reserved: [0; 8],
+    }, &program_id));
+
+    let (policy_state, policy_bump) = PolicyState::derive_pda(&quote_mint, &program_id);
+    context.set_account(&policy_state, &anchor_account(&PolicyState {
+        bump: policy_bump,
+        ..fixture_policy_state().with_quote_mint(quote_mint)
+    }, &program_id));
+
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+    let treasury_ata = Pubkey::new_unique();
+    context.set_account(&treasury_ata, &token_account(&quote_mint, &treasury_authority, 100_000));
+
+    let (daily_distribution_state, _) = DailyDistributionState::derive_pda(distribution_day, &quote_mint, &program_id);
+    context.set_account(&daily_distribution_state, &anchor_account(&DailyDistributionState {
+        distribution_day,
+        quote_mint,
+        treasury_ata,
+        total_investors: investor_count,
+        processed_bitmap: vec![0; DailyDistributionState::bitmap_len(investor_count)],
+        started_at: now,
+        lock_snapshot_timestamp: now,
+        treasury_balance_at_start: 100_000,
+        ..fixture_daily_distribution_state()
+    }, &program_id));
+    let (stream_error_log, stream_error_log_bump) = StreamErrorLog::derive_pda(distribution_day, &quote_mint, &program_id);
+    context.set_account(&stream_error_log, &anchor_account(&StreamErrorLog {
+        distribution_day,
+        quote_mint,
+        entries: Vec::new(),
+        total_errors: 0,
+        payer: context.payer.pubkey(),
+        bump: stream_error_log_bump,
+    }, &program_id));
+
+    let investors: Vec<(Pubkey, Pubkey)> = (0..investor_count)
+        .map(|_| {
+            let investor = Pubkey::new_unique();
+            let stream_account = Pubkey::new_unique();
+            let start = (now - 100) as u64;
+            context.set_account(&stream_account, &streamflow_account(&StreamflowStream {
+                recipient: investor,
+                mint: quote_mint,
+                ..fixture_streamflow_stream(start, start + 10_000_000, locked_amount)
+            }));
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(&investor, &quote_mint);
+            context.set_account(&investor_ata, &token_account(&quote_mint, &investor, 0));
+            (investor, stream_account)
+        })
+        .collect();
+
+    // The day's snapshot lists every investor in order
+    let entries = stream_snapshot_entries(&mut context, &investors).await;
+    let (investor_snapshot, _) = InvestorSnapshot::derive_pda(distribution_day, &quote_mint, &program_id);
+    context.set_account(&investor_snapshot, &anchor_account(&InvestorSnapshot {
+        distribution_day,
+        quote_mint,
+        investor_count,
+        total_locked: entries.iter().map(|entry| entry.locked_amount).sum(),
+        taken_at: now,
+        payer: context.payer.pubkey(),
+        entries,
+    }, &program_id));
+
+    let accounts = meteora_fee_router::accounts::ProcessInvestorPage {
+        authority: context.payer.pubkey(),
+        quote_mint,
+        program_config,
+        daily_distribution_state,
+        policy_state,
+        treasury_ata,
+        treasury_authority,
+        token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::system_program::ID,
+        global_distribution_state: None,
+        treasury_state: None,
+        creator_ata: None,
+        protocol_fee_ata: None,
+        protocol_share_ata: None,
+        investor_blacklist: None,
+        stream_error_log,
+        investor_snapshot,
+    };
+    (context, accounts, investors)
+}
+
+/// Start a registry-driven day for `investor_count` investors (see `setup_investor_page`)
+/// 
+/// The investors are written to registry pages of `INVESTOR_REGISTRY_PAGE_SIZE`
+/// in order, and every payout clears the 1 token minimum.
+async fn setup_registry_distribution(
+    investor_count: u32,
+    locked_amount: u64,
+) -> (ProgramTestContext, meteora_fee_router::accounts::ProcessInvestorPageFromRegistry, Vec<(Pubkey, Pubkey)>) {
+    let program_id = meteora_fee_router::id();
+    let (mut context, page, investors) = setup_investor_page(investor_count, locked_amount).await;
+    let quote_mint = page.quote_mint;
+
+    let account = context.banks_client.get_account(page.daily_distribution_state).await.unwrap().unwrap();
+    let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    context.set_account(&page.daily_distribution_state, &anchor_account(&DailyDistributionState {
+        registry_driven: true,
+        min_payout_threshold: 1,
+        ..distribution
+    }, &program_id));
+
+    let page_count = investors.chunks(INVESTOR_REGISTRY_PAGE_SIZE).len() as u32;
+    let (investor_registry, registry_bump) = InvestorRegistryState::derive_pda(&quote_mint, &program_id);
+    context.set_account(&investor_registry, &anchor_account(&InvestorRegistryState {
+        quote_mint,
+        investor_capacity: 10_000,
+        investor_count,
+        page_count,
+        last_page_index: page_count - 1,
+        bump: registry_bump,
+    }, &program_id));
+
+    for (page_index, chunk) in investors.chunks(INVESTOR_REGISTRY_PAGE_SIZE).enumerate() {
+        let page_index = page_index as u32;
+        let (address, page_bump) = InvestorRegistryPage::derive_pda(&investor_registry, page_index, &program_id);
+        let next_page = if page_index + 1 < page_count {
+            InvestorRegistryPage::derive_pda(&investor_registry, page_index + 1, &program_id).0
+        } else {
+            Pubkey::default()
+        };
+        context.set_account(&address, &anchor_account(&InvestorRegistryPage {
+            registry: investor_registry,
+            page_index,
+            next_page,
+            entries: chunk
+                .iter()
+                .map(|(investor, stream_account)| InvestorEntry { investor: *investor, stream_account: *stream_account })
+                .collect(),
+            bump: page_bump,
+        }, &program_id));
+    }
+
+    let (registry_page, _) = InvestorRegistryPage::derive_pda(&investor_registry, 0, &program_id);
+    let accounts = meteora_fee_router::accounts::ProcessInvestorPageFromRegistry {
+        page,
+        investor_registry,
+        registry_page,
+    };
+    (context, accounts, investors)
+}
+
+/// Build the `process_investor_page` instruction for `investors` and their streams
+fn investor_page_instruction(
+    accounts: &meteora_fee_router::accounts::ProcessInvestorPage,
+    distribution_day: i64,
+    investors: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let program_id = meteora_fee_router::id();
+    let mut account_metas = accounts.to_account_metas(None);
+    for (investor, stream_account) in investors {
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(investor, &accounts.quote_mint);
+        let (investor_cache, _) = InvestorAggregateCache::derive_pda(distribution_day, &accounts.quote_mint, investor, &program_id);
+        let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, investor, &program_id);
+        account_metas.push(AccountMeta::new_readonly(*stream_account, false));
+        account_metas.push(AccountMeta::new_readonly(*investor, false));
+        account_metas.push(AccountMeta::new(investor_ata, false));
+        account_metas.push(AccountMeta::new_readonly(anchor_spl::associated_token::ID, false));
+        account_metas.push(AccountMeta::new(investor_cache, false));
+        account_metas.push(AccountMeta::new(payout_summary, false));
+    }
+    Instruction {
+        program_id,
+        accounts: account_metas,
+        data: meteora_fee_router::instruction::ProcessInvestorPage { expected_page_index: 0 }.data(),
+    }
+}
+
+/// Build the `process_investor_page_from_snapshot` instruction paying the snapshot `entries` of `page_index`
+fn snapshot_page_instruction(
+    accounts: &meteora_fee_router::accounts::ProcessInvestorPageFromSnapshot,
+    distribution_day: i64,
+    page_index: u32,
+    entries: &[SnapshotEntry],
+) -> Instruction {
+    let program_id = meteora_fee_router::id();
+    let mut account_metas = accounts.to_account_metas(None);
+    for entry in entries {
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(&entry.investor, &accounts.page.quote_mint);
+        let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, &entry.investor, &program_id);
+        account_metas.push(AccountMeta::new_readonly(entry.investor, false));
+        account_metas.push(AccountMeta::new(investor_ata, false));
+        account_metas.push(AccountMeta::new(payout_summary, false));
+    }
+    Instruction {
+        program_id,
+        accounts: account_metas,
+        data: meteora_fee_router::instruction::ProcessInvestorPageFromSnapshot { page_index }.data(),
+    }
+}
+
+/// Start a bank with a DAMM V2 honorary position created by `creating_authority`
+/// 
+/// The position holds seeded liquidity and `POSITION_FEES` of unclaimed quote
+/// fees. The payer is the policy authority. Returns the `close_position`
+/// accounts for the payer as authority.
+async fn setup_close_position(
+    creating_authority: Option<Pubkey>,
+) -> (ProgramTestContext, meteora_fee_router::accounts::ClosePosition) {
+    let program_id = meteora_fee_router::id();
+    let mut program_test = ProgramTest::new("meteora_fee_router", program_id, processor!(process_fee_router));
+    program_test.add_program("meteora_cp_amm", METEORA_CP_AMM_PROGRAM_ID, processor!(process_mock_close_position_cpis));
+    let mut context = program_test.start_with_context().await;
+    let authority = context.payer.pubkey();
+
+    let quote_mint = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let position_nft_mint = Pubkey::new_unique();
+    context.set_account(&quote_mint, &mint_account());
+    context.set_account(&base_mint, &mint_account());
+
+    let vault = Pubkey::new_unique();
+    let (position_owner_pda, position_owner_bump) =
+        Pubkey::find_program_address(&[VAULT_SEED, vault.as_ref(), POSITION_OWNER_SEED], &program_id);
+    let pool = Pubkey::new_unique();
+    let quote_vault = Pubkey::new_unique();
+    let base_vault = Pubkey::new_unique();
+    let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+    context.set_account(&pool, &meteora_pool_account(&fixture_meteora_pool(quote_mint, base_mint, quote_vault, base_vault)));
+    context.set_account(&quote_vault, &token_account(&quote_mint, &event_authority, POSITION_FEES));
+    context.set_account(&base_vault, &token_account(&base_mint, &event_authority, 0));
+
+    // CP-AMM position holding the seeded liquidity
+    let position = Pubkey::new_unique();
+    let mut position_account = solana_sdk::account::Account::new(1_000_000_000, POSITION_ACCOUNT_SIZE, &METEORA_CP_AMM_PROGRAM_ID);
+    position_account.data[position_layout::POOL..position_layout::POOL + 32].copy_from_slice(pool.as_ref());
+    position_account.data[position_layout::UNLOCKED_LIQUIDITY..position_layout::UNLOCKED_LIQUIDITY + 16]
+        .copy_from_slice(&1_000u128.to_le_bytes());
+    context.set_account(&position, &position_account.into());
+
+    let (position_metadata, _) = PositionMetadata::derive_pda(&position_nft_mint, &program_id);
+    context.set_account(&position_metadata, &anchor_account(&PositionMetadata {
+        position,
+        pool,
+        quote_mint,
+        base_mint,
+        created_at: 0,
+        position_owner_bump,
+        pool_type: PoolType::MeteoraDAMM,
+        last_claim_timestamp: 0,
+        total_fees_claimed: 0,
+        position_value_quote: 0,
+        authority: creating_authority.unwrap_or(authority),
+        reserved: [0; 8],
+    }, &program_id));
+
+    let authority_quote_ata = Pubkey::new_unique();
+    let authority_base_ata = Pubkey::new_unique();
+    context.set_account(&authority_quote_ata, &token_account(&quote_mint, &authority, 0));
+    context.set_account(&authority_base_ata, &token_account(&base_mint, &authority, 0));
+
+    let (treasury_state, treasury_bump) = TreasuryState::derive_pda(&quote_mint, &program_id);
+    let treasury_ata = Pubkey::new_unique();
+    context.set_account(&treasury_ata, &token_account(&quote_mint, &treasury_state, 0));
+    context.set_account(&treasury_state, &anchor_account(&TreasuryState {
+        treasury_ata,
+        bump: treasury_bump,
+        ..fixture_treasury_state()
+            .with_quote_mint(quote_mint)
+            .with_claim_authority(position_owner_pda)
+            .with_allowed_mints([quote_mint, Pubkey::default(), Pubkey::default(), Pubkey::default()])
+    }, &program_id));
+
+    let (policy_state, policy_bump) = Pubkey::find_program_address(&[b"policy", quote_mint.as_ref()], &program_id);
+    context.set_account(&policy_state, &anchor_account(&PolicyState {
+        policy_authority: authority,
+        bump: policy_bump,
+        ..fixture_policy_state().with_quote_mint(quote_mint)
+    }, &program_id));
+
+    let accounts = meteora_fee_router::accounts::ClosePosition {
+        authority,
+        vault,
+        position_owner_pda,
+        position_nft_mint,
+        position_nft_account: Pubkey::new_unique(),
+        pool,
+        position,
+        quote_mint,
+        base_mint,
+        authority_quote_ata,
+        authority_base_ata,
+        treasury_state,
+        treasury_ata,
+        policy_state,
+        quote_vault,
+        base_vault,
+        position_metadata,
+        pool_authority: POOL_AUTHORITY,
+        event_authority,
+        meteora_program: METEORA_CP_AMM_PROGRAM_ID,
+        token_program: anchor_spl::token::ID,
+        position_nft_token_program: anchor_spl::token_2022::ID,
+    };
+    (context, accounts)
+}
+
+/// Read the balance of the token account at `address`
+async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+/// Send `instruction` in its own transaction signed by the payer
+async fn send_instruction(context: &mut ProgramTestContext, instruction: Instruction) -> std::result::Result<(), BanksClientError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Send `instruction` in its own transaction signed and paid for by `signer`
+async fn send_instruction_as(
+    context: &mut ProgramTestContext,
+    signer: &solana_sdk::signature::Keypair,
+    instruction: Instruction,
+) -> std::result::Result<(), BanksClientError> {
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&signer.pubkey()), &[signer], context.last_blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+#[cfg(test)]
+mod distribution_tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_atomic_claim_and_start_distribution() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let daily_distribution_state = accounts.start.daily_distribution_state;
+        let treasury_ata = accounts.start.treasury_ata;
+
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: accounts.to_account_metas(None),
+            data: meteora_fee_router::instruction::InitializeAtomicDistribution { distribution_day }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The day starts from the treasury balance after the claim
+        let account = context.banks_client.get_account(daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.treasury_balance_at_start, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+        assert_eq!(distribution.total_amount_to_distribute, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+        assert_eq!(distribution.total_fees_claimed_at_distribution_start, POSITION_FEES);
+
+        // Distribution parameters mirror the policy
+        let policy = fixture_policy_state();
+        assert_eq!(distribution.initial_total_deposit, policy.y0_total_allocation);
+        assert_eq!(distribution.investor_fee_share_bps, policy.investor_fee_share_bps);
+        assert_eq!(distribution.daily_cap_total, policy.daily_cap_lamports);
+        assert_eq!(distribution.daily_cap_remaining, policy.daily_cap_lamports);
+        assert_eq!(distribution.min_payout_threshold, policy.min_payout_lamports);
+
+        // The day's stream error log starts empty
+        let account = context.banks_client.get_account(accounts.start.stream_error_log).await.unwrap().unwrap();
+        let stream_error_log = StreamErrorLog::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(stream_error_log.distribution_day, distribution_day);
+        assert!(stream_error_log.entries.is_empty());
+
+        let account = context.banks_client.get_account(treasury_ata).await.unwrap().unwrap();
+        let treasury = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(treasury.amount, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_auto_claim_before_distribution_start() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let instruction = auto_claim_start_instruction(&mut context, &accounts, distribution_day).await;
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The day starts from the treasury balance after the claim
+        let account = context.banks_client.get_account(accounts.start.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.total_amount_to_distribute, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+        assert_eq!(distribution.total_fees_claimed_at_distribution_start, POSITION_FEES);
+        assert_eq!(token_balance(&mut context, accounts.start.treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_auto_claim_skips_claims_within_interval() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+        let account = context.banks_client.get_account(accounts.start.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.start.treasury_state, &anchor_account(&TreasuryState {
+            last_claim_timestamp: now,
+            ..treasury_state
+        }, &program_id));
+
+        // The start goes ahead on the current treasury balance
+        let instruction = auto_claim_start_instruction(&mut context, &accounts, distribution_day).await;
+        send_instruction(&mut context, instruction).await.unwrap();
+        let account = context.banks_client.get_account(accounts.start.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.total_amount_to_distribute, TREASURY_BALANCE_BEFORE_CLAIM);
+        assert_eq!(token_balance(&mut context, accounts.start.treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_auto_claim_failure_after_cpi_aborts_start() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let quote_mint = accounts.claim.quote_mint;
+
+        // The position CPI succeeds but pays nothing
+        let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+        context.set_account(&accounts.claim.quote_vault, &token_account(&quote_mint, &event_authority, 0));
+
+        let instruction = auto_claim_start_instruction(&mut context, &accounts, distribution_day).await;
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::NoFeesToClaim as u32),
+            )
+        );
+        assert!(context.banks_client.get_account(accounts.start.daily_distribution_state).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_repeated_start_reports_existing_distribution() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let start_accounts = accounts.start.to_account_metas(None);
+
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: accounts.to_account_metas(None),
+            data: meteora_fee_router::instruction::InitializeAtomicDistribution { distribution_day }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // A second start for the same day fails with the dedicated error
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: start_accounts,
+            data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+        };
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::DistributionAlreadyExistsForDay as u32),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_dust_rolls_over_across_days() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, first_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        // Pro-rata shares of three equal investors do not divide evenly, leaving rounding dust
+        let investors = [
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+        ];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        let investor_atas: Vec<Pubkey> = investors
+            .iter()
+            .map(|(investor, _, _)| anchor_spl::associated_token::get_associated_token_address(investor, &quote_mint))
+            .collect();
+
+        let mut dust_rollover = 0;
+        let mut lifetime_dust = 0;
+        for day_index in 0..2 {
+            let distribution_day = first_day + day_index * 86_400;
+            if day_index > 0 {
+                let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+                clock.unix_timestamp += 86_400;
+                context.set_sysvar(&clock);
+
+                // A day's worth of fresh fees lands next to yesterday's leftovers
+                let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+                let leftover = token_balance(&mut context, start.treasury_ata).await;
+                context.set_account(&start.treasury_ata, &token_account(&quote_mint, &treasury_authority, leftover + TREASURY_BALANCE_BEFORE_CLAIM));
+            }
+            prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+            let treasury_before = token_balance(&mut context, start.treasury_ata).await;
+            let mut paid_before = 0;
+            for ata in investor_atas.iter().chain([&creator_ata]) {
+                paid_before += token_balance(&mut context, *ata).await;
+            }
+
+            let day = run_distribution_day(&mut context, &start, distribution_day, &investors, creator_ata).await;
+
+            // Yesterday's dust is carried in, but only once: it is part of today's treasury balance
+            assert_eq!(day.dust_rolled_over, dust_rollover);
+            assert_eq!(day.total_amount_to_distribute + day.dust_rolled_over, treasury_before);
+            assert!(day.new_dust() > 0);
+
+            // Every token that left the treasury reached an investor or the creator
+            let treasury_after = token_balance(&mut context, start.treasury_ata).await;
+            let mut paid_after = 0;
+            for ata in investor_atas.iter().chain([&creator_ata]) {
+                paid_after += token_balance(&mut context, *ata).await;
+            }
+            assert_eq!(treasury_before - treasury_after, paid_after - paid_before);
+
+            // All of the day's dust stays in the treasury and is handed to the next day
+            let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+            let global = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+            assert_eq!(global.dust_rollover, day.dust_carried_over);
+            assert!(treasury_after >= global.dust_rollover);
+            dust_rollover = global.dust_rollover;
+
+            // Each day's new dust is counted once in the lifetime total
+            lifetime_dust += day.new_dust();
+            let account = context.banks_client.get_account(start.treasury_state).await.unwrap().unwrap();
+            let treasury = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+            assert_eq!(treasury.accumulated_dust_lifetime, lifetime_dust);
+        }
+        assert!(dust_rollover > 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_pause_and_policy_authorities_are_independent() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let start = accounts.start;
+        let pause_authority = solana_sdk::signature::Keypair::new();
+        context.set_account(&pause_authority.pubkey(), &solana_sdk::account::Account::new(1_000_000_000, 0, &anchor_lang::system_program::ID).into());
+
+        // The payer holds the policy role and a separate key holds the pause role
+        let account = context.banks_client.get_account(start.program_config).await.unwrap().unwrap();
+        let program_config = ProgramConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.program_config, &anchor_account(&ProgramConfig {
+            program_pause_authority: pause_authority.pubkey(),
+            ..program_config
+        }, &program_id));
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            policy_authority: context.payer.pubkey(),
+            ..policy
+        }, &program_id));
+
+        let pause_instruction = |authority: Pubkey| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::ManageProgramPause {
+                authority,
+                program_config: start.program_config,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::PauseDistribution {}.data(),
+        };
+        let settings_instruction = |authority: Pubkey| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::UpdatePolicySettings {
+                authority,
+                quote_mint: start.quote_mint,
+                policy_state: start.policy_state,
+                governance_proposal: None,
+                governance_proposal_transaction: None,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::UpdatePolicySettings {
+                params: UpdatePolicySettingsParams { auto_extend_sunset: Some(true), ..Default::default() },
+            }
+            .data(),
+        };
+        let unauthorized = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::Unauthorized as u32),
+        );
+
+        // The policy authority cannot pause
+        let policy_authority = context.payer.pubkey();
+        let error = send_instruction(&mut context, pause_instruction(policy_authority)).await.unwrap_err().unwrap();
+        assert_eq!(error, unauthorized);
+
+        // The pause authority cannot change policy settings
+        let error = send_instruction_as(&mut context, &pause_authority, settings_instruction(pause_authority.pubkey()))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(error, unauthorized);
+
+        // Each authority still controls its own instruction
+        send_instruction_as(&mut context, &pause_authority, pause_instruction(pause_authority.pubkey())).await.unwrap();
+        send_instruction(&mut context, settings_instruction(policy_authority)).await.unwrap();
+        let account = context.banks_client.get_account(start.program_config).await.unwrap().unwrap();
+        assert!(ProgramConfig::try_deserialize(&mut account.data.as_slice()).unwrap().is_paused);
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        assert!(PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap().auto_extend_sunset);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_policy_settings_enable_sunset_auto_extension() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        let sunset_timestamp = distribution_day + 30 * 86_400;
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            policy_authority: context.payer.pubkey(),
+            sunset_timestamp,
+            ..policy
+        }, &program_id));
+
+        let instruction = Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::UpdatePolicySettings {
+                authority: context.payer.pubkey(),
+                quote_mint,
+                policy_state: start.policy_state,
+                governance_proposal: None,
+                governance_proposal_transaction: None,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::UpdatePolicySettings {
+                params: UpdatePolicySettingsParams { auto_extend_sunset: Some(true), ..Default::default() },
+            }
+            .data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        let investors = [(Pubkey::new_unique(), Pubkey::new_unique(), 500_000)];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+        run_distribution_day(&mut context, &start, distribution_day, &investors, creator_ata).await;
+
+        // Settling the day pushed the sunset back one interval
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(policy.auto_extend_sunset);
+        assert_eq!(policy.sunset_timestamp, sunset_timestamp + 86_400);
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let global = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(global.auto_extension_count, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_policy_settings_switch_page_hash_algorithm() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            policy_authority: context.payer.pubkey(),
+            ..policy
+        }, &program_id));
+
+        let instruction = Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::UpdatePolicySettings {
+                authority: context.payer.pubkey(),
+                quote_mint,
+                policy_state: start.policy_state,
+                governance_proposal: None,
+                governance_proposal_transaction: None,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::UpdatePolicySettings {
+                params: UpdatePolicySettingsParams { page_hash_algorithm: Some(HashAlgorithm::Keccak256), ..Default::default() },
+            }
+            .data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // Pages of the next distribution are hashed with Keccak-256
+        let investors = [(Pubkey::new_unique(), Pubkey::new_unique(), 500_000)];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+        let daily_state = run_distribution_day(&mut context, &start, distribution_day, &investors, creator_ata).await;
+
+        let stream_accounts = [investors[0].1];
+        assert_eq!(daily_state.last_page_hash, DailyDistributionState::calculate_page_hash(&stream_accounts, HashAlgorithm::Keccak256));
+        assert_ne!(daily_state.last_page_hash, DailyDistributionState::calculate_page_hash(&stream_accounts, HashAlgorithm::Sha256));
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(policy.page_hash_algorithm, HashAlgorithm::Keccak256);
+        assert!(!policy.auto_extend_sunset);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_policy_settings_in_governance_mode_require_completed_proposal() {
+        use meteora_fee_router::shared::governance::{
+            GovernanceProposal, ProposalInstruction, ProposalTransaction, PROPOSAL_ACCOUNT_TYPE,
+            PROPOSAL_STATE_COMPLETED, PROPOSAL_TRANSACTION_ACCOUNT_TYPE, SPL_GOVERNANCE_PROGRAM_ID,
+        };
+
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let start = accounts.start;
+        let governance = Pubkey::new_unique();
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            policy_authority: context.payer.pubkey(),
+            governance_authority: governance,
+            ..policy
+        }, &program_id));
+
+        let governance_account = |data: Vec<u8>| -> solana_sdk::account::AccountSharedData {
+            solana_sdk::account::Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: SPL_GOVERNANCE_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            }.into()
+        };
+        let set_proposal = |context: &mut ProgramTestContext, proposal: Pubkey, state: u8| {
+            context.set_account(&proposal, &governance_account(GovernanceProposal {
+                account_type: PROPOSAL_ACCOUNT_TYPE,
+                governance,
+                governing_token_mint: Pubkey::new_unique(),
+                state,
+            }.try_to_vec().unwrap()));
+        };
+        let set_proposal_transaction = |context: &mut ProgramTestContext, transaction: Pubkey, proposal: Pubkey, data: Vec<u8>| {
+            context.set_account(&transaction, &governance_account(ProposalTransaction {
+                account_type: PROPOSAL_TRANSACTION_ACCOUNT_TYPE,
+                proposal,
+                option_index: 0,
+                transaction_index: 0,
+                hold_up_time: 0,
+                instructions: vec![ProposalInstruction { program_id, accounts: Vec::new(), data }],
+                executed_at: Some(0),
+                execution_status: 1,
+                reserved_v2: [0; 8],
+            }.try_to_vec().unwrap()));
+        };
+        let policy_authority = context.payer.pubkey();
+        let settings_instruction = |params: UpdatePolicySettingsParams, proposal: Option<(Pubkey, Pubkey)>| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::UpdatePolicySettings {
+                authority: policy_authority,
+                quote_mint: start.quote_mint,
+                policy_state: start.policy_state,
+                governance_proposal: proposal.map(|(proposal, _)| proposal),
+                governance_proposal_transaction: proposal.map(|(_, transaction)| transaction),
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::UpdatePolicySettings { params }.data(),
+        };
+        let invalid_proposal = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::InvalidGovernanceProposal as u32),
+        );
+        let enable_auto_extend = UpdatePolicySettingsParams { auto_extend_sunset: Some(true), ..Default::default() };
+        let update_data = meteora_fee_router::instruction::UpdatePolicySettings { params: enable_auto_extend.clone() }.data();
+        let (proposal, transaction) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // The policy authority alone can no longer update the policy
+        let error = send_instruction(&mut context, settings_instruction(enable_auto_extend.clone(), None)).await.unwrap_err().unwrap();
+        assert_eq!(error, invalid_proposal);
+
+        // A proposal still being voted on is rejected
+        set_proposal(&mut context, proposal, 2); // ProposalState::Voting
+        set_proposal_transaction(&mut context, transaction, proposal, update_data.clone());
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, settings_instruction(enable_auto_extend.clone(), Some((proposal, transaction)))).await.unwrap_err().unwrap();
+        assert_eq!(error, invalid_proposal);
+
+        // A completed proposal only backs the update it carries
+        set_proposal(&mut context, proposal, PROPOSAL_STATE_COMPLETED);
+        let other_update = UpdatePolicySettingsParams { page_hash_algorithm: Some(HashAlgorithm::Keccak256), ..Default::default() };
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, settings_instruction(other_update, Some((proposal, transaction)))).await.unwrap_err().unwrap();
+        assert_eq!(error, invalid_proposal);
+
+        send_instruction(&mut context, settings_instruction(enable_auto_extend, Some((proposal, transaction)))).await.unwrap();
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(policy.auto_extend_sunset);
+
+        // Governance can hand the policy back to direct authority mode
+        let direct_mode = UpdatePolicySettingsParams { governance_authority: Some(Pubkey::default()), ..Default::default() };
+        let (proposal, transaction) = (Pubkey::new_unique(), Pubkey::new_unique());
+        set_proposal(&mut context, proposal, PROPOSAL_STATE_COMPLETED);
+        set_proposal_transaction(
+            &mut context,
+            transaction,
+            proposal,
+            meteora_fee_router::instruction::UpdatePolicySettings { params: direct_mode.clone() }.data(),
+        );
+        send_instruction(&mut context, settings_instruction(direct_mode, Some((proposal, transaction)))).await.unwrap();
+        let disable_auto_extend = UpdatePolicySettingsParams { auto_extend_sunset: Some(false), ..Default::default() };
+        send_instruction(&mut context, settings_instruction(disable_auto_extend, None)).await.unwrap();
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(policy.governance_authority, Pubkey::default());
+        assert!(!policy.auto_extend_sunset);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_audit_completed_distribution_requires_payout_summaries() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        let investors = [
+            (Pubkey::new_unique(), Pubkey::new_unique(), 500_000),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 300_000),
+        ];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+        let daily_state = run_distribution_day(&mut context, &start, distribution_day, &investors, creator_ata).await;
+        assert_eq!(
+            daily_state.amount_distributed + daily_state.creator_remainder,
+            daily_state.get_effective_distribution_amount()
+        );
+
+        let auditor = context.payer.pubkey();
+        let audit_instruction = |payout_summaries: &[Pubkey]| {
+            let mut account_metas = meteora_fee_router::accounts::AuditCompletedDistribution {
+                authority: auditor,
+                quote_mint,
+                global_distribution_state: start.global_distribution_state,
+                daily_distribution_state: start.daily_distribution_state,
+                policy_snapshot: start.policy_snapshot,
+            }
+            .to_account_metas(None);
+            account_metas.extend(payout_summaries.iter().map(|summary| AccountMeta::new_readonly(*summary, false)));
+            Instruction {
+                program_id,
+                accounts: account_metas,
+                data: meteora_fee_router::instruction::AuditCompletedDistribution { distribution_day }.data(),
+            }
+        };
+
+        // The audit cannot pass without the payout summaries
+        let error = send_instruction(&mut context, audit_instruction(&[])).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::PayoutSummariesRequired as u32),
+            )
+        );
+
+        let payout_summaries: Vec<Pubkey> = investors
+            .iter()
+            .map(|(investor, _, _)| InvestorPayoutSummary::derive_pda(distribution_day, investor, &program_id).0)
+            .collect();
+        let transaction = Transaction::new_signed_with_payer(
+            &[audit_instruction(&payout_summaries)],
+            Some(&auditor),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+        assert_eq!(simulation.result, Some(Ok(())));
+        let logs = simulation.simulation_details.unwrap().logs;
+        assert!(logs.iter().any(|log| log.contains("Distribution audit passed")), "{:?}", logs);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_upgrade_v1_policy_format() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let quote_mint = accounts.start.quote_mint;
+        let policy_state = accounts.start.policy_state;
+        let (_, policy_bump) = PolicyState::derive_pda(&quote_mint, &program_id);
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let set_v1_policy = |context: &mut ProgramTestContext, policy_authority: &Pubkey| {
+            let data = v1_policy_account_data(&quote_mint, policy_authority);
+            let mut account = solana_sdk::account::Account::new(rent.minimum_balance(data.len()), data.len(), &program_id);
+            account.data = data;
+            context.set_account(&policy_state, &account.into());
+        };
+        let authority = context.payer.pubkey();
+        let upgrade_instruction = || Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::UpgradePolicyFormat {
+                authority,
+                quote_mint,
+                policy_state,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::UpgradePolicyFormat { target_version: 2 }.data(),
+        };
+
+        // Only the v1 policy authority can migrate
+        set_v1_policy(&mut context, &Pubkey::new_unique());
+        let error = send_instruction(&mut context, upgrade_instruction()).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(0, InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::Unauthorized as u32))
+        );
+
+        // The account grows to the v2 layout, rent topped up by the authority
+        set_v1_policy(&mut context, &authority);
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, upgrade_instruction()).await.unwrap();
+        let account = context.banks_client.get_account(policy_state).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), PolicyState::INIT_SPACE);
+        assert!(account.lamports >= rent.minimum_balance(PolicyState::INIT_SPACE));
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(policy.quote_mint, quote_mint);
+        assert_eq!(policy.policy_authority, authority);
+        assert_eq!(policy.investor_fee_share_bps, 7000);
+        assert_eq!(policy.creator_fee_share_bps, 3000);
+        assert_eq!(policy.daily_cap_lamports, 5_000_000);
+        assert_eq!(policy.min_payout_lamports, 1000);
+        assert_eq!(policy.y0_total_allocation, 2_000_000);
+        assert_eq!(policy.bump, policy_bump);
+        assert_eq!(policy.format_version(), 2);
+
+        // v2 is the latest layout
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, upgrade_instruction()).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::UnsupportedPolicyVersion as u32),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_page_auto_completion_settles_the_day() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        // Settle inline after the first page
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            max_pages_before_auto_complete: 1,
+            ..policy
+        }, &program_id));
+
+        let investors = [
+            (Pubkey::new_unique(), Pubkey::new_unique(), 400_000),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 600_000),
+        ];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+        let instruction = Instruction {
+            program_id,
+            accounts: start.to_account_metas(None),
+            data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+        let treasury_before = token_balance(&mut context, start.treasury_ata).await;
+
+        let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+        let page = meteora_fee_router::accounts::ProcessInvestorPage {
+            authority: context.payer.pubkey(),
+            quote_mint,
+            program_config: start.program_config,
+            daily_distribution_state: start.daily_distribution_state,
+            policy_state: start.policy_state,
+            treasury_ata: start.treasury_ata,
+            treasury_authority,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+            global_distribution_state: Some(start.global_distribution_state),
+            treasury_state: Some(start.treasury_state),
+            creator_ata: Some(creator_ata),
+            protocol_fee_ata: None,
+            protocol_share_ata: None,
+            investor_blacklist: None,
+            stream_error_log: start.stream_error_log,
+            investor_snapshot: start.investor_snapshot,
+        };
+        let page_investors: Vec<(Pubkey, Pubkey)> = investors
+            .iter()
+            .map(|(investor, stream_account, _)| (*investor, *stream_account))
+            .collect();
+
+        // A claim recorded without the tokens arriving fails the completion checks
+        let account = context.banks_client.get_account(start.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.treasury_state, &anchor_account(&TreasuryState {
+            total_fees_claimed: treasury_state.total_fees_claimed + 10_000_000,
+            ..treasury_state.clone()
+        }, &program_id));
+        let error = send_instruction(&mut context, investor_page_instruction(&page, distribution_day, &page_investors))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::TreasuryBalanceMismatch as u32),
+            )
+        );
+
+        context.set_account(&start.treasury_state, &anchor_account(&treasury_state, &program_id));
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, investor_page_instruction(&page, distribution_day, &page_investors)).await.unwrap();
+
+        // The page paid the investors and settled the day without `complete_daily_distribution`
+        let account = context.banks_client.get_account(start.daily_distribution_state).await.unwrap().unwrap();
+        let day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(day.is_complete);
+        assert_eq!(day.investors_processed, 2);
+
+        let mut investor_payouts = 0;
+        for (investor, _, _) in investors.iter() {
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(investor, &quote_mint);
+            let payout = token_balance(&mut context, investor_ata).await;
+            assert!(payout > 0);
+            investor_payouts += payout;
+        }
+        assert_eq!(investor_payouts, day.amount_distributed);
+        let creator_payout = token_balance(&mut context, creator_ata).await;
+        assert!(creator_payout > 0);
+        let treasury_after = token_balance(&mut context, start.treasury_ata).await;
+        assert_eq!(treasury_before - treasury_after, investor_payouts + creator_payout);
+
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let global = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(global.total_distributions, 1);
+        assert_eq!(global.total_investor_payouts, investor_payouts);
+        assert_eq!(global.total_creator_payouts, creator_payout);
+        assert_eq!(global.dust_rollover, day.dust_carried_over);
+        let account = context.banks_client.get_account(start.treasury_state).await.unwrap().unwrap();
+        let treasury = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(treasury.accumulated_dust_lifetime, day.new_dust());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_snapshot_page_auto_completion_settles_the_day() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            max_pages_before_auto_complete: 1,
+            ..policy
+        }, &program_id));
+
+        let investors = [
+            (Pubkey::new_unique(), Pubkey::new_unique(), 400_000),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 600_000),
+        ];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+
+        // The page reads the recorded snapshot entries instead of the streams
+        let entries: Vec<SnapshotEntry> = investors
+            .iter()
+            .map(|(investor, stream_account, locked_amount)| SnapshotEntry {
+                investor: *investor,
+                stream_account: *stream_account,
+                locked_amount: *locked_amount,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            })
+            .collect();
+        let account = context.banks_client.get_account(start.investor_snapshot).await.unwrap().unwrap();
+        let snapshot = InvestorSnapshot::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.investor_snapshot, &anchor_account(&InvestorSnapshot {
+            entries: entries.clone(),
+            ..snapshot
+        }, &program_id));
+        let instruction = Instruction {
+            program_id,
+            accounts: start.to_account_metas(None),
+            data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+        let treasury_before = token_balance(&mut context, start.treasury_ata).await;
+
+        let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+        let accounts = meteora_fee_router::accounts::ProcessInvestorPageFromSnapshot {
+            page: meteora_fee_router::accounts::ProcessInvestorPage {
+                authority: context.payer.pubkey(),
+                quote_mint,
+                program_config: start.program_config,
+                daily_distribution_state: start.daily_distribution_state,
+                policy_state: start.policy_state,
+                treasury_ata: start.treasury_ata,
+                treasury_authority,
+                token_program: anchor_spl::token::ID,
+                system_program: anchor_lang::system_program::ID,
+                global_distribution_state: Some(start.global_distribution_state),
+                treasury_state: Some(start.treasury_state),
+                creator_ata: Some(creator_ata),
+                protocol_fee_ata: None,
+                protocol_share_ata: None,
+                investor_blacklist: None,
+                stream_error_log: start.stream_error_log,
+                investor_snapshot: start.investor_snapshot,
+            },
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+        send_instruction(&mut context, snapshot_page_instruction(&accounts, distribution_day, 0, &entries)).await.unwrap();
+
+        let account = context.banks_client.get_account(start.daily_distribution_state).await.unwrap().unwrap();
+        let day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(day.is_complete);
+        let creator_payout = token_balance(&mut context, creator_ata).await;
+        assert!(creator_payout > 0);
+        let treasury_after = token_balance(&mut context, start.treasury_ata).await;
+        assert_eq!(treasury_before - treasury_after, day.amount_distributed + creator_payout);
+
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let global = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(global.total_distributions, 1);
+        assert_eq!(global.total_creator_payouts, creator_payout);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_protocol_dust_without_wallet_is_carried_forward() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        // Dust is meant for the protocol, but global state has no protocol fee wallet
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            dust_destination: DustDestination::ToProtocol,
+            ..policy
+        }, &program_id));
+
+        let investors = [
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+            (Pubkey::new_unique(), Pubkey::new_unique(), 333_333),
+        ];
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+
+        // Completion succeeds without a protocol fee ATA and keeps the dust for the next day
+        let day = run_distribution_day(&mut context, &start, distribution_day, &investors, creator_ata).await;
+        assert!(day.is_complete);
+        assert!(day.dust_carried_over > 0);
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let global = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(global.dust_rollover, day.dust_carried_over);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_snapshot_pages_pay_every_investor() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let mut start = accounts.start;
+        let quote_mint = start.quote_mint;
+
+        // Cliff amounts only carry weight when the policy counts them
+        let account = context.banks_client.get_account(start.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.policy_state, &anchor_account(&PolicyState {
+            include_cliff_in_weight: false,
+            ..policy
+        }, &program_id));
+
+        // More investors than fit on one snapshot page
+        let investors: Vec<(Pubkey, Pubkey, u64)> = (0..MAX_SAFE_INVESTORS_PER_PAGE + 2)
+            .map(|_| (Pubkey::new_unique(), Pubkey::new_unique(), 100_000))
+            .collect();
+        let account = context.banks_client.get_account(start.global_distribution_state).await.unwrap().unwrap();
+        let creator_wallet = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().creator_wallet;
+        let creator_ata = Pubkey::new_unique();
+        context.set_account(&creator_ata, &token_account(&quote_mint, &creator_wallet, 0));
+        prepare_distribution_day(&mut context, &mut start, distribution_day, &investors).await;
+
+        // The first investor has half of its locked amount behind a cliff
+        let entries: Vec<SnapshotEntry> = investors
+            .iter()
+            .enumerate()
+            .map(|(index, (investor, stream_account, locked_amount))| SnapshotEntry {
+                investor: *investor,
+                stream_account: *stream_account,
+                locked_amount: *locked_amount,
+                cliff_locked: if index == 0 { locked_amount / 2 } else { 0 },
+                lock_duration_seconds: 0,
+            })
+            .collect();
+        let account = context.banks_client.get_account(start.investor_snapshot).await.unwrap().unwrap();
+        let snapshot = InvestorSnapshot::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&start.investor_snapshot, &anchor_account(&InvestorSnapshot {
+            entries: entries.clone(),
+            ..snapshot
+        }, &program_id));
+        let instruction = Instruction {
+            program_id,
+            accounts: start.to_account_metas(None),
+            data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury_authority", quote_mint.as_ref()], &program_id);
+        let accounts = meteora_fee_router::accounts::ProcessInvestorPageFromSnapshot {
+            page: meteora_fee_router::accounts::ProcessInvestorPage {
+                authority: context.payer.pubkey(),
+                quote_mint,
+                program_config: start.program_config,
+                daily_distribution_state: start.daily_distribution_state,
+                policy_state: start.policy_state,
+                treasury_ata: start.treasury_ata,
+                treasury_authority,
+                token_program: anchor_spl::token::ID,
+                system_program: anchor_lang::system_program::ID,
+                global_distribution_state: None,
+                treasury_state: None,
+                creator_ata: None,
+                protocol_fee_ata: None,
+                protocol_share_ata: None,
+                investor_blacklist: None,
+                stream_error_log: start.stream_error_log,
+                investor_snapshot: start.investor_snapshot,
+            },
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+        let (first_page, second_page) = entries.split_at(MAX_SAFE_INVESTORS_PER_PAGE as usize);
+
+        // Pages follow the cursor
+        let error = send_instruction(&mut context, snapshot_page_instruction(&accounts, distribution_day, 1, second_page))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::InvalidPageIndex as u32),
+            )
+        );
+        send_instruction(&mut context, snapshot_page_instruction(&accounts, distribution_day, 0, first_page)).await.unwrap();
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, snapshot_page_instruction(&accounts, distribution_day, 1, second_page)).await.unwrap();
+
+        // Every investor was paid and has a payout summary matching the transfer
+        let mut payouts = Vec::new();
+        for entry in entries.iter() {
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(&entry.investor, &quote_mint);
+            let payout = token_balance(&mut context, investor_ata).await;
+            let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, &entry.investor, &program_id);
+            let account = context.banks_client.get_account(payout_summary).await.unwrap().unwrap();
+            let summary = InvestorPayoutSummary::try_deserialize(&mut account.data.as_slice()).unwrap();
+            assert_eq!(summary.amount_paid, payout);
+            assert_eq!(summary.payout_count, 1);
+            payouts.push(payout);
+        }
+        assert!(payouts[1] > 0);
+        assert!(payouts[0].abs_diff(payouts[1] / 2) <= 1);
+
+        let account = context.banks_client.get_account(start.daily_distribution_state).await.unwrap().unwrap();
+        let day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(!day.has_more_investors());
+        assert_eq!(day.pages_processed, 2);
+        assert_eq!(day.amount_distributed, payouts.iter().sum::<u64>());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_start_rejects_allocation_above_y0() {
+        let (mut context, accounts, distribution_day) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let (_, allocation_registry_bump) = AllocationRegistry::derive_pda(&accounts.start.quote_mint, &program_id);
+        context.set_account(&accounts.start.allocation_registry, &anchor_account(&AllocationRegistry {
+            quote_mint: accounts.start.quote_mint,
+            total_registered_allocation: fixture_policy_state().y0_total_allocation * 2,
+            registered_streams: Vec::new(),
+            bump: allocation_registry_bump,
+        }, &program_id));
+
+        let instruction = Instruction {
+            program_id,
+            accounts: accounts.start.to_account_metas(None),
+            data: meteora_fee_router::instruction::StartDailyDistribution { distribution_day }.data(),
+        };
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::AllocationExceedsY0 as u32),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_claim_rejects_base_dust_of_unlisted_mint() {
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let base_mint = accounts.claim.base_mint;
+        let account = context.banks_client.get_account(accounts.claim.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.policy_state, &anchor_account(&PolicyState {
+            allow_base_fee_grace_period: true,
+            ..policy
+        }, &program_id));
+        let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+        context.set_account(&accounts.claim.base_vault, &token_account(&base_mint, &event_authority, 50));
+
+        // Base dust under the grace period is still a base mint transfer
+        let instruction = Instruction {
+            program_id,
+            accounts: accounts.claim.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClaimFees {}.data(),
+        };
+        let error = send_instruction(&mut context, instruction.clone()).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::MintNotAllowed as u32),
+            )
+        );
+
+        // Whitelisting the base mint lets the dust through
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.treasury_state, &anchor_account(&TreasuryState {
+            allowed_mints: [accounts.claim.quote_mint, base_mint, Pubkey::default(), Pubkey::default()],
+            ..treasury_state
+        }, &program_id));
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, instruction).await.unwrap();
+        assert_eq!(token_balance(&mut context, accounts.claim.position_owner_base_ata).await, 50);
+        assert_eq!(token_balance(&mut context, accounts.claim.treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_claim_accepts_base_dust_under_grace_period() {
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let base_mint = accounts.claim.base_mint;
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.treasury_state, &anchor_account(&TreasuryState {
+            allowed_mints: [accounts.claim.quote_mint, base_mint, Pubkey::default(), Pubkey::default()],
+            ..treasury_state
+        }, &program_id));
+        let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+        let instruction = Instruction {
+            program_id,
+            accounts: accounts.claim.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClaimFees {}.data(),
+        };
+        let base_fees_rejected = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::BaseFeesClaimedError as u32),
+        );
+
+        // Without the grace period any base fee aborts the claim
+        context.set_account(&accounts.claim.base_vault, &token_account(&base_mint, &event_authority, 50));
+        let error = send_instruction(&mut context, instruction.clone()).await.unwrap_err().unwrap();
+        assert_eq!(error, base_fees_rejected);
+
+        // The grace period only covers amounts below the threshold
+        let account = context.banks_client.get_account(accounts.claim.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.policy_state, &anchor_account(&PolicyState {
+            allow_base_fee_grace_period: true,
+            ..policy
+        }, &program_id));
+        context.set_account(&accounts.claim.base_vault, &token_account(&base_mint, &event_authority, GRACE_AMOUNT_THRESHOLD));
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, instruction.clone()).await.unwrap_err().unwrap();
+        assert_eq!(error, base_fees_rejected);
+
+        // Dust below the threshold is accepted, kept in the base ATA and recorded
+        context.set_account(&accounts.claim.base_vault, &token_account(&base_mint, &event_authority, GRACE_AMOUNT_THRESHOLD - 1));
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, instruction).await.unwrap();
+        assert_eq!(token_balance(&mut context, accounts.claim.position_owner_base_ata).await, GRACE_AMOUNT_THRESHOLD - 1);
+        assert_eq!(token_balance(&mut context, accounts.claim.treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(treasury_state.base_fees_accepted_under_grace, GRACE_AMOUNT_THRESHOLD - 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_claim_runs_when_estimate_rounds_to_zero() {
+        use meteora_fee_router::integrations::meteora::cpi::{position_layout, POSITION_ACCOUNT_SIZE};
+
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+
+        // A readable position whose estimate is zero on both sides
+        let mut data = vec![0u8; POSITION_ACCOUNT_SIZE];
+        data[position_layout::POOL..position_layout::POOL + 32].copy_from_slice(accounts.claim.pool.as_ref());
+        context.set_account(&accounts.claim.position, &solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: METEORA_CP_AMM_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }.into());
+
+        // The CPI still runs and the measured claim is recorded
+        send_instruction(&mut context, Instruction {
+            program_id,
+            accounts: accounts.claim.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClaimFees {}.data(),
+        }).await.unwrap();
+        assert_eq!(token_balance(&mut context, accounts.claim.treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(treasury_state.total_fees_claimed, POSITION_FEES);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_claim_capped_by_treasury_balance_before_claim() {
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(treasury_state.total_fees_claimed, 0);
+        context.set_account(&accounts.claim.treasury_state, &anchor_account(&TreasuryState {
+            max_claim_per_interval_bps: BPS_DENOMINATOR, // claims up to the current balance
+            ..treasury_state
+        }, &program_id));
+        let instruction = Instruction {
+            program_id,
+            accounts: accounts.claim.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClaimFees {}.data(),
+        };
+
+        // The cap applies to the first claim: 750k of fees exceed the 250k treasury
+        let error = send_instruction(&mut context, instruction.clone()).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::ClaimExceedsRateLimit as u32),
+        ));
+
+        // A claim matching the treasury balance is allowed
+        let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+        context.set_account(&accounts.claim.quote_vault, &token_account(&accounts.claim.quote_mint, &event_authority, TREASURY_BALANCE_BEFORE_CLAIM));
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, instruction).await.unwrap();
+        assert_eq!(token_balance(&mut context, accounts.claim.treasury_ata).await, 2 * TREASURY_BALANCE_BEFORE_CLAIM);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_raydium_claim_checks_program_and_vaults() {
+        use meteora_fee_router::integrations::raydium_clmm::{PoolState as RaydiumPoolState, RAYDIUM_CLMM_PROGRAM_ID};
+        use bytemuck::Zeroable;
+
+        let (mut context, mut accounts, _) = setup_atomic_distribution().await;
+        let program_id = meteora_fee_router::id();
+        let account = context.banks_client.get_account(accounts.claim.position_metadata).await.unwrap().unwrap();
+        let metadata = PositionMetadata::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.position_metadata, &anchor_account(&PositionMetadata {
+            pool_type: PoolType::RaydiumCLMM,
+            ..metadata
+        }, &program_id));
+
+        let mut pool = RaydiumPoolState::zeroed();
+        pool.token_vault_0 = Pubkey::new_unique();
+        pool.token_vault_1 = Pubkey::new_unique();
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(bytemuck::bytes_of(&pool));
+        context.set_account(&accounts.claim.pool, &solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: RAYDIUM_CLMM_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }.into());
+
+        let claim_instruction = |accounts: &meteora_fee_router::accounts::ClaimFees, token_vault_0: Pubkey| {
+            let mut metas = accounts.to_account_metas(None);
+            metas.push(AccountMeta::new(Pubkey::new_unique(), false)); // protocol_position
+            metas.push(AccountMeta::new(token_vault_0, false));
+            metas.push(AccountMeta::new(pool.token_vault_1, false));
+            for _ in 0..4 {
+                metas.push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+            }
+            Instruction {
+                program_id,
+                accounts: metas,
+                data: meteora_fee_router::instruction::ClaimFees {}.data(),
+            }
+        };
+
+        // The Raydium program must be passed for Raydium positions
+        let error = send_instruction(&mut context, claim_instruction(&accounts.claim, pool.token_vault_0)).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(anchor_lang::error::ErrorCode::AccountNotEnoughKeys as u32),
+        ));
+
+        // ...and only at the Raydium CLMM address
+        accounts.claim.raydium_program = Some(METEORA_CP_AMM_PROGRAM_ID);
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, claim_instruction(&accounts.claim, pool.token_vault_0)).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintAddress as u32),
+        ));
+
+        // Vaults that are not the pool's are rejected before the CPI
+        accounts.claim.raydium_program = Some(RAYDIUM_CLMM_PROGRAM_ID);
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let error = send_instruction(&mut context, claim_instruction(&accounts.claim, Pubkey::new_unique())).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::PoolVaultMismatch as u32),
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_claim_below_minimum_is_rejected() {
+        let (mut context, accounts, _) = setup_atomic_distribution().await;
+        let treasury_ata = accounts.claim.treasury_ata;
+        let claim_accounts = accounts.claim.to_account_metas(None);
+
+        // Less than the minimum is pending - the claim is rolled back
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: claim_accounts.clone(),
+            data: meteora_fee_router::instruction::ClaimFeesWithMinimum { min_claim_amount: POSITION_FEES + 1 }.data(),
+        };
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::ClaimBelowMinimum as u32),
+            )
+        );
+        let account = context.banks_client.get_account(treasury_ata).await.unwrap().unwrap();
+        let treasury = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(treasury.amount, TREASURY_BALANCE_BEFORE_CLAIM);
+
+        // Exactly the minimum is claimed
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: claim_accounts,
+            data: meteora_fee_router::instruction::ClaimFeesWithMinimum { min_claim_amount: POSITION_FEES }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+        let account = context.banks_client.get_account(treasury_ata).await.unwrap().unwrap();
+        let treasury = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(treasury.amount, TREASURY_BALANCE_BEFORE_CLAIM + POSITION_FEES);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_instant_mode_pays_investors_on_claim() {
+        let (mut context, accounts, distribution_day, entries) = setup_instant_distribution(&[600_000, 400_000]).await;
+        let quote_mint = accounts.claim.quote_mint;
+        let treasury_ata = accounts.claim.treasury_ata;
+        let creator_ata = accounts.creator_ata;
+
+        let instruction = instant_distribution_instruction(&accounts, distribution_day, &entries);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // 1M of 2M Y0 locked: investors get 50% of the claim pro rata, the creator 50%
+        let first_ata = anchor_spl::associated_token::get_associated_token_address(&entries[0].investor, &quote_mint);
+        let second_ata = anchor_spl::associated_token::get_associated_token_address(&entries[1].investor, &quote_mint);
+        assert_eq!(token_balance(&mut context, first_ata).await, 225_000);
+        assert_eq!(token_balance(&mut context, second_ata).await, 150_000);
+        assert_eq!(token_balance(&mut context, creator_ata).await, 375_000);
+
+        // Fees that were already in the treasury wait for the daily crank
+        assert_eq!(token_balance(&mut context, treasury_ata).await, TREASURY_BALANCE_BEFORE_CLAIM);
+
+        // The distribution is recorded on the day and globally
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let daily_state = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert!(daily_state.is_complete);
+        assert_eq!(daily_state.total_amount_to_distribute, POSITION_FEES);
+        assert_eq!(daily_state.amount_distributed, 375_000);
+        assert_eq!(daily_state.investors_processed, 2);
+        let account = context.banks_client.get_account(accounts.global_distribution_state).await.unwrap().unwrap();
+        let global_state = GlobalDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(global_state.total_distributions, 1);
+        assert_eq!(global_state.total_investor_payouts, 375_000);
+        assert_eq!(global_state.total_creator_payouts, 375_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_instant_mode_shares_daily_cap_across_claims() {
+        let (mut context, accounts, distribution_day, entries) = setup_instant_distribution(&[600_000, 400_000]).await;
+        let program_id = meteora_fee_router::id();
+        let quote_mint = accounts.claim.quote_mint;
+        let account = context.banks_client.get_account(accounts.claim.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.policy_state, &anchor_account(&PolicyState {
+            daily_cap_lamports: 500_000,
+            ..policy
+        }, &program_id));
+
+        let instruction = instant_distribution_instruction(&accounts, distribution_day, &entries);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // Refill the position and clear the claim interval for a second claim the same day
+        let account = context.banks_client.get_account(accounts.claim.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.claim.treasury_state, &anchor_account(&TreasuryState {
+            last_claim_timestamp: 0,
+            ..treasury_state
+        }, &program_id));
+        let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &METEORA_CP_AMM_PROGRAM_ID);
+        context.set_account(&accounts.claim.quote_vault, &token_account(&quote_mint, &event_authority, POSITION_FEES));
+
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let instruction = instant_distribution_instruction(&accounts, distribution_day, &entries);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The second claim only gets the 125k left under the 500k cap
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let daily_state = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(daily_state.total_amount_to_distribute, 2 * POSITION_FEES);
+        let first_ata = anchor_spl::associated_token::get_associated_token_address(&entries[0].investor, &quote_mint);
+        let second_ata = anchor_spl::associated_token::get_associated_token_address(&entries[1].investor, &quote_mint);
+        let total_paid = token_balance(&mut context, first_ata).await + token_balance(&mut context, second_ata).await;
+        assert_eq!(total_paid, 499_987);
+        assert_eq!(daily_state.amount_distributed, total_paid);
+        assert_eq!(daily_state.daily_cap_remaining, 500_000 - total_paid);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_instant_mode_rejects_paused_and_past_days() {
+        let (mut context, mut accounts, distribution_day, entries) = setup_instant_distribution(&[600_000, 400_000]).await;
+        let program_id = meteora_fee_router::id();
+        let quote_mint = accounts.claim.quote_mint;
+
+        // A snapshot from an earlier day cannot be paid against
+        let past_day = distribution_day - 86_400;
+        let account = context.banks_client.get_account(accounts.investor_snapshot).await.unwrap().unwrap();
+        let snapshot = InvestorSnapshot::try_deserialize(&mut account.data.as_slice()).unwrap();
+        let today_snapshot = accounts.investor_snapshot;
+        accounts.investor_snapshot = InvestorSnapshot::derive_pda(past_day, &quote_mint, &program_id).0;
+        accounts.daily_distribution_state = DailyDistributionState::derive_pda(past_day, &quote_mint, &program_id).0;
+        context.set_account(&accounts.investor_snapshot, &anchor_account(&InvestorSnapshot {
+            distribution_day: past_day,
+            ..snapshot
+        }, &program_id));
+        let instruction = instant_distribution_instruction(&accounts, past_day, &entries);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::InvalidDistributionDay as u32),
+            )
+        );
+
+        // Paused distributions are rejected
+        accounts.investor_snapshot = today_snapshot;
+        accounts.daily_distribution_state = DailyDistributionState::derive_pda(distribution_day, &quote_mint, &program_id).0;
+        let account = context.banks_client.get_account(accounts.program_config).await.unwrap().unwrap();
+        let config = ProgramConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.program_config, &anchor_account(&ProgramConfig {
+            is_paused: true,
+            ..config
+        }, &program_id));
+        let instruction = instant_distribution_instruction(&accounts, distribution_day, &entries);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::DistributionPaused as u32),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_instant_mode_rejects_large_registry() {
+        let locked_amounts = vec![50_000; INSTANT_DISTRIBUTION_MAX_INVESTORS as usize + 1];
+        let (mut context, accounts, distribution_day, entries) = setup_instant_distribution(&locked_amounts).await;
+
+        let instruction = instant_distribution_instruction(&accounts, distribution_day, &entries);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::InstantDistributionTooManyInvestors as u32),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_investor_page_transfers_to_investor_atas() {
+        let (mut context, accounts, investors) = setup_investor_page(2, 600_000).await;
+        let program_id = meteora_fee_router::id();
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // 1.2M of 2M Y0 locked: the 50% investor share of 100k is split evenly
+        let mut total_paid = 0;
+        for (investor, _) in investors.iter() {
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(investor, &quote_mint);
+            let paid = token_balance(&mut context, investor_ata).await;
+            assert!((24_999..=25_000).contains(&paid), "investor paid {}", paid);
+            total_paid += paid;
+
+            let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, investor, &program_id);
+            let account = context.banks_client.get_account(payout_summary).await.unwrap().unwrap();
+            let summary = InvestorPayoutSummary::try_deserialize(&mut account.data.as_slice()).unwrap();
+            assert_eq!(summary.amount_paid, paid);
+        }
+        assert_eq!(token_balance(&mut context, accounts.treasury_ata).await, 100_000 - total_paid);
+
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.amount_distributed, total_paid);
+        assert_eq!(distribution.failed_payouts_count, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_investor_page_must_follow_snapshot() {
+        let (mut context, accounts, investors) = setup_investor_page(2, 600_000).await;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+        let snapshot_mismatch = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::SnapshotPageMismatch as u32),
+        );
+        let second_page = |investors: &[(Pubkey, Pubkey)]| {
+            let mut instruction = investor_page_instruction(&accounts, distribution_day, investors);
+            instruction.data = meteora_fee_router::instruction::ProcessInvestorPage { expected_page_index: 1 }.data();
+            instruction
+        };
+
+        // Streams outside the snapshot are rejected
+        let outsider = (Pubkey::new_unique(), investors[1].1);
+        let error = send_instruction(&mut context, investor_page_instruction(&accounts, distribution_day, &[outsider]))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(error, snapshot_mismatch);
+
+        send_instruction(&mut context, investor_page_instruction(&accounts, distribution_day, &investors[..1])).await.unwrap();
+
+        // A paid stream cannot be passed again on a later page, alone or mixed in
+        for page in [vec![investors[0]], vec![investors[0], investors[1]]] {
+            let error = send_instruction(&mut context, second_page(&page)).await.unwrap_err().unwrap();
+            assert_eq!(error, snapshot_mismatch);
+        }
+
+        send_instruction(&mut context, second_page(&investors[1..])).await.unwrap();
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.current_cursor, 2);
+        assert!(!distribution.has_more_investors());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_investor_cache_adds_streams_from_later_pages() {
+        let (mut context, accounts, investors) = setup_investor_page(2, 600_000).await;
+        let program_id = meteora_fee_router::id();
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        // The first investor also owns the second stream, paid on the next page
+        let investor = investors[0].0;
+        let account = context.banks_client.get_account(investors[1].1).await.unwrap().unwrap();
+        let second_stream = StreamflowStream::try_from_account_data(&account.data).unwrap();
+        context.set_account(&investors[1].1, &streamflow_account(&StreamflowStream {
+            recipient: investor,
+            ..second_stream
+        }));
+        set_page_snapshot(&mut context, &accounts, &[investors[0], (investor, investors[1].1)]).await;
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(&investor, &quote_mint);
+
+        send_instruction(&mut context, investor_page_instruction(&accounts, distribution_day, &investors[..1])).await.unwrap();
+        let paid_first_page = token_balance(&mut context, investor_ata).await;
+        assert!(paid_first_page > 0);
+
+        let mut instruction = investor_page_instruction(&accounts, distribution_day, &[(investor, investors[1].1)]);
+        instruction.data = meteora_fee_router::instruction::ProcessInvestorPage { expected_page_index: 1 }.data();
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The second page pays the new stream rather than the cached first one again
+        let (investor_cache, _) = InvestorAggregateCache::derive_pda(distribution_day, &quote_mint, &investor, &program_id);
+        let account = context.banks_client.get_account(investor_cache).await.unwrap().unwrap();
+        let cache = InvestorAggregateCache::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(cache.quote_mint, quote_mint);
+        assert_eq!(cache.stream_count, 2);
+        let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+        assert_eq!(cache.total_locked, 2 * second_stream.locked_amount(now as u64));
+        assert_eq!(cache.total_deposited, 2 * second_stream.deposited_amount);
+        assert_eq!(cache.end_time, second_stream.end_time);
+        assert_eq!(cache.payer, context.payer.pubkey());
+
+        let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, &investor, &program_id);
+        let account = context.banks_client.get_account(payout_summary).await.unwrap().unwrap();
+        let summary = InvestorPayoutSummary::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(summary.payout_count, 2);
+        assert_eq!(summary.amount_paid, token_balance(&mut context, investor_ata).await);
+
+        // The cache is closable once the day has completed
+        let rent = context.banks_client.get_account(investor_cache).await.unwrap().unwrap().lamports;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.daily_distribution_state, &anchor_account(&DailyDistributionState {
+            is_complete: true,
+            completed_at: now - 2 * 86_400,
+            ..distribution
+        }, &program_id));
+
+        let cranker = context.payer.pubkey();
+        let payer_before = context.banks_client.get_balance(cranker).await.unwrap();
+        let instruction = Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::CloseInvestorCache {
+                authority: cranker,
+                payer: cranker,
+                quote_mint,
+                daily_distribution_state: accounts.daily_distribution_state,
+                investor_cache,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::CloseInvestorCache {}.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+        assert!(context.banks_client.get_account(investor_cache).await.unwrap().is_none());
+        let payer_after = context.banks_client.get_balance(cranker).await.unwrap();
+        assert_eq!(payer_after, payer_before + rent - 5_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_registry_day_of_5000_investors_pays_every_page() {
+        let investor_count = 5_000u32;
+        let (mut context, mut accounts, investors) = setup_registry_distribution(investor_count, 200).await;
+        let program_id = meteora_fee_router::id();
+        let quote_mint = accounts.page.quote_mint;
+        let account = context.banks_client.get_account(accounts.page.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        // Registry days only take registry pages
+        let instruction = investor_page_instruction(&accounts.page, distribution_day, &investors[..1]);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::RegistryPagesRequired as u32),
+            )
+        );
+
+        // 100 registry pages, each paid in calls of at most MAX_INVESTORS_PER_PAGE investors
+        let registry_pages: Vec<&[(Pubkey, Pubkey)]> = investors.chunks(INVESTOR_REGISTRY_PAGE_SIZE).collect();
+        assert_eq!(registry_pages.len(), 100);
+        let mut slot = context.banks_client.get_root_slot().await.unwrap();
+        for (page_index, registry_entries) in registry_pages.iter().enumerate() {
+            // A new slot per registry page keeps the writes to the day's state under the block's cost limit
+            slot += 1;
+            context.warp_to_slot(slot).unwrap();
+            accounts.registry_page =
+                InvestorRegistryPage::derive_pda(&accounts.investor_registry, page_index as u32, &program_id).0;
+            for page_investors in registry_entries.chunks(MAX_INVESTORS_PER_PAGE as usize) {
+                let mut instruction = investor_page_instruction(&accounts.page, distribution_day, page_investors);
+                let investor_metas = instruction.accounts.split_off(accounts.page.to_account_metas(None).len());
+                instruction.accounts = accounts.to_account_metas(None);
+                instruction.accounts.extend(investor_metas);
+                instruction.data = meteora_fee_router::instruction::ProcessInvestorPageFromRegistry {}.data();
+                send_instruction(&mut context, instruction).await.unwrap();
+            }
+        }
+
+        let account = context.banks_client.get_account(accounts.page.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.investors_processed, investor_count);
+        assert_eq!(distribution.current_cursor, investor_count);
+        assert!(!distribution.has_more_investors());
+
+        // Every investor was paid, including those past the first pages
+        for (investor, _) in [investors[0], investors[2_500], investors[4_999]] {
+            let investor_ata = anchor_spl::associated_token::get_associated_token_address(&investor, &quote_mint);
+            assert!(token_balance(&mut context, investor_ata).await > 0);
+        }
+        let treasury_balance = token_balance(&mut context, accounts.page.treasury_ata).await;
+        assert_eq!(distribution.amount_distributed, 100_000 - treasury_balance);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_close_payout_summary_returns_rent_to_payer() {
+        let (mut context, accounts, investors) = setup_investor_page(1, 600_000).await;
+        let program_id = meteora_fee_router::id();
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        let distribution_day = distribution.distribution_day;
+
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        send_instruction(&mut context, instruction).await.unwrap();
+        let (payout_summary, _) = InvestorPayoutSummary::derive_pda(distribution_day, &investors[0].0, &program_id);
+        let account = context.banks_client.get_account(payout_summary).await.unwrap().unwrap();
+        let summary = InvestorPayoutSummary::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(summary.payer, context.payer.pubkey());
+        let rent = account.lamports;
+
+        let cranker = context.payer.pubkey();
+        let close_instruction = |payer: Pubkey| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::CloseInvestorPayoutSummary {
+                authority: cranker,
+                payer,
+                quote_mint: accounts.quote_mint,
+                daily_distribution_state: accounts.daily_distribution_state,
+                investor_payout_summary: payout_summary,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::CloseInvestorPayoutSummary {}.data(),
+        };
+
+        // Kept while the day is still open
+        let error = send_instruction(&mut context, close_instruction(cranker)).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::SnapshotNotClosable as u32),
+            )
+        );
+
+        // Completed more than the retention period ago
+        let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.daily_distribution_state, &anchor_account(&DailyDistributionState {
+            is_complete: true,
+            completed_at: now - 2 * 86_400,
+            ..distribution
+        }, &program_id));
+
+        // Only the cranker that paid for it gets the rent back
+        let other_payer = Pubkey::new_unique();
+        let error = send_instruction(&mut context, close_instruction(other_payer)).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::RentPayerMismatch as u32),
+            )
+        );
+
+        // A fresh blockhash so the retry is not deduplicated against the first attempt
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let payer_before = context.banks_client.get_balance(cranker).await.unwrap();
+        send_instruction(&mut context, close_instruction(cranker)).await.unwrap();
+        assert!(context.banks_client.get_account(payout_summary).await.unwrap().is_none());
+        let payer_after = context.banks_client.get_balance(cranker).await.unwrap();
+        assert_eq!(payer_after, payer_before + rent - 5_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_close_stream_error_log_returns_rent_to_payer() {
+        let (mut context, accounts, _) = setup_investor_page(1, 600_000).await;
+        let program_id = meteora_fee_router::id();
+        let cranker = context.payer.pubkey();
+        let rent = context.banks_client.get_account(accounts.stream_error_log).await.unwrap().unwrap().lamports;
+
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.daily_distribution_state, &anchor_account(&DailyDistributionState {
+            is_complete: true,
+            ..distribution
+        }, &program_id));
+
+        let close_instruction = |payer: Pubkey| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::CloseStreamErrorLog {
+                authority: cranker,
+                payer,
+                quote_mint: accounts.quote_mint,
+                daily_distribution_state: accounts.daily_distribution_state,
+                stream_error_log: accounts.stream_error_log,
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::CloseStreamErrorLog {}.data(),
+        };
+
+        // The caller cannot redirect the rent to themselves
+        let error = send_instruction(&mut context, close_instruction(Pubkey::new_unique())).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::RentPayerMismatch as u32),
+            )
+        );
+
+        let payer_before = context.banks_client.get_balance(cranker).await.unwrap();
+        send_instruction(&mut context, close_instruction(cranker)).await.unwrap();
+        assert!(context.banks_client.get_account(accounts.stream_error_log).await.unwrap().is_none());
+        let payer_after = context.banks_client.get_balance(cranker).await.unwrap();
+        assert_eq!(payer_after, payer_before + rent - 5_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_only_policy_authority_takes_todays_snapshot() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, investors) = setup_investor_page(2, 600_000).await;
+        // Today's snapshot is taken by the test itself
+        context.set_account(&accounts.investor_snapshot, &solana_sdk::account::AccountSharedData::default());
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+        let authority = context.payer.pubkey();
+
+        let snapshot_instruction = |day: i64| {
+            let (investor_snapshot, _) = InvestorSnapshot::derive_pda(day, &accounts.quote_mint, &program_id);
+            let mut account_metas = meteora_fee_router::accounts::TakeInvestorSnapshot {
+                authority,
+                quote_mint: accounts.quote_mint,
+                policy_state: accounts.policy_state,
+                investor_snapshot,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None);
+            account_metas.extend(investors.iter().map(|(_, stream_account)| AccountMeta::new_readonly(*stream_account, false)));
+            Instruction {
+                program_id,
+                accounts: account_metas,
+                data: meteora_fee_router::instruction::TakeInvestorSnapshot { distribution_day: day }.data(),
+            }
+        };
+        let custom_error = |error: FeeRouterError| {
+            TransactionError::InstructionError(0, InstructionError::Custom(ERROR_CODE_OFFSET + error as u32))
+        };
+
+        // A cranker cannot front-run the day with their own investor set
+        let error = send_instruction(&mut context, snapshot_instruction(distribution_day)).await.unwrap_err().unwrap();
+        assert_eq!(error, custom_error(FeeRouterError::Unauthorized));
+
+        let account = context.banks_client.get_account(accounts.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.policy_state, &anchor_account(&PolicyState {
+            policy_authority: authority,
+            ..policy
+        }, &program_id));
+
+        let error = send_instruction(&mut context, snapshot_instruction(distribution_day - 86_400)).await.unwrap_err().unwrap();
+        assert_eq!(error, custom_error(FeeRouterError::InvalidDistributionDay));
+
+        // A fresh blockhash so the retry is not deduplicated against the first attempt
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        send_instruction(&mut context, snapshot_instruction(distribution_day)).await.unwrap();
+        let (investor_snapshot, _) = InvestorSnapshot::derive_pda(distribution_day, &accounts.quote_mint, &program_id);
+        let account = context.banks_client.get_account(investor_snapshot).await.unwrap().unwrap();
+        let snapshot = InvestorSnapshot::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(snapshot.investor_count, 2);
+        assert_eq!(snapshot.entries[1].investor, investors[1].0);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_daily_snapshot_cleanup_keeps_account_count_stable() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, accounts, investors) = setup_investor_page(1, 600_000).await;
+        // Today's snapshot is taken by the test itself
+        context.set_account(&accounts.investor_snapshot, &solana_sdk::account::AccountSharedData::default());
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let first_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+        let authority = context.payer.pubkey();
+
+        // Cleanup is cranked by someone other than the authority that took the snapshots
+        let cranker = solana_sdk::signature::Keypair::new();
+        context.set_account(&cranker.pubkey(), &solana_sdk::account::Account::new(1_000_000_000, 0, &anchor_lang::system_program::ID).into());
+
+        let account = context.banks_client.get_account(accounts.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.policy_state, &anchor_account(&PolicyState {
+            policy_authority: authority,
+            ..policy
+        }, &program_id));
+
+        let snapshot_address = |day: i64| InvestorSnapshot::derive_pda(day, &quote_mint, &program_id).0;
+        let daily_state_address = |day: i64| DailyDistributionState::derive_pda(day, &quote_mint, &program_id).0;
+        let take_instruction = |day: i64| {
+            let mut account_metas = meteora_fee_router::accounts::TakeInvestorSnapshot {
+                authority,
+                quote_mint,
+                policy_state: accounts.policy_state,
+                investor_snapshot: snapshot_address(day),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None);
+            account_metas.push(AccountMeta::new_readonly(investors[0].1, false));
+            Instruction {
+                program_id,
+                accounts: account_metas,
+                data: meteora_fee_router::instruction::TakeInvestorSnapshot { distribution_day: day }.data(),
+            }
+        };
+        let close_instruction = |day: i64, payer: Pubkey| Instruction {
+            program_id,
+            accounts: meteora_fee_router::accounts::CloseInvestorSnapshot {
+                authority: cranker.pubkey(),
+                payer,
+                quote_mint,
+                daily_distribution_state: daily_state_address(day),
+                investor_snapshot: snapshot_address(day),
+            }
+            .to_account_metas(None),
+            data: meteora_fee_router::instruction::CloseInvestorSnapshot {}.data(),
+        };
+        let custom_error = |error: FeeRouterError| {
+            TransactionError::InstructionError(0, InstructionError::Custom(ERROR_CODE_OFFSET + error as u32))
+        };
+
+        for day_index in 0..30i64 {
+            let day = first_day + day_index * 86_400;
+            // Yesterday's rejected close is retried today, so it must not be deduplicated
+            context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+            let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+            clock.unix_timestamp = day + 100;
+            context.set_sysvar(&clock);
+
+            send_instruction(&mut context, take_instruction(day)).await.unwrap();
+            context.set_account(&daily_state_address(day), &anchor_account(&DailyDistributionState {
+                is_complete: true,
+                completed_at: day + 100,
+                ..fixture_daily_distribution_state().with_day(day).with_quote_mint(quote_mint)
+            }, &program_id));
+
+            if day_index >= 1 {
+                // Yesterday's snapshot is still within its retention period
+                let error = send_instruction_as(&mut context, &cranker, close_instruction(day - 86_400, authority)).await;
+                assert_eq!(error.unwrap_err().unwrap(), custom_error(FeeRouterError::SnapshotNotClosable));
+            }
+            if day_index >= 2 {
+                let closed_day = day - 2 * 86_400;
+                let error = send_instruction_as(&mut context, &cranker, close_instruction(closed_day, cranker.pubkey())).await;
+                assert_eq!(error.unwrap_err().unwrap(), custom_error(FeeRouterError::RentPayerMismatch));
+                let payer_before = context.banks_client.get_balance(authority).await.unwrap();
+                let rent = context.banks_client.get_account(snapshot_address(closed_day)).await.unwrap().unwrap().lamports;
+                send_instruction_as(&mut context, &cranker, close_instruction(closed_day, authority)).await.unwrap();
+                assert_eq!(context.banks_client.get_balance(authority).await.unwrap(), payer_before + rent);
+            }
+
+            // Only today's and yesterday's snapshots are ever live
+            let mut live_snapshots = 0;
+            for past_index in 0..=day_index {
+                let address = snapshot_address(first_day + past_index * 86_400);
+                if context.banks_client.get_account(address).await.unwrap().is_some() {
+                    live_snapshots += 1;
+                }
+            }
+            assert_eq!(live_snapshots, day_index.min(1) + 1);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_investor_page_rejects_foreign_ata() {
+        let (mut context, accounts, investors) = setup_investor_page(2, 600_000).await;
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        // The first investor's ATA address holds someone else's token account
+        let investor_ata = anchor_spl::associated_token::get_associated_token_address(&investors[0].0, &quote_mint);
+        context.set_account(&investor_ata, &token_account(&quote_mint, &Pubkey::new_unique(), 0));
+
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::InvestorAtaOwnerMismatch as u32),
+            )
+        );
+        assert_eq!(token_balance(&mut context, accounts.treasury_ata).await, 100_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_frozen_investor_ata_is_skipped() {
+        let (mut context, accounts, investors) = setup_investor_page(3, 400_000).await;
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        // The second investor's ATA is frozen
+        let investor_atas: Vec<Pubkey> = investors
+            .iter()
+            .map(|(investor, _)| anchor_spl::associated_token::get_associated_token_address(investor, &quote_mint))
+            .collect();
+        let mut frozen_ata = spl_token::state::Account::unpack(
+            context.banks_client.get_account(investor_atas[1]).await.unwrap().unwrap().data.as_slice(),
+        )
+        .unwrap();
+        frozen_ata.state = spl_token::state::AccountState::Frozen;
+        let mut frozen_account = solana_sdk::account::Account::new(1_000_000_000, spl_token::state::Account::LEN, &spl_token::id());
+        frozen_ata.pack_into_slice(&mut frozen_account.data);
+        context.set_account(&investor_atas[1], &frozen_account.into());
+
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The other two investors are paid, the frozen one is counted as failed
+        let first_payout = token_balance(&mut context, investor_atas[0]).await;
+        assert!(first_payout > 0);
+        assert_eq!(token_balance(&mut context, investor_atas[1]).await, 0);
+        assert_eq!(token_balance(&mut context, investor_atas[2]).await, first_payout);
+
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.failed_payouts_count, 1);
+        assert_eq!(distribution.amount_distributed, 2 * first_payout);
+        assert_eq!(token_balance(&mut context, accounts.treasury_ata).await, 100_000 - 2 * first_payout);
+
+        // The skipped investor still moves the cursor, so the day can complete
+        assert_eq!(distribution.current_cursor, 3);
+        assert!(!distribution.has_more_investors());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_initialized_blacklist_is_required_on_pages() {
+        let program_id = meteora_fee_router::id();
+        let (mut context, mut accounts, investors) = setup_investor_page(2, 600_000).await;
+        let quote_mint = accounts.quote_mint;
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution_day = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap().distribution_day;
+
+        let account = context.banks_client.get_account(accounts.policy_state).await.unwrap().unwrap();
+        let policy = PolicyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        context.set_account(&accounts.policy_state, &anchor_account(&PolicyState {
+            blacklist_enabled: true,
+            ..policy
+        }, &program_id));
+        let (investor_blacklist, blacklist_bump) = InvestorBlacklist::derive_pda(&quote_mint, &program_id);
+        context.set_account(&investor_blacklist, &anchor_account(&InvestorBlacklist {
+            quote_mint,
+            blacklisted: vec![investors[0].0],
+            bump: blacklist_bump,
+        }, &program_id));
+
+        // Leaving the blacklist out no longer bypasses it
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::BlacklistRequired as u32),
+            )
+        );
+
+        accounts.investor_blacklist = Some(investor_blacklist);
+        let instruction = investor_page_instruction(&accounts, distribution_day, &investors);
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        let blacklisted_ata = anchor_spl::associated_token::get_associated_token_address(&investors[0].0, &quote_mint);
+        let eligible_ata = anchor_spl::associated_token::get_associated_token_address(&investors[1].0, &quote_mint);
+        assert_eq!(token_balance(&mut context, blacklisted_ata).await, 0);
+        assert!(token_balance(&mut context, eligible_ata).await > 0);
+        let account = context.banks_client.get_account(accounts.daily_distribution_state).await.unwrap().unwrap();
+        let distribution = DailyDistributionState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(distribution.skipped_blacklisted, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_add_investor_links_new_registry_page() {
+        let page_size = INVESTOR_REGISTRY_PAGE_SIZE as u32;
+        let (mut context, quote_mint, policy_state, investor_registry) = setup_investor_registry(page_size - 1).await;
+
+        // Fills the last slot of page 0
+        let last_on_first_page = Pubkey::new_unique();
+        let instruction = add_investor_instruction(&mut context, quote_mint, policy_state, investor_registry, last_on_first_page).await;
+        send_instruction(&mut context, instruction).await.unwrap();
+        let first_page = registry_page(&mut context, investor_registry, 0).await;
+        assert_eq!(first_page.entries.len(), INVESTOR_REGISTRY_PAGE_SIZE);
+        assert_eq!(first_page.entries.last().unwrap().investor, last_on_first_page);
+        assert_eq!(first_page.next_page, Pubkey::default());
+
+        // Page 0 is full, so page 1 is created and linked
+        let first_on_second_page = Pubkey::new_unique();
+        let instruction = add_investor_instruction(&mut context, quote_mint, policy_state, investor_registry, first_on_second_page).await;
+        send_instruction(&mut context, instruction).await.unwrap();
+        let (second_page_address, _) = InvestorRegistryPage::derive_pda(&investor_registry, 1, &meteora_fee_router::id());
+        let first_page = registry_page(&mut context, investor_registry, 0).await;
+        assert_eq!(first_page.next_page, second_page_address);
+        let second_page = registry_page(&mut context, investor_registry, 1).await;
+        assert_eq!(second_page.page_index, 1);
+        assert_eq!(second_page.entries.len(), 1);
+        assert_eq!(second_page.entries[0].investor, first_on_second_page);
+
+        let account = context.banks_client.get_account(investor_registry).await.unwrap().unwrap();
+        let registry = InvestorRegistryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(registry.investor_count, page_size + 1);
+        assert_eq!(registry.page_count, 2);
+        assert_eq!(registry.last_page_index, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_close_position_removes_metadata() {
+        let (mut context, accounts) = setup_close_position(None).await;
+        let authority_lamports_before = context.banks_client.get_balance(accounts.authority).await.unwrap();
+
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: accounts.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClosePosition { claim_final_fees: true }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+
+        // The metadata and the CP-AMM position are gone, their rent back with the authority
+        assert!(context.banks_client.get_account(accounts.position_metadata).await.unwrap().is_none());
+        assert!(context.banks_client.get_account(accounts.position).await.unwrap().is_none());
+        let authority_lamports_after = context.banks_client.get_balance(accounts.authority).await.unwrap();
+        assert!(authority_lamports_after > authority_lamports_before);
+
+        // The final fees were claimed into the treasury and recorded there
+        assert_eq!(token_balance(&mut context, accounts.treasury_ata).await, POSITION_FEES);
+        assert_eq!(token_balance(&mut context, accounts.authority_quote_ata).await, 0);
+        let account = context.banks_client.get_account(accounts.treasury_state).await.unwrap().unwrap();
+        let treasury_state = TreasuryState::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(treasury_state.total_fees_claimed, POSITION_FEES);
+        assert_eq!(treasury_state.claim_count, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_close_position_without_recorded_authority_uses_policy_authority() {
+        // Positions created before the authority was recorded read the default key
+        let (mut context, accounts) = setup_close_position(Some(Pubkey::default())).await;
+
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: accounts.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClosePosition { claim_final_fees: false }.data(),
+        };
+        send_instruction(&mut context, instruction).await.unwrap();
+        assert!(context.banks_client.get_account(accounts.position_metadata).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "program-test syscall stubs break other unit tests; run with --ignored"]
+    async fn test_close_position_requires_creating_authority() {
+        // The payer is the policy authority, which only stands in for unrecorded authorities
+        let (mut context, accounts) = setup_close_position(Some(Pubkey::new_unique())).await;
+
+        let instruction = Instruction {
+            program_id: meteora_fee_router::id(),
+            accounts: accounts.to_account_metas(None),
+            data: meteora_fee_router::instruction::ClosePosition { claim_final_fees: false }.data(),
+        };
+        let error = send_instruction(&mut context, instruction).await.unwrap_err().unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + FeeRouterError::Unauthorized as u32),
+            )
+        );
+        assert!(context.banks_client.get_account(accounts.position_metadata).await.unwrap().is_some());
+    }
+}
@@ -1,7 +1,10 @@
 use meteora_fee_router::errors::FeeRouterError;
 use meteora_fee_router::modules::distribution::state::DailyDistributionState;
 use meteora_fee_router::integrations::streamflow::cpi::{StreamError, StreamErrorType};
+use meteora_fee_router::shared::crypto::HashAlgorithm;
+use meteora_fee_router::shared::rent::require_rent_lamports;
 use anchor_lang::prelude::*;
+use super::test_fixtures::*;
 
 #[cfg(test)]
 mod error_condition_tests {
@@ -39,6 +42,61 @@ mod error_condition_tests {
             FeeRouterError::TreasuryBalanceMismatch,
             FeeRouterError::TreasuryTransferFailed,
             FeeRouterError::MeteoraCpiFailed,
+            FeeRouterError::InsufficientPositionCreationFee,
+            FeeRouterError::ForceCompleteNotYetAllowed,
+            FeeRouterError::StreamExpiredOrCancelled,
+            FeeRouterError::SnapshotCapacityExceeded,
+            FeeRouterError::Unauthorized,
+            FeeRouterError::ProtocolFeeWalletNotSet,
+            FeeRouterError::ClaimerAlreadyAuthorized,
+            FeeRouterError::ClaimerListFull,
+            FeeRouterError::ClaimerNotFound,
+            FeeRouterError::PageTooLarge,
+            FeeRouterError::ClaimExceedsRateLimit,
+            FeeRouterError::RaydiumCpiFailed,
+            FeeRouterError::InsufficientInvestors,
+            FeeRouterError::DistributionWindowExpired,
+            FeeRouterError::TooManyAccountsInPage,
+            FeeRouterError::PolicySunsetted,
+            FeeRouterError::InvalidSunsetExtension,
+            FeeRouterError::SnapshotNotClosable,
+            FeeRouterError::InvalidMinPayout,
+            FeeRouterError::DistributionPaused,
+            FeeRouterError::PoolFeeModeMismatch,
+            FeeRouterError::PageTransferPartialFailure,
+            FeeRouterError::DistributionAlreadyExistsForDay,
+            FeeRouterError::ClaimBelowMinimum,
+            FeeRouterError::InstantDistributionDisabled,
+            FeeRouterError::InstantDistributionTooManyInvestors,
+            FeeRouterError::StreamAlreadyRegistered,
+            FeeRouterError::AllocationRegistryFull,
+            FeeRouterError::AllocationExceedsY0,
+            FeeRouterError::UnsupportedPolicyVersion,
+            FeeRouterError::InvalidPageSize,
+            FeeRouterError::TokenAccountMintMismatch,
+            FeeRouterError::InvestorRegistryFull,
+            FeeRouterError::RegistryPageMismatch,
+            FeeRouterError::TreasuryClaimStale,
+            FeeRouterError::InsufficientRentForAccount,
+            FeeRouterError::InvalidStreamAccountData,
+            FeeRouterError::CreatorShareBelowMinimum,
+            FeeRouterError::DailyCapTooLarge,
+            FeeRouterError::MinPayoutExceedsDailyCap,
+            FeeRouterError::Y0BelowMinPayout,
+            FeeRouterError::CreatorWalletMismatch,
+            FeeRouterError::SeedConstraintViolation,
+            FeeRouterError::InvestorAtaOwnerMismatch,
+            FeeRouterError::PoolVaultMismatch,
+            FeeRouterError::InvalidRampDuration,
+            FeeRouterError::InvalidDurationBonusMultiplier,
+            FeeRouterError::InvalidClaimInterval,
+            FeeRouterError::RentPayerMismatch,
+            FeeRouterError::RegistryPagesRequired,
+            FeeRouterError::BlacklistRequired,
+            FeeRouterError::InvalidDistributionDay,
+            FeeRouterError::PayoutSummariesRequired,
+            FeeRouterError::SnapshotPageMismatch,
+            FeeRouterError::InvalidGovernanceProposal,
         ];
 
         // Verify each error can be converted to an anchor error
@@ -48,6 +106,75 @@ mod error_condition_tests {
         }
     }
 
+    #[test]
+    fn test_pool_fee_mode_mismatch_vs_base_fee_detected() {
+        use meteora_fee_router::integrations::meteora::accounts::{CollectFeeMode, Pool, PoolStatus};
+        use meteora_fee_router::integrations::meteora::validation::validate_quote_only_pool;
+
+        let quote_mint = Pubkey::new_unique();
+        let mut pool: Pool = bytemuck::Zeroable::zeroed();
+        pool.pool_status = PoolStatus::Enabled as u8;
+        pool.token_a_mint = Pubkey::new_unique();
+        pool.token_b_mint = quote_mint;
+
+        // Static config issues: pool collects fees in both tokens or in the base token
+        for fee_mode in [CollectFeeMode::Both, CollectFeeMode::OnlyTokenA] {
+            pool.collect_fee_mode = fee_mode as u8;
+            let error = validate_quote_only_pool(&pool, &quote_mint).unwrap_err();
+            assert_eq!(error, FeeRouterError::PoolFeeModeMismatch.into());
+            assert_ne!(error, FeeRouterError::BaseFeeDetected.into());
+        }
+
+        pool.collect_fee_mode = CollectFeeMode::OnlyTokenB as u8;
+        assert!(validate_quote_only_pool(&pool, &quote_mint).is_ok());
+
+        // Runtime issue: base tokens actually received during a claim
+        let runtime: anchor_lang::error::Error = FeeRouterError::BaseFeeDetected.into();
+        assert_ne!(runtime, FeeRouterError::PoolFeeModeMismatch.into());
+    }
+
+    #[test]
+    fn test_rich_error_context() {
+        let error: anchor_lang::error::Error = FeeRouterError::BaseFeesClaimedError
+            .with_context(format!("amount: {}", 42))
+            .into();
+
+        // The offending value is appended to the static message
+        match error {
+            anchor_lang::error::Error::AnchorError(anchor_error) => {
+                assert!(anchor_error.error_msg.ends_with("(amount: 42)"));
+                assert_eq!(
+                    anchor_error.error_code_number,
+                    anchor_lang::error::ERROR_CODE_OFFSET + FeeRouterError::BaseFeesClaimedError as u32
+                );
+            }
+            _ => panic!("expected an anchor error"),
+        }
+    }
+
+    #[test]
+    fn test_insufficient_rent_reports_lamports() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::default();
+        let mut lamports = 1_000_000u64;
+        let mut data: Vec<u8> = Vec::new();
+        let payer = AccountInfo::new(&key, true, true, &mut lamports, &mut data, &owner, false, 0);
+
+        assert!(require_rent_lamports(&payer, 1_000_000).is_ok());
+
+        // The shortfall is reported with both amounts
+        match require_rent_lamports(&payer, 2_039_280).unwrap_err() {
+            anchor_lang::error::Error::AnchorError(anchor_error) => {
+                assert!(anchor_error.error_msg.ends_with("(need 2039280 lamports, have 1000000)"));
+                assert_eq!(
+                    anchor_error.error_code_number,
+                    anchor_lang::error::ERROR_CODE_OFFSET + FeeRouterError::InsufficientRentForAccount as u32
+                );
+            }
+            _ => panic!("expected an anchor error"),
+        }
+    }
+
     #[test]
     fn test_streamflow_error_types() {
         let stream_account = Pubkey::new_unique();
@@ -106,24 +233,24 @@ mod error_condition_tests {
 
     #[test]
     fn test_idempotency_violation_error() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         let investor_accounts = vec![
             Pubkey::new_unique(),
             Pubkey::new_unique(),
         ];
 
         // Process a page
-        let page_hash = DailyDistributionState::calculate_page_hash(&investor_accounts);
+        let page_hash = DailyDistributionState::calculate_page_hash(&investor_accounts, HashAlgorithm::Sha256);
         state.update_page_state(page_hash, 10, 5000);
 
         // Try to process the same page again - should fail
-        let result = state.validate_page_for_retry(&investor_accounts);
+        let result = state.validate_page_for_retry(&investor_accounts, HashAlgorithm::Sha256);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_daily_cap_exceeded_scenario() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         
         // Set a low daily cap
         state.daily_cap_total = 1000;
@@ -175,29 +302,23 @@ mod error_condition_tests {
             locked_amount: 1000,
             total_deposited: 2000,
             investor_ata: Pubkey::new_unique(),
+            start_time: 0,
+            end_time: 0,
+            cliff_locked: 0,
+            lock_duration_seconds: 0,
         };
         
-        let weight = investor.calculate_weight(0); // Zero total
+        let weight = investor.calculate_weight(0, true).unwrap(); // Zero total
         assert_eq!(weight, 0); // Should handle gracefully
         
-        let payout = investor.calculate_payout(0, 1000); // Zero total
+        let payout = investor.calculate_payout(0, 1000).unwrap(); // Zero total
         assert_eq!(payout, 0); // Should handle gracefully
     }
 
     #[test]
     fn test_invalid_parameter_validation() {
-        use meteora_fee_router::modules::distribution::state::PolicyState;
-        
         // Test invalid fee share BPS
-        let mut policy = PolicyState {
-            quote_mint: Pubkey::new_unique(),
-            investor_fee_share_bps: 15000, // > 10000
-            daily_cap_lamports: 1000000,
-            min_payout_lamports: 1000,
-            y0_total_allocation: 2000000,
-            policy_authority: Pubkey::new_unique(),
-            reserved: [0; 64],
-        };
+        let mut policy = fixture_policy_state().with_fee_shares(15000, 5000); // > 10000
         
         assert!(policy.validate().is_err());
         
@@ -209,21 +330,21 @@ mod error_condition_tests {
 
     #[test]
     fn test_distribution_state_validation_errors() {
-        let state = create_test_daily_state();
+        let state = fixture_daily_distribution_state();
         
         // Test distribution not started error condition
         assert!(state.has_more_investors()); // Should have investors to process
         
         // Test invalid page scenarios
         let empty_accounts: Vec<Pubkey> = vec![];
-        let result = state.validate_page_for_retry(&empty_accounts);
+        let result = state.validate_page_for_retry(&empty_accounts, HashAlgorithm::Sha256);
         // Empty accounts should still be valid (edge case)
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_minimum_payout_threshold_errors() {
-        use meteora_fee_router::integrations::streamflow::calculations::calculate_distribution;
+        use meteora_fee_router::integrations::streamflow::calculations::{calculate_distribution, WeightingOptions};
         use meteora_fee_router::integrations::streamflow::accounts::InvestorStreamData;
         
         // Create investor with very small locked amount
@@ -234,6 +355,10 @@ mod error_condition_tests {
                 locked_amount: 1, // Very small
                 total_deposited: 1,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
         ];
         
@@ -244,6 +369,7 @@ mod error_condition_tests {
             1000000,
             10000,
             1000, // High minimum threshold
+            WeightingOptions::default(),
         ).unwrap();
         
         // Payout should be below minimum
@@ -254,7 +380,7 @@ mod error_condition_tests {
 
     #[test]
     fn test_no_investors_error_scenario() {
-        use meteora_fee_router::integrations::streamflow::calculations::calculate_distribution;
+        use meteora_fee_router::integrations::streamflow::calculations::{calculate_distribution, WeightingOptions};
         
         // Empty investor list
         let investors: Vec<_> = vec![];
@@ -266,6 +392,7 @@ mod error_condition_tests {
             1000000,
             5000,
             100,
+            WeightingOptions::default(),
         ).unwrap();
         
         // Should handle empty investor list gracefully
@@ -273,31 +400,4 @@ mod error_condition_tests {
         assert_eq!(result.total_distributed, 0);
         assert_eq!(result.creator_remainder, 1000); // All to creator
     }
-
-    // Helper function
-    fn create_test_daily_state() -> DailyDistributionState {
-        DailyDistributionState {
-            distribution_day: 1672531200,
-            quote_mint: Pubkey::new_unique(),
-            treasury_ata: Pubkey::new_unique(),
-            total_amount_to_distribute: 100_000,
-            amount_distributed: 0,
-            current_cursor: 0,
-            total_investors: 50,
-            investors_processed: 0,
-            is_complete: false,
-            started_at: 1672531200,
-            completed_at: 0,
-            dust_carried_over: 0,
-            daily_cap_total: 1_000_000,
-            daily_cap_remaining: 1_000_000,
-            min_payout_threshold: 1000,
-            initial_total_deposit: 2_000_000,
-            investor_fee_share_bps: 5000,
-            last_page_hash: [0; 32],
-            pages_processed: 0,
-            failed_payouts_count: 0,
-            reserved: [0; 20],
-        }
-    }
 }
@@ -1,6 +1,10 @@
 use meteora_fee_router::integrations::streamflow::calculations::*;
 use meteora_fee_router::integrations::streamflow::accounts::InvestorStreamData;
-use meteora_fee_router::integrations::streamflow::cpi::calculate_locked_fraction;
+use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_fraction, calculate_uncapped_locked_fraction};
+use meteora_fee_router::errors::FeeRouterError;
+use meteora_fee_router::modules::distribution::state::CapApplicationMode;
+use meteora_fee_router::shared::compute_budget::*;
+use meteora_fee_router::shared::math::checked_mul_div;
 use anchor_lang::prelude::*;
 
 #[cfg(test)]
@@ -26,6 +30,11 @@ mod mathematical_tests {
         // Test edge case: zero initial deposit
         let fraction_edge = calculate_locked_fraction(100, 0);
         assert_eq!(fraction_edge, 0); // Should handle division by zero
+
+        // Test Y0 undercount: locked total above Y0 is clamped to 100%
+        let fraction_over = calculate_locked_fraction(1_500_000, 1_000_000);
+        assert_eq!(fraction_over, 10000);
+        assert_eq!(calculate_uncapped_locked_fraction(1_500_000, 1_000_000), 15000);
     }
 
     #[test]
@@ -42,6 +51,10 @@ mod mathematical_tests {
                 locked_amount: 300_000, // 30% of total
                 total_deposited: 500_000,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
             InvestorStreamData {
                 investor: investor2,
@@ -49,6 +62,10 @@ mod mathematical_tests {
                 locked_amount: 500_000, // 50% of total
                 total_deposited: 800_000,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
             InvestorStreamData {
                 investor: investor3,
@@ -56,6 +73,10 @@ mod mathematical_tests {
                 locked_amount: 200_000, // 20% of total
                 total_deposited: 300_000,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
         ];
 
@@ -73,6 +94,7 @@ mod mathematical_tests {
             initial_total_deposit,
             investor_fee_share_bps,
             min_payout_lamports,
+            WeightingOptions::default(),
         ).unwrap();
 
         // Verify locked fraction: 1M / 2M = 50% = 5000 bps
@@ -114,6 +136,10 @@ mod mathematical_tests {
                 locked_amount: 333_333, // 1/3 of total
                 total_deposited: 333_333,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
             InvestorStreamData {
                 investor: Pubkey::new_unique(),
@@ -121,6 +147,10 @@ mod mathematical_tests {
                 locked_amount: 333_333, // 1/3 of total
                 total_deposited: 333_333,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
             InvestorStreamData {
                 investor: Pubkey::new_unique(),
@@ -128,6 +158,10 @@ mod mathematical_tests {
                 locked_amount: 333_334, // 1/3 of total (with remainder)
                 total_deposited: 333_334,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
         ];
 
@@ -140,6 +174,7 @@ mod mathematical_tests {
             1_000_000,
             10000, // 100% to investors
             1,
+            WeightingOptions::default(),
         ).unwrap();
 
         // With 100 tokens and 3 equal investors, each should get 33 (floor division)
@@ -159,6 +194,10 @@ mod mathematical_tests {
                 locked_amount: 1, // Very small amount
                 total_deposited: 1,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
             InvestorStreamData {
                 investor: Pubkey::new_unique(),
@@ -166,6 +205,10 @@ mod mathematical_tests {
                 locked_amount: 999_999, // Most of the total
                 total_deposited: 999_999,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
         ];
 
@@ -176,6 +219,7 @@ mod mathematical_tests {
             1_000_000,
             10000,
             100, // High minimum threshold
+            WeightingOptions::default(),
         ).unwrap();
 
         // First investor should not meet minimum
@@ -187,6 +231,159 @@ mod mathematical_tests {
         assert!(large_payout.meets_minimum);
     }
 
+    #[test]
+    fn test_daily_cap_post_cap_minimum_redistribution() {
+        let locked = [600_000u64, 300_000, 100_000];
+        let investors: Vec<InvestorStreamData> = locked
+            .iter()
+            .map(|&locked_amount| InvestorStreamData {
+                investor: Pubkey::new_unique(),
+                stream_account: Pubkey::new_unique(),
+                locked_amount,
+                total_deposited: locked_amount,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            })
+            .collect();
+
+        let result = calculate_distribution(
+            10_000,
+            &investors,
+            1_000_000,
+            1_000_000,
+            10000,
+            1000, // High minimum threshold
+            WeightingOptions::default(),
+        ).unwrap();
+
+        // 6000 / 3000 / 1000 all meet the minimum before capping
+        assert!(result.investor_payouts.iter().all(|p| p.meets_minimum));
+
+        // Tight cap halves payouts to 3000 / 1500 / 500
+        let capped = apply_daily_cap(result, 5_000, 1000, CapApplicationMode::ProportionalScale);
+
+        // The smallest investor drops below the minimum and is zeroed out
+        assert_eq!(capped.post_cap_minimum_failures, 1);
+        assert_eq!(capped.investor_payouts[2].payout_amount, 0);
+        assert!(!capped.investor_payouts[2].meets_minimum);
+
+        // Their 500 is redistributed 2:1 to the remaining investors
+        assert_eq!(capped.investor_payouts[0].payout_amount, 3_333);
+        assert_eq!(capped.investor_payouts[1].payout_amount, 1_666);
+        assert_eq!(capped.total_distributed, 4_999);
+        assert_eq!(capped.dust_amount, 1);
+    }
+
+    #[test]
+    fn test_daily_cap_modes_compared() {
+        let locked = [600_000u64, 300_000, 100_000];
+        let investors: Vec<InvestorStreamData> = locked
+            .iter()
+            .map(|&locked_amount| InvestorStreamData {
+                investor: Pubkey::new_unique(),
+                stream_account: Pubkey::new_unique(),
+                locked_amount,
+                total_deposited: locked_amount,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            })
+            .collect();
+
+        // Payouts of 6000 / 3000 / 1000 before capping
+        let result = calculate_distribution(
+            10_000,
+            &investors,
+            1_000_000,
+            1_000_000,
+            10000,
+            100,
+            WeightingOptions::default(),
+        ).unwrap();
+
+        // Proportional scaling reduces everyone by 10%
+        let scaled = apply_daily_cap(result.clone(), 9_000, 100, CapApplicationMode::ProportionalScale);
+        let scaled_amounts: Vec<u64> = scaled.investor_payouts.iter().map(|p| p.payout_amount).collect();
+        assert_eq!(scaled_amounts, vec![5_400, 2_700, 900]);
+        assert_eq!(scaled.total_distributed, 9_000);
+        assert_eq!(scaled.post_cap_minimum_failures, 0);
+
+        // Trimming takes the whole excess from the smallest payout
+        let trimmed = apply_daily_cap(result.clone(), 9_000, 100, CapApplicationMode::TrimFromBottom);
+        let trimmed_amounts: Vec<u64> = trimmed.investor_payouts.iter().map(|p| p.payout_amount).collect();
+        assert_eq!(trimmed_amounts, vec![6_000, 3_000, 0]);
+        assert_eq!(trimmed.total_distributed, 9_000);
+        assert_eq!(trimmed.post_cap_minimum_failures, 1);
+        assert!(!trimmed.investor_payouts[2].meets_minimum);
+
+        // A deeper cap works upwards through the smallest payouts
+        let deep = apply_daily_cap(result, 5_500, 100, CapApplicationMode::TrimFromBottom);
+        let deep_amounts: Vec<u64> = deep.investor_payouts.iter().map(|p| p.payout_amount).collect();
+        assert_eq!(deep_amounts, vec![5_500, 0, 0]);
+        assert_eq!(deep.total_distributed, 5_500);
+        assert_eq!(deep.dust_amount, 0);
+    }
+
+    #[test]
+    fn test_page_payable_is_all_or_nothing() {
+        // Every ATA present and the treasury covers the page
+        assert!(validate_page_payable(&[], 3, 9_000, 9_000).is_ok());
+
+        // A single missing ATA rejects the whole page
+        let missing = Pubkey::new_unique();
+        let error = validate_page_payable(&[missing], 3, 6_000, 9_000).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("1 of 3 investor ATAs missing"));
+        assert!(message.contains(&missing.to_string()));
+
+        // A treasury short of the page total is rejected before any transfer
+        assert!(validate_page_payable(&[], 3, 9_001, 9_000).is_err());
+    }
+
+    #[test]
+    fn test_duration_weighting() {
+        let year = 365 * 86400u64;
+
+        // Weight grows linearly with remaining lock, capped at four years
+        assert_eq!(calculate_duration_weighted_amount(1_000, 0), 0);
+        assert_eq!(calculate_duration_weighted_amount(1_000, year), 250);
+        assert_eq!(calculate_duration_weighted_amount(1_000, 4 * year), 1_000);
+        assert_eq!(calculate_duration_weighted_amount(1_000, 8 * year), 1_000);
+
+        // Same locked amount, four years vs one year left
+        let investors: Vec<InvestorStreamData> = [4 * year, year]
+            .iter()
+            .map(|&lock_duration_seconds| InvestorStreamData {
+                investor: Pubkey::new_unique(),
+                stream_account: Pubkey::new_unique(),
+                locked_amount: 500_000,
+                total_deposited: 500_000,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds,
+            })
+            .collect();
+
+        // Without duration weighting both are paid equally
+        let flat = calculate_distribution(10_000, &investors, 1_000_000, 1_000_000, 10000, 100, WeightingOptions::default()).unwrap();
+        assert_eq!(flat.investor_payouts[0].payout_amount, 5_000);
+        assert_eq!(flat.investor_payouts[1].payout_amount, 5_000);
+
+        // With it, the longer lock gets four times the weight
+        let weighted = calculate_distribution(10_000, &investors, 1_000_000, 1_000_000, 10000, 100, WeightingOptions { use_duration_weighting: true, ..Default::default() }).unwrap();
+        assert_eq!(weighted.investor_payouts[0].payout_amount, 8_000);
+        assert_eq!(weighted.investor_payouts[1].payout_amount, 2_000);
+        assert_eq!(weighted.investor_payouts[0].weight_bps, 8_000);
+        assert_eq!(weighted.total_distributed, 10_000);
+    }
+
     #[test]
     fn test_edge_case_all_unlocked() {
         // Test scenario where all tokens are unlocked (100% to creator)
@@ -197,6 +394,10 @@ mod mathematical_tests {
                 locked_amount: 0, // All unlocked
                 total_deposited: 1_000_000,
                 investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
             },
         ];
 
@@ -207,6 +408,7 @@ mod mathematical_tests {
             1_000_000,
             5000, // 50% max to investors
             100,
+            WeightingOptions::default(),
         ).unwrap();
 
         // Should be 0 to investors, all to creator
@@ -215,6 +417,61 @@ mod mathematical_tests {
         assert_eq!(result.creator_remainder, 10_000);
     }
 
+    #[test]
+    fn test_duration_bonus_weighting() {
+        const DAY: u64 = 86400;
+        let short_locker = Pubkey::new_unique();
+        let long_locker = Pubkey::new_unique();
+
+        let investors = vec![
+            InvestorStreamData {
+                investor: short_locker,
+                stream_account: Pubkey::new_unique(),
+                locked_amount: 500_000,
+                total_deposited: 500_000,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: DAY * 182, // 6 months
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            },
+            InvestorStreamData {
+                investor: long_locker,
+                stream_account: Pubkey::new_unique(),
+                locked_amount: 500_000,
+                total_deposited: 500_000,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: DAY * 365 * 4, // 4 years
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            },
+        ];
+
+        // Multiplier is 1x at 6 months and reaches the 2x max at 4 years
+        assert_eq!(calculate_duration_multiplier_bps(DAY * 182, 20000), 10000);
+        assert_eq!(calculate_duration_multiplier_bps(DAY * 365 * 4, 20000), 20000);
+        assert_eq!(calculate_duration_multiplier_bps(DAY * 365 * 10, 20000), 20000);
+
+        let result = calculate_distribution(
+            9_000,
+            &investors,
+            1_000_000,
+            1_000_000,
+            10000,
+            1,
+            WeightingOptions { duration_bonus_max_multiplier_bps: 20000, ..Default::default() }, // 2x max duration bonus
+        ).unwrap();
+
+        let short_payout = result.investor_payouts.iter().find(|p| p.investor == short_locker).unwrap();
+        let long_payout = result.investor_payouts.iter().find(|p| p.investor == long_locker).unwrap();
+
+        // Same locked amount, 4-year locker gets 2x the weight
+        assert_eq!(short_payout.payout_amount, 3_000);
+        assert_eq!(long_payout.payout_amount, 6_000);
+        assert_eq!(long_payout.weight_bps, short_payout.weight_bps * 2);
+    }
+
     #[test]
     fn test_weight_calculation() {
         let investor_data = InvestorStreamData {
@@ -223,16 +480,20 @@ mod mathematical_tests {
             locked_amount: 250_000,
             total_deposited: 500_000,
             investor_ata: Pubkey::new_unique(),
+            start_time: 0,
+            end_time: 0,
+            cliff_locked: 0,
+            lock_duration_seconds: 0,
         };
 
         let total_locked = 1_000_000u64;
-        let weight = investor_data.calculate_weight(total_locked);
+        let weight = investor_data.calculate_weight(total_locked, true).unwrap();
         
         // 250k / 1M = 25% = 2500 basis points
         assert_eq!(weight, 2500);
 
         // Test edge case: zero total locked
-        let weight_zero = investor_data.calculate_weight(0);
+        let weight_zero = investor_data.calculate_weight(0, true).unwrap();
         assert_eq!(weight_zero, 0);
     }
 
@@ -244,18 +505,152 @@ mod mathematical_tests {
             locked_amount: 300_000,
             total_deposited: 500_000,
             investor_ata: Pubkey::new_unique(),
+            start_time: 0,
+            end_time: 0,
+            cliff_locked: 0,
+            lock_duration_seconds: 0,
         };
 
         let total_locked = 1_000_000u64;
         let investor_fee_quote = 5_000u64;
         
-        let payout = investor_data.calculate_payout(total_locked, investor_fee_quote);
+        let payout = investor_data.calculate_payout(total_locked, investor_fee_quote).unwrap();
         
         // 300k / 1M * 5000 = 1500
         assert_eq!(payout, 1500);
 
         // Test edge cases
-        assert_eq!(investor_data.calculate_payout(0, investor_fee_quote), 0);
-        assert_eq!(investor_data.calculate_payout(total_locked, 0), 0);
+        assert_eq!(investor_data.calculate_payout(0, investor_fee_quote).unwrap(), 0);
+        assert_eq!(investor_data.calculate_payout(total_locked, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cliff_weight_policy() {
+        // Stream that has not started: 40_000 of the 100_000 locked is an unreleased cliff
+        let cliff_investor = InvestorStreamData {
+            investor: Pubkey::new_unique(),
+            stream_account: Pubkey::new_unique(),
+            locked_amount: 100_000,
+            total_deposited: 100_000,
+            investor_ata: Pubkey::new_unique(),
+            start_time: 0,
+            end_time: 0,
+            cliff_locked: 40_000,
+            lock_duration_seconds: 0,
+        };
+        let total_locked = 200_000u64;
+
+        // Cliff counted: 100_000 / 200_000 = 50%
+        assert_eq!(cliff_investor.weight_locked_amount(true), 100_000);
+        assert_eq!(cliff_investor.calculate_weight(total_locked, true).unwrap(), 5000);
+
+        // Cliff excluded: 60_000 / 200_000 = 30%
+        assert_eq!(cliff_investor.weight_locked_amount(false), 60_000);
+        assert_eq!(cliff_investor.calculate_weight(total_locked, false).unwrap(), 3000);
+    }
+
+    #[test]
+    fn test_distribution_overflow_is_reported() {
+        const DAY: u64 = 86400;
+        let investor = |locked_amount: u64, end_time: u64| InvestorStreamData {
+            investor: Pubkey::new_unique(),
+            stream_account: Pubkey::new_unique(),
+            locked_amount,
+            total_deposited: locked_amount,
+            investor_ata: Pubkey::new_unique(),
+            start_time: 0,
+            end_time,
+            cliff_locked: 0,
+            lock_duration_seconds: 0,
+        };
+        let overflow: anchor_lang::error::Error = FeeRouterError::ArithmeticOverflow.into();
+
+        // Page locked amounts that do not fit in a u64
+        let half = u64::MAX / 2 + 1;
+        let investors = vec![investor(half, 0), investor(half, 0)];
+        let error = calculate_distribution(10_000, &investors, u64::MAX, u64::MAX, 10000, 1, WeightingOptions::default()).unwrap_err();
+        assert_eq!(error, overflow);
+
+        // A 2x duration bonus on a near-u64::MAX lock
+        let investors = vec![investor(u64::MAX - 1, DAY * 365 * 4)];
+        let error = calculate_distribution(10_000, &investors, u64::MAX, u64::MAX, 10000, 1, WeightingOptions { duration_bonus_max_multiplier_bps: 20000, ..Default::default() }).unwrap_err();
+        assert_eq!(error, overflow);
+
+        // A page larger than the total it is weighted against
+        let investors = vec![investor(1_000, 0)];
+        let error = calculate_distribution(10_000, &investors, 999, 1_000, 10000, 1, WeightingOptions::default()).unwrap_err();
+        assert_eq!(error, FeeRouterError::ArithmeticUnderflow.into());
+
+        // Helpers fail instead of truncating the u128 result
+        let whale = investor(u64::MAX, 0);
+        assert_eq!(whale.calculate_weight(1, true).unwrap_err(), overflow);
+        assert_eq!(whale.calculate_payout(1, u64::MAX).unwrap_err(), overflow);
+        assert_eq!(checked_mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(checked_mul_div(1, 1, 0).unwrap_err(), FeeRouterError::DivisionByZero.into());
+    }
+
+    #[test]
+    fn test_process_page_compute_estimate() {
+        // Fixed page cost plus a constant cost per investor
+        assert_eq!(estimate_process_page_compute(0), PROCESS_PAGE_BASE_COMPUTE);
+        assert_eq!(
+            estimate_process_page_compute(10) - estimate_process_page_compute(9),
+            PROCESS_PAGE_PER_INVESTOR_COMPUTE
+        );
+
+        // The safe page size fits the default limit; one more investor does not
+        assert!(estimate_process_page_compute(MAX_SAFE_INVESTORS_PER_PAGE) <= DEFAULT_COMPUTE_UNIT_LIMIT);
+        assert!(estimate_process_page_compute(MAX_SAFE_INVESTORS_PER_PAGE + 1) > DEFAULT_COMPUTE_UNIT_LIMIT);
+
+        // Large pages stay within the raised 400k limit
+        assert!(estimate_process_page_compute(2 * MAX_SAFE_INVESTORS_PER_PAGE) <= 400_000);
+    }
+
+    #[test]
+    fn test_remaining_accounts_length_validation() {
+        use meteora_fee_router::shared::constants::ACCOUNTS_PER_INVESTOR;
+
+        // Empty and oversized pages are rejected before any account is read
+        assert!(validate_remaining_accounts_length(0).is_err());
+        assert!(validate_remaining_accounts_length(200).is_err());
+        assert!(validate_remaining_accounts_length(50 * ACCOUNTS_PER_INVESTOR + 1).is_err());
+
+        // Partial investor groups are rejected
+        assert!(validate_remaining_accounts_length(ACCOUNTS_PER_INVESTOR + 1).is_err());
+
+        // A page within the compute budget passes
+        assert_eq!(validate_remaining_accounts_length(2 * ACCOUNTS_PER_INVESTOR).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_payout_statistics() {
+        // No payouts - nothing to report
+        assert_eq!(calculate_payout_statistics(&mut []), PayoutStatistics::default());
+
+        // Odd count: median is the middle payout
+        let stats = calculate_payout_statistics(&mut [300, 100, 200]);
+        assert_eq!(stats.investor_count, 3);
+        assert_eq!(stats.total_distributed, 600);
+        assert_eq!(stats.median_payout, 200);
+        assert_eq!(stats.max_payout, 300);
+        assert_eq!(stats.min_payout, 100);
+
+        // Even count: median is the mean of the middle two
+        let stats = calculate_payout_statistics(&mut [400, 100, 200, 300]);
+        assert_eq!(stats.median_payout, 250);
+
+        // Equal payouts are perfectly fair
+        let stats = calculate_payout_statistics(&mut [500; 10]);
+        assert_eq!(stats.gini_coefficient_bps, 0);
+
+        // One investor receiving everything approaches the maximum (n - 1) / n
+        let mut payouts = [0u64; 10];
+        payouts[3] = 1_000_000;
+        let stats = calculate_payout_statistics(&mut payouts);
+        assert_eq!(stats.gini_coefficient_bps, 9000);
+
+        // Moderately unequal payouts land in between: G = 1/3 for 1, 2, 3
+        let stats = calculate_payout_statistics(&mut [1, 2, 3]);
+        assert_eq!(stats.gini_coefficient_bps, 2222);
     }
 }
@@ -2,3 +2,7 @@ pub mod pda_tests;
 pub mod math_tests;
 pub mod state_tests;
 pub mod error_tests;
+pub mod compute_tests;
+pub mod distribution_tests;
+pub mod constraint_tests;
+pub mod test_fixtures;
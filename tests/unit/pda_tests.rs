@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use meteora_fee_router::modules::distribution::state::{PolicyState, DailyDistributionState, GlobalDistributionState};
+use meteora_fee_router::modules::distribution::state::{PolicyState, DailyDistributionState, GlobalDistributionState, ProgramConfig};
 use meteora_fee_router::modules::position::state::PositionState;
 use meteora_fee_router::shared::constants::*;
 
@@ -77,6 +77,19 @@ mod pda_derivation_tests {
         assert_eq!(pda, expected_pda);
     }
 
+    #[test]
+    fn test_program_config_pda_derivation() {
+        let program_id = Pubkey::new_unique();
+        
+        let (pda, bump) = ProgramConfig::derive_pda(&program_id);
+        
+        // Single program-wide config, independent of any quote mint
+        let expected_seeds: &[&[u8]] = &[b"program_config", &[bump]];
+        let expected_pda = Pubkey::create_program_address(expected_seeds, &program_id).unwrap();
+        
+        assert_eq!(pda, expected_pda);
+    }
+
     #[test]
     fn test_position_owner_pda_derivation() {
         let program_id = Pubkey::new_unique();
@@ -142,4 +155,31 @@ mod pda_derivation_tests {
         
         assert_ne!(pda3, pda4);
     }
+
+    #[test]
+    fn test_verify_pda_reports_mismatch() {
+        use meteora_fee_router::errors::FeeRouterError;
+        use meteora_fee_router::shared::pda::verify_pda;
+
+        let program_id = Pubkey::new_unique();
+        let quote_mint = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"treasury_authority", quote_mint.as_ref()];
+        let (treasury_authority, bump) = Pubkey::find_program_address(seeds, &program_id);
+
+        // The canonical PDA passes and yields its bump for signing
+        assert_eq!(verify_pda(&treasury_authority, seeds, &program_id).unwrap(), bump);
+
+        // Any other address names both sides of the mismatch
+        let wrong = Pubkey::new_unique();
+        match verify_pda(&wrong, seeds, &program_id).unwrap_err() {
+            anchor_lang::error::Error::AnchorError(anchor_error) => {
+                assert_eq!(
+                    anchor_error.error_code_number,
+                    anchor_lang::error::ERROR_CODE_OFFSET + FeeRouterError::SeedConstraintViolation as u32
+                );
+                assert!(anchor_error.error_msg.ends_with(&format!("(expected {}, got {})", treasury_authority, wrong)));
+            }
+            _ => panic!("expected an anchor error"),
+        }
+    }
 }
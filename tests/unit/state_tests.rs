@@ -1,6 +1,8 @@
-use meteora_fee_router::modules::distribution::state::{DailyDistributionState, PolicyState};
-use meteora_fee_router::integrations::streamflow::accounts::StreamflowStream;
+use meteora_fee_router::modules::distribution::state::DailyDistributionState;
+use meteora_fee_router::shared::constants::MAX_INVESTORS_PER_PAGE;
+use meteora_fee_router::shared::crypto::HashAlgorithm;
 use anchor_lang::prelude::*;
+use super::test_fixtures::*;
 
 #[cfg(test)]
 mod state_transition_tests {
@@ -8,33 +10,9 @@ mod state_transition_tests {
 
     #[test]
     fn test_daily_distribution_state_initialization() {
-        let quote_mint = Pubkey::new_unique();
-        let treasury_ata = Pubkey::new_unique();
-        let distribution_day = 1672531200i64;
-        
-        let state = DailyDistributionState {
-            distribution_day,
-            quote_mint,
-            treasury_ata,
-            total_amount_to_distribute: 100_000,
-            amount_distributed: 0,
-            current_cursor: 0,
-            total_investors: 50,
-            investors_processed: 0,
-            is_complete: false,
-            started_at: distribution_day,
-            completed_at: 0,
-            dust_carried_over: 0,
-            daily_cap_total: 1_000_000,
-            daily_cap_remaining: 1_000_000,
-            min_payout_threshold: 1000,
-            initial_total_deposit: 2_000_000,
-            investor_fee_share_bps: 5000,
-            last_page_hash: [0; 32],
-            pages_processed: 0,
-            failed_payouts_count: 0,
-            reserved: [0; 20],
-        };
+        let state = fixture_daily_distribution_state()
+            .with_amount_to_distribute(100_000)
+            .with_total_investors(50);
 
         // Test initial state
         assert!(!state.is_complete);
@@ -47,7 +25,7 @@ mod state_transition_tests {
 
     #[test]
     fn test_distribution_progress_updates() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         
         // Test progress update
         let page_hash = [1u8; 32];
@@ -73,7 +51,7 @@ mod state_transition_tests {
 
     #[test]
     fn test_daily_cap_management() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         
         // Test cap checking
         assert!(state.can_distribute(500_000));
@@ -90,7 +68,7 @@ mod state_transition_tests {
 
     #[test]
     fn test_dust_management() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         
         // Test dust accumulation
         state.add_dust(150);
@@ -104,24 +82,9 @@ mod state_transition_tests {
         assert_eq!(effective, 100_000 + 225);
     }
 
-    #[test]
-    fn test_failed_payout_tracking() {
-        let mut state = create_test_daily_state();
-        
-        // Test failed payout tracking
-        assert!(!state.has_failed_payouts());
-        
-        state.add_failed_payouts(3);
-        assert!(state.has_failed_payouts());
-        assert_eq!(state.failed_payouts_count, 3);
-        
-        state.add_failed_payouts(2);
-        assert_eq!(state.failed_payouts_count, 5);
-    }
-
     #[test]
     fn test_completion_state_transitions() {
-        let mut state = create_test_daily_state();
+        let mut state = fixture_daily_distribution_state();
         let completion_time = 1672617600i64;
         
         // Process all investors
@@ -134,9 +97,46 @@ mod state_transition_tests {
         assert_eq!(state.completed_at, completion_time);
     }
 
+    #[test]
+    fn test_processed_bitmap_out_of_order() {
+        let mut state = fixture_daily_distribution_state().with_total_investors(20);
+
+        // Process the second half first
+        for index in 10..20 {
+            assert!(state.mark_investor_processed(index));
+        }
+        assert!(state.is_investor_processed(15));
+        assert!(!state.is_investor_processed(5));
+        assert!(state.has_more_investors());
+
+        // Retrying an already-processed investor is a no-op
+        assert!(!state.mark_investor_processed(12));
+
+        // Process a single investor out of order, then the rest
+        assert!(state.mark_investor_processed(3));
+        assert!(state.has_more_investors());
+        for index in 0..10 {
+            state.mark_investor_processed(index);
+        }
+        assert!(!state.has_more_investors());
+
+        // Indices beyond the day's investors are rejected
+        assert!(!state.mark_investor_processed(20));
+        assert!(!state.has_more_investors());
+
+        // Days larger than a fixed-size bitmap are tracked to the last investor
+        let mut state = fixture_daily_distribution_state().with_total_investors(5_000);
+        for index in 0..4_999 {
+            assert!(state.mark_investor_processed(index));
+        }
+        assert!(state.has_more_investors());
+        assert!(state.mark_investor_processed(4_999));
+        assert!(!state.has_more_investors());
+    }
+
     #[test]
     fn test_idempotency_page_validation() {
-        let state = create_test_daily_state();
+        let state = fixture_daily_distribution_state();
         let investor_accounts = vec![
             Pubkey::new_unique(),
             Pubkey::new_unique(),
@@ -144,8 +144,8 @@ mod state_transition_tests {
         ];
         
         // Test page hash calculation
-        let hash1 = DailyDistributionState::calculate_page_hash(&investor_accounts);
-        let hash2 = DailyDistributionState::calculate_page_hash(&investor_accounts);
+        let hash1 = DailyDistributionState::calculate_page_hash(&investor_accounts, HashAlgorithm::Sha256);
+        let hash2 = DailyDistributionState::calculate_page_hash(&investor_accounts, HashAlgorithm::Sha256);
         assert_eq!(hash1, hash2); // Should be deterministic
         
         // Test different accounts produce different hashes
@@ -153,27 +153,130 @@ mod state_transition_tests {
             Pubkey::new_unique(),
             Pubkey::new_unique(),
         ];
-        let hash3 = DailyDistributionState::calculate_page_hash(&different_accounts);
+        let hash3 = DailyDistributionState::calculate_page_hash(&different_accounts, HashAlgorithm::Sha256);
         assert_ne!(hash1, hash3);
         
         // Test page not already processed
         assert!(!state.is_page_already_processed(&hash1));
         
         // Test validation passes for new page
-        assert!(state.validate_page_for_retry(&investor_accounts).is_ok());
+        assert!(state.validate_page_for_retry(&investor_accounts, HashAlgorithm::Sha256).is_ok());
     }
 
     #[test]
-    fn test_policy_state_validation() {
-        let mut policy = PolicyState {
-            quote_mint: Pubkey::new_unique(),
-            investor_fee_share_bps: 5000,
-            daily_cap_lamports: 1_000_000,
-            min_payout_lamports: 1000,
-            y0_total_allocation: 2_000_000,
-            policy_authority: Pubkey::new_unique(),
-            reserved: [0; 64],
+    fn test_previous_pages_cannot_be_replayed() {
+        let mut state = fixture_daily_distribution_state();
+        let pages: Vec<Vec<Pubkey>> = (0..5)
+            .map(|_| (0..10).map(|_| Pubkey::new_unique()).collect())
+            .collect();
+
+        for page in pages.iter() {
+            assert!(state.validate_page_for_retry(page, HashAlgorithm::Sha256).is_ok());
+            let page_hash = DailyDistributionState::calculate_page_hash(page, HashAlgorithm::Sha256);
+            state.update_page_state(page_hash, page.len() as u32, 0);
+        }
+
+        // Any of the last 4 pages is rejected
+        for page in pages[1..].iter() {
+            assert!(state.validate_page_for_retry(page, HashAlgorithm::Sha256).is_err());
+        }
+
+        // Older pages have left the history
+        assert!(state.validate_page_for_retry(&pages[0], HashAlgorithm::Sha256).is_ok());
+
+        // Page start cursors are kept most recent first
+        assert_eq!(state.page_cursor_history, [40, 30, 20, 10, 0, 0, 0, 0]);
+        assert_eq!(state.current_cursor, 50);
+    }
+
+    #[test]
+    fn test_pages_must_follow_cursor() {
+        use meteora_fee_router::errors::FeeRouterError;
+
+        let mut state = fixture_daily_distribution_state().with_total_investors(25);
+        let page_index_error: anchor_lang::error::Error = FeeRouterError::InvalidPageIndex.into();
+
+        // Pages submitted ahead of the cursor are rejected
+        assert_eq!(state.validate_page_order(1, 10).unwrap_err(), page_index_error);
+        assert!(state.validate_page_order(0, 10).is_ok());
+        state.update_page_state([1; 32], 10, 0);
+
+        // Replaying page 0 or skipping to page 2 is rejected
+        assert_eq!(state.validate_page_order(0, 10).unwrap_err(), page_index_error);
+        assert_eq!(state.validate_page_order(2, 10).unwrap_err(), page_index_error);
+        assert!(state.validate_page_order(1, 10).is_ok());
+        state.update_page_state([2; 32], 10, 0);
+        assert_eq!(state.pages_processed, 2);
+
+        // The last page may not run past the investor set
+        assert_eq!(state.validate_page_order(2, 10).unwrap_err(), FeeRouterError::PaginationError.into());
+        assert!(state.validate_page_order(2, 5).is_ok());
+    }
+
+    #[test]
+    fn test_page_hash_algorithm_is_configurable() {
+        use anchor_lang::solana_program::{hash, keccak};
+
+        let investor_accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let data: Vec<u8> = investor_accounts.iter().flat_map(|account| account.to_bytes()).collect();
+
+        let sha256 = DailyDistributionState::calculate_page_hash(&investor_accounts, HashAlgorithm::Sha256);
+        let keccak256 = DailyDistributionState::calculate_page_hash(&investor_accounts, HashAlgorithm::Keccak256);
+        assert_eq!(sha256, hash::hash(&data).to_bytes());
+        assert_eq!(keccak256, keccak::hash(&data).to_bytes());
+        assert_ne!(sha256, keccak256);
+
+        // Policies default to SHA-256, including older ones with a zeroed byte
+        assert_eq!(fixture_policy_state().page_hash_algorithm, HashAlgorithm::default());
+        assert_eq!(HashAlgorithm::try_from_slice(&[0]).unwrap(), HashAlgorithm::Sha256);
+
+        // A retry is only detected with the algorithm that hashed the page
+        let mut state = fixture_daily_distribution_state();
+        state.update_page_state(keccak256, 2, 0);
+        assert!(state.validate_page_for_retry(&investor_accounts, HashAlgorithm::Keccak256).is_err());
+        assert!(state.validate_page_for_retry(&investor_accounts, HashAlgorithm::Sha256).is_ok());
+    }
+
+    #[test]
+    fn test_payouts_hash_reconstructed_off_chain() {
+        use meteora_fee_router::integrations::streamflow::calculations::InvestorPayout;
+        use anchor_lang::solana_program::hash::hash;
+
+        let payout = |investor: Pubkey, payout_amount: u64, meets_minimum: bool| InvestorPayout {
+            investor,
+            investor_ata: Pubkey::new_unique(),
+            payout_amount,
+            weight_bps: 0,
+            meets_minimum,
         };
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let payouts = vec![
+            payout(first, 1_500, true),
+            payout(Pubkey::new_unique(), 0, true),
+            payout(Pubkey::new_unique(), 400, false),
+            payout(second, 2_000, true),
+        ];
+
+        // Verifier rebuilds the hash from the paid (investor, amount) pairs only
+        let mut expected = Vec::new();
+        for (investor, amount) in [(first, 1_500u64), (second, 2_000u64)] {
+            expected.extend_from_slice(investor.as_ref());
+            expected.extend_from_slice(&amount.to_le_bytes());
+        }
+        assert_eq!(DailyDistributionState::calculate_payouts_hash(&payouts, HashAlgorithm::Sha256), hash(&expected).to_bytes());
+
+        // A different amount changes the hash
+        let mut tampered = payouts.clone();
+        tampered[3].payout_amount = 2_001;
+        assert_ne!(
+            DailyDistributionState::calculate_payouts_hash(&tampered, HashAlgorithm::Sha256),
+            DailyDistributionState::calculate_payouts_hash(&payouts, HashAlgorithm::Sha256)
+        );
+    }
+
+    #[test]
+    fn test_policy_state_validation() {
+        let mut policy = fixture_policy_state();
         
         // Test valid policy
         assert!(policy.validate().is_ok());
@@ -186,29 +289,168 @@ mod state_transition_tests {
         policy.investor_fee_share_bps = 5000;
         policy.y0_total_allocation = 0;
         assert!(policy.validate().is_err());
+
+        // Test investor + creator share exceeding 100%
+        policy.y0_total_allocation = 2_000_000;
+        policy.creator_fee_share_bps = 5001;
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_rejects_nonsensical_ranges() {
+        use meteora_fee_router::errors::FeeRouterError;
+        use meteora_fee_router::modules::distribution::state::PolicyState;
+
+        let expect = |policy: &PolicyState, error: FeeRouterError| {
+            assert_eq!(policy.validate().unwrap_err(), error.into());
+        };
+
+        // Minimum payout above the daily cap
+        let mut policy = fixture_policy_state();
+        policy.min_payout_lamports = 1_000_000_000;
+        policy.daily_cap_lamports = 100;
+        expect(&policy, FeeRouterError::MinPayoutExceedsDailyCap);
+        policy.min_payout_lamports = 100;
+        expect(&policy, FeeRouterError::MinPayoutExceedsDailyCap);
+
+        // No cap places no upper bound on the minimum payout
+        policy.min_payout_lamports = 1_000_000;
+        policy.daily_cap_lamports = 0;
+        assert!(policy.validate().is_ok());
+
+        let mut policy = fixture_policy_state();
+        policy.daily_cap_lamports = u64::MAX / 2;
+        expect(&policy, FeeRouterError::DailyCapTooLarge);
+
+        let mut policy = fixture_policy_state();
+        policy.y0_total_allocation = policy.min_payout_lamports - 1;
+        expect(&policy, FeeRouterError::Y0BelowMinPayout);
+
+        // The creator keeps at least 1%
+        let mut policy = fixture_policy_state().with_fee_shares(9901, 0);
+        expect(&policy, FeeRouterError::CreatorShareBelowMinimum);
+        policy.investor_fee_share_bps = 9900;
+        assert!(policy.validate().is_ok());
+    }
+
+    #[test]
+    fn test_policy_format_upgrade_preserves_data() {
+        use meteora_fee_router::modules::distribution::state::{migrate_v1_to_v2, PolicyState, PolicyStateV1};
+
+        // The original 168-byte layout cannot be loaded as the current one
+        let quote_mint = Pubkey::new_unique();
+        let policy_authority = Pubkey::new_unique();
+        let v1_data = v1_policy_account_data(&quote_mint, &policy_authority);
+        assert_eq!(v1_data.len(), PolicyStateV1::LEN);
+        assert_eq!(PolicyState::stored_format_version(&v1_data).unwrap(), 1);
+        assert!(PolicyState::try_deserialize(&mut v1_data.as_slice()).is_err());
+
+        // Every v1 field is carried over; the creator keeps its implicit remainder
+        let v1_policy = PolicyStateV1::try_from_account_data(&v1_data).unwrap();
+        let policy = migrate_v1_to_v2(&v1_policy, 254);
+        assert_eq!(policy.quote_mint, quote_mint);
+        assert_eq!(policy.investor_fee_share_bps, 7000);
+        assert_eq!(policy.creator_fee_share_bps, 3000);
+        assert_eq!(policy.daily_cap_lamports, 5_000_000);
+        assert_eq!(policy.min_payout_lamports, 1000);
+        assert_eq!(policy.y0_total_allocation, 2_000_000);
+        assert_eq!(policy.policy_authority, policy_authority);
+        assert_eq!(policy.bump, 254);
+        assert_eq!(policy.format_version(), 2);
+        assert!(policy.validate().is_ok());
+
+        // The migrated account has the current layout
+        let mut v2_data = Vec::new();
+        policy.try_serialize(&mut v2_data).unwrap();
+        assert_eq!(v2_data.len(), PolicyState::INIT_SPACE);
+        assert_eq!(PolicyState::stored_format_version(&v2_data).unwrap(), 2);
+
+        // Data with another discriminator is not a v1 policy
+        let mut other_data = v1_data.clone();
+        other_data[0] ^= 1;
+        assert!(PolicyStateV1::try_from_account_data(&other_data).is_err());
+    }
+
+    #[test]
+    fn test_policy_creator_amount() {
+        let mut policy = fixture_policy_state();
+        policy.creator_fee_share_bps = 3000;
+
+        // 30% of 10_001 = 3000.3 -> floor to 3000
+        assert_eq!(policy.calculate_creator_amount(10_001), 3000);
+
+        // Zero creator share sends nothing to creator
+        policy.creator_fee_share_bps = 0;
+        assert_eq!(policy.calculate_creator_amount(10_000), 0);
+    }
+
+    #[test]
+    fn test_policy_protocol_fee_split() {
+        let mut policy = fixture_policy_state();
+        policy.investor_fee_share_bps = 5000;
+        policy.creator_fee_share_bps = 3000;
+        policy.protocol_fee_bps = 200;
+
+        // Wallet must be set once a protocol cut is configured
+        assert!(policy.validate().is_err());
+        policy.protocol_fee_wallet = Pubkey::new_unique();
+        assert!(policy.validate().is_ok());
+
+        // 2% protocol cut alongside 50% investors and 30% creator
+        let total = 1_000_000u64;
+        let investor_amount = total * policy.investor_fee_share_bps / 10000;
+        let protocol_amount = policy.calculate_protocol_fee_amount(total);
+        let creator_amount = policy.calculate_creator_amount(total);
+        assert_eq!(investor_amount, 500_000);
+        assert_eq!(protocol_amount, 20_000);
+        assert_eq!(creator_amount, 300_000);
+        assert_eq!(total - investor_amount - protocol_amount - creator_amount, 180_000);
+
+        // All three shares together cannot exceed 100%
+        policy.protocol_fee_bps = 2001;
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_fee_share_ramp() {
+        use meteora_fee_router::errors::FeeRouterError;
+
+        let ramp_start = 1672531200i64;
+        let one_year = 365 * 86400i64;
+        let mut policy = fixture_policy_state();
+        policy.investor_fee_share_bps = 9900;
+        policy.creator_fee_share_bps = 0;
+        policy.ramp_start_bps = 8000;
+        policy.ramp_end_bps = 4000;
+        policy.ramp_duration_seconds = one_year;
+        policy.ramp_start_timestamp = ramp_start;
+        assert!(policy.validate().is_ok());
+
+        // Share decreases from 80% to 40% over 365 days
+        assert_eq!(policy.fee_share_ramp(ramp_start - 1), 8000);
+        assert_eq!(policy.fee_share_ramp(ramp_start), 8000);
+        assert_eq!(policy.fee_share_ramp(ramp_start + one_year / 2), 6000);
+        assert_eq!(policy.fee_share_ramp(ramp_start + one_year), 4000);
+        assert_eq!(policy.fee_share_ramp(ramp_start + one_year * 2), 4000);
+
+        // investor_fee_share_bps caps the ramp
+        policy.investor_fee_share_bps = 5000;
+        assert_eq!(policy.fee_share_ramp(ramp_start), 5000);
+        assert_eq!(policy.fee_share_ramp(ramp_start + one_year), 4000);
+
+        // Disabled ramp falls back to the flat share
+        policy.ramp_duration_seconds = 0;
+        assert_eq!(policy.fee_share_ramp(ramp_start), 5000);
+
+        policy.ramp_duration_seconds = -1;
+        assert_eq!(policy.validate().unwrap_err(), FeeRouterError::InvalidRampDuration.into());
     }
 
     #[test]
     fn test_streamflow_stream_calculations() {
         let current_time = 1672531200u64; // Jan 1, 2023
-        let stream = StreamflowStream {
-            magic: 0,
-            version: 1,
-            created_at: current_time - 86400, // Created 1 day ago
-            start_time: current_time - 3600,  // Started 1 hour ago
-            end_time: current_time + 86400,   // Ends in 1 day
-            deposited_amount: 100_000,
-            withdrawn_amount: 0,
-            recipient: Pubkey::new_unique(),
-            sender: Pubkey::new_unique(),
-            mint: Pubkey::new_unique(),
-            escrow_tokens: Pubkey::new_unique(),
-            name: [0; 64],
-            can_cancel: true,
-            can_transfer: false,
-            cancelled: false,
-            metadata: [0; 128],
-        };
+        // Started 1 hour ago, ends in 1 day
+        let stream = fixture_streamflow_stream(current_time - 3600, current_time + 86400, 100_000);
         
         // Test unlocked amount calculation
         // Stream duration: 86400 + 3600 = 90000 seconds
@@ -228,38 +470,1695 @@ mod state_transition_tests {
         // Test stream is active
         assert!(stream.is_active(current_time));
         assert!(!stream.is_fully_vested(current_time));
+        assert_eq!(stream.locked_fraction_pct(current_time), 96);
         
         // Test fully vested scenario
         let future_time = current_time + 90000;
         assert_eq!(stream.unlocked_amount(future_time), 100_000);
         assert_eq!(stream.locked_amount(future_time), 0);
         assert!(stream.is_fully_vested(future_time));
+        assert_eq!(stream.locked_fraction_pct(future_time), 0);
+        
+        // Cliff vesting: 10_000 released at cliff_time, remaining 90_000 vests
+        // linearly from cliff_time to end_time
+        let start = current_time;
+        let cliff_stream = fixture_streamflow_stream(start, start + 100_000, 100_000)
+            .with_cliff(start + 10_000, 10_000);
+        
+        // Pre-start: nothing unlocked
+        assert_eq!(cliff_stream.unlocked_amount(start - 1), 0);
+        assert_eq!(cliff_stream.locked_amount(start - 1), 100_000);
+        
+        // Pre-cliff: still nothing unlocked
+        assert_eq!(cliff_stream.unlocked_amount(start + 5_000), 0);
+        assert_eq!(cliff_stream.locked_amount(start + 5_000), 100_000);
+        assert_eq!(cliff_stream.unlocked_amount(start + 9_999), 0);
+        assert_eq!(cliff_stream.locked_fraction_pct(start + 5_000), 100);
+        
+        // At cliff: the cliff amount unlocks, linear component starts from zero
+        assert_eq!(cliff_stream.unlocked_amount(start + 10_000), 10_000);
+        assert_eq!(cliff_stream.locked_amount(start + 10_000), 90_000);
+        
+        // Mid-vesting: cliff + half of the remainder
+        assert_eq!(cliff_stream.unlocked_amount(start + 55_000), 55_000);
+        assert_eq!(cliff_stream.locked_amount(start + 55_000), 45_000);
+        
+        // Post-end: fully unlocked
+        assert_eq!(cliff_stream.unlocked_amount(start + 100_000), 100_000);
+        assert_eq!(cliff_stream.unlocked_amount(start + 100_001), 100_000);
+        assert_eq!(cliff_stream.locked_amount(start + 100_001), 0);
+        
+        // The cliff counts as locked until it is released at the cliff
+        assert_eq!(cliff_stream.locked_cliff_amount(start - 1), 10_000);
+        assert_eq!(cliff_stream.locked_cliff_amount(start + 9_999), 10_000);
+        assert_eq!(cliff_stream.locked_cliff_amount(start + 10_000), 0);
+        
+        // Without a cliff time the cliff amount is released at start
+        let start_cliff_stream = fixture_streamflow_stream(start, start + 100_000, 100_000)
+            .with_cliff(0, 10_000);
+        assert_eq!(start_cliff_stream.unlocked_amount(start - 1), 0);
+        assert_eq!(start_cliff_stream.unlocked_amount(start), 10_000);
+        assert_eq!(start_cliff_stream.locked_cliff_amount(start), 0);
     }
 
-    // Helper function to create test state
-    fn create_test_daily_state() -> DailyDistributionState {
-        DailyDistributionState {
-            distribution_day: 1672531200,
+    #[test]
+    fn test_expired_stream_fails_validation() {
+        use meteora_fee_router::integrations::streamflow::cpi::validate_stream_account;
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        // Ended one second ago
+        let mut stream = fixture_streamflow_stream(current_time - 86400, current_time - 1, 100_000);
+        assert!(stream.is_cancelled_or_expired(current_time));
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert!(validate_stream_account(&account_info, current_time).is_err());
+
+        // A live stream passes validation
+        stream.end_time = current_time + 86400;
+        assert!(!stream.is_cancelled_or_expired(current_time));
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert!(validate_stream_account(&account_info, current_time).is_ok());
+
+        // Cancelled streams are rejected even before their end time
+        stream.cancelled = true;
+        assert!(stream.is_cancelled_or_expired(current_time));
+    }
+
+    #[test]
+    fn test_short_stream_excluded_by_min_remaining_lock() {
+        use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_amounts, count_streams_below_min_duration};
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        let quote_mint = Pubkey::new_unique();
+        // Ends in 6 days
+        let stream = fixture_streamflow_stream(current_time - 86400 * 30, current_time + 86400 * 6, 100_000)
+            .with_mint(quote_mint);
+        assert_eq!(stream.remaining_lock_seconds(current_time), 86400 * 6);
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let accounts = vec![account_info];
+
+        // Policy requires 30 days of remaining lock - stream is excluded
+        let min_remaining = 86400 * 30;
+        let (investor_data, total_locked) = calculate_locked_amounts(&accounts, current_time, &quote_mint, min_remaining).unwrap();
+        assert!(investor_data.is_empty());
+        assert_eq!(total_locked, 0);
+        assert_eq!(count_streams_below_min_duration(&accounts, current_time, min_remaining), 1);
+
+        // No filter - stream counts
+        let (investor_data, total_locked) = calculate_locked_amounts(&accounts, current_time, &quote_mint, 0).unwrap();
+        assert_eq!(investor_data.len(), 1);
+        assert!(total_locked > 0);
+        assert_eq!(count_streams_below_min_duration(&accounts, current_time, 0), 0);
+    }
+
+    #[test]
+    fn test_read_stream_data_parses_streamflow_layout() {
+        use meteora_fee_router::integrations::streamflow::cpi::read_stream_data;
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let escrow_tokens = Pubkey::new_unique();
+
+        // Raw Streamflow v1 `Contract` bytes, offsets as in their JS SDK `streamLayout`
+        let mut data = vec![0u8; 1104];
+        data[8] = 2; // version
+        data[9..17].copy_from_slice(&1_700_000_000u64.to_le_bytes()); // created_at
+        data[17..25].copy_from_slice(&25_000u64.to_le_bytes()); // withdrawn_amount
+        data[33..41].copy_from_slice(&1_731_536_000u64.to_le_bytes()); // end_time
+        data[49..81].copy_from_slice(sender.as_ref());
+        data[113..145].copy_from_slice(recipient.as_ref());
+        data[177..209].copy_from_slice(mint.as_ref());
+        data[209..241].copy_from_slice(escrow_tokens.as_ref());
+        data[409..417].copy_from_slice(&1_700_000_000u64.to_le_bytes()); // start_time
+        data[417..425].copy_from_slice(&1_000_000u64.to_le_bytes()); // net_amount_deposited
+        data[441..449].copy_from_slice(&1_702_592_000u64.to_le_bytes()); // cliff
+        data[449..457].copy_from_slice(&100_000u64.to_le_bytes()); // cliff_amount
+        data[457] = 1; // cancelable_by_sender
+        data[461] = 1; // transferable_by_recipient
+        data[463..469].copy_from_slice(b"seed-a");
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let stream = read_stream_data(&account_info).unwrap();
+
+        assert_eq!(stream.version, 2);
+        assert_eq!(stream.created_at, 1_700_000_000);
+        assert_eq!(stream.start_time, 1_700_000_000);
+        assert_eq!(stream.end_time, 1_731_536_000);
+        assert_eq!(stream.cliff_time, 1_702_592_000);
+        assert_eq!(stream.cliff_amount, 100_000);
+        assert_eq!(stream.deposited_amount, 1_000_000);
+        assert_eq!(stream.withdrawn_amount, 25_000);
+        assert_eq!(stream.sender, sender);
+        assert_eq!(stream.recipient, recipient);
+        assert_eq!(stream.mint, mint);
+        assert_eq!(stream.escrow_tokens, escrow_tokens);
+        assert_eq!(&stream.name[..6], b"seed-a");
+        assert!(stream.can_cancel);
+        assert!(stream.can_transfer);
+        assert!(!stream.cancelled);
+
+        // A cancelled stream has a non-zero canceled_at
+        data[25..33].copy_from_slice(&1_710_000_000u64.to_le_bytes());
+        let mut lamports = 0u64;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert!(read_stream_data(&account_info).unwrap().cancelled);
+
+        // Accounts smaller than a stream are rejected
+        let mut short_data = vec![0u8; 1103];
+        let mut lamports = 0u64;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut short_data, &owner, false, 0);
+        assert!(read_stream_data(&account_info).is_err());
+    }
+
+    #[test]
+    fn test_malformed_stream_reported_as_invalid_data() {
+        use meteora_fee_router::errors::FeeRouterError;
+        use meteora_fee_router::integrations::streamflow::accounts::{stream_layout, StreamflowStream};
+        use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_amounts_with_errors, StreamErrorType};
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        let quote_mint = Pubkey::new_unique();
+        let stream = fixture_streamflow_stream(current_time - 86400, current_time + 86400 * 30, 100_000)
+            .with_mint(quote_mint);
+        let data = streamflow_account_data(&stream);
+
+        // Truncated buffer - a clean error instead of an out-of-bounds read
+        let mut truncated = data[..stream_layout::START_TIME].to_vec();
+        assert_eq!(
+            StreamflowStream::try_from_account_data(&truncated).unwrap_err(),
+            FeeRouterError::InvalidStreamAccountData.into()
+        );
+
+        // Same size as a stream but with a foreign header (e.g. an Anchor discriminator)
+        let mut foreign = data.clone();
+        foreign[stream_layout::MAGIC..stream_layout::MAGIC + 8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            StreamflowStream::try_from_account_data(&foreign).unwrap_err(),
+            FeeRouterError::InvalidStreamAccountData.into()
+        );
+
+        // Both are logged as invalid stream data while valid streams still count
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = [0u64; 3];
+        let mut valid = data;
+        let [truncated_lamports, foreign_lamports, valid_lamports] = &mut lamports;
+        let accounts = vec![
+            AccountInfo::new(&keys[0], false, false, truncated_lamports, &mut truncated, &owner, false, 0),
+            AccountInfo::new(&keys[1], false, false, foreign_lamports, &mut foreign, &owner, false, 0),
+            AccountInfo::new(&keys[2], false, false, valid_lamports, &mut valid, &owner, false, 0),
+        ];
+        let (investor_data, total_locked, errors) =
+            calculate_locked_amounts_with_errors(&accounts, current_time, &quote_mint, 0, None).unwrap();
+        assert_eq!(investor_data.len(), 1);
+        assert!(total_locked > 0);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| error.error_type == StreamErrorType::InvalidStreamData));
+        assert_eq!(errors[0].stream_account, keys[0]);
+        assert_eq!(errors[1].stream_account, keys[1]);
+    }
+
+    #[test]
+    fn test_estimate_claimable_fees_matches_claim() {
+        use meteora_fee_router::integrations::meteora::accounts::{CollectFeeMode, Pool};
+        use meteora_fee_router::integrations::meteora::cpi::{estimate_claimable_fees, position_layout, POSITION_ACCOUNT_SIZE};
+        use meteora_fee_router::integrations::meteora::METEORA_CP_AMM_PROGRAM_ID;
+
+        let u256 = |value: u128| -> [u8; 32] {
+            let mut bytes = [0u8; 32];
+            bytes[..16].copy_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        // Quote (token B) fee per liquidity grew by 250_000 + 2^-64 since the checkpoint
+        let fee_b_checkpoint = u64::MAX as u128;
+        let fee_b_growth = (250_000u128 << 64) + 1;
+        let fee_a_growth = 10u128 << 64;
+        let pool_key = Pubkey::new_unique();
+        let mut pool: Pool = bytemuck::Zeroable::zeroed();
+        pool.collect_fee_mode = CollectFeeMode::OnlyTokenB as u8;
+        pool.fee_a_per_liquidity = u256(fee_a_growth);
+        pool.fee_b_per_liquidity = u256(fee_b_checkpoint + fee_b_growth);
+        let mut pool_data = vec![0u8; 8];
+        pool_data.extend_from_slice(bytemuck::bytes_of(&pool));
+
+        // 3.0 liquidity (Q64.64) split across unlocked and permanently locked, 1_000 quote pending
+        let mut position_data = vec![0u8; POSITION_ACCOUNT_SIZE];
+        position_data[position_layout::POOL..position_layout::POOL + 32].copy_from_slice(pool_key.as_ref());
+        position_data[position_layout::FEE_B_PER_TOKEN_CHECKPOINT..position_layout::FEE_B_PER_TOKEN_CHECKPOINT + 32]
+            .copy_from_slice(&u256(fee_b_checkpoint));
+        position_data[position_layout::FEE_B_PENDING..position_layout::FEE_B_PENDING + 8].copy_from_slice(&1_000u64.to_le_bytes());
+        position_data[position_layout::UNLOCKED_LIQUIDITY..position_layout::UNLOCKED_LIQUIDITY + 16]
+            .copy_from_slice(&(2u128 << 64).to_le_bytes());
+        position_data[position_layout::PERMANENT_LOCKED_LIQUIDITY..position_layout::PERMANENT_LOCKED_LIQUIDITY + 16]
+            .copy_from_slice(&(1u128 << 64).to_le_bytes());
+
+        let owner = METEORA_CP_AMM_PROGRAM_ID;
+        let position_key = Pubkey::new_unique();
+        let (mut pool_lamports, mut position_lamports) = (0u64, 0u64);
+        let pool_info = AccountInfo::new(&pool_key, false, false, &mut pool_lamports, &mut pool_data, &owner, false, 0);
+        let position_info = AccountInfo::new(&position_key, false, false, &mut position_lamports, &mut position_data, &owner, false, 0);
+
+        // CP-AMM pays pending + liquidity * growth >> 128
+        let claimed_quote = 1_000 + 3 * 250_000;
+        let (estimated_quote, estimated_base) = estimate_claimable_fees(&pool_info, &position_info).unwrap();
+        assert!(estimated_quote.abs_diff(claimed_quote) * 20 <= claimed_quote);
+        assert_eq!(estimated_quote, claimed_quote);
+        assert_eq!(estimated_base, 30);
+
+        // Accounts that are not CP-AMM accounts cannot be estimated
+        let other_owner = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![0u8; POSITION_ACCOUNT_SIZE];
+        let foreign = AccountInfo::new(&position_key, false, false, &mut lamports, &mut data, &other_owner, false, 0);
+        assert!(estimate_claimable_fees(&pool_info, &foreign).is_err());
+    }
+
+    #[test]
+    fn test_governance_proposal_validation() {
+        use meteora_fee_router::shared::governance::{
+            GovernanceProposal, ProposalInstruction, ProposalTransaction, ValidateGovernanceProposal,
+            PROPOSAL_ACCOUNT_TYPE, PROPOSAL_STATE_COMPLETED, PROPOSAL_TRANSACTION_ACCOUNT_TYPE, SPL_GOVERNANCE_PROGRAM_ID,
+        };
+
+        assert_eq!(SPL_GOVERNANCE_PROGRAM_ID.to_string(), "GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+        let program_id = Pubkey::new_unique();
+        let governance = Pubkey::new_unique();
+        let update_data = vec![1u8, 2, 3, 4];
+        let proposal_key = Pubkey::new_unique();
+        let transaction_key = Pubkey::new_unique();
+        let proposal = GovernanceProposal {
+            account_type: PROPOSAL_ACCOUNT_TYPE,
+            governance,
+            governing_token_mint: Pubkey::new_unique(),
+            state: PROPOSAL_STATE_COMPLETED,
+        };
+        let transaction = ProposalTransaction {
+            account_type: PROPOSAL_TRANSACTION_ACCOUNT_TYPE,
+            proposal: proposal_key,
+            option_index: 0,
+            transaction_index: 0,
+            hold_up_time: 0,
+            instructions: vec![ProposalInstruction { program_id, accounts: Vec::new(), data: update_data.clone() }],
+            executed_at: Some(1_700_000_000),
+            execution_status: 1,
+            reserved_v2: [0; 8],
+        };
+
+        let validate = |proposal: &GovernanceProposal, owner: Pubkey, data: &[u8]| {
+            let mut proposal_data = proposal.try_to_vec().unwrap();
+            // Real proposal accounts carry more fields after the ones read
+            proposal_data.extend_from_slice(&[0u8; 64]);
+            let mut transaction_data = transaction.try_to_vec().unwrap();
+            let (mut proposal_lamports, mut transaction_lamports) = (0u64, 0u64);
+            let proposal_info = AccountInfo::new(&proposal_key, false, false, &mut proposal_lamports, &mut proposal_data, &owner, false, 0);
+            let transaction_info = AccountInfo::new(&transaction_key, false, false, &mut transaction_lamports, &mut transaction_data, &owner, false, 0);
+            ValidateGovernanceProposal::validate(&governance, Some(&proposal_info), Some(&transaction_info), &program_id, data)
+        };
+
+        // Direct authority mode skips governance entirely
+        assert!(ValidateGovernanceProposal::validate(&Pubkey::default(), None, None, &program_id, &update_data).is_ok());
+
+        // Governance mode requires a completed proposal of the governance carrying the update
+        assert!(validate(&proposal, SPL_GOVERNANCE_PROGRAM_ID, &update_data).is_ok());
+        assert!(ValidateGovernanceProposal::validate(&governance, None, None, &program_id, &update_data).is_err());
+        assert!(validate(&proposal, SPL_GOVERNANCE_PROGRAM_ID, &[9]).is_err());
+        assert!(validate(&proposal, Pubkey::new_unique(), &update_data).is_err());
+        assert!(validate(&GovernanceProposal { state: PROPOSAL_STATE_COMPLETED - 1, ..proposal.clone() }, SPL_GOVERNANCE_PROGRAM_ID, &update_data).is_err());
+        assert!(validate(&GovernanceProposal { governance: Pubkey::new_unique(), ..proposal.clone() }, SPL_GOVERNANCE_PROGRAM_ID, &update_data).is_err());
+        assert!(validate(&GovernanceProposal { account_type: PROPOSAL_TRANSACTION_ACCOUNT_TYPE, ..proposal }, SPL_GOVERNANCE_PROGRAM_ID, &update_data).is_err());
+    }
+
+    #[test]
+    fn test_remove_liquidity_instruction_data() {
+        use meteora_fee_router::integrations::meteora::cpi::RemoveLiquidityParameters;
+
+        let params = RemoveLiquidityParameters {
+            liquidity_delta: (1u128 << 64) + 2,
+            token_a_amount_threshold: 3,
+            token_b_amount_threshold: u64::MAX - 4,
+        };
+        let data = params.instruction_data();
+
+        // sha256("global:remove_liquidity")[..8], then u128 + u64 + u64 little-endian
+        assert_eq!(data.len(), 8 + 16 + 8 + 8);
+        assert_eq!(data[..8], [80, 85, 209, 72, 24, 206, 177, 108]);
+        assert_eq!(data[8..24], [2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(data[24..32], 3u64.to_le_bytes());
+        assert_eq!(data[32..40], [0xfb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_claim_token_sides_follow_pool_order() {
+        use meteora_fee_router::integrations::meteora::cpi::{order_claim_token_sides, read_pool, ClaimTokenSide};
+        use meteora_fee_router::integrations::meteora::METEORA_CP_AMM_PROGRAM_ID;
+
+        let quote_mint = Pubkey::new_unique();
+        let base_mint = Pubkey::new_unique();
+        let quote_vault = Pubkey::new_unique();
+        let base_vault = Pubkey::new_unique();
+        let quote_ata = Pubkey::new_unique();
+        let base_ata = Pubkey::new_unique();
+        let other_vault = Pubkey::new_unique();
+        let token_owner = anchor_spl::token::ID;
+        let mut lamports = [0u64; 7];
+        let mut data: [Vec<u8>; 7] = Default::default();
+        let [quote_ata_lamports, base_ata_lamports, quote_vault_lamports, base_vault_lamports, quote_mint_lamports, base_mint_lamports, other_vault_lamports] = &mut lamports;
+        let [quote_ata_data, base_ata_data, quote_vault_data, base_vault_data, quote_mint_data, base_mint_data, other_vault_data] = &mut data;
+        let quote = ClaimTokenSide {
+            token_account: AccountInfo::new(&quote_ata, false, true, quote_ata_lamports, quote_ata_data, &token_owner, false, 0),
+            vault: AccountInfo::new(&quote_vault, false, true, quote_vault_lamports, quote_vault_data, &token_owner, false, 0),
+            mint: AccountInfo::new(&quote_mint, false, false, quote_mint_lamports, quote_mint_data, &token_owner, false, 0),
+        };
+        let base = ClaimTokenSide {
+            token_account: AccountInfo::new(&base_ata, false, true, base_ata_lamports, base_ata_data, &token_owner, false, 0),
+            vault: AccountInfo::new(&base_vault, false, true, base_vault_lamports, base_vault_data, &token_owner, false, 0),
+            mint: AccountInfo::new(&base_mint, false, false, base_mint_lamports, base_mint_data, &token_owner, false, 0),
+        };
+        let keys = |side: &ClaimTokenSide| (side.token_account.key(), side.vault.key(), side.mint.key());
+
+        // Quote is token A - sides keep their order
+        let pool_key = Pubkey::new_unique();
+        let owner = METEORA_CP_AMM_PROGRAM_ID;
+        let mut pool_lamports = 0u64;
+        let mut pool_data = meteora_pool_account_data(&fixture_meteora_pool(quote_mint, base_mint, quote_vault, base_vault));
+        let pool_info = AccountInfo::new(&pool_key, false, true, &mut pool_lamports, &mut pool_data, &owner, false, 0);
+        let pool = read_pool(&pool_info).unwrap();
+        let (token_a, token_b) = order_claim_token_sides(&pool, quote.clone(), base.clone()).unwrap();
+        assert_eq!(keys(&token_a), (quote_ata, quote_vault, quote_mint));
+        assert_eq!(keys(&token_b), (base_ata, base_vault, base_mint));
+
+        // Quote is token B - the base side goes first
+        let pool = fixture_meteora_pool(base_mint, quote_mint, base_vault, quote_vault);
+        let (token_a, token_b) = order_claim_token_sides(&pool, quote.clone(), base.clone()).unwrap();
+        assert_eq!(keys(&token_a), (base_ata, base_vault, base_mint));
+        assert_eq!(keys(&token_b), (quote_ata, quote_vault, quote_mint));
+
+        // Vaults that do not belong to the pool are rejected
+        let pool = fixture_meteora_pool(quote_mint, base_mint, quote_vault, base_vault);
+        let foreign_vault = ClaimTokenSide {
+            vault: AccountInfo::new(&other_vault, false, true, other_vault_lamports, other_vault_data, &token_owner, false, 0),
+            ..quote.clone()
+        };
+        assert!(order_claim_token_sides(&pool, foreign_vault, base.clone()).is_err());
+
+        // So are swapped vaults and pools without the quote mint
+        assert!(order_claim_token_sides(&pool, ClaimTokenSide { vault: base.vault.clone(), ..quote.clone() }, base.clone()).is_err());
+        let pool = fixture_meteora_pool(Pubkey::new_unique(), base_mint, quote_vault, base_vault);
+        assert!(order_claim_token_sides(&pool, quote, base).is_err());
+    }
+
+    #[test]
+    fn test_cross_mint_streams_filtered() {
+        use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_amounts, validate_stream_mint};
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        let quote_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let stream = fixture_streamflow_stream(current_time - 86400, current_time + 86400 * 365, 100_000)
+            .with_mint(other_mint);
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert!(validate_stream_mint(&account_info, &other_mint).is_ok());
+        assert!(validate_stream_mint(&account_info, &quote_mint).is_err());
+
+        // A stream of another mint does not count towards the distribution
+        let accounts = vec![account_info];
+        let (investor_data, total_locked) = calculate_locked_amounts(&accounts, current_time, &quote_mint, 0).unwrap();
+        assert!(investor_data.is_empty());
+        assert_eq!(total_locked, 0);
+    }
+
+    #[test]
+    fn test_stream_with_supplied_investor_ata() {
+        use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_amounts_with_errors, derive_investor_ata};
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        let quote_mint = Pubkey::new_unique();
+        let stream = fixture_streamflow_stream(current_time - 86400, current_time + 86400 * 365, 100_000)
+            .with_mint(quote_mint);
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let accounts = vec![account_info];
+
+        // The payout target is the recipient's quote ATA, not the wallet itself
+        let investor_ata = derive_investor_ata(&stream.recipient, &quote_mint);
+        assert_ne!(investor_ata, stream.recipient);
+
+        let supplied_atas = vec![Pubkey::new_unique(), investor_ata];
+        let (investor_data, total_locked, errors) =
+            calculate_locked_amounts_with_errors(&accounts, current_time, &quote_mint, 0, Some(&supplied_atas)).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(investor_data.len(), 1);
+        assert_eq!(investor_data[0].investor_ata, investor_ata);
+        assert!(total_locked > 0);
+    }
+
+    #[test]
+    fn test_stream_with_missing_investor_ata() {
+        use meteora_fee_router::integrations::streamflow::cpi::{calculate_locked_amounts_with_errors, StreamErrorType};
+        use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+
+        let current_time = 1672531200u64;
+        let quote_mint = Pubkey::new_unique();
+        let stream = fixture_streamflow_stream(current_time - 86400, current_time + 86400 * 365, 100_000)
+            .with_mint(quote_mint);
+
+        let key = Pubkey::new_unique();
+        let owner = STREAMFLOW_PROGRAM_ID;
+        let mut lamports = 0u64;
+        let mut data = streamflow_account_data(&stream);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let accounts = vec![account_info];
+
+        // Only the wallet is supplied - the stream is reported instead of counted
+        let supplied_atas = vec![stream.recipient];
+        let (investor_data, total_locked, errors) =
+            calculate_locked_amounts_with_errors(&accounts, current_time, &quote_mint, 0, Some(&supplied_atas)).unwrap();
+        assert!(investor_data.is_empty());
+        assert_eq!(total_locked, 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_type, StreamErrorType::MissingInvestorAta);
+        assert_eq!(errors[0].stream_account, key);
+        assert_eq!(errors[0].investor, Some(stream.recipient));
+    }
+
+    #[test]
+    fn test_treasury_min_claim_interval() {
+        use meteora_fee_router::modules::claiming::state::TreasuryState;
+
+        let mut treasury = fixture_treasury_state();
+        treasury.min_claim_interval_seconds = TreasuryState::resolve_min_claim_interval(600).unwrap();
+        treasury.last_claim_timestamp = 1_000_000;
+        let interval = treasury.min_claim_interval_seconds;
+
+        // Interval not elapsed
+        assert!(!treasury.can_claim(1_000_599, interval));
+
+        // Exactly elapsed
+        assert!(treasury.can_claim(1_000_600, interval));
+
+        // Zero keeps the one hour default, negative intervals are rejected
+        treasury.min_claim_interval_seconds = 0;
+        assert_eq!(treasury.effective_min_claim_interval().unwrap(), 3600);
+        assert!(!treasury.can_claim(1_003_599, treasury.effective_min_claim_interval().unwrap()));
+        assert!(treasury.can_claim(1_003_600, treasury.effective_min_claim_interval().unwrap()));
+        assert_eq!(TreasuryState::resolve_min_claim_interval(0).unwrap(), 3600);
+        assert!(TreasuryState::resolve_min_claim_interval(-1).is_err());
+    }
+
+    #[test]
+    fn test_treasury_reserve_ratio() {
+        let mut treasury = fixture_treasury_state().with_reserve_ratio(2000); // 20% reserve
+
+        // Only 80% of the balance is available for distribution
+        assert_eq!(treasury.calculate_distributable_amount(1_000_000), 800_000);
+
+        // No reserve - full balance
+        treasury.reserve_ratio_bps = 0;
+        assert_eq!(treasury.calculate_distributable_amount(1_000_000), 1_000_000);
+
+        // Full reserve - nothing distributable
+        treasury.reserve_ratio_bps = 10000;
+        assert_eq!(treasury.calculate_distributable_amount(1_000_000), 0);
+    }
+
+    #[test]
+    fn test_rolling_average_distribution_rate() {
+        use meteora_fee_router::modules::distribution::state::GlobalDistributionState;
+
+        let mut global = GlobalDistributionState {
             quote_mint: Pubkey::new_unique(),
-            treasury_ata: Pubkey::new_unique(),
-            total_amount_to_distribute: 100_000,
-            amount_distributed: 0,
-            current_cursor: 0,
-            total_investors: 50,
-            investors_processed: 0,
-            is_complete: false,
-            started_at: 1672531200,
-            completed_at: 0,
-            dust_carried_over: 0,
-            daily_cap_total: 1_000_000,
-            daily_cap_remaining: 1_000_000,
-            min_payout_threshold: 1000,
-            initial_total_deposit: 2_000_000,
-            investor_fee_share_bps: 5000,
-            last_page_hash: [0; 32],
-            pages_processed: 0,
-            failed_payouts_count: 0,
-            reserved: [0; 20],
-        }
+            last_distribution_timestamp: 0,
+            total_distributions: 0,
+            total_amount_distributed: 0,
+            rolling_avg_distribution_bps_of_treasury: 0,
+            creator_wallet: Pubkey::new_unique(),
+            protocol_fee_wallet: None,
+            total_investor_payouts: 0,
+            total_creator_payouts: 0,
+            avg_seconds_per_page: 0,
+            bump: 255,
+            position_initialized_at: 0,
+            health_score: 100,
+            auto_extension_count: 0,
+            dust_rollover: 0,
+            reserved: [0; 2],
+        };
+
+        // First distribution seeds the average
+        let mut daily = fixture_daily_distribution_state();
+        daily.treasury_balance_at_start = 100_000;
+        global.update_rolling_average(daily.distribution_ratio_bps(80_000), 2000);
+        global.update_after_distribution(1672531200, 60_000, 20_000, 0);
+        assert_eq!(global.rolling_avg_distribution_bps_of_treasury, 8000);
+
+        // Lifetime split is tracked alongside the aggregate
+        global.update_after_distribution(1672617600, 25_000, 4_000, 1_000);
+        assert_eq!(global.total_investor_payouts, 85_000);
+        assert_eq!(global.total_creator_payouts, 24_000);
+        assert_eq!(global.total_amount_distributed, 110_000);
+        assert_eq!(global.total_distributions, 2);
+
+        // EMA with alpha = 20%: 0.2 * 3000 + 0.8 * 8000 = 7000
+        global.update_rolling_average(daily.distribution_ratio_bps(30_000), 2000);
+        assert_eq!(global.rolling_avg_distribution_bps_of_treasury, 7000);
+
+        // Average surfaces an expected amount for a treasury balance
+        assert_eq!(global.average_distribution_amount(50_000), 35_000);
+    }
+
+    #[test]
+    fn test_page_processing_latency() {
+        use meteora_fee_router::modules::distribution::state::GlobalDistributionState;
+
+        let mut daily = fixture_daily_distribution_state();
+        assert_eq!(daily.page_processing_latency_seconds(), 0);
+        assert_eq!(daily.seconds_per_page(), 0);
+
+        // Four pages finishing 10 minutes after the start
+        daily.pages_processed = 4;
+        daily.last_page_completed_at = daily.started_at + 600;
+        assert_eq!(daily.page_processing_latency_seconds(), 600);
+        assert_eq!(daily.seconds_per_page(), 150);
+
+        let mut global = GlobalDistributionState {
+            quote_mint: Pubkey::new_unique(),
+            last_distribution_timestamp: 0,
+            total_distributions: 0,
+            total_amount_distributed: 0,
+            rolling_avg_distribution_bps_of_treasury: 0,
+            creator_wallet: Pubkey::new_unique(),
+            protocol_fee_wallet: None,
+            total_investor_payouts: 0,
+            total_creator_payouts: 0,
+            avg_seconds_per_page: 0,
+            bump: 255,
+            position_initialized_at: 0,
+            health_score: 100,
+            auto_extension_count: 0,
+            dust_rollover: 0,
+            reserved: [0; 2],
+        };
+
+        // First day seeds the average, later days are smoothed (alpha = 20%)
+        global.update_avg_seconds_per_page(daily.seconds_per_page(), daily.pages_processed, 2000);
+        assert_eq!(global.avg_seconds_per_page, 150);
+        global.update_avg_seconds_per_page(400, 2, 2000);
+        assert_eq!(global.avg_seconds_per_page, 200);
+
+        // Days without pages leave the average alone
+        global.update_avg_seconds_per_page(0, 0, 2000);
+        assert_eq!(global.avg_seconds_per_page, 200);
+    }
+
+    #[test]
+    fn test_position_age_days() {
+        use meteora_fee_router::modules::distribution::state::GlobalDistributionState;
+
+        let mut global = GlobalDistributionState {
+            quote_mint: Pubkey::new_unique(),
+            last_distribution_timestamp: 0,
+            total_distributions: 0,
+            total_amount_distributed: 0,
+            rolling_avg_distribution_bps_of_treasury: 0,
+            creator_wallet: Pubkey::new_unique(),
+            protocol_fee_wallet: None,
+            total_investor_payouts: 0,
+            total_creator_payouts: 0,
+            avg_seconds_per_page: 0,
+            bump: 255,
+            position_initialized_at: 0,
+            health_score: 100,
+            auto_extension_count: 0,
+            dust_rollover: 0,
+            reserved: [0; 2],
+        };
+        let created_at = 1672531200i64;
+
+        // Not linked yet
+        assert_eq!(global.position_age_days(created_at + 86400 * 10), 0);
+
+        // Whole days only
+        global.position_initialized_at = created_at;
+        assert_eq!(global.position_age_days(created_at), 0);
+        assert_eq!(global.position_age_days(created_at + 86400 * 10 + 3600), 10);
+        assert_eq!(global.position_age_days(created_at - 86400), 0);
+    }
+
+    #[test]
+    fn test_pause_metrics() {
+        use meteora_fee_router::modules::distribution::state::ProgramConfig;
+
+        let mut config = ProgramConfig {
+            program_pause_authority: Pubkey::new_unique(),
+            is_paused: false,
+            pause_count: 0,
+            resume_count: 0,
+            last_paused_at: 0,
+            total_paused_seconds: 0,
+            reserved: [0; 8],
+        };
+        let now = 1672531200i64;
+
+        config.record_pause(now);
+        config.record_resume(now + 600);
+        config.record_pause(now + 3600);
+        config.record_resume(now + 4000);
+
+        assert_eq!(config.pause_count, 2);
+        assert_eq!(config.resume_count, 2);
+        assert_eq!(config.last_paused_at, now + 3600);
+        assert_eq!(config.total_paused_seconds, 1000);
+
+        // A resume without an open pause adds no paused time
+        config.record_resume(now + 5000);
+        assert_eq!(config.total_paused_seconds, 1000);
+    }
+
+    #[test]
+    fn test_investor_cache_validity() {
+        use meteora_fee_router::modules::distribution::state::InvestorAggregateCache;
+
+        let distribution_day = 1672531200i64;
+        let snapshot_time = distribution_day + 60;
+        let mut cache = InvestorAggregateCache::new(distribution_day, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        cache.last_updated = snapshot_time + 10;
+
+        // Written after the snapshot for the same day - reusable
+        assert!(cache.is_valid_for(distribution_day, snapshot_time));
+
+        // Different day - not reusable
+        assert!(!cache.is_valid_for(distribution_day + 86400, snapshot_time));
+
+        // Written before the snapshot - stale
+        cache.last_updated = snapshot_time - 1;
+        assert!(!cache.is_valid_for(distribution_day, snapshot_time));
+    }
+
+    #[test]
+    fn test_investor_cache_aggregates_streams_across_pages() {
+        use meteora_fee_router::integrations::streamflow::accounts::InvestorStreamData;
+        use meteora_fee_router::modules::distribution::state::InvestorAggregateCache;
+
+        let investor = Pubkey::new_unique();
+        let stream = |locked_amount: u64, total_deposited: u64, start_time: u64, end_time: u64, lock_duration_seconds: u64| InvestorStreamData {
+            investor,
+            stream_account: Pubkey::new_unique(),
+            locked_amount,
+            total_deposited,
+            investor_ata: Pubkey::new_unique(),
+            start_time,
+            end_time,
+            cliff_locked: 0,
+            lock_duration_seconds,
+        };
+        let mut cache = InvestorAggregateCache::new(FIXTURE_DISTRIBUTION_DAY, Pubkey::new_unique(), investor, Pubkey::new_unique());
+
+        // First page
+        let first = stream(100_000, 150_000, 1_000, 50_000, 10_000);
+        cache.add_streams(&[&first], FIXTURE_DISTRIBUTION_DAY + 10);
+        assert_eq!(cache.total_locked, 100_000);
+        assert_eq!(cache.total_deposited, 150_000);
+        assert_eq!((cache.start_time, cache.end_time), (1_000, 50_000));
+        assert_eq!(cache.lock_duration_seconds, 10_000);
+
+        // A later page adds the investor's other streams instead of replacing them
+        let second = stream(300_000, 300_000, 500, 40_000, 30_000);
+        cache.add_streams(&[&second], FIXTURE_DISTRIBUTION_DAY + 20);
+        assert_eq!(cache.total_locked, 400_000);
+        assert_eq!(cache.total_deposited, 450_000);
+        assert_eq!(cache.stream_count, 2);
+        assert_eq!((cache.start_time, cache.end_time), (500, 50_000));
+        // (100_000 * 10_000 + 300_000 * 30_000) / 400_000
+        assert_eq!(cache.lock_duration_seconds, 25_000);
+        assert_eq!(cache.last_updated, FIXTURE_DISTRIBUTION_DAY + 20);
+    }
+
+    #[test]
+    fn test_investor_payout_summary_accumulates() {
+        use meteora_fee_router::modules::distribution::state::InvestorPayoutSummary;
+
+        let investor = Pubkey::new_unique();
+        let mut summary = InvestorPayoutSummary::new(FIXTURE_DISTRIBUTION_DAY, investor, Pubkey::new_unique());
+        assert_eq!(summary.amount_paid, 0);
+
+        // Streams of one investor can be paid on different pages
+        summary.record_payout(40_000, FIXTURE_DISTRIBUTION_DAY + 60);
+        summary.record_payout(10_000, FIXTURE_DISTRIBUTION_DAY + 120);
+        assert_eq!(summary.amount_paid, 50_000);
+        assert_eq!(summary.payout_count, 2);
+        assert_eq!(summary.last_paid_at, FIXTURE_DISTRIBUTION_DAY + 120);
+
+        // One summary per investor and day
+        let program_id = Pubkey::new_unique();
+        let (today, _) = InvestorPayoutSummary::derive_pda(FIXTURE_DISTRIBUTION_DAY, &investor, &program_id);
+        let (tomorrow, _) = InvestorPayoutSummary::derive_pda(FIXTURE_DISTRIBUTION_DAY + 86400, &investor, &program_id);
+        assert_ne!(today, tomorrow);
+    }
+
+    #[test]
+    fn test_investor_snapshot_pages() {
+        use meteora_fee_router::modules::distribution::state::{InvestorSnapshot, SnapshotEntry};
+
+        let entries: Vec<SnapshotEntry> = (0..5u64)
+            .map(|i| SnapshotEntry {
+                investor: Pubkey::new_unique(),
+                stream_account: Pubkey::new_unique(),
+                locked_amount: 100_000 * (i + 1),
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            })
+            .collect();
+        let snapshot = InvestorSnapshot {
+            distribution_day: 1672531200,
+            quote_mint: Pubkey::new_unique(),
+            investor_count: entries.len() as u32,
+            total_locked: 1_500_000,
+            taken_at: 1672531260,
+            payer: Pubkey::new_unique(),
+            entries: entries.clone(),
+        };
+
+        // Pages are fixed slices; the last one is short
+        assert_eq!(InvestorSnapshot::page_start(1, 2), 2);
+        assert_eq!(snapshot.page_entries(0, 2).unwrap(), &entries[0..2]);
+        assert_eq!(snapshot.page_entries(1, 2).unwrap(), &entries[2..4]);
+        assert_eq!(snapshot.page_entries(2, 2).unwrap(), &entries[4..5]);
+
+        // Past the last entry
+        assert!(snapshot.page_entries(3, 2).is_none());
+        assert!(snapshot.page_entries(0, 0).is_none());
+    }
+
+    #[test]
+    fn test_policy_snapshot_recorded_from_policy() {
+        use meteora_fee_router::modules::distribution::state::PolicySnapshot;
+
+        let mut policy = fixture_policy_state();
+        let distribution_day = 1672531200i64;
+        let snapshot = PolicySnapshot::from_policy(&policy, distribution_day, distribution_day + 30);
+
+        // Snapshot survives a later policy update and round-trips through account data
+        policy.investor_fee_share_bps = 1000;
+        let mut data = Vec::new();
+        snapshot.try_serialize(&mut data).unwrap();
+        let restored = PolicySnapshot::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(restored.distribution_day, distribution_day);
+        assert_eq!(restored.quote_mint, policy.quote_mint);
+        assert_eq!(restored.investor_fee_share_bps, 5000);
+        assert_eq!(restored.daily_cap_lamports, policy.daily_cap_lamports);
+        assert_eq!(restored.min_payout_lamports, policy.min_payout_lamports);
+        assert_eq!(restored.y0_total_allocation, policy.y0_total_allocation);
+        assert_eq!(restored.recorded_at, distribution_day + 30);
+        assert_eq!(data.len(), 8 + PolicySnapshot::INIT_SPACE);
+    }
+
+    #[test]
+    fn test_distribution_window() {
+        let mut daily = fixture_daily_distribution_state();
+        let started_at = daily.started_at;
+
+        assert!(daily.is_open(started_at));
+        assert!(daily.is_open(started_at + 86400 * 2 - 1));
+
+        // 48 hours after start, pages are rejected until force-completed
+        assert!(!daily.is_open(started_at + 86400 * 2));
+
+        daily.mark_complete(started_at + 60);
+        assert!(!daily.is_open(started_at + 120));
+    }
+
+    #[test]
+    fn test_treasury_balance_assertion() {
+        let mut daily = fixture_daily_distribution_state();
+        daily.total_amount_to_distribute = 100_000;
+        daily.amount_distributed = 60_000;
+        daily.total_fees_claimed_at_distribution_start = 500_000;
+
+        // Undistributed 40k must still be in the treasury
+        assert!(daily.verify_treasury_balance(40_000, 500_000).is_ok());
+        assert!(daily.verify_treasury_balance(39_999, 500_000).is_err());
+
+        // A claim during the distribution cannot cover a shortfall
+        assert!(daily.verify_treasury_balance(45_000, 510_000).is_err());
+        assert!(daily.verify_treasury_balance(50_000, 510_000).is_ok());
+    }
+
+    #[test]
+    fn test_distribution_integrity_checks() {
+        use meteora_fee_router::modules::distribution::state::PolicySnapshot;
+
+        let policy = fixture_policy_state();
+        let mut daily = fixture_daily_distribution_state();
+        let snapshot = PolicySnapshot::from_policy(&policy, daily.distribution_day, daily.started_at);
+        daily.total_amount_to_distribute = 100_000;
+        daily.total_investors = 10;
+        daily.investors_processed = 10;
+        daily.amount_distributed = 50_000;
+        daily.creator_remainder = 50_000;
+        daily.mark_complete(daily.started_at + 600);
+
+        // 50% investor share of 100k
+        let investor_fee_quote = snapshot.expected_investor_fee_quote(
+            daily.get_effective_distribution_amount(),
+            daily.investor_fee_share_bps,
+        );
+        assert_eq!(investor_fee_quote, 50_000);
+
+        let (checks, discrepancy) = daily.integrity_checks(investor_fee_quote, daily.completed_at);
+        assert_eq!(checks, [true; 5]);
+        assert_eq!(discrepancy, 0);
+
+        // Overpaid investors break the settlement and a miscounted page is caught
+        daily.amount_distributed = 60_000;
+        daily.investors_processed = 11;
+        let (checks, discrepancy) = daily.integrity_checks(investor_fee_quote, daily.completed_at);
+        assert_eq!(checks, [true, false, false, false, true]);
+        assert_eq!(discrepancy, 10_000);
+
+        // Failed payouts count towards the processed investors
+        daily.amount_distributed = 50_000;
+        daily.investors_processed = 9;
+        daily.failed_payouts_count = 2;
+        let (checks, _) = daily.integrity_checks(investor_fee_quote, daily.completed_at);
+        assert_eq!(checks, [true, true, true, false, true]);
+        daily.failed_payouts_count = 1;
+        let (checks, _) = daily.integrity_checks(investor_fee_quote, daily.completed_at);
+        assert_eq!(checks, [true; 5]);
+
+        // Not yet recorded in global state
+        let (checks, _) = daily.integrity_checks(investor_fee_quote, daily.completed_at - 1);
+        assert!(!checks[0]);
+    }
+
+    #[test]
+    fn test_completed_distribution_audit_violations() {
+        use meteora_fee_router::modules::distribution::state::PolicySnapshot;
+
+        let policy = fixture_policy_state();
+        let mut daily = fixture_daily_distribution_state();
+        let snapshot = PolicySnapshot::from_policy(&policy, daily.distribution_day, daily.started_at);
+        daily.total_amount_to_distribute = 100_000;
+        daily.amount_distributed = 50_000;
+        daily.creator_remainder = 50_000;
+        daily.mark_complete(daily.started_at + 600);
+        assert!(daily.audit_violations(&snapshot, 50_000, daily.completed_at).is_empty());
+
+        // Payout summaries must add up to the amount distributed
+        let violations = daily.audit_violations(&snapshot, 49_999, daily.completed_at);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("payout summaries"));
+
+        // Overpaid investors leave the creator remainder short
+        daily.amount_distributed = 60_000;
+        let violations = daily.audit_violations(&snapshot, 60_000, daily.completed_at);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].contains("exceeds investor fee quote"));
+        assert!(violations[1].contains("creator remainder"));
+
+        // Not yet recorded in global state
+        daily.amount_distributed = 50_000;
+        let violations = daily.audit_violations(&snapshot, 50_000, daily.completed_at - 1);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("not recorded globally"));
+    }
+
+    #[test]
+    fn test_snapshot_close_retention() {
+        let mut daily = fixture_daily_distribution_state();
+        let completed_at = daily.started_at + 3600;
+
+        // Not closable while the distribution is running
+        assert!(!daily.can_close_snapshot(completed_at + 86400 * 2));
+
+        daily.mark_complete(completed_at);
+        assert!(!daily.can_close_snapshot(completed_at + 86400));
+        assert!(daily.can_close_snapshot(completed_at + 86400 + 1));
+    }
+
+    #[test]
+    fn test_stale_claim_blocks_distribution() {
+        use meteora_fee_router::errors::FeeRouterError;
+
+        let mut policy = fixture_policy_state();
+        let now = 1672531200i64;
+        let seven_days_ago = now - 7 * 86400;
+
+        // Disabled by default
+        assert!(!policy.is_claim_stale(seven_days_ago, now));
+
+        // A 7 day old claim is stale with a 3 day maximum
+        policy.max_claim_interval_seconds = 3 * 86400;
+        assert!(policy.is_claim_stale(seven_days_ago, now));
+        assert!(!policy.is_claim_stale(now - 3 * 86400, now));
+
+        // Claiming fees unblocks the distribution
+        let mut treasury = fixture_treasury_state();
+        treasury.last_claim_timestamp = seven_days_ago;
+        assert!(policy.is_claim_stale(treasury.last_claim_timestamp, now));
+        treasury.last_claim_timestamp = now;
+        assert!(!policy.is_claim_stale(treasury.last_claim_timestamp, now));
+
+        policy.max_claim_interval_seconds = -1;
+        assert_eq!(policy.validate().unwrap_err(), FeeRouterError::InvalidClaimInterval.into());
+    }
+
+    #[test]
+    fn test_policy_sunset() {
+        let mut policy = fixture_policy_state();
+        let now = 1672531200i64;
+
+        // No sunset configured
+        assert!(!policy.is_sunsetted(now));
+        assert!(!policy.is_valid_sunset_extension(now + 86400));
+
+        // Past sunset blocks new distributions
+        policy.sunset_timestamp = now - 1;
+        assert!(policy.is_sunsetted(now));
+
+        // Extensions must move the sunset later or remove it
+        assert!(!policy.is_valid_sunset_extension(now - 86400));
+        assert!(policy.is_valid_sunset_extension(now + 86400));
+        assert!(policy.is_valid_sunset_extension(0));
+
+        policy.sunset_timestamp = now + 86400;
+        assert!(!policy.is_sunsetted(now));
+    }
+
+    #[test]
+    fn test_policy_sunset_auto_extension() {
+        let mut policy = fixture_policy_state();
+        let now = 1672531200i64;
+        policy.sunset_timestamp = now + 90 * 86400;
+
+        // Fixed sunset by default
+        assert_eq!(policy.auto_extended_sunset(), None);
+
+        // Each completed distribution pushes the sunset back a day
+        policy.auto_extend_sunset = true;
+        for _ in 0..100 {
+            policy.sunset_timestamp = policy.auto_extended_sunset().unwrap();
+        }
+        assert_eq!(policy.sunset_timestamp, now + 190 * 86400);
+        assert!(!policy.is_sunsetted(now + 100 * 86400));
+
+        // Nothing to extend without a sunset
+        policy.sunset_timestamp = 0;
+        assert_eq!(policy.auto_extended_sunset(), None);
+    }
+
+    #[test]
+    fn test_min_payout_update_validation() {
+        let mut policy = fixture_policy_state();
+        policy.daily_cap_lamports = 1_000_000;
+
+        assert!(policy.is_valid_min_payout(1));
+        assert!(policy.is_valid_min_payout(1_000_000));
+        assert!(!policy.is_valid_min_payout(0));
+        assert!(!policy.is_valid_min_payout(1_000_001));
+
+        // No daily cap leaves only the lower bound
+        policy.daily_cap_lamports = 0;
+        assert!(policy.is_valid_min_payout(u64::MAX));
+        assert!(!policy.is_valid_min_payout(0));
+    }
+
+    #[test]
+    fn test_policy_daily_cap_for_distribution() {
+        let mut policy = fixture_policy_state();
+        assert_eq!(policy.daily_cap_for_distribution(), 1_000_000);
+
+        // A policy cap of 0 does not limit the day
+        policy.daily_cap_lamports = 0;
+        assert_eq!(policy.daily_cap_for_distribution(), u64::MAX);
+    }
+
+    #[test]
+    fn test_min_investors_to_distribute() {
+        let mut policy = fixture_policy_state();
+
+        // Default of 1: an empty snapshot cannot start a distribution
+        assert!(!policy.has_enough_investors(0));
+        assert!(policy.has_enough_investors(1));
+
+        policy.min_investors_to_distribute = 5;
+        assert!(!policy.has_enough_investors(4));
+        assert!(policy.has_enough_investors(5));
+
+        // Unset (0) falls back to the default of 1
+        policy.min_investors_to_distribute = 0;
+        assert!(!policy.has_enough_investors(0));
+        assert!(policy.has_enough_investors(1));
+    }
+
+    #[test]
+    fn test_page_size_stats() {
+        let mut state = fixture_daily_distribution_state();
+
+        state.record_page_stats(50);
+        state.record_page_stats(12);
+        state.record_page_stats(30);
+
+        assert_eq!(state.max_page_size_used, 50);
+        assert_eq!(state.min_page_size_used, 12);
+    }
+
+    #[test]
+    fn test_page_slot_ordering() {
+        let mut state = fixture_daily_distribution_state();
+        state.execution_slot = 1_000;
+
+        // Pages before the distribution started are rejected
+        assert!(state.record_page_slot(999).is_err());
+
+        state.record_page_slot(1_005).unwrap();
+        // Several pages may land in the same slot
+        state.record_page_slot(1_005).unwrap();
+        assert_eq!(state.current_page_slot, 1_005);
+
+        assert!(state.record_page_slot(1_004).is_err());
+        assert_eq!(state.current_page_slot, 1_005);
+    }
+
+    #[test]
+    fn test_expected_page_count_matches_pages_processed() {
+        for total_investors in [1u32, 11, 12, 13, 50] {
+            let mut state = fixture_daily_distribution_state().with_total_investors(total_investors);
+            state.expected_page_count = DailyDistributionState::calculate_expected_page_count(
+                total_investors,
+                MAX_INVESTORS_PER_PAGE,
+            );
+
+            // Process full pages until every investor is done
+            while state.has_more_investors() {
+                let page_size = std::cmp::min(MAX_INVESTORS_PER_PAGE, total_investors - state.current_cursor);
+                state.update_page_state([0; 32], page_size, 0);
+            }
+
+            assert_eq!(state.pages_processed, state.expected_page_count, "{} investors", total_investors);
+        }
+
+        assert_eq!(DailyDistributionState::calculate_expected_page_count(0, MAX_INVESTORS_PER_PAGE), 0);
+        assert_eq!(DailyDistributionState::calculate_expected_page_count(50, 0), 0);
+    }
+
+    #[test]
+    fn test_effective_page_size_throttled_mid_distribution() {
+        let mut policy = fixture_policy_state();
+        assert_eq!(policy.initial_page_size(), MAX_INVESTORS_PER_PAGE);
+        policy.target_page_size = 8;
+        assert_eq!(policy.initial_page_size(), 8);
+        assert!(policy.validate().is_ok());
+        policy.target_page_size = MAX_INVESTORS_PER_PAGE + 1;
+        assert!(policy.validate().is_err());
+
+        // 50 investors, two full pages done before congestion
+        let mut state = fixture_daily_distribution_state().with_total_investors(50);
+        state.expected_page_count = DailyDistributionState::calculate_expected_page_count(50, MAX_INVESTORS_PER_PAGE);
+        state.update_page_state([1; 32], MAX_INVESTORS_PER_PAGE, 0);
+        state.update_page_state([2; 32], MAX_INVESTORS_PER_PAGE, 0);
+
+        // Remaining 26 investors now need 6 pages of 5
+        state.set_effective_page_size(5).unwrap();
+        assert_eq!(state.effective_page_size, 5);
+        assert_eq!(state.expected_page_count, 2 + 6);
+
+        assert!(state.set_effective_page_size(0).is_err());
+        assert!(state.set_effective_page_size(MAX_INVESTORS_PER_PAGE + 1).is_err());
+        assert_eq!(state.effective_page_size, 5);
+    }
+
+    #[test]
+    fn test_distribution_health_score() {
+        use meteora_fee_router::modules::distribution::state::GlobalDistributionState;
+        use meteora_fee_router::shared::constants::*;
+
+        let day = FIXTURE_DISTRIBUTION_DAY;
+        let mut state = fixture_daily_distribution_state();
+        state.update_page_state([1u8; 32], 50, 50_000);
+        state.update_daily_cap(50_000);
+
+        // Daily run with every investor paid and little dust
+        assert_eq!(GlobalDistributionState::calculate_health_score(&state, day - 86_400 + 3_600, 50_000), (100, 0));
+
+        // The first distribution has nothing to miss
+        assert_eq!(GlobalDistributionState::calculate_health_score(&state, 0, 50_000), (100, 0));
+
+        // Two missed days
+        assert_eq!(
+            GlobalDistributionState::calculate_health_score(&state, day - 3 * 86_400, 50_000),
+            (80, HEALTH_FLAG_MISSED_DAYS)
+        );
+
+        // Cap nearly exhausted, investors left unpaid and more than 1% dust
+        let mut unhealthy = fixture_daily_distribution_state();
+        unhealthy.update_page_state([1u8; 32], 40, 50_000);
+        unhealthy.update_daily_cap(950_000);
+        unhealthy.add_dust(501);
+        assert_eq!(
+            GlobalDistributionState::calculate_health_score(&unhealthy, day - 86_400, 50_000),
+            (65, HEALTH_FLAG_CAP_BINDING | HEALTH_FLAG_FAILED_PAYOUTS | HEALTH_FLAG_HIGH_DUST)
+        );
+
+        // The score never drops below zero
+        assert_eq!(GlobalDistributionState::calculate_health_score(&unhealthy, day - 30 * 86_400, 50_000).0, 0);
+    }
+
+    #[test]
+    fn test_distribution_summary() {
+        let mut state = fixture_daily_distribution_state();
+
+        // Nothing processed yet
+        let summary = state.to_summary();
+        assert_eq!(summary.progress_pct, 0);
+        assert_eq!(summary.estimated_completion_time, 0);
+        assert_eq!(summary.effective_apr_bps, 0);
+
+        // 10 of 50 investors processed 600s after start
+        state.investors_processed = 10;
+        state.amount_distributed = 20_000;
+        state.last_page_completed_at = state.started_at + 600;
+        let summary = state.to_summary();
+        assert_eq!(summary.progress_pct, 20);
+        assert_eq!(summary.estimated_completion_time, state.started_at + 3000);
+        // 20_000 / 2_000_000 = 1% per day, 365% per year
+        assert_eq!(summary.effective_apr_bps, 36_500);
+
+        state.is_complete = true;
+        state.completed_at = state.started_at + 2000;
+        assert_eq!(state.to_summary().estimated_completion_time, state.started_at + 2000);
+    }
+
+    #[test]
+    fn test_investor_blacklist() {
+        use meteora_fee_router::modules::distribution::state::InvestorBlacklist;
+        use meteora_fee_router::integrations::streamflow::accounts::InvestorStreamData;
+        use meteora_fee_router::integrations::streamflow::calculations::{calculate_distribution, WeightingOptions};
+
+        let sanctioned = Pubkey::new_unique();
+        let eligible = Pubkey::new_unique();
+        let mut blacklist = InvestorBlacklist {
+            quote_mint: Pubkey::new_unique(),
+            blacklisted: Vec::new(),
+            bump: 255,
+        };
+
+        blacklist.add(sanctioned).unwrap();
+        assert!(blacklist.is_blacklisted(&sanctioned));
+        assert!(!blacklist.is_blacklisted(&eligible));
+        assert!(blacklist.add(sanctioned).is_err());
+
+        // Filtering the page the way process_investor_page does
+        let mut investor_data: Vec<InvestorStreamData> = [sanctioned, eligible]
+            .iter()
+            .map(|investor| InvestorStreamData {
+                investor: *investor,
+                stream_account: Pubkey::new_unique(),
+                locked_amount: 500_000,
+                total_deposited: 500_000,
+                investor_ata: Pubkey::new_unique(),
+                start_time: 0,
+                end_time: 0,
+                cliff_locked: 0,
+                lock_duration_seconds: 0,
+            })
+            .collect();
+        investor_data.retain(|data| !blacklist.is_blacklisted(&data.investor));
+
+        let result = calculate_distribution(10_000, &investor_data, 1_000_000, 1_000_000, 10000, 1, WeightingOptions::default()).unwrap();
+
+        // The blacklisted investor receives nothing and their share is not redistributed
+        assert!(result.investor_payouts.iter().all(|p| p.investor != sanctioned));
+        assert_eq!(result.total_distributed, 5_000);
+        assert_eq!(result.dust_amount, 5_000);
+
+        // Removal
+        blacklist.remove(sanctioned).unwrap();
+        assert!(!blacklist.is_blacklisted(&sanctioned));
+        assert!(blacklist.remove(sanctioned).is_err());
+
+        // Capacity is bounded
+        for _ in 0..100 {
+            blacklist.add(Pubkey::new_unique()).unwrap();
+        }
+        assert!(blacklist.add(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_allocation_registry_against_y0() {
+        use meteora_fee_router::modules::distribution::state::AllocationRegistry;
+
+        let mut registry = AllocationRegistry {
+            quote_mint: Pubkey::new_unique(),
+            total_registered_allocation: 0,
+            registered_streams: Vec::new(),
+            bump: 255,
+        };
+
+        let stream = Pubkey::new_unique();
+        registry.register(stream, 1_000_000).unwrap();
+        registry.register(Pubkey::new_unique(), 1_000_000).unwrap();
+        assert_eq!(registry.total_registered_allocation, 2_000_000);
+
+        // A stream is only counted once
+        assert!(registry.register(stream, 1_000_000).is_err());
+        assert_eq!(registry.total_registered_allocation, 2_000_000);
+
+        // Up to 10% above Y0 is tolerated as rounding
+        assert!(!registry.exceeds_y0(2_000_000));
+        assert_eq!(registry.overage_bps(2_000_000), 0);
+        registry.register(Pubkey::new_unique(), 200_000).unwrap();
+        assert!(!registry.exceeds_y0(2_000_000));
+        assert_eq!(registry.overage_bps(2_000_000), 1000);
+
+        registry.register(Pubkey::new_unique(), 1).unwrap();
+        assert!(registry.exceeds_y0(2_000_000));
+        assert_eq!(registry.overage_bps(2_000_000), 1000);
+    }
+
+    #[test]
+    fn test_investor_registry_pages_for_5000_investors() {
+        use meteora_fee_router::modules::distribution::state::{InvestorEntry, InvestorRegistryPage, InvestorRegistryState};
+        use meteora_fee_router::shared::constants::INVESTOR_REGISTRY_PAGE_SIZE;
+
+        let program_id = meteora_fee_router::id();
+        let mut registry = InvestorRegistryState {
+            quote_mint: Pubkey::new_unique(),
+            investor_capacity: 10_000,
+            investor_count: 0,
+            page_count: 0,
+            last_page_index: 0,
+            bump: 255,
+        };
+        let registry_key = Pubkey::new_unique();
+        let mut pages: Vec<InvestorRegistryPage> = Vec::new();
+        let investors: Vec<Pubkey> = (0..5_000).map(|_| Pubkey::new_unique()).collect();
+
+        // Append the way `add_investor` does
+        for investor in investors.iter() {
+            assert!(registry.has_capacity());
+            let entry = InvestorEntry { investor: *investor, stream_account: Pubkey::new_unique() };
+            let last_page_len = pages.last().map_or(0, |page| page.entries.len());
+            let page_index = registry.next_entry_page_index(last_page_len);
+            if pages.last().is_some_and(|page| page.page_index == page_index) {
+                pages.last_mut().unwrap().push(entry).unwrap();
+            } else {
+                let (page_key, _) = InvestorRegistryPage::derive_pda(&registry_key, page_index, &program_id);
+                if let Some(previous) = pages.last_mut() {
+                    assert!(!previous.has_room());
+                    previous.next_page = page_key;
+                }
+                pages.push(InvestorRegistryPage {
+                    registry: registry_key,
+                    page_index,
+                    next_page: Pubkey::default(),
+                    entries: vec![entry],
+                    bump: 255,
+                });
+            }
+            registry.record_added(page_index);
+        }
+
+        assert_eq!(registry.investor_count, 5_000);
+        assert_eq!(registry.page_count, 100);
+        assert_eq!(registry.last_page_index, 99);
+        assert_eq!(pages.len(), 100);
+        assert!(pages.iter().all(|page| page.entries.len() == INVESTOR_REGISTRY_PAGE_SIZE));
+
+        // Pages are linked in order
+        for window in pages.windows(2) {
+            let (next_key, _) = InvestorRegistryPage::derive_pda(&registry_key, window[1].page_index, &program_id);
+            assert_eq!(window[0].next_page, next_key);
+        }
+        assert_eq!(pages[99].next_page, Pubkey::default());
+
+        // The day's cursor walks every investor in registry order
+        for (cursor, investor) in investors.iter().enumerate() {
+            let (page_index, offset) = InvestorRegistryState::position_of(cursor as u32);
+            assert_eq!(pages[page_index as usize].entries[offset].investor, *investor);
+        }
+
+        // A full page still fits its account
+        let mut data = Vec::new();
+        pages[0].try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + InvestorRegistryPage::INIT_SPACE);
+    }
+
+    #[test]
+    fn test_stream_error_log_records_up_to_capacity() {
+        use meteora_fee_router::integrations::streamflow::cpi::StreamErrorType;
+        use meteora_fee_router::modules::distribution::state::StreamErrorLog;
+        use meteora_fee_router::shared::constants::MAX_STREAM_ERROR_LOG_ENTRIES;
+
+        let mut log = StreamErrorLog {
+            distribution_day: 19_000,
+            quote_mint: Pubkey::new_unique(),
+            entries: Vec::new(),
+            total_errors: 0,
+            payer: Pubkey::new_unique(),
+            bump: 255,
+        };
+
+        let stream = Pubkey::new_unique();
+        assert!(log.record(stream, StreamErrorType::MintMismatch));
+        assert_eq!(log.entries[0].stream_account, stream);
+        assert_eq!(log.entries[0].error_type, StreamErrorType::MintMismatch);
+
+        for _ in 1..MAX_STREAM_ERROR_LOG_ENTRIES {
+            assert!(log.record(Pubkey::new_unique(), StreamErrorType::StreamExpired));
+        }
+        assert_eq!(log.dropped_errors(), 0);
+
+        // Errors past capacity are only counted
+        assert!(!log.record(Pubkey::new_unique(), StreamErrorType::StreamExpired));
+        assert_eq!(log.entries.len(), MAX_STREAM_ERROR_LOG_ENTRIES);
+        assert_eq!(log.total_errors, MAX_STREAM_ERROR_LOG_ENTRIES as u32 + 1);
+        assert_eq!(log.dropped_errors(), 1);
+
+        // A full log still fits its account
+        let mut data = Vec::new();
+        log.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + StreamErrorLog::INIT_SPACE);
+    }
+
+    #[test]
+    fn test_auto_complete_page_threshold() {
+        let mut policy = fixture_policy_state();
+
+        // Disabled by default
+        assert!(!policy.should_auto_complete(1_000));
+
+        // Fires once the configured number of pages is reached
+        policy.max_pages_before_auto_complete = 10;
+        assert!(!policy.should_auto_complete(0));
+        assert!(!policy.should_auto_complete(9));
+        assert!(policy.should_auto_complete(10));
+        assert!(policy.should_auto_complete(11));
+    }
+
+    #[test]
+    fn test_position_owner_bump_verification() {
+        use meteora_fee_router::modules::position::state::{PoolType, PositionMetadata};
+        use meteora_fee_router::shared::constants::{VAULT_SEED, POSITION_OWNER_SEED};
+
+        let program_id = meteora_fee_router::ID;
+        let vault = Pubkey::new_unique();
+        let (position_owner, bump) = Pubkey::find_program_address(
+            &[VAULT_SEED, vault.as_ref(), POSITION_OWNER_SEED],
+            &program_id,
+        );
+
+        let mut metadata = PositionMetadata {
+            position: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            created_at: 0,
+            position_owner_bump: bump,
+            pool_type: PoolType::MeteoraDAMM,
+            last_claim_timestamp: 0,
+            total_fees_claimed: 0,
+            position_value_quote: 0,
+            authority: Pubkey::new_unique(),
+            reserved: [0; 8],
+        };
+
+        // Canonical PDA passes
+        assert!(metadata.verify_position_owner(&vault, &position_owner, &program_id).is_ok());
+
+        // Unrelated account is rejected
+        assert!(metadata.verify_position_owner(&vault, &Pubkey::new_unique(), &program_id).is_err());
+
+        // A PDA derived with a different (non-canonical) bump is rejected
+        let other_bump = (0..bump).rev().find_map(|candidate| {
+            Pubkey::create_program_address(
+                &[VAULT_SEED, vault.as_ref(), POSITION_OWNER_SEED, &[candidate]],
+                &program_id,
+            )
+            .ok()
+            .map(|key| (key, candidate))
+        });
+        if let Some((wrong_bump_pda, _)) = other_bump {
+            assert!(metadata.verify_position_owner(&vault, &wrong_bump_pda, &program_id).is_err());
+        }
+
+        // Tampered stored bump no longer matches the canonical PDA
+        metadata.position_owner_bump = bump.wrapping_sub(1);
+        assert!(metadata.verify_position_owner(&vault, &position_owner, &program_id).is_err());
+    }
+
+    #[test]
+    fn test_position_claim_history_and_fee_apr() {
+        use meteora_fee_router::modules::position::state::{PoolType, PositionMetadata};
+
+        const DAY: i64 = 86400;
+        let mut metadata = PositionMetadata {
+            position: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            created_at: 0,
+            position_owner_bump: 255,
+            pool_type: PoolType::MeteoraDAMM,
+            last_claim_timestamp: 0,
+            total_fees_claimed: 0,
+            position_value_quote: 0,
+            authority: Pubkey::new_unique(),
+            reserved: [0; 8],
+        };
+
+        metadata.record_claim(30_000, DAY * 73);
+        metadata.record_claim(20_000, DAY * 146);
+        assert_eq!(metadata.total_fees_claimed, 50_000);
+        assert_eq!(metadata.last_claim_timestamp, DAY * 146);
+
+        // Position value unknown - no APR
+        assert_eq!(metadata.calculate_fee_apr_bps(DAY * 146), 0);
+
+        // 50k fees on a 1M position over 146 days (0.4 year) = 12.5% APR
+        metadata.position_value_quote = 1_000_000;
+        assert_eq!(metadata.calculate_fee_apr_bps(DAY * 146), 1250);
+
+        // No age yet
+        assert_eq!(metadata.calculate_fee_apr_bps(0), 0);
+    }
+
+    #[test]
+    fn test_position_close_authority_falls_back_to_policy_authority() {
+        use meteora_fee_router::modules::position::state::{PoolType, PositionMetadata};
+
+        let policy_authority = Pubkey::new_unique();
+        let mut metadata = PositionMetadata {
+            position: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            created_at: 0,
+            position_owner_bump: 255,
+            pool_type: PoolType::MeteoraDAMM,
+            last_claim_timestamp: 0,
+            total_fees_claimed: 0,
+            position_value_quote: 0,
+            authority: Pubkey::new_unique(),
+            reserved: [0; 8],
+        };
+
+        // A recorded creating authority is the only closer
+        assert_eq!(metadata.close_authority(&policy_authority), metadata.authority);
+
+        // Positions created before the authority was recorded fall back to the policy authority
+        metadata.authority = Pubkey::default();
+        assert_eq!(metadata.close_authority(&policy_authority), policy_authority);
+    }
+
+    #[test]
+    fn test_treasury_claim_rate_limit() {
+        let mut treasury = fixture_treasury_state().with_claim_rate_limit(3000); // 30% cap
+
+        // 1000-unit treasury with a 30% cap: 300 allowed, 400 rejected
+        assert!(treasury.check_claim_rate_limit(300, 1000).is_ok());
+        assert!(treasury.check_claim_rate_limit(400, 1000).is_err());
+
+        // Cap disabled
+        treasury.max_claim_per_interval_bps = 0;
+        assert!(treasury.check_claim_rate_limit(400, 1000).is_ok());
+    }
+
+    #[test]
+    fn test_treasury_additional_claimers() {
+        let primary = Pubkey::new_unique();
+        let mut treasury = fixture_treasury_state().with_claim_authority(primary);
+
+        // 4 positions share the treasury: primary + 3 additional claimers
+        let claimers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for claimer in &claimers {
+            treasury.add_claimer(*claimer).unwrap();
+        }
+        assert!(treasury.is_authorized_claimer(&primary));
+        for claimer in &claimers {
+            assert!(treasury.is_authorized_claimer(claimer));
+        }
+        assert!(!treasury.is_authorized_claimer(&Pubkey::new_unique()));
+        assert!(!treasury.is_authorized_claimer(&Pubkey::default()));
+
+        // No room for a fifth position, duplicates rejected
+        assert!(treasury.add_claimer(Pubkey::new_unique()).is_err());
+        assert!(treasury.add_claimer(primary).is_err());
+
+        // Removing frees the slot
+        treasury.remove_claimer(claimers[1]).unwrap();
+        assert!(!treasury.is_authorized_claimer(&claimers[1]));
+        assert!(treasury.remove_claimer(claimers[1]).is_err());
+        assert!(treasury.add_claimer(Pubkey::new_unique()).is_ok());
+    }
+
+    #[test]
+    fn test_treasury_mint_whitelist() {
+        let quote_mint = Pubkey::new_unique();
+        let mut allowed_mints = [Pubkey::default(); 4];
+        allowed_mints[0] = quote_mint;
+        let mut treasury = fixture_treasury_state()
+            .with_quote_mint(quote_mint)
+            .with_allowed_mints(allowed_mints);
+
+        // Claiming a non-whitelisted token is rejected
+        let wsol = Pubkey::new_unique();
+        assert!(treasury.require_allowed_mint(&quote_mint).is_ok());
+        assert!(treasury.require_allowed_mint(&wsol).is_err());
+        assert!(treasury.require_allowed_mint(&Pubkey::default()).is_err());
+
+        // Whitelisting wSOL allows it, duplicates rejected
+        treasury.add_allowed_mint(wsol).unwrap();
+        assert!(treasury.require_allowed_mint(&wsol).is_ok());
+        assert!(treasury.add_allowed_mint(wsol).is_err());
+
+        // Only 4 slots
+        treasury.add_allowed_mint(Pubkey::new_unique()).unwrap();
+        treasury.add_allowed_mint(Pubkey::new_unique()).unwrap();
+        assert!(treasury.add_allowed_mint(Pubkey::new_unique()).is_err());
+
+        // Removing frees the slot and rejects further claims
+        treasury.remove_allowed_mint(wsol).unwrap();
+        assert!(treasury.require_allowed_mint(&wsol).is_err());
+        assert!(treasury.remove_allowed_mint(wsol).is_err());
+    }
+
+    #[test]
+    fn test_dust_rollover_survives_skipped_days() {
+        let mut global = fixture_global_distribution_state();
+
+        // Two completed days leave dust, then two days pass without a distribution
+        global.record_dust_rollover(3);
+        global.record_dust_rollover(2);
+        assert_eq!(global.dust_rollover, 5);
+
+        // The next start takes all of it and resets the rollover
+        assert_eq!(global.take_dust_rollover(), 5);
+        assert_eq!(global.dust_rollover, 0);
+    }
+
+    #[test]
+    fn test_treasury_lifetime_dust() {
+        let mut treasury = fixture_treasury_state();
+        assert_eq!(treasury.accumulated_dust_lifetime, 0);
+
+        // Dust from each completed day accumulates
+        assert_eq!(treasury.record_lifetime_dust(7), 7);
+        assert_eq!(treasury.record_lifetime_dust(0), 7);
+        assert_eq!(treasury.record_lifetime_dust(5), 12);
+
+        // Saturates instead of overflowing
+        treasury.accumulated_dust_lifetime = u64::MAX - 1;
+        assert_eq!(treasury.record_lifetime_dust(10), u64::MAX);
+    }
+
+    #[test]
+    fn test_raydium_quote_only_tick_range() {
+        use meteora_fee_router::integrations::raydium_clmm::{
+            quote_only_tick_range, tick_array_start_index, PoolState, RAYDIUM_CLMM_PROGRAM_ID,
+        };
+        use bytemuck::Zeroable;
+
+        assert_eq!(RAYDIUM_CLMM_PROGRAM_ID.to_string(), "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+
+        let mut pool = PoolState::zeroed();
+        pool.token_mint_0 = Pubkey::new_unique();
+        pool.token_mint_1 = Pubkey::new_unique();
+        pool.tick_spacing = 10;
+        pool.tick_current = -15;
+
+        // Quote is token 1: range sits at or below the current tick (token 1 only)
+        let token_mint_1 = pool.token_mint_1;
+        let (lower, upper) = quote_only_tick_range(&pool, &token_mint_1).unwrap();
+        assert_eq!((lower, upper), (-30, -20));
+        assert!(upper <= pool.tick_current);
+
+        // Quote is token 0: range sits above the current tick (token 0 only)
+        let token_mint_0 = pool.token_mint_0;
+        let (lower, upper) = quote_only_tick_range(&pool, &token_mint_0).unwrap();
+        assert_eq!((lower, upper), (-10, 0));
+        assert!(lower > pool.tick_current);
+
+        // Tick arrays hold 60 ticks * spacing
+        assert_eq!(tick_array_start_index(-30, 10), -600);
+        assert_eq!(tick_array_start_index(0, 10), 0);
+        assert_eq!(tick_array_start_index(599, 10), 0);
+        assert_eq!(tick_array_start_index(600, 10), 600);
+
+        // Round-trips through account data after the discriminator
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(bytemuck::bytes_of(&pool));
+        let parsed = PoolState::from_account_data(&data).unwrap();
+        assert_eq!({ parsed.tick_current }, -15);
+        assert!(parsed.is_open_position_enabled());
+        assert!(PoolState::from_account_data(&data[..data.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_raydium_claim_vaults_must_match_pool() {
+        use meteora_fee_router::integrations::raydium_clmm::{validate_claim_vaults, PoolState};
+        use bytemuck::Zeroable;
+
+        let mut pool = PoolState::zeroed();
+        pool.token_vault_0 = Pubkey::new_unique();
+        pool.token_vault_1 = Pubkey::new_unique();
+        let (vault_0, vault_1) = (pool.token_vault_0, pool.token_vault_1);
+
+        assert!(validate_claim_vaults(&pool, &vault_0, &vault_1).is_ok());
+        assert!(validate_claim_vaults(&pool, &vault_1, &vault_0).is_err());
+        assert!(validate_claim_vaults(&pool, &vault_0, &Pubkey::new_unique()).is_err());
     }
 }
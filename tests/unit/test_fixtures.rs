@@ -0,0 +1,405 @@
+use meteora_fee_router::integrations::meteora::{CollectFeeMode, Pool, METEORA_CP_AMM_PROGRAM_ID};
+use meteora_fee_router::integrations::streamflow::accounts::{stream_layout, StreamflowStream, STREAMFLOW_STREAM_SIZE};
+use meteora_fee_router::integrations::streamflow::STREAMFLOW_PROGRAM_ID;
+use meteora_fee_router::modules::claiming::state::TreasuryState;
+use meteora_fee_router::modules::distribution::state::{CapApplicationMode, DailyDistributionState, DustDestination, FeeDistributionMode, GlobalDistributionState, PolicyState};
+use meteora_fee_router::shared::constants::MAX_INVESTORS_PER_PAGE;
+use meteora_fee_router::shared::crypto::HashAlgorithm;
+use anchor_lang::prelude::*;
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::program_pack::Pack;
+
+// Distribution day used by the fixtures (Jan 1, 2023)
+pub const FIXTURE_DISTRIBUTION_DAY: i64 = 1672531200;
+
+/// Policy with a 50/50 fee split, a 1M daily cap and a 2M Y0
+pub fn fixture_policy_state() -> PolicyState {
+    PolicyState {
+        quote_mint: Pubkey::new_unique(),
+        investor_fee_share_bps: 5000,
+        creator_fee_share_bps: 5000,
+        daily_cap_lamports: 1_000_000,
+        min_payout_lamports: 1000,
+        y0_total_allocation: 2_000_000,
+        policy_authority: Pubkey::new_unique(),
+        allow_base_fee_grace_period: false,
+        position_creation_fee_lamports: 0,
+        ramp_start_bps: 0,
+        ramp_end_bps: 0,
+        ramp_duration_seconds: 0,
+        ramp_start_timestamp: 0,
+        duration_bonus_enabled: false,
+        duration_bonus_max_multiplier_bps: 10000,
+        min_remaining_lock_seconds: 0,
+        avg_alpha_bps: 0,
+        dust_destination: DustDestination::CarryToNextDay,
+        min_investors_to_distribute: 1,
+        include_cliff_in_weight: true,
+        sunset_timestamp: 0,
+        protocol_fee_bps: 0,
+        protocol_fee_wallet: Pubkey::default(),
+        max_pages_before_auto_complete: 0,
+        cap_application_mode: CapApplicationMode::ProportionalScale,
+        auto_claim_before_distribution: false,
+        use_duration_weighting: false,
+        fee_distribution_mode: FeeDistributionMode::Batched,
+        bump: 255,
+        version: 2,
+        page_hash_algorithm: HashAlgorithm::Sha256,
+        target_page_size: 0,
+        max_claim_interval_seconds: 0,
+        auto_extend_sunset: false,
+        blacklist_enabled: false,
+        governance_authority: Pubkey::default(),
+        reserved: [0; 15],
+    }
+}
+
+/// Day just started for 50 investors, 100k to distribute, matching `fixture_policy_state`
+pub fn fixture_daily_distribution_state() -> DailyDistributionState {
+    DailyDistributionState {
+        distribution_day: FIXTURE_DISTRIBUTION_DAY,
+        quote_mint: Pubkey::new_unique(),
+        treasury_ata: Pubkey::new_unique(),
+        total_amount_to_distribute: 100_000,
+        amount_distributed: 0,
+        current_cursor: 0,
+        total_investors: 50,
+        investors_processed: 0,
+        is_complete: false,
+        started_at: FIXTURE_DISTRIBUTION_DAY,
+        completed_at: 0,
+        dust_carried_over: 0,
+        daily_cap_total: 1_000_000,
+        daily_cap_remaining: 1_000_000,
+        min_payout_threshold: 1000,
+        initial_total_deposit: 2_000_000,
+        investor_fee_share_bps: 5000,
+        last_page_hash: [0; 32],
+        pages_processed: 0,
+        streams_below_min_duration: 0,
+        ata_creation_fees_lamports: 0,
+        processed_bitmap: vec![0; DailyDistributionState::bitmap_len(50)],
+        treasury_balance_at_start: 0,
+        lock_snapshot_timestamp: 0,
+        last_page_completed_at: 0,
+        total_fees_claimed_at_distribution_start: 0,
+        max_page_size_used: 0,
+        min_page_size_used: 0,
+        skipped_blacklisted: 0,
+        execution_slot: 0,
+        current_page_slot: 0,
+        expected_page_count: 5,
+        page_cursor_history: [0; 8],
+        page_hash_history: [[0; 32]; 4],
+        effective_page_size: MAX_INVESTORS_PER_PAGE,
+        failed_payouts_count: 0,
+        dust_rolled_over: 0,
+        registry_driven: false,
+        creator_remainder: 0,
+    }
+}
+
+/// Global state before any distribution, with no protocol fee wallet
+pub fn fixture_global_distribution_state() -> GlobalDistributionState {
+    GlobalDistributionState {
+        quote_mint: Pubkey::new_unique(),
+        last_distribution_timestamp: 0,
+        total_distributions: 0,
+        total_amount_distributed: 0,
+        rolling_avg_distribution_bps_of_treasury: 0,
+        creator_wallet: Pubkey::new_unique(),
+        protocol_fee_wallet: None,
+        total_investor_payouts: 0,
+        total_creator_payouts: 0,
+        avg_seconds_per_page: 0,
+        bump: 255,
+        position_initialized_at: 0,
+        health_score: 100,
+        auto_extension_count: 0,
+        dust_rollover: 0,
+        reserved: [0; 2],
+    }
+}
+
+/// Treasury that has never claimed, with no reserve, rate limit or whitelist
+pub fn fixture_treasury_state() -> TreasuryState {
+    TreasuryState {
+        quote_mint: Pubkey::new_unique(),
+        treasury_ata: Pubkey::new_unique(),
+        total_fees_claimed: 0,
+        last_claim_timestamp: 0,
+        claim_count: 0,
+        claim_authority: Pubkey::new_unique(),
+        base_fees_accepted_under_grace: 0,
+        reserve_ratio_bps: 0,
+        additional_claimers: [Pubkey::default(); 3],
+        max_claim_per_interval_bps: 0,
+        allowed_mints: [Pubkey::default(); 4],
+        bump: 255,
+        accumulated_dust_lifetime: 0,
+        min_claim_interval_seconds: 3600,
+        reserved: [0; 47],
+    }
+}
+
+/// Linear stream without a cliff, created at `start`
+pub fn fixture_streamflow_stream(start: u64, end: u64, deposited: u64) -> StreamflowStream {
+    StreamflowStream {
+        magic: 0,
+        version: 1,
+        created_at: start,
+        start_time: start,
+        end_time: end,
+        cliff_time: 0,
+        cliff_amount: 0,
+        deposited_amount: deposited,
+        withdrawn_amount: 0,
+        recipient: Pubkey::new_unique(),
+        sender: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        escrow_tokens: Pubkey::new_unique(),
+        name: [0; 64],
+        can_cancel: true,
+        can_transfer: false,
+        cancelled: false,
+    }
+}
+
+/// Serialize an Anchor account into an account owned by `owner`
+pub fn anchor_account<T: AccountSerialize>(state: &T, owner: &Pubkey) -> AccountSharedData {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    let mut account = Account::new(1_000_000_000, data.len(), owner);
+    account.data = data;
+    account.into()
+}
+
+/// Policy account data in the original v1 layout: a 70% investor share, a 5M
+/// daily cap, a 1000 minimum payout and a 2M Y0
+pub fn v1_policy_account_data(quote_mint: &Pubkey, policy_authority: &Pubkey) -> Vec<u8> {
+    let mut data = <PolicyState as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(quote_mint.as_ref());
+    data.extend_from_slice(&7000u64.to_le_bytes()); // investor_fee_share_bps
+    data.extend_from_slice(&5_000_000u64.to_le_bytes()); // daily_cap_lamports
+    data.extend_from_slice(&1000u64.to_le_bytes()); // min_payout_lamports
+    data.extend_from_slice(&2_000_000u64.to_le_bytes()); // y0_total_allocation
+    data.extend_from_slice(policy_authority.as_ref());
+    data.extend_from_slice(&[0u8; 64]); // reserved
+    data
+}
+
+/// Pack `stream` in the Streamflow v1 account layout
+pub fn streamflow_account_data(stream: &StreamflowStream) -> Vec<u8> {
+    let mut data = vec![0u8; STREAMFLOW_STREAM_SIZE];
+    let mut write = |offset: usize, bytes: &[u8]| data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    write(stream_layout::MAGIC, &stream.magic.to_le_bytes());
+    write(stream_layout::VERSION, &[stream.version]);
+    write(stream_layout::CREATED_AT, &stream.created_at.to_le_bytes());
+    write(stream_layout::WITHDRAWN_AMOUNT, &stream.withdrawn_amount.to_le_bytes());
+    write(stream_layout::CANCELED_AT, &u64::from(stream.cancelled).to_le_bytes());
+    write(stream_layout::END_TIME, &stream.end_time.to_le_bytes());
+    write(stream_layout::SENDER, stream.sender.as_ref());
+    write(stream_layout::RECIPIENT, stream.recipient.as_ref());
+    write(stream_layout::MINT, stream.mint.as_ref());
+    write(stream_layout::ESCROW_TOKENS, stream.escrow_tokens.as_ref());
+    write(stream_layout::START_TIME, &stream.start_time.to_le_bytes());
+    write(stream_layout::NET_AMOUNT_DEPOSITED, &stream.deposited_amount.to_le_bytes());
+    write(stream_layout::CLIFF, &stream.cliff_time.to_le_bytes());
+    write(stream_layout::CLIFF_AMOUNT, &stream.cliff_amount.to_le_bytes());
+    write(stream_layout::CANCELABLE_BY_SENDER, &[stream.can_cancel as u8]);
+    write(stream_layout::TRANSFERABLE_BY_SENDER, &[stream.can_transfer as u8]);
+    write(stream_layout::STREAM_NAME, &stream.name);
+    data
+}
+
+/// Streamflow-owned account holding `stream`
+pub fn streamflow_account(stream: &StreamflowStream) -> AccountSharedData {
+    let data = streamflow_account_data(stream);
+    let mut account = Account::new(1_000_000_000, data.len(), &STREAMFLOW_PROGRAM_ID);
+    account.data = data;
+    account.into()
+}
+
+/// Enabled CP-AMM pool of the given mints and vaults, collecting fees in token A
+pub fn fixture_meteora_pool(token_a_mint: Pubkey, token_b_mint: Pubkey, token_a_vault: Pubkey, token_b_vault: Pubkey) -> Pool {
+    let mut pool: Pool = bytemuck::Zeroable::zeroed();
+    pool.token_a_mint = token_a_mint;
+    pool.token_b_mint = token_b_mint;
+    pool.token_a_vault = token_a_vault;
+    pool.token_b_vault = token_b_vault;
+    pool.collect_fee_mode = CollectFeeMode::OnlyTokenA as u8;
+    pool
+}
+
+/// CP-AMM account data holding `pool` after the discriminator
+pub fn meteora_pool_account_data(pool: &Pool) -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    data.extend_from_slice(bytemuck::bytes_of(pool));
+    data
+}
+
+/// CP-AMM-owned account holding `pool`
+pub fn meteora_pool_account(pool: &Pool) -> AccountSharedData {
+    let data = meteora_pool_account_data(pool);
+    let mut account = Account::new(1_000_000_000, data.len(), &METEORA_CP_AMM_PROGRAM_ID);
+    account.data = data;
+    account.into()
+}
+
+/// Pack an SPL token account holding `amount` of `mint` for `owner`
+pub fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> AccountSharedData {
+    let mut account = Account::new(1_000_000_000, spl_token::state::Account::LEN, &spl_token::id());
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut account.data);
+    account.into()
+}
+
+/// Pack an initialized SPL mint with 6 decimals
+pub fn mint_account() -> AccountSharedData {
+    let mut account = Account::new(1_000_000_000, spl_token::state::Mint::LEN, &spl_token::id());
+    spl_token::state::Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut account.data);
+    account.into()
+}
+
+/// Chainable mutators for `fixture_policy_state`
+pub trait PolicyStateFixture {
+    fn with_quote_mint(self, quote_mint: Pubkey) -> Self;
+    fn with_fee_shares(self, investor_fee_share_bps: u64, creator_fee_share_bps: u64) -> Self;
+    fn with_daily_cap(self, daily_cap_lamports: u64) -> Self;
+    fn with_y0(self, y0_total_allocation: u64) -> Self;
+}
+
+impl PolicyStateFixture for PolicyState {
+    fn with_quote_mint(mut self, quote_mint: Pubkey) -> Self {
+        self.quote_mint = quote_mint;
+        self
+    }
+
+    fn with_fee_shares(mut self, investor_fee_share_bps: u64, creator_fee_share_bps: u64) -> Self {
+        self.investor_fee_share_bps = investor_fee_share_bps;
+        self.creator_fee_share_bps = creator_fee_share_bps;
+        self
+    }
+
+    fn with_daily_cap(mut self, daily_cap_lamports: u64) -> Self {
+        self.daily_cap_lamports = daily_cap_lamports;
+        self
+    }
+
+    fn with_y0(mut self, y0_total_allocation: u64) -> Self {
+        self.y0_total_allocation = y0_total_allocation;
+        self
+    }
+}
+
+/// Chainable mutators for `fixture_daily_distribution_state`
+pub trait DailyDistributionStateFixture {
+    fn with_day(self, distribution_day: i64) -> Self;
+    fn with_quote_mint(self, quote_mint: Pubkey) -> Self;
+    fn with_amount_to_distribute(self, total_amount_to_distribute: u64) -> Self;
+    fn with_total_investors(self, total_investors: u32) -> Self;
+    fn with_daily_cap(self, daily_cap: u64) -> Self;
+}
+
+impl DailyDistributionStateFixture for DailyDistributionState {
+    fn with_day(mut self, distribution_day: i64) -> Self {
+        self.distribution_day = distribution_day;
+        self.started_at = distribution_day;
+        self
+    }
+
+    fn with_quote_mint(mut self, quote_mint: Pubkey) -> Self {
+        self.quote_mint = quote_mint;
+        self
+    }
+
+    fn with_amount_to_distribute(mut self, total_amount_to_distribute: u64) -> Self {
+        self.total_amount_to_distribute = total_amount_to_distribute;
+        self
+    }
+
+    fn with_total_investors(mut self, total_investors: u32) -> Self {
+        self.total_investors = total_investors;
+        self.processed_bitmap = vec![0; DailyDistributionState::bitmap_len(total_investors)];
+        self
+    }
+
+    fn with_daily_cap(mut self, daily_cap: u64) -> Self {
+        self.daily_cap_total = daily_cap;
+        self.daily_cap_remaining = daily_cap;
+        self
+    }
+}
+
+/// Chainable mutators for `fixture_treasury_state`
+pub trait TreasuryStateFixture {
+    fn with_quote_mint(self, quote_mint: Pubkey) -> Self;
+    fn with_claim_authority(self, claim_authority: Pubkey) -> Self;
+    fn with_reserve_ratio(self, reserve_ratio_bps: u64) -> Self;
+    fn with_claim_rate_limit(self, max_claim_per_interval_bps: u64) -> Self;
+    fn with_allowed_mints(self, allowed_mints: [Pubkey; 4]) -> Self;
+}
+
+impl TreasuryStateFixture for TreasuryState {
+    fn with_quote_mint(mut self, quote_mint: Pubkey) -> Self {
+        self.quote_mint = quote_mint;
+        self
+    }
+
+    fn with_claim_authority(mut self, claim_authority: Pubkey) -> Self {
+        self.claim_authority = claim_authority;
+        self
+    }
+
+    fn with_reserve_ratio(mut self, reserve_ratio_bps: u64) -> Self {
+        self.reserve_ratio_bps = reserve_ratio_bps;
+        self
+    }
+
+    fn with_claim_rate_limit(mut self, max_claim_per_interval_bps: u64) -> Self {
+        self.max_claim_per_interval_bps = max_claim_per_interval_bps;
+        self
+    }
+
+    fn with_allowed_mints(mut self, allowed_mints: [Pubkey; 4]) -> Self {
+        self.allowed_mints = allowed_mints;
+        self
+    }
+}
+
+/// Chainable mutators for `fixture_streamflow_stream`
+pub trait StreamflowStreamFixture {
+    fn with_mint(self, mint: Pubkey) -> Self;
+    fn with_recipient(self, recipient: Pubkey) -> Self;
+    fn with_cliff(self, cliff_time: u64, cliff_amount: u64) -> Self;
+}
+
+impl StreamflowStreamFixture for StreamflowStream {
+    fn with_mint(mut self, mint: Pubkey) -> Self {
+        self.mint = mint;
+        self
+    }
+
+    fn with_recipient(mut self, recipient: Pubkey) -> Self {
+        self.recipient = recipient;
+        self
+    }
+
+    fn with_cliff(mut self, cliff_time: u64, cliff_amount: u64) -> Self {
+        self.cliff_time = cliff_time;
+        self.cliff_amount = cliff_amount;
+        self
+    }
+}